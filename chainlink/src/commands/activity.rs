@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::db::Database;
+use crate::models::ActivityItem;
+use crate::utils::{current_user, parse_relative_duration, truncate};
+
+/// Which feed items to keep.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ActivityFilter<'a> {
+    /// Only items attributed to this author
+    pub author: Option<&'a str>,
+    /// Only issue closes
+    pub closed_only: bool,
+}
+
+impl<'a> ActivityFilter<'a> {
+    /// `--closed-by-me`: closes attributed to `$CHAINLINK_USER`.
+    pub fn closed_by(user: &'a str) -> Self {
+        ActivityFilter {
+            author: Some(user),
+            closed_only: true,
+        }
+    }
+
+    fn keeps(&self, item: &ActivityItem) -> bool {
+        self.author
+            .is_none_or(|a| item.author.as_deref() == Some(a))
+            && (!self.closed_only || item.kind == "closed")
+    }
+}
+
+/// `$CHAINLINK_USER`, required by `--closed-by-me`.
+pub fn require_current_user() -> Result<String> {
+    current_user().context("Set $CHAINLINK_USER to the name you comment and close issues as")
+}
+
+fn feed(db: &Database, since: &str, filter: ActivityFilter) -> Result<Vec<ActivityItem>> {
+    let cutoff = Utc::now()
+        .checked_sub_signed(parse_relative_duration(since)?)
+        .with_context(|| format!("--since '{}' reaches too far back", since))?;
+    let mut items = db.activity_feed(cutoff)?;
+    items.retain(|item| filter.keeps(item));
+    Ok(items)
+}
+
+pub fn run_json(db: &Database, since: &str, filter: ActivityFilter) -> Result<()> {
+    let items = feed(db, since, filter)?;
+    println!("{}", serde_json::to_string_pretty(&items)?);
+    Ok(())
+}
+
+pub fn run(db: &Database, since: &str, filter: ActivityFilter) -> Result<()> {
+    let items = feed(db, since, filter)?;
+
+    if items.is_empty() {
+        println!("No activity in the last {}.", since);
+        return Ok(());
+    }
+
+    for item in &items {
+        println!("{}", format_item(item));
+    }
+    Ok(())
+}
+
+fn format_item(item: &ActivityItem) -> String {
+    let issue = item
+        .issue_id
+        .map(|id| format!("#{}", id))
+        .unwrap_or_default();
+    // Comments can span several lines; the feed shows only the first
+    let summary = item.summary.lines().next().unwrap_or("");
+    let author = item
+        .author
+        .as_ref()
+        .map(|a| format!("  ({})", a))
+        .unwrap_or_default();
+    format!(
+        "{}  {:<15} {:<5} {}{}",
+        item.timestamp.format("%Y-%m-%d %H:%M"),
+        item.kind,
+        issue,
+        truncate(summary, 60),
+        author
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_run_with_activity() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Something", None, "medium").unwrap();
+        db.add_comment(id, "first line\nsecond line").unwrap();
+
+        run(&db, "7d", ActivityFilter::default()).unwrap();
+        run_json(&db, "1h", ActivityFilter::default()).unwrap();
+    }
+
+    #[test]
+    fn test_run_empty() {
+        let (db, _dir) = setup_test_db();
+        run(&db, "1h", ActivityFilter::default()).unwrap();
+    }
+
+    #[test]
+    fn test_run_rejects_bad_duration() {
+        let (db, _dir) = setup_test_db();
+        let err = run(&db, "soon", ActivityFilter::default()).unwrap_err();
+        assert!(err.to_string().contains("Invalid duration"));
+    }
+
+    #[test]
+    fn test_run_rejects_huge_duration() {
+        let (db, _dir) = setup_test_db();
+        for since in ["99999999999999d", "100000000000d"] {
+            assert!(run(&db, since, ActivityFilter::default()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_format_item_uses_first_comment_line() {
+        let item = ActivityItem {
+            timestamp: Utc::now(),
+            kind: "comment".to_string(),
+            issue_id: Some(7),
+            summary: "first line\nsecond line".to_string(),
+            author: None,
+        };
+        let row = format_item(&item);
+        assert!(row.contains("#7"));
+        assert!(row.ends_with("first line"));
+    }
+
+    #[test]
+    fn test_author_filter_selects_only_that_authors_items() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("First", None, "medium").unwrap();
+        let b = db.create_issue("Second", None, "medium").unwrap();
+        db.add_comment_by(a, "alice was here", Some("alice"))
+            .unwrap();
+        db.add_comment_by(b, "bob was here", Some("bob")).unwrap();
+        db.close_issue_by(a, None, Some("alice")).unwrap();
+        db.close_issue_by(b, None, Some("bob")).unwrap();
+
+        let alice = feed(
+            &db,
+            "1h",
+            ActivityFilter {
+                author: Some("alice"),
+                closed_only: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(alice.len(), 2);
+        assert!(alice
+            .iter()
+            .all(|item| item.author.as_deref() == Some("alice")));
+        assert!(format_item(&alice[0]).ends_with("(alice)"));
+
+        let closed = feed(&db, "1h", ActivityFilter::closed_by("bob")).unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].kind, "closed");
+        assert_eq!(closed[0].issue_id, Some(b));
+
+        // Unfiltered, the feed still has both issue creations (no author)
+        let all = feed(&db, "1h", ActivityFilter::default()).unwrap();
+        assert_eq!(all.len(), 6);
+    }
+}
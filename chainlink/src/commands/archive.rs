@@ -1,33 +1,87 @@
 use anyhow::{bail, Result};
+use std::io::{self, Write};
 
-use crate::db::Database;
+use crate::db::{is_done, Database};
+use crate::utils::info;
 
-pub fn archive(db: &Database, id: i64) -> Result<()> {
+pub fn archive(db: &Database, id: i64, note: Option<&str>, author: Option<&str>) -> Result<()> {
     let issue = match db.get_issue(id)? {
         Some(i) => i,
         None => bail!("Issue #{} not found", id),
     };
 
-    if issue.status != "closed" {
+    if !is_done(&issue.status) {
         bail!(
-            "Can only archive closed issues. Issue #{} is '{}'",
+            "Can only archive closed or won't-do issues. Issue #{} is '{}'",
             id,
             issue.status
         );
     }
 
     if db.archive_issue(id)? {
-        println!("Archived issue #{}", id);
+        if let Some(note) = note {
+            db.add_comment_by(id, &format!("[archived] {}", note), author)?;
+        }
+        info(format_args!("Archived issue #{}", id));
     } else {
-        println!("Issue #{} could not be archived", id);
+        info(format_args!("Issue #{} could not be archived", id));
     }
 
     Ok(())
 }
 
+/// Archive every closed issue matching the label and/or priority filter in one
+/// transaction. Matching issues that are still open are skipped with a note.
+pub fn archive_matching(
+    db: &Database,
+    label: Option<&str>,
+    priority: Option<&str>,
+    note: Option<&str>,
+    author: Option<&str>,
+) -> Result<()> {
+    let issues = db.list_issues(Some("all"), label, priority)?;
+
+    let (archived, skipped) = db.transaction(|| {
+        let (mut archived, mut skipped) = (0, 0);
+        for issue in &issues {
+            match issue.status.as_str() {
+                "closed" | "wontdo" => {
+                    if db.archive_issue(issue.id)? {
+                        if let Some(note) = note {
+                            db.add_comment_by(issue.id, &format!("[archived] {}", note), author)?;
+                        }
+                        archived += 1;
+                    }
+                }
+                "archived" => {}
+                status => {
+                    info(format_args!(
+                        "Skipping #{} ({}): {}",
+                        issue.id, status, issue.title
+                    ));
+                    skipped += 1;
+                }
+            }
+        }
+        Ok((archived, skipped))
+    })?;
+
+    if skipped > 0 {
+        info(format_args!(
+            "Archived {} issue(s); skipped {} that are not closed",
+            archived, skipped
+        ));
+    } else if archived > 0 {
+        info(format_args!("Archived {} issue(s)", archived));
+    } else {
+        info("No closed issues match");
+    }
+    Ok(())
+}
+
 pub fn unarchive(db: &Database, id: i64) -> Result<()> {
     if db.unarchive_issue(id)? {
-        println!("Unarchived issue #{} (now closed)", id);
+        info(format_args!("Unarchived issue #{} (now closed)", id));
     } else {
         bail!("Issue #{} not found or not archived", id);
     }
@@ -58,7 +112,27 @@ pub fn list(db: &Database) -> Result<()> {
     Ok(())
 }
 
-pub fn archive_older(db: &Database, days: i64) -> Result<()> {
+pub fn archive_older(db: &Database, days: i64, dry_run: bool) -> Result<()> {
+    if dry_run {
+        let issues = db.closed_older_than(days)?;
+        if issues.is_empty() {
+            println!(
+                "No issues to archive (none closed more than {} days ago)",
+                days
+            );
+            return Ok(());
+        }
+        println!(
+            "Would archive {} issue(s) closed more than {} days ago:\n",
+            issues.len(),
+            days
+        );
+        for issue in issues {
+            println!("#{:<4} {:8} {}", issue.id, issue.priority, issue.title);
+        }
+        return Ok(());
+    }
+
     let count = db.archive_older_than(days)?;
     if count > 0 {
         println!(
@@ -75,6 +149,44 @@ pub fn archive_older(db: &Database, days: i64) -> Result<()> {
     Ok(())
 }
 
+/// Permanently delete archived issues (only those archived more than `older`
+/// days ago, if given), asking first unless `force`.
+pub fn purge(db: &Database, older: Option<u32>, force: bool) -> Result<()> {
+    let issues = db.purgeable_archived(older)?;
+    if issues.is_empty() {
+        match older {
+            Some(days) => info(format_args!(
+                "No issues archived more than {} days ago to purge",
+                days
+            )),
+            None => info("No archived issues to purge"),
+        }
+        return Ok(());
+    }
+
+    if !force {
+        for issue in &issues {
+            println!("#{:<4} {:8} {}", issue.id, issue.priority, issue.title);
+        }
+        print!(
+            "Permanently delete {} archived issue(s)? [y/N] ",
+            issues.len()
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let count = db.purge_archived(older)?;
+    info(format_args!("Purged {} archived issue(s)", count));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,13 +200,104 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_archive_matching_label_skips_open_issues() {
+        let (db, _dir) = setup_test_db();
+        let done1 = db.create_issue("Done one", None, "low").unwrap();
+        let done2 = db.create_issue("Done two", None, "high").unwrap();
+        let still_open = db.create_issue("Not done yet", None, "low").unwrap();
+        let other = db.create_issue("Unlabeled", None, "low").unwrap();
+        for id in [done1, done2, still_open] {
+            db.add_label(id, "done").unwrap();
+        }
+        for id in [done1, done2, other] {
+            db.close_issue(id, None).unwrap();
+        }
+
+        archive_matching(&db, Some("done"), None, Some("sweep"), None).unwrap();
+
+        let status = |id| db.get_issue(id).unwrap().unwrap().status;
+        assert_eq!(status(done1), "archived");
+        assert_eq!(status(done2), "archived");
+        assert_eq!(status(still_open), "open");
+        assert_eq!(status(other), "closed");
+        assert!(db.get_comments(done1, None).unwrap()[0]
+            .content
+            .contains("sweep"));
+    }
+
+    #[test]
+    fn test_archive_matching_label_and_priority() {
+        let (db, _dir) = setup_test_db();
+        let low = db.create_issue("Low", None, "low").unwrap();
+        let high = db.create_issue("High", None, "high").unwrap();
+        for id in [low, high] {
+            db.add_label(id, "done").unwrap();
+            db.close_issue(id, None).unwrap();
+        }
+
+        archive_matching(&db, Some("done"), Some("low"), None, None).unwrap();
+
+        assert_eq!(db.get_issue(low).unwrap().unwrap().status, "archived");
+        assert_eq!(db.get_issue(high).unwrap().unwrap().status, "closed");
+    }
+
+    fn archive_days_ago(db: &Database, title: &str, days: i64) -> i64 {
+        let id = db.create_issue(title, None, "medium").unwrap();
+        db.close_issue(id, None).unwrap();
+        db.archive_issue(id).unwrap();
+        let then = (chrono::Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        db.backdate_archived_at(id, &then);
+        id
+    }
+
+    #[test]
+    fn test_purge_removes_only_archived_issues() {
+        let (db, _dir) = setup_test_db();
+        let archived = archive_days_ago(&db, "Archived", 0);
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.close_issue(closed, None).unwrap();
+        let open = db.create_issue("Open", None, "medium").unwrap();
+
+        purge(&db, None, true).unwrap();
+
+        assert!(db.get_issue(archived).unwrap().is_none());
+        assert!(db.get_issue(closed).unwrap().is_some());
+        assert!(db.get_issue(open).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_purge_respects_older_threshold() {
+        let (db, _dir) = setup_test_db();
+        let old = archive_days_ago(&db, "Old", 90);
+        let recent = archive_days_ago(&db, "Recent", 5);
+
+        purge(&db, Some(30), true).unwrap();
+
+        assert!(db.get_issue(old).unwrap().is_none());
+        assert_eq!(db.get_issue(recent).unwrap().unwrap().status, "archived");
+    }
+
+    #[test]
+    fn test_purge_keeps_archived_parent_of_open_subissue() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        db.close_issue(parent, None).unwrap();
+        db.archive_issue(parent).unwrap();
+
+        assert!(db.purgeable_archived(None).unwrap().is_empty());
+        assert_eq!(db.purge_archived(None).unwrap(), 0);
+        assert!(db.get_issue(child).unwrap().is_some());
+    }
+
     #[test]
     fn test_archive_closed_issue() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
 
-        archive(&db, id).unwrap();
+        archive(&db, id, None, None).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(
             archived.iter().any(|i| i.id == id),
@@ -102,12 +305,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_archive_dropped_issue() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Won't do", None, "low").unwrap();
+        db.drop_issue(id).unwrap();
+
+        archive(&db, id, None, None).unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "archived");
+        unarchive(&db, id).unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "wontdo");
+    }
+
+    #[test]
+    fn test_archive_with_note_adds_comment() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        db.close_issue(id, None).unwrap();
+
+        archive(&db, id, Some("won't fix"), None).unwrap();
+        let comments = db.get_comments(id, None).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].content, "[archived] won't fix");
+    }
+
+    #[test]
+    fn test_archive_without_note_adds_no_comment() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        db.close_issue(id, None).unwrap();
+
+        archive(&db, id, None, None).unwrap();
+        assert!(db.get_comments(id, None).unwrap().is_empty());
+    }
+
     #[test]
     fn test_archive_open_issue_fails() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = archive(&db, id);
+        let result = archive(&db, id, None, None);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -119,7 +356,7 @@ mod tests {
     fn test_archive_nonexistent_fails() {
         let (db, _dir) = setup_test_db();
 
-        let result = archive(&db, 99999);
+        let result = archive(&db, 99999, None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -128,8 +365,8 @@ mod tests {
     fn test_unarchive_issue() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
-        archive(&db, id).unwrap();
+        db.close_issue(id, None).unwrap();
+        archive(&db, id, None, None).unwrap();
 
         unarchive(&db, id).unwrap();
         let archived = db.list_archived_issues().unwrap();
@@ -170,8 +407,8 @@ mod tests {
     fn test_list_with_archived() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
-        archive(&db, id).unwrap();
+        db.close_issue(id, None).unwrap();
+        archive(&db, id, None, None).unwrap();
 
         list(&db).unwrap();
         let archived = db.list_archived_issues().unwrap();
@@ -183,7 +420,7 @@ mod tests {
     fn test_archive_older_none() {
         let (db, _dir) = setup_test_db();
 
-        archive_older(&db, 30).unwrap();
+        archive_older(&db, 30, false).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(
             archived.is_empty(),
@@ -191,13 +428,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_archive_older_dry_run_changes_nothing() {
+        let (db, _dir) = setup_test_db();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        let open = db.create_issue("Open", None, "medium").unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        let candidates = db.closed_older_than(0).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].id, closed);
+
+        archive_older(&db, 0, true).unwrap();
+        assert_eq!(db.get_issue(closed).unwrap().unwrap().status, "closed");
+        assert_eq!(db.get_issue(open).unwrap().unwrap().status, "open");
+        assert!(db.list_archived_issues().unwrap().is_empty());
+
+        archive_older(&db, 0, false).unwrap();
+        assert_eq!(db.get_issue(closed).unwrap().unwrap().status, "archived");
+    }
+
     #[test]
     fn test_archive_unarchive_roundtrip() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
 
-        archive(&db, id).unwrap();
+        archive(&db, id, None, None).unwrap();
         let archived = db.list_archived_issues().unwrap();
         assert!(archived.iter().any(|i| i.id == id));
 
@@ -210,8 +467,8 @@ mod tests {
     fn test_archived_issue_not_in_open_or_closed_list() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
-        archive(&db, id).unwrap();
+        db.close_issue(id, None).unwrap();
+        archive(&db, id, None, None).unwrap();
 
         let open_issues = db.list_issues(Some("open"), None, None).unwrap();
         let closed_issues = db.list_issues(Some("closed"), None, None).unwrap();
@@ -225,7 +482,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue(&title, None, "medium").unwrap();
 
-            let result = archive(&db, id);
+            let result = archive(&db, id, None, None);
             prop_assert!(result.is_err());
         }
 
@@ -233,9 +490,9 @@ mod tests {
         fn prop_archive_closed_succeeds(title in "[a-zA-Z0-9 ]{1,30}") {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue(&title, None, "medium").unwrap();
-            db.close_issue(id).unwrap();
+            db.close_issue(id, None).unwrap();
 
-            archive(&db, id).unwrap();
+            archive(&db, id, None, None).unwrap();
             let archived = db.list_archived_issues().unwrap();
             prop_assert!(archived.iter().any(|i| i.id == id));
         }
@@ -0,0 +1,88 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+use crate::models::Attachment;
+use crate::utils::info;
+
+/// One line of an attachment listing: the path or URL, then its description.
+pub fn format_attachment(attachment: &Attachment) -> String {
+    match &attachment.description {
+        Some(desc) => format!("  {} — {}", attachment.path_or_url, desc),
+        None => format!("  {}", attachment.path_or_url),
+    }
+}
+
+/// Link a file path or URL to an issue. The path is stored as given; the
+/// file itself isn't copied or checked.
+pub fn add(
+    db: &Database,
+    issue_id: i64,
+    path_or_url: &str,
+    description: Option<&str>,
+) -> Result<()> {
+    db.require_issue(issue_id)?;
+    if path_or_url.trim().is_empty() {
+        bail!("Attachment path or URL cannot be empty");
+    }
+    db.add_attachment(issue_id, path_or_url, description)?;
+    info(format_args!("Attached {} to #{}", path_or_url, issue_id));
+    Ok(())
+}
+
+pub fn list(db: &Database, issue_id: i64) -> Result<()> {
+    db.require_issue(issue_id)?;
+    let attachments = db.get_attachments(issue_id)?;
+    if attachments.is_empty() {
+        println!("No attachments on #{}", issue_id);
+        return Ok(());
+    }
+    println!("Attachments on #{}:", issue_id);
+    for attachment in &attachments {
+        println!("{}", format_attachment(attachment));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_add_and_list_attachments() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        add(&db, id, "logs/crash.txt", Some("Crash log")).unwrap();
+        add(&db, id, "https://example.com/spec", None).unwrap();
+        assert!(list(&db, id).is_ok());
+
+        let lines: Vec<String> = db
+            .get_attachments(id)
+            .unwrap()
+            .iter()
+            .map(format_attachment)
+            .collect();
+        assert_eq!(
+            lines,
+            vec!["  logs/crash.txt — Crash log", "  https://example.com/spec"]
+        );
+    }
+
+    #[test]
+    fn test_add_attachment_rejects_missing_issue_and_empty_path() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Issue", None, "medium").unwrap();
+
+        assert!(add(&db, 999, "file.txt", None).is_err());
+        assert!(add(&db, id, "  ", None).is_err());
+        assert!(db.get_attachments(id).unwrap().is_empty());
+    }
+}
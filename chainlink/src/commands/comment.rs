@@ -1,14 +1,101 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
 
 use crate::db::Database;
+use crate::utils::info;
 
-pub fn run(db: &Database, issue_id: i64, content: &str) -> Result<()> {
+pub fn run(db: &Database, issue_id: i64, content: &str, author: Option<&str>) -> Result<()> {
     db.require_issue(issue_id)?;
-    db.add_comment(issue_id, content)?;
-    println!("Added comment to issue #{}", issue_id);
+    db.add_comment_by(issue_id, content, author)?;
+    info(format_args!("Added comment to issue #{}", issue_id));
     Ok(())
 }
 
+/// Replace the content of the most recent comment on an issue (`--amend`).
+pub fn amend(db: &Database, issue_id: i64, content: &str) -> Result<()> {
+    db.require_issue(issue_id)?;
+    let Some(comment_id) = db.last_comment_id(issue_id)? else {
+        bail!("Issue #{} has no comments to amend", issue_id);
+    };
+    db.update_comment(comment_id, content)?;
+    info(format_args!("Amended last comment on issue #{}", issue_id));
+    Ok(())
+}
+
+/// Add (or amend) a comment whose body is read from a file (e.g. a pasted log or stack trace).
+pub fn run_from_file(
+    db: &Database,
+    issue_id: i64,
+    path: &Path,
+    amend: bool,
+    author: Option<&str>,
+) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read comment file {}", path.display()))?;
+    save(
+        db,
+        issue_id,
+        strip_trailing_newlines(&content),
+        amend,
+        author,
+    )
+}
+
+/// Add (or amend) a comment whose body is read from stdin (`chainlink comment <id> -`).
+pub fn run_from_stdin(
+    db: &Database,
+    issue_id: i64,
+    amend: bool,
+    author: Option<&str>,
+) -> Result<()> {
+    let mut content = String::new();
+    io::stdin()
+        .read_to_string(&mut content)
+        .context("Failed to read comment from stdin")?;
+    save(
+        db,
+        issue_id,
+        strip_trailing_newlines(&content),
+        amend,
+        author,
+    )
+}
+
+pub fn save(
+    db: &Database,
+    issue_id: i64,
+    content: &str,
+    amend: bool,
+    author: Option<&str>,
+) -> Result<()> {
+    if amend {
+        self::amend(db, issue_id, content)
+    } else {
+        run(db, issue_id, content, author)
+    }
+}
+
+/// Pin (or unpin) a comment so `show` lists it before the others.
+pub fn pin(db: &Database, issue_id: i64, comment_id: i64, pinned: bool) -> Result<()> {
+    db.require_issue(issue_id)?;
+    if !db.set_comment_pinned(issue_id, comment_id, pinned)? {
+        bail!("Issue #{} has no comment {}", issue_id, comment_id);
+    }
+    info(format_args!(
+        "{} comment {} on issue #{}",
+        if pinned { "Pinned" } else { "Unpinned" },
+        comment_id,
+        issue_id
+    ));
+    Ok(())
+}
+
+fn strip_trailing_newlines(s: &str) -> &str {
+    s.trim_end_matches(['\r', '\n'])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,10 +116,10 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, "This is a comment");
+        let result = run(&db, issue_id, "This is a comment", None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments.len(), 1);
         assert_eq!(comments[0].content, "This is a comment");
     }
@@ -41,7 +128,7 @@ mod tests {
     fn test_add_comment_to_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = run(&db, 99999, "Comment on nothing");
+        let result = run(&db, 99999, "Comment on nothing", None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -51,11 +138,11 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        run(&db, issue_id, "First comment").unwrap();
-        run(&db, issue_id, "Second comment").unwrap();
-        run(&db, issue_id, "Third comment").unwrap();
+        run(&db, issue_id, "First comment", None).unwrap();
+        run(&db, issue_id, "Second comment", None).unwrap();
+        run(&db, issue_id, "Third comment", None).unwrap();
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments.len(), 3);
         assert_eq!(comments[0].content, "First comment");
         assert_eq!(comments[1].content, "Second comment");
@@ -67,10 +154,10 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, "");
+        let result = run(&db, issue_id, "", None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments.len(), 1);
         assert_eq!(comments[0].content, "");
     }
@@ -81,10 +168,10 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let unicode_content = "こんにちは 🎉 مرحبا αβγδ ← → ↑ ↓";
-        let result = run(&db, issue_id, unicode_content);
+        let result = run(&db, issue_id, unicode_content, None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments[0].content, unicode_content);
     }
 
@@ -94,10 +181,10 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let long_content = "a".repeat(100000);
-        let result = run(&db, issue_id, &long_content);
+        let result = run(&db, issue_id, &long_content, None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments[0].content.len(), 100000);
     }
 
@@ -107,10 +194,10 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let multiline = "Line 1\nLine 2\nLine 3\n\nLine 5";
-        let result = run(&db, issue_id, multiline);
+        let result = run(&db, issue_id, multiline, None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments[0].content, multiline);
     }
 
@@ -120,10 +207,10 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let special = "Quotes: \"test\" 'test' `test` | Symbols: @#$%^&*() | SQL: '; DROP TABLE;--";
-        let result = run(&db, issue_id, special);
+        let result = run(&db, issue_id, special, None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments[0].content, special);
     }
 
@@ -133,10 +220,10 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let malicious = "'); DELETE FROM comments; --";
-        run(&db, issue_id, malicious).unwrap();
+        run(&db, issue_id, malicious, None).unwrap();
 
         // Verify comment was stored literally, not executed
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments.len(), 1);
         assert_eq!(comments[0].content, malicious);
 
@@ -149,13 +236,13 @@ mod tests {
     fn test_comment_on_closed_issue() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
         // Should still be able to comment on closed issues
-        let result = run(&db, issue_id, "Comment on closed issue");
+        let result = run(&db, issue_id, "Comment on closed issue", None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments.len(), 1);
     }
 
@@ -165,13 +252,83 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         let with_null = "before\0after";
-        let result = run(&db, issue_id, with_null);
+        let result = run(&db, issue_id, with_null, None);
         assert!(result.is_ok());
 
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments[0].content, with_null);
     }
 
+    #[test]
+    fn test_comment_from_file() {
+        let (db, dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        let path = dir.path().join("trace.log");
+        let body = "panicked at src/main.rs:10:5\n  0: foo::bar\n\n  1: main";
+        fs::write(&path, format!("{}\n", body)).unwrap();
+
+        run_from_file(&db, issue_id, &path, false, None).unwrap();
+
+        let comments = db.get_comments(issue_id, None).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].content, body);
+    }
+
+    #[test]
+    fn test_comment_from_missing_file() {
+        let (db, dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        let result = run_from_file(&db, issue_id, &dir.path().join("nope.txt"), false, None);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to read comment file"));
+        assert!(db.get_comments(issue_id, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_amend_replaces_only_latest_comment() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        run(&db, issue_id, "First comment", None).unwrap();
+        run(&db, issue_id, "Secnod comment", None).unwrap();
+        save(&db, issue_id, "Second comment", true, None).unwrap();
+
+        let comments = db.get_comments(issue_id, None).unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].content, "First comment");
+        assert_eq!(comments[1].content, "Second comment");
+    }
+
+    #[test]
+    fn test_amend_without_comments_fails() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        let result = amend(&db, issue_id, "Nothing to fix");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("no comments"));
+        assert!(db.get_comments(issue_id, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pin_requires_comment_on_issue() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+        let comment_id = db.add_comment(other, "Elsewhere").unwrap();
+
+        let result = pin(&db, issue_id, comment_id, true);
+        assert!(result.unwrap_err().to_string().contains("has no comment"));
+
+        pin(&db, other, comment_id, true).unwrap();
+        assert!(db.get_comments(other, None).unwrap()[0].pinned);
+    }
+
     // ==================== Property-Based Tests ====================
 
     proptest! {
@@ -180,10 +337,10 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            let result = run(&db, issue_id, &content);
+            let result = run(&db, issue_id, &content, None);
             prop_assert!(result.is_ok());
 
-            let comments = db.get_comments(issue_id).unwrap();
+            let comments = db.get_comments(issue_id, None).unwrap();
             prop_assert_eq!(comments.len(), 1);
             prop_assert_eq!(&comments[0].content, &content);
         }
@@ -192,7 +349,7 @@ mod tests {
         fn prop_nonexistent_issue_fails(issue_id in 1000i64..10000) {
             let (db, _dir) = setup_test_db();
             // Don't create any issues
-            let result = run(&db, issue_id, "Comment");
+            let result = run(&db, issue_id, "Comment", None);
             prop_assert!(result.is_err());
         }
 
@@ -202,10 +359,10 @@ mod tests {
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
             for i in 0..count {
-                run(&db, issue_id, &format!("Comment {}", i)).unwrap();
+                run(&db, issue_id, &format!("Comment {}", i), None).unwrap();
             }
 
-            let comments = db.get_comments(issue_id).unwrap();
+            let comments = db.get_comments(issue_id, None).unwrap();
             prop_assert_eq!(comments.len(), count);
 
             for (i, comment) in comments.iter().enumerate() {
@@ -223,9 +380,9 @@ mod tests {
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
             let content = format!("{}{}{}", prefix, emoji, suffix);
-            run(&db, issue_id, &content).unwrap();
+            run(&db, issue_id, &content, None).unwrap();
 
-            let comments = db.get_comments(issue_id).unwrap();
+            let comments = db.get_comments(issue_id, None).unwrap();
             prop_assert_eq!(&comments[0].content, &content);
         }
     }
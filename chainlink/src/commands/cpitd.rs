@@ -3,13 +3,16 @@
 //! Shells out to the `cpitd` Python tool, parses its JSON output,
 //! and creates chainlink issues for detected code clones.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 use std::process::Command;
 
 use anyhow::{bail, Context, Result};
 use serde::Deserialize;
 
 use crate::db::Database;
+use crate::models::Issue;
+use crate::utils::info;
 
 // ---------------------------------------------------------------------------
 // cpitd JSON output types
@@ -41,7 +44,8 @@ struct CpitdCloneGroup {
 // Installation detection
 // ---------------------------------------------------------------------------
 
-fn find_cpitd() -> bool {
+/// Whether the `cpitd` binary is on PATH.
+pub fn find_cpitd() -> bool {
     Command::new("cpitd")
         .arg("--version")
         .output()
@@ -115,6 +119,32 @@ fn dedup_marker(file_a: &str, file_b: &str) -> String {
     format!("<!-- cpitd:file_a={}:file_b={} -->", a, b)
 }
 
+/// The dedup marker embedded in a clone issue description, if any.
+fn extract_marker(description: &str) -> Option<&str> {
+    let start = description.find("<!-- cpitd:")?;
+    let len = description[start..].find(" -->")? + " -->".len();
+    Some(&description[start..start + len])
+}
+
+/// The two file paths recorded in a clone issue's dedup marker.
+pub fn marker_files(description: &str) -> Option<(&str, &str)> {
+    let body = extract_marker(description)?
+        .strip_prefix("<!-- cpitd:file_a=")?
+        .strip_suffix(" -->")?;
+    body.split_once(":file_b=")
+}
+
+/// `description` with the dedup marker (and the blank line after it) removed.
+pub fn strip_marker(description: &str) -> String {
+    match extract_marker(description) {
+        Some(marker) => description
+            .replacen(marker, "", 1)
+            .trim_start_matches('\n')
+            .to_string(),
+        None => description.to_string(),
+    }
+}
+
 fn find_existing_clone_issue(db: &Database, file_a: &str, file_b: &str) -> Result<Option<i64>> {
     let marker = dedup_marker(file_a, file_b);
     let issues = db.list_issues(Some("open"), Some("cpitd"), None)?;
@@ -169,7 +199,29 @@ fn format_clone_description(report: &CpitdCloneReport) -> String {
     desc
 }
 
-fn create_clone_issue(db: &Database, report: &CpitdCloneReport, quiet: bool) -> Result<i64> {
+/// Line-count thresholds above which clones are filed at a higher priority.
+/// Unset thresholds leave every clone at `low`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SeverityThresholds {
+    pub medium_lines: Option<usize>,
+    pub high_lines: Option<usize>,
+}
+
+fn severity_for(total_lines: usize, thresholds: &SeverityThresholds) -> &'static str {
+    if thresholds.high_lines.is_some_and(|t| total_lines >= t) {
+        "high"
+    } else if thresholds.medium_lines.is_some_and(|t| total_lines >= t) {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+fn create_clone_issue(
+    db: &Database,
+    report: &CpitdCloneReport,
+    thresholds: &SeverityThresholds,
+) -> Result<i64> {
     let title = format!(
         "Code clone: {} <-> {} ({} lines)",
         shorten_path(&report.file_a),
@@ -178,13 +230,13 @@ fn create_clone_issue(db: &Database, report: &CpitdCloneReport, quiet: bool) ->
     );
 
     let description = format_clone_description(report);
-    let id = db.create_issue(&title, Some(&description), "low")?;
+    let priority = severity_for(report.total_cloned_lines, thresholds);
+    let id = db.create_issue(&title, Some(&description), priority)?;
+    db.set_issue_source(id, "cpitd")?;
     db.add_label(id, "cpitd")?;
     db.add_label(id, "refactor")?;
 
-    if !quiet {
-        println!("  Created issue #{}: {}", id, title);
-    }
+    info(format_args!("  Created issue #{}: {}", id, title));
 
     Ok(id)
 }
@@ -199,7 +251,7 @@ fn relate_clone_issues(db: &Database, created: &[(i64, String, String)]) -> Resu
         for i in 0..ids.len() {
             for j in (i + 1)..ids.len() {
                 // Ignore errors (e.g. relation already exists)
-                let _ = db.add_relation(ids[i], ids[j]);
+                let _ = db.add_relation(ids[i], ids[j], None);
             }
         }
     }
@@ -210,53 +262,106 @@ fn relate_clone_issues(db: &Database, created: &[(i64, String, String)]) -> Resu
 // Public commands
 // ---------------------------------------------------------------------------
 
-pub fn scan(
-    db: &Database,
-    paths: &[String],
-    min_tokens: u32,
-    ignore_patterns: &[String],
-    dry_run: bool,
-    quiet: bool,
-) -> Result<()> {
+/// Options for `cpitd scan`.
+pub struct ScanOpts<'a> {
+    pub paths: &'a [String],
+    pub min_tokens: u32,
+    pub ignore_patterns: &'a [String],
+    pub thresholds: SeverityThresholds,
+    /// Close open clone issues within the scanned paths whose pair is no
+    /// longer reported
+    pub auto_close: bool,
+    /// Leave pairs that already have an open issue untouched
+    pub skip_tracked: bool,
+    pub dry_run: bool,
+}
+
+pub fn scan(db: &Database, opts: &ScanOpts<'_>) -> Result<()> {
     if !find_cpitd() {
         return suggest_install();
     }
 
-    if !quiet {
-        println!("Running cpitd clone detection...");
-    }
+    info("Running cpitd clone detection...");
 
-    let output = run_cpitd(paths, min_tokens, ignore_patterns)?;
+    let output = run_cpitd(opts.paths, opts.min_tokens, opts.ignore_patterns)?;
 
     if output.clone_reports.is_empty() {
-        if !quiet {
-            println!("No code clones detected.");
-        }
-        return Ok(());
-    }
-
-    if !quiet {
-        println!("Found {} clone pair(s).\n", output.total_pairs);
+        info("No code clones detected.");
+    } else {
+        info(format_args!(
+            "Found {} clone pair(s).\n",
+            output.total_pairs
+        ));
     }
 
-    if dry_run {
-        for report in &output.clone_reports {
+    if opts.dry_run {
+        let reports = if opts.skip_tracked {
+            filter_tracked(db, &output.clone_reports)?
+        } else {
+            output.clone_reports.iter().collect()
+        };
+        for report in reports {
             println!(
-                "  Would create: {} <-> {} ({} lines, {} group(s))",
+                "  Would create: {} <-> {} ({} lines, {} group(s), {} priority)",
                 report.file_a,
                 report.file_b,
                 report.total_cloned_lines,
                 report.groups.len(),
+                severity_for(report.total_cloned_lines, &opts.thresholds),
             );
         }
+        if opts.auto_close {
+            for issue in resolved_clone_issues(db, &output.clone_reports, opts.paths)? {
+                println!("  Would close: #{} {}", issue.id, issue.title);
+            }
+        }
         return Ok(());
     }
 
-    let mut created_count = 0usize;
-    let mut updated_count = 0usize;
+    let summary = apply_reports(db, &output.clone_reports, opts)?;
+
+    info(format_args!(
+        "\ncpitd scan complete: {} created, {} updated, {} resolved",
+        summary.created, summary.updated, summary.resolved,
+    ));
+    if summary.skipped > 0 {
+        info(format_args!(
+            "Skipped {} already-tracked pair(s)",
+            summary.skipped
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ScanSummary {
+    created: usize,
+    updated: usize,
+    resolved: usize,
+    skipped: usize,
+}
+
+/// Create or update issues for the reported clones and, with `auto_close`,
+/// close open clone issues in the scanned paths whose pair was not reported
+/// this run.
+fn apply_reports(
+    db: &Database,
+    reports: &[CpitdCloneReport],
+    opts: &ScanOpts<'_>,
+) -> Result<ScanSummary> {
+    let mut summary = ScanSummary::default();
     let mut created_ids: Vec<(i64, String, String)> = Vec::new();
 
-    for report in &output.clone_reports {
+    let to_process = if opts.skip_tracked {
+        let untracked = filter_tracked(db, reports)?;
+        summary.skipped = reports.len() - untracked.len();
+        untracked
+    } else {
+        reports.iter().collect()
+    };
+
+    for report in to_process {
         match find_existing_clone_issue(db, &report.file_a, &report.file_b)? {
             Some(existing_id) => {
                 let comment = format!(
@@ -265,15 +370,16 @@ pub fn scan(
                     report.groups.len(),
                 );
                 db.add_comment(existing_id, &comment)?;
-                updated_count += 1;
-                if !quiet {
-                    println!("  Updated issue #{} (clone still present)", existing_id);
-                }
+                summary.updated += 1;
+                info(format_args!(
+                    "  Updated issue #{} (clone still present)",
+                    existing_id
+                ));
             }
             None => {
-                let id = create_clone_issue(db, report, quiet)?;
+                let id = create_clone_issue(db, report, &opts.thresholds)?;
                 created_ids.push((id, report.file_a.clone(), report.file_b.clone()));
-                created_count += 1;
+                summary.created += 1;
             }
         }
     }
@@ -282,14 +388,86 @@ pub fn scan(
         relate_clone_issues(db, &created_ids)?;
     }
 
-    if !quiet {
-        println!(
-            "\ncpitd scan complete: {} created, {} updated",
-            created_count, updated_count,
-        );
+    if opts.auto_close {
+        summary.resolved = close_resolved_clone_issues(db, reports, opts.paths)?;
     }
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Drop reports whose file pair already has an open clone issue.
+fn filter_tracked<'r>(
+    db: &Database,
+    reports: &'r [CpitdCloneReport],
+) -> Result<Vec<&'r CpitdCloneReport>> {
+    let mut untracked = Vec::new();
+    for report in reports {
+        if find_existing_clone_issue(db, &report.file_a, &report.file_b)?.is_none() {
+            untracked.push(report);
+        }
+    }
+    Ok(untracked)
+}
+
+/// Whether `file` lies under one of the scanned `paths`; no paths means the
+/// whole project was scanned.
+fn in_scanned_paths(file: &str, paths: &[String]) -> bool {
+    let file = Path::new(file.trim_start_matches("./"));
+    paths.is_empty()
+        || paths.iter().any(|path| {
+            let path = path.trim_start_matches("./");
+            path.is_empty() || path == "." || file.starts_with(path)
+        })
+}
+
+/// Open clone issues whose files are both within the scanned `paths` but
+/// whose pair matches none of `reports`.
+fn resolved_clone_issues(
+    db: &Database,
+    reports: &[CpitdCloneReport],
+    paths: &[String],
+) -> Result<Vec<Issue>> {
+    let seen: HashSet<String> = reports
+        .iter()
+        .map(|r| dedup_marker(&r.file_a, &r.file_b))
+        .collect();
+
+    let mut resolved = Vec::new();
+    for issue in db.list_issues(Some("open"), Some("cpitd"), None)? {
+        let Some(description) = issue.description.as_deref() else {
+            continue;
+        };
+        let (Some(marker), Some((file_a, file_b))) =
+            (extract_marker(description), marker_files(description))
+        else {
+            continue;
+        };
+        if seen.contains(marker)
+            || !in_scanned_paths(file_a, paths)
+            || !in_scanned_paths(file_b, paths)
+        {
+            continue;
+        }
+        resolved.push(issue);
+    }
+    Ok(resolved)
+}
+
+fn close_resolved_clone_issues(
+    db: &Database,
+    reports: &[CpitdCloneReport],
+    paths: &[String],
+) -> Result<usize> {
+    let resolved = resolved_clone_issues(db, reports, paths)?;
+    for issue in &resolved {
+        db.add_comment(issue.id, "[cpitd] clone resolved")?;
+        db.close_issue(issue.id, None)?;
+        info(format_args!(
+            "  Closed issue #{} (clone no longer detected)",
+            issue.id
+        ));
+    }
+    Ok(resolved.len())
 }
 
 pub fn status(db: &Database) -> Result<()> {
@@ -311,16 +489,16 @@ pub fn clear(db: &Database) -> Result<()> {
     let issues = db.list_issues(Some("open"), Some("cpitd"), None)?;
 
     if issues.is_empty() {
-        println!("No open cpitd clone issues to close.");
+        info("No open cpitd clone issues to close.");
         return Ok(());
     }
 
     let count = issues.len();
     for issue in &issues {
-        db.close_issue(issue.id)?;
+        db.close_issue(issue.id, None)?;
     }
 
-    println!("Closed {} cpitd clone issue(s).", count);
+    info(format_args!("Closed {} cpitd clone issue(s).", count));
     Ok(())
 }
 
@@ -373,6 +551,20 @@ mod tests {
         assert!(output.clone_reports.is_empty());
     }
 
+    #[test]
+    fn test_marker_files_parses_sample_description() {
+        let desc = "<!-- cpitd:file_a=src/a.rs:file_b=src/b.rs -->\n\nDetected code clones between:\n- `src/a.rs`";
+        assert_eq!(marker_files(desc), Some(("src/a.rs", "src/b.rs")));
+        assert_eq!(
+            strip_marker(desc),
+            "Detected code clones between:\n- `src/a.rs`"
+        );
+
+        assert_eq!(marker_files("Plain description"), None);
+        assert_eq!(strip_marker("Plain description"), "Plain description");
+        assert_eq!(marker_files("<!-- cpitd:garbage -->"), None);
+    }
+
     #[test]
     fn test_dedup_marker_commutative() {
         let m1 = dedup_marker("src/a.rs", "src/b.rs");
@@ -389,6 +581,195 @@ mod tests {
         assert!(marker.ends_with(" -->"));
     }
 
+    #[test]
+    fn test_severity_for_defaults_to_low() {
+        let none = SeverityThresholds::default();
+        assert_eq!(severity_for(0, &none), "low");
+        assert_eq!(severity_for(10_000, &none), "low");
+    }
+
+    #[test]
+    fn test_severity_for_thresholds() {
+        let t = SeverityThresholds {
+            medium_lines: Some(20),
+            high_lines: Some(100),
+        };
+        assert_eq!(severity_for(19, &t), "low");
+        assert_eq!(severity_for(20, &t), "medium");
+        assert_eq!(severity_for(99, &t), "medium");
+        assert_eq!(severity_for(100, &t), "high");
+
+        let high_only = SeverityThresholds {
+            medium_lines: None,
+            high_lines: Some(50),
+        };
+        assert_eq!(severity_for(49, &high_only), "low");
+        assert_eq!(severity_for(50, &high_only), "high");
+    }
+
+    #[test]
+    fn test_extract_marker() {
+        let marker = dedup_marker("src/a.rs", "src/b.rs");
+        let desc = format!("{}\n\nDetected code clones", marker);
+        assert_eq!(extract_marker(&desc), Some(marker.as_str()));
+        assert_eq!(extract_marker("No marker here"), None);
+    }
+
+    fn report(file_a: &str, file_b: &str) -> CpitdCloneReport {
+        CpitdCloneReport {
+            file_a: file_a.to_string(),
+            file_b: file_b.to_string(),
+            total_cloned_lines: 10,
+            groups: vec![CpitdCloneGroup {
+                lines_a: vec![1, 10],
+                lines_b: vec![20, 29],
+                line_count: 10,
+                token_count: 80,
+            }],
+        }
+    }
+
+    fn scan_opts(auto_close: bool) -> ScanOpts<'static> {
+        ScanOpts {
+            paths: &[],
+            min_tokens: 50,
+            ignore_patterns: &[],
+            thresholds: SeverityThresholds::default(),
+            auto_close,
+            skip_tracked: false,
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn test_rescan_closes_vanished_clone_issue() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+
+        let first = [
+            report("src/a.rs", "src/b.rs"),
+            report("src/c.rs", "src/d.rs"),
+        ];
+        let summary = apply_reports(&db, &first, &scan_opts(true)).unwrap();
+        assert_eq!(summary.created, 2);
+        let gone = find_existing_clone_issue(&db, "src/a.rs", "src/b.rs")
+            .unwrap()
+            .unwrap();
+        let kept = find_existing_clone_issue(&db, "src/c.rs", "src/d.rs")
+            .unwrap()
+            .unwrap();
+
+        // Second scan only reports c/d
+        let summary = apply_reports(&db, &first[1..], &scan_opts(true)).unwrap();
+        assert_eq!(
+            summary,
+            ScanSummary {
+                created: 0,
+                updated: 1,
+                resolved: 1,
+                skipped: 0,
+            }
+        );
+
+        let gone_issue = db.get_issue(gone).unwrap().unwrap();
+        assert_eq!(gone_issue.status, "closed");
+        let comments = db.get_comments(gone, None).unwrap();
+        assert_eq!(comments.last().unwrap().content, "[cpitd] clone resolved");
+        assert_eq!(db.get_issue(kept).unwrap().unwrap().status, "open");
+    }
+
+    #[test]
+    fn test_rescan_no_auto_close_keeps_issues_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+
+        apply_reports(&db, &[report("src/a.rs", "src/b.rs")], &scan_opts(false)).unwrap();
+        let summary = apply_reports(&db, &[], &scan_opts(false)).unwrap();
+        assert_eq!(summary.resolved, 0);
+        assert!(find_existing_clone_issue(&db, "src/a.rs", "src/b.rs")
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_auto_close_only_within_scanned_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+        let reports = [
+            report("src/a.rs", "src/b.rs"),
+            report("lib/c.rs", "lib/d.rs"),
+            report("src/e.rs", "lib/f.rs"),
+        ];
+        apply_reports(&db, &reports, &scan_opts(false)).unwrap();
+        let id = |a, b| find_existing_clone_issue(&db, a, b).unwrap().unwrap();
+        let (in_src, in_lib, across) = (
+            id("src/a.rs", "src/b.rs"),
+            id("lib/c.rs", "lib/d.rs"),
+            id("src/e.rs", "lib/f.rs"),
+        );
+
+        // A scan of src/ that finds nothing only resolves issues entirely in src/
+        let paths = ["./src".to_string()];
+        let opts = ScanOpts {
+            paths: &paths,
+            ..scan_opts(true)
+        };
+        let candidates: Vec<i64> = resolved_clone_issues(&db, &[], &paths)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(candidates, vec![in_src]);
+        let summary = apply_reports(&db, &[], &opts).unwrap();
+        assert_eq!(summary.resolved, 1);
+        assert_eq!(db.get_issue(in_src).unwrap().unwrap().status, "closed");
+        assert_eq!(db.get_issue(in_lib).unwrap().unwrap().status, "open");
+        assert_eq!(db.get_issue(across).unwrap().unwrap().status, "open");
+    }
+
+    #[test]
+    fn test_in_scanned_paths() {
+        let paths = ["src".to_string()];
+        assert!(in_scanned_paths("src/a.rs", &paths));
+        assert!(in_scanned_paths("./src/a.rs", &paths));
+        assert!(!in_scanned_paths("srcs/a.rs", &paths));
+        assert!(!in_scanned_paths("lib/a.rs", &paths));
+        assert!(in_scanned_paths("lib/a.rs", &[]));
+        assert!(in_scanned_paths("lib/a.rs", &[".".to_string()]));
+    }
+
+    #[test]
+    fn test_skip_tracked_filters_existing_pairs() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).unwrap();
+
+        apply_reports(&db, &[report("src/a.rs", "src/b.rs")], &scan_opts(true)).unwrap();
+        let tracked = find_existing_clone_issue(&db, "src/a.rs", "src/b.rs")
+            .unwrap()
+            .unwrap();
+
+        let reports = [
+            report("src/b.rs", "src/a.rs"),
+            report("src/c.rs", "src/d.rs"),
+        ];
+        let untracked = filter_tracked(&db, &reports).unwrap();
+        assert_eq!(untracked.len(), 1);
+        assert_eq!(untracked[0].file_a, "src/c.rs");
+
+        let opts = ScanOpts {
+            skip_tracked: true,
+            ..scan_opts(true)
+        };
+        let summary = apply_reports(&db, &reports, &opts).unwrap();
+        assert_eq!(summary.created, 1);
+        assert_eq!(summary.updated, 0);
+        assert_eq!(summary.skipped, 1);
+        // Skipped pairs were still detected, so they must not be auto-closed
+        assert_eq!(summary.resolved, 0);
+        assert!(db.get_comments(tracked, None).unwrap().is_empty());
+        assert_eq!(db.get_issue(tracked).unwrap().unwrap().status, "open");
+    }
+
     #[test]
     fn test_shorten_path_basic() {
         assert_eq!(shorten_path("src/commands/foo.rs"), "foo.rs");
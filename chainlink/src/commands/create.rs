@@ -1,6 +1,8 @@
 use anyhow::{bail, Result};
+use std::io::{self, IsTerminal, Write};
 
-use crate::db::Database;
+use crate::store::Store;
+use crate::utils::{info, verbosity};
 
 const VALID_PRIORITIES: [&str; 4] = ["low", "medium", "high", "critical"];
 
@@ -67,64 +69,121 @@ pub fn list_templates() -> Vec<&'static str> {
     TEMPLATES.iter().map(|t| t.name).collect()
 }
 
-pub fn validate_priority(priority: &str) -> bool {
-    VALID_PRIORITIES.contains(&priority)
+/// Check a priority before it reaches the database; the error lists the valid values.
+pub fn validate_priority(priority: &str) -> Result<()> {
+    if !VALID_PRIORITIES.contains(&priority) {
+        bail!(
+            "invalid priority '{}'; valid: {}",
+            priority,
+            VALID_PRIORITIES.join(", ")
+        );
+    }
+    Ok(())
 }
 
 /// Options shared by create and subissue commands.
 pub struct CreateOpts<'a> {
     pub labels: &'a [String],
     pub work: bool,
-    pub quiet: bool,
+    /// Skip the duplicate-title check
+    pub force: bool,
+    /// Priority used when neither `--priority` nor a template sets one
+    pub default_priority: &'a str,
+    /// Accept a title that is empty after trimming (for scripts)
+    pub allow_empty_title: bool,
+}
+
+/// Trim `title`, rejecting it if nothing is left unless explicitly allowed.
+fn check_title(title: &str, allow_empty: bool) -> Result<&str> {
+    let title = title.trim();
+    if title.is_empty() && !allow_empty {
+        bail!("Issue title is empty. Pass --allow-empty-title to create it anyway");
+    }
+    Ok(title)
+}
+
+/// Warn about open issues with similar titles. Returns false if the user
+/// declined to create the issue; non-interactive callers only get the warning.
+fn confirm_not_duplicate(db: &dyn Store, title: &str) -> Result<bool> {
+    let similar = db.find_similar_titles(title)?;
+    if similar.is_empty() {
+        return Ok(true);
+    }
+
+    eprintln!("Warning: Similar open issue(s) already exist:");
+    for (id, existing) in &similar {
+        eprintln!("  #{} {}", id, existing);
+    }
+
+    if !io::stdin().is_terminal() {
+        return Ok(true);
+    }
+
+    eprint!("Create anyway? [y/N] ");
+    io::stderr().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Resolve the priority, description and extra label for a new issue,
+/// applying `template` when one is named. Explicit values win over the
+/// template's defaults; a description is appended after its prefix.
+fn apply_template(
+    template: Option<&str>,
+    description: Option<&str>,
+    priority: Option<&str>,
+    default_priority: &str,
+) -> Result<(String, Option<String>, Option<&'static str>)> {
+    let Some(tmpl_name) = template else {
+        return Ok((
+            priority.unwrap_or(default_priority).to_string(),
+            description.map(|s| s.to_string()),
+            None,
+        ));
+    };
+    let tmpl = get_template(tmpl_name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unknown template '{}'. Available: {}",
+            tmpl_name,
+            list_templates().join(", ")
+        )
+    })?;
+
+    // Template priority is default, user can override
+    let priority = priority.unwrap_or(tmpl.priority);
+
+    // Combine template description prefix with user description
+    let desc = match (tmpl.description_prefix, description) {
+        (Some(prefix), Some(user_desc)) => Some(format!("{}\n\n{}", prefix, user_desc)),
+        (Some(prefix), None) => Some(prefix.to_string()),
+        (None, user_desc) => user_desc.map(|s| s.to_string()),
+    };
+
+    Ok((priority.to_string(), desc, Some(tmpl.label)))
 }
 
 pub fn run(
-    db: &Database,
+    db: &dyn Store,
     title: &str,
     description: Option<&str>,
-    priority: &str,
+    priority: Option<&str>,
     template: Option<&str>,
     opts: &CreateOpts<'_>,
 ) -> Result<()> {
-    // Apply template if specified
-    let (final_priority, final_description, template_label) = if let Some(tmpl_name) = template {
-        let tmpl = get_template(tmpl_name).ok_or_else(|| {
-            anyhow::anyhow!(
-                "Unknown template '{}'. Available: {}",
-                tmpl_name,
-                list_templates().join(", ")
-            )
-        })?;
-
-        // Template priority is default, user can override
-        let priority = if priority != "medium" {
-            priority
-        } else {
-            tmpl.priority
-        };
+    let title = check_title(title, opts.allow_empty_title)?;
 
-        // Combine template description prefix with user description
-        let desc = match (tmpl.description_prefix, description) {
-            (Some(prefix), Some(user_desc)) => Some(format!("{}\n\n{}", prefix, user_desc)),
-            (Some(prefix), None) => Some(prefix.to_string()),
-            (None, user_desc) => user_desc.map(|s| s.to_string()),
-        };
+    let (final_priority, final_description, template_label) =
+        apply_template(template, description, priority, opts.default_priority)?;
 
-        (priority.to_string(), desc, Some(tmpl.label))
-    } else {
-        (
-            priority.to_string(),
-            description.map(|s| s.to_string()),
-            None,
-        )
-    };
+    validate_priority(&final_priority)?;
+    for lbl in opts.labels {
+        db.normalize_label(lbl)?;
+    }
 
-    if !validate_priority(&final_priority) {
-        bail!(
-            "Invalid priority '{}'. Must be one of: {}",
-            final_priority,
-            VALID_PRIORITIES.join(", ")
-        );
+    if !opts.force && !confirm_not_duplicate(db, title)? {
+        println!("Cancelled.");
+        return Ok(());
     }
 
     let id = db.create_issue(title, final_description.as_deref(), &final_priority)?;
@@ -139,7 +198,7 @@ pub fn run(
         db.add_label(id, lbl)?;
     }
 
-    if opts.quiet {
+    if verbosity().is_quiet() {
         println!("{}", id);
     } else {
         println!("Created issue #{}", id);
@@ -152,10 +211,8 @@ pub fn run(
     if opts.work {
         if let Ok(Some(session)) = db.get_current_session() {
             db.set_session_issue(session.id, id)?;
-            if !opts.quiet {
-                println!("Now working on: #{} {}", id, title);
-            }
-        } else if !opts.quiet {
+            info(format_args!("Now working on: #{} {}", id, title));
+        } else {
             eprintln!("Warning: --work specified but no active session");
         }
     }
@@ -164,19 +221,20 @@ pub fn run(
 }
 
 pub fn run_subissue(
-    db: &Database,
+    db: &dyn Store,
     parent_id: i64,
     title: &str,
     description: Option<&str>,
-    priority: &str,
+    priority: Option<&str>,
+    template: Option<&str>,
     opts: &CreateOpts<'_>,
 ) -> Result<()> {
-    if !validate_priority(priority) {
-        bail!(
-            "Invalid priority '{}'. Must be one of: {}",
-            priority,
-            VALID_PRIORITIES.join(", ")
-        );
+    let title = check_title(title, opts.allow_empty_title)?;
+    let (priority, description, template_label) =
+        apply_template(template, description, priority, opts.default_priority)?;
+    validate_priority(&priority)?;
+    for lbl in opts.labels {
+        db.normalize_label(lbl)?;
     }
 
     // Verify parent exists
@@ -185,27 +243,33 @@ pub fn run_subissue(
         bail!("Parent issue #{} not found", parent_id);
     }
 
-    let id = db.create_subissue(parent_id, title, description, priority)?;
+    let id = db.create_subissue(parent_id, title, description.as_deref(), &priority)?;
+
+    // Auto-add label from template
+    if let Some(lbl) = template_label {
+        db.add_label(id, lbl)?;
+    }
 
     // Add user-specified labels
     for lbl in opts.labels {
         db.add_label(id, lbl)?;
     }
 
-    if opts.quiet {
+    if verbosity().is_quiet() {
         println!("{}", id);
     } else {
         println!("Created subissue #{} under #{}", id, parent_id);
+        if let Some(tmpl) = template {
+            println!("  Applied template: {}", tmpl);
+        }
     }
 
     // Set as active session work item
     if opts.work {
         if let Ok(Some(session)) = db.get_current_session() {
             db.set_session_issue(session.id, id)?;
-            if !opts.quiet {
-                println!("Now working on: #{} {}", id, title);
-            }
-        } else if !opts.quiet {
+            info(format_args!("Now working on: #{} {}", id, title));
+        } else {
             eprintln!("Warning: --work specified but no active session");
         }
     }
@@ -220,34 +284,68 @@ mod tests {
 
     // ==================== Unit Tests ====================
 
+    #[test]
+    fn test_check_title_trims_and_rejects_blank() {
+        assert_eq!(check_title("  Fix bug \n", false).unwrap(), "Fix bug");
+        let err = check_title(" \t ", false).unwrap_err();
+        assert!(err.to_string().contains("--allow-empty-title"));
+        assert_eq!(check_title("   ", true).unwrap(), "");
+    }
+
     #[test]
     fn test_validate_priority_valid() {
-        assert!(validate_priority("low"));
-        assert!(validate_priority("medium"));
-        assert!(validate_priority("high"));
-        assert!(validate_priority("critical"));
+        assert!(validate_priority("low").is_ok());
+        assert!(validate_priority("medium").is_ok());
+        assert!(validate_priority("high").is_ok());
+        assert!(validate_priority("critical").is_ok());
     }
 
     #[test]
     fn test_validate_priority_invalid() {
-        assert!(!validate_priority(""));
-        assert!(!validate_priority("urgent"));
-        assert!(!validate_priority("LOW")); // Case sensitive
-        assert!(!validate_priority("MEDIUM"));
-        assert!(!validate_priority("High"));
-        assert!(!validate_priority("CRITICAL"));
-        assert!(!validate_priority(" medium"));
-        assert!(!validate_priority("medium "));
-        assert!(!validate_priority("medium\n"));
+        assert!(validate_priority("").is_err());
+        assert!(validate_priority("urgent").is_err());
+        assert!(validate_priority("LOW").is_err()); // Case sensitive
+        assert!(validate_priority("MEDIUM").is_err());
+        assert!(validate_priority("High").is_err());
+        assert!(validate_priority("CRITICAL").is_err());
+        assert!(validate_priority(" medium").is_err());
+        assert!(validate_priority("medium ").is_err());
+        assert!(validate_priority("medium\n").is_err());
+    }
+
+    #[test]
+    fn test_validate_priority_error_lists_valid_values() {
+        let err = validate_priority("urgent").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid priority 'urgent'; valid: low, medium, high, critical"
+        );
     }
 
     #[test]
     fn test_validate_priority_malicious() {
         // Security: ensure no injection vectors
-        assert!(!validate_priority("'; DROP TABLE issues; --"));
-        assert!(!validate_priority("high\0medium"));
-        assert!(!validate_priority("medium; DELETE FROM issues"));
-        assert!(!validate_priority("<script>alert('xss')</script>"));
+        assert!(validate_priority("'; DROP TABLE issues; --").is_err());
+        assert!(validate_priority("high\0medium").is_err());
+        assert!(validate_priority("medium; DELETE FROM issues").is_err());
+        assert!(validate_priority("<script>alert('xss')</script>").is_err());
+    }
+
+    #[test]
+    fn test_apply_template_defaults_and_overrides() {
+        let (priority, desc, label) =
+            apply_template(Some("bug"), Some("Crashes on save"), None, "low").unwrap();
+        assert_eq!(priority, "high");
+        assert!(desc.unwrap().ends_with("Actual: \n\nCrashes on save"));
+        assert_eq!(label, Some("bug"));
+
+        let (priority, _, _) = apply_template(Some("bug"), None, Some("low"), "medium").unwrap();
+        assert_eq!(priority, "low");
+
+        let (priority, desc, label) = apply_template(None, None, None, "medium").unwrap();
+        assert_eq!((priority.as_str(), desc, label), ("medium", None, None));
+
+        assert!(apply_template(Some("nope"), None, None, "medium").is_err());
     }
 
     #[test]
@@ -287,7 +385,7 @@ mod tests {
         // Verify all templates have required fields
         for template in TEMPLATES {
             assert!(!template.name.is_empty());
-            assert!(validate_priority(template.priority));
+            assert!(validate_priority(template.priority).is_ok());
             assert!(!template.label.is_empty());
         }
     }
@@ -319,7 +417,7 @@ mod tests {
                     !["low", "medium", "high", "critical"].contains(&s.as_str())
                 })
         ) {
-            prop_assert!(!validate_priority(&priority));
+            prop_assert!(validate_priority(&priority).is_err());
         }
 
         #[test]
@@ -1,17 +1,88 @@
 use anyhow::{bail, Result};
 use std::io::{self, Write};
 
+pub use crate::config::DeleteMode;
 use crate::db::Database;
+use crate::models::Issue;
+use crate::utils::info;
 
-pub fn run(db: &Database, id: i64, force: bool) -> Result<()> {
-    // Check if issue exists first
-    let issue = match db.get_issue(id)? {
-        Some(i) => i,
-        None => bail!("Issue #{} not found", id),
-    };
+/// Close (if needed) and archive an issue. Returns false if it was already archived.
+fn close_and_archive(db: &Database, issue: &Issue, closed_by: Option<&str>) -> Result<bool> {
+    if issue.status == "archived" {
+        return Ok(false);
+    }
+    if issue.status != "closed" {
+        db.close_issue_by(issue.id, None, closed_by)?;
+    }
+    db.archive_issue(issue.id)
+}
+
+/// One line per issue being deleted that blocks others outside the batch,
+/// since deleting it silently drops those dependencies.
+fn unblock_warnings(db: &Database, issues: &[Issue]) -> Result<Vec<String>> {
+    let mut warnings = Vec::new();
+    for issue in issues {
+        let blocked: Vec<String> = db
+            .get_blocking(issue.id)?
+            .into_iter()
+            .filter(|id| !issues.iter().any(|i| i.id == *id))
+            .map(|id| format!("#{}", id))
+            .collect();
+        if !blocked.is_empty() {
+            warnings.push(format!(
+                "Warning: #{} blocks {}; deleting it will unblock {}",
+                issue.id,
+                blocked.join(", "),
+                if blocked.len() == 1 { "it" } else { "them" }
+            ));
+        }
+    }
+    Ok(warnings)
+}
+
+pub fn run(
+    db: &Database,
+    ids: &[i64],
+    force: bool,
+    mode: DeleteMode,
+    closed_by: Option<&str>,
+) -> Result<()> {
+    // Resolve every id up front; missing ones are reported but don't abort the rest
+    let mut issues = Vec::new();
+    for &id in ids {
+        match db.get_issue(id)? {
+            Some(issue) => issues.push(issue),
+            None => eprintln!("Warning: Issue #{} not found, skipping", id),
+        }
+    }
+
+    if issues.is_empty() {
+        match ids {
+            [id] => bail!("Issue #{} not found", id),
+            _ => bail!("None of the given issues were found"),
+        }
+    }
 
     if !force {
-        print!("Delete issue #{} \"{}\"? [y/N] ", id, issue.title);
+        for warning in unblock_warnings(db, &issues)? {
+            eprintln!("{}", warning);
+        }
+        let verb = if mode == DeleteMode::Archive {
+            "Archive"
+        } else {
+            "Delete"
+        };
+        if let [issue] = issues.as_slice() {
+            print!("{} issue #{} \"{}\"? [y/N] ", verb, issue.id, issue.title);
+        } else {
+            let id_strs: Vec<String> = issues.iter().map(|i| format!("#{}", i.id)).collect();
+            print!(
+                "{} {} issues ({})? [y/N] ",
+                verb,
+                issues.len(),
+                id_strs.join(", ")
+            );
+        }
         io::stdout().flush()?;
 
         let mut input = String::new();
@@ -23,19 +94,36 @@ pub fn run(db: &Database, id: i64, force: bool) -> Result<()> {
         }
     }
 
-    if db.delete_issue(id)? {
-        println!("Deleted issue #{}", id);
-    } else {
-        bail!("Failed to delete issue #{}", id);
-    }
-
-    Ok(())
+    db.transaction(|| {
+        for issue in &issues {
+            let removed = match mode {
+                DeleteMode::Hard => db.delete_issue(issue.id)?,
+                DeleteMode::Trash => db.trash_issue(issue.id)?.is_some(),
+                DeleteMode::Archive => close_and_archive(db, issue, closed_by)?,
+            };
+            if removed {
+                match mode {
+                    DeleteMode::Hard => info(format_args!("Deleted issue #{}", issue.id)),
+                    DeleteMode::Trash => {
+                        info(format_args!("Moved issue #{} to the trash", issue.id))
+                    }
+                    DeleteMode::Archive => info(format_args!("Archived issue #{}", issue.id)),
+                }
+            } else if mode == DeleteMode::Archive {
+                eprintln!("Warning: Issue #{} is already archived", issue.id);
+            } else {
+                // Already removed earlier in this batch (e.g. as a subissue of a deleted parent)
+                eprintln!("Warning: Issue #{} was already deleted", issue.id);
+            }
+        }
+        Ok(())
+    })
 }
 
 /// Internal function for testing without stdin interaction
 #[cfg(test)]
 pub fn run_force(db: &Database, id: i64) -> Result<()> {
-    run(db, id, true)
+    run(db, &[id], true, DeleteMode::Hard, None)
 }
 
 #[cfg(test)]
@@ -75,6 +163,63 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_deleting_a_blocker_warns_about_blocked_issues() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "medium").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let other = db.create_issue("Also blocked", None, "medium").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+        db.add_dependency(other, blocker).unwrap();
+
+        let issue = db.get_issue(blocker).unwrap().unwrap();
+        let warnings = unblock_warnings(&db, std::slice::from_ref(&issue)).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains(&format!("#{}", blocked)));
+        assert!(warnings[0].contains(&format!("#{}", other)));
+
+        // Issues deleted in the same batch aren't worth warning about
+        let both = [issue, db.get_issue(blocked).unwrap().unwrap()];
+        assert_eq!(
+            unblock_warnings(&db, &both).unwrap(),
+            vec![format!(
+                "Warning: #{} blocks #{}; deleting it will unblock it",
+                blocker, other
+            )]
+        );
+    }
+
+    #[test]
+    fn test_delete_in_archive_mode_keeps_the_issue() {
+        let (db, _dir) = setup_test_db();
+        let open = db.create_issue("Open", None, "medium").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.add_label(open, "bug").unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        run(&db, &[open, closed], true, DeleteMode::Archive, None).unwrap();
+
+        for id in [open, closed] {
+            assert_eq!(db.get_issue(id).unwrap().unwrap().status, "archived");
+        }
+        assert_eq!(db.get_labels(open).unwrap(), vec!["bug"]);
+        // Archiving again is a no-op, not an error
+        assert!(run(&db, &[open], true, DeleteMode::Archive, None).is_ok());
+    }
+
+    #[test]
+    fn test_delete_to_trash_keeps_a_restorable_copy() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("To trash", None, "medium").unwrap();
+
+        run(&db, &[issue_id], true, DeleteMode::Trash, None).unwrap();
+
+        assert!(db.get_issue(issue_id).unwrap().is_none());
+        let trashed = db.list_trashed().unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].issue_id, issue_id);
+    }
+
     #[test]
     fn test_delete_cascades_labels() {
         let (db, _dir) = setup_test_db();
@@ -99,7 +244,7 @@ mod tests {
         run_force(&db, issue_id).unwrap();
 
         // Comments should be gone
-        let comments = db.get_comments(issue_id).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert!(comments.is_empty());
     }
 
@@ -141,7 +286,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
-        db.add_relation(issue1, issue2).unwrap();
+        db.add_relation(issue1, issue2, None).unwrap();
 
         // Delete issue1
         run_force(&db, issue1).unwrap();
@@ -155,7 +300,7 @@ mod tests {
     fn test_delete_closed_issue() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
         let result = run_force(&db, issue_id);
         assert!(result.is_ok());
@@ -167,7 +312,7 @@ mod tests {
     fn test_delete_archived_issue() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Archived issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
         db.archive_issue(issue_id).unwrap();
 
         let result = run_force(&db, issue_id);
@@ -221,6 +366,41 @@ mod tests {
         assert_eq!(issues[0].id, id3);
     }
 
+    #[test]
+    fn test_delete_many_skips_missing_ids() {
+        let (db, _dir) = setup_test_db();
+        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
+        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
+        let keep = db.create_issue("Keep", None, "medium").unwrap();
+
+        let result = run(&db, &[id1, 99999, id2], true, DeleteMode::Hard, None);
+        assert!(result.is_ok());
+
+        assert!(db.get_issue(id1).unwrap().is_none());
+        assert!(db.get_issue(id2).unwrap().is_none());
+        assert!(db.get_issue(keep).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_delete_many_all_missing_fails() {
+        let (db, _dir) = setup_test_db();
+
+        let result = run(&db, &[99998, 99999], true, DeleteMode::Hard, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_delete_many_parent_and_child() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "low").unwrap();
+
+        // Child is cascaded away with the parent; listing it too must not fail
+        let result = run(&db, &[parent, child], true, DeleteMode::Hard, None);
+        assert!(result.is_ok());
+        assert!(db.get_issue(child).unwrap().is_none());
+    }
+
     // ==================== Property-Based Tests ====================
 
     proptest! {
@@ -271,7 +451,7 @@ mod tests {
 
             run_force(&db, issue_id).unwrap();
 
-            let remaining_comments = db.get_comments(issue_id).unwrap();
+            let remaining_comments = db.get_comments(issue_id, None).unwrap();
             prop_assert!(remaining_comments.is_empty());
         }
     }
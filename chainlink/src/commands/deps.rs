@@ -1,55 +1,89 @@
 use anyhow::{bail, Result};
 
+use crate::commands::next::priority_weight;
 use crate::db::Database;
-use crate::utils::truncate;
+use crate::utils::{info, truncate};
 
 pub fn block(db: &Database, issue_id: i64, blocker_id: i64) -> Result<()> {
-    // Check if both issues exist
-    db.require_issue(issue_id)?;
-    db.require_issue(blocker_id)?;
+    // Check both issues up front so a single error names everything missing
+    let mut missing = Vec::new();
+    for id in [issue_id, blocker_id] {
+        if !missing.contains(&id) && db.get_issue(id)?.is_none() {
+            missing.push(id);
+        }
+    }
+    match missing.as_slice() {
+        [] => {}
+        [id] => bail!("Issue #{} not found", id),
+        [a, b, ..] => bail!("Issues #{} and #{} not found", a, b),
+    }
 
     if issue_id == blocker_id {
         bail!("An issue cannot block itself");
     }
 
     if db.add_dependency(issue_id, blocker_id)? {
-        println!("Issue #{} is now blocked by #{}", issue_id, blocker_id);
+        info(format_args!(
+            "Issue #{} is now blocked by #{}",
+            issue_id, blocker_id
+        ));
     } else {
-        println!("Dependency already exists");
+        info("Dependency already exists");
     }
     Ok(())
 }
 
 pub fn unblock(db: &Database, issue_id: i64, blocker_id: i64) -> Result<()> {
     if db.remove_dependency(issue_id, blocker_id)? {
-        println!(
+        info(format_args!(
             "Removed: #{} no longer blocked by #{}",
             issue_id, blocker_id
-        );
+        ));
     } else {
-        println!("No such dependency found");
+        info("No such dependency found");
     }
     Ok(())
 }
 
+/// Blocked issues grouped under priority headings, most urgent first, each listing
+/// only the blockers that are still open.
+fn blocked_lines(db: &Database) -> Result<Vec<String>> {
+    let mut issues = db.list_blocked_issues()?;
+    issues.sort_by_key(|i| (std::cmp::Reverse(priority_weight(&i.priority)), i.id));
+
+    let mut lines = Vec::new();
+    let mut current_priority: Option<String> = None;
+    for issue in issues {
+        if current_priority.as_deref() != Some(issue.priority.as_str()) {
+            lines.push(format!("{}:", issue.priority));
+            current_priority = Some(issue.priority.clone());
+        }
+        let blockers: Vec<String> = db
+            .get_open_blockers(issue.id)?
+            .iter()
+            .map(|b| format!("#{}", b))
+            .collect();
+        lines.push(format!(
+            "  #{:<4} {} (blocked by {})",
+            issue.id,
+            truncate(&issue.title, 40),
+            blockers.join(", ")
+        ));
+    }
+    Ok(lines)
+}
+
 pub fn list_blocked(db: &Database) -> Result<()> {
-    let issues = db.list_blocked_issues()?;
+    let lines = blocked_lines(db)?;
 
-    if issues.is_empty() {
+    if lines.is_empty() {
         println!("No blocked issues.");
         return Ok(());
     }
 
     println!("Blocked issues:");
-    for issue in issues {
-        let blockers = db.get_blockers(issue.id)?;
-        let blocker_strs: Vec<String> = blockers.iter().map(|b| format!("#{}", b)).collect();
-        println!(
-            "  #{:<4} {} (blocked by: {})",
-            issue.id,
-            truncate(&issue.title, 40),
-            blocker_strs.join(", ")
-        );
+    for line in lines {
+        println!("{}", line);
     }
 
     Ok(())
@@ -119,6 +153,16 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_block_both_missing_names_both() {
+        let (db, _dir) = setup_test_db();
+
+        let err = block(&db, 998, 999).unwrap_err().to_string();
+        assert!(err.contains("#998"), "got: {}", err);
+        assert!(err.contains("#999"), "got: {}", err);
+        assert!(err.contains("not found"));
+    }
+
     #[test]
     fn test_block_self() {
         let (db, _dir) = setup_test_db();
@@ -216,6 +260,34 @@ mod tests {
         assert!(blockers.contains(&blocker2));
     }
 
+    #[test]
+    fn test_list_blocked_grouped_by_priority() {
+        let (db, _dir) = setup_test_db();
+        let low = db.create_issue("Low blocked", None, "low").unwrap();
+        let critical = db
+            .create_issue("Critical blocked", None, "critical")
+            .unwrap();
+        let open_blocker = db.create_issue("Open blocker", None, "medium").unwrap();
+        let other_blocker = db.create_issue("Other blocker", None, "medium").unwrap();
+        let closed_blocker = db.create_issue("Closed blocker", None, "medium").unwrap();
+        db.add_dependency(low, open_blocker).unwrap();
+        db.add_dependency(critical, open_blocker).unwrap();
+        db.add_dependency(critical, other_blocker).unwrap();
+        db.add_dependency(critical, closed_blocker).unwrap();
+        db.close_issue(closed_blocker, None).unwrap();
+
+        let lines = blocked_lines(&db).unwrap();
+        assert_eq!(lines[0], "critical:");
+        assert!(lines[1].contains(&format!("#{}", critical)));
+        assert!(lines[1].ends_with(&format!(
+            "(blocked by #{}, #{})",
+            open_blocker, other_blocker
+        )));
+        assert_eq!(lines[2], "low:");
+        assert!(lines[3].contains("Low blocked"));
+        assert_eq!(lines.len(), 4);
+    }
+
     // List ready tests
     #[test]
     fn test_list_ready_empty() {
@@ -253,7 +325,7 @@ mod tests {
     fn test_list_ready_excludes_closed() {
         let (db, _dir) = setup_test_db();
         let issue = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(issue).unwrap();
+        db.close_issue(issue, None).unwrap();
 
         let ready = db.list_ready_issues().unwrap();
         assert!(!ready.iter().any(|i| i.id == issue));
@@ -287,7 +359,7 @@ mod tests {
         assert!(!ready.iter().any(|i| i.id == blocked));
 
         // Close the blocker
-        db.close_issue(blocker).unwrap();
+        db.close_issue(blocker, None).unwrap();
 
         // Now blocked issue should be ready
         let ready = db.list_ready_issues().unwrap();
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::commands::cpitd;
+use crate::daemon;
+use crate::db::Database;
+
+/// Environment facts that don't come from the database.
+pub struct Environment<'a> {
+    /// The project's `.chainlink` directory, if there is one
+    pub chainlink_dir: Option<&'a Path>,
+    pub daemon_pid: Option<u32>,
+    pub cpitd_available: bool,
+}
+
+impl<'a> Environment<'a> {
+    /// Probe the daemon and cpitd for the project at `chainlink_dir`.
+    pub fn detect(chainlink_dir: Option<&'a Path>) -> Self {
+        Environment {
+            chainlink_dir,
+            daemon_pid: chainlink_dir.and_then(daemon::running_pid),
+            cpitd_available: cpitd::find_cpitd(),
+        }
+    }
+}
+
+/// The doctor report, one line per check.
+pub fn report(db: &Database, env: &Environment<'_>) -> Result<Vec<String>> {
+    let mut lines = Vec::new();
+
+    lines.push(format!("chainlink {}", env!("CARGO_PKG_VERSION")));
+    lines.push(match env.chainlink_dir {
+        Some(dir) => format!("Project: {}", dir.display()),
+        None => "Project: (none; using --db)".to_string(),
+    });
+
+    lines.push(format!("Schema version: {}", db.schema_version()?));
+    lines.push(format!("Issues: {}", db.count_issues()?));
+    lines.push(format!("Comments: {}", db.count_all_comments()?));
+
+    lines.push(match env.daemon_pid {
+        Some(pid) => format!("Daemon: running (PID {})", pid),
+        None => "Daemon: not running".to_string(),
+    });
+    lines.push(format!(
+        "cpitd: {}",
+        if env.cpitd_available {
+            "available"
+        } else {
+            "not found in PATH"
+        }
+    ));
+
+    let problems = db.validate_integrity()?;
+    if problems.is_empty() {
+        lines.push("Integrity: ok".to_string());
+    } else {
+        lines.push(format!("Integrity: {} problem(s)", problems.len()));
+        lines.extend(problems.iter().map(|p| format!("  {}", p)));
+    }
+
+    Ok(lines)
+}
+
+pub fn run(db: &Database, chainlink_dir: Option<&Path>) -> Result<()> {
+    for line in report(db, &Environment::detect(chainlink_dir))? {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::SCHEMA_VERSION;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_report_includes_schema_version_and_counts() {
+        let (db, dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        db.create_issue("B", None, "medium").unwrap();
+        db.add_comment(a, "one").unwrap();
+        db.add_comment(a, "two").unwrap();
+        db.add_comment(a, "three").unwrap();
+
+        let env = Environment {
+            chainlink_dir: Some(dir.path()),
+            daemon_pid: None,
+            cpitd_available: false,
+        };
+        let lines = report(&db, &env).unwrap();
+
+        assert!(lines.contains(&format!("Schema version: {}", SCHEMA_VERSION)));
+        assert!(lines.contains(&"Issues: 2".to_string()));
+        assert!(lines.contains(&"Comments: 3".to_string()));
+        assert!(lines.contains(&"Daemon: not running".to_string()));
+        assert!(lines.contains(&"cpitd: not found in PATH".to_string()));
+        assert!(lines.contains(&"Integrity: ok".to_string()));
+    }
+
+    #[test]
+    fn test_schema_version_matches_current() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+    }
+}
@@ -1,12 +1,17 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 
-use crate::db::Database;
-use crate::models::Issue;
+use crate::db::{Database, SCHEMA_VERSION};
+use crate::models::{Issue, Session, TimeEntry};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct ExportedIssue {
     pub id: i64,
     pub title: String,
@@ -16,27 +21,109 @@ pub struct ExportedIssue {
     pub parent_id: Option<i64>,
     pub labels: Vec<String>,
     pub comments: Vec<ExportedComment>,
+    /// Ids of issues blocking this one
+    #[serde(default)]
+    pub blocked_by: Vec<i64>,
+    #[serde(default)]
+    pub related: Vec<ExportedRelation>,
     pub created_at: String,
     pub updated_at: String,
     pub closed_at: Option<String>,
+    /// How a closed issue was resolved (see `close --resolution`)
+    #[serde(default)]
+    pub resolution: Option<String>,
+    #[serde(default)]
+    pub due_at: Option<String>,
+    /// Recurrence spec, e.g. "weekly"
+    #[serde(default)]
+    pub recurrence: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+/// A related issue, with the note given to `relate --reason`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "RelatedEntry")]
+pub struct ExportedRelation {
+    pub id: i64,
+    pub note: Option<String>,
+}
+
+/// A `related` entry as read back: exports written before relation notes
+/// were exported list bare ids.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum RelatedEntry {
+    Id(i64),
+    Full {
+        id: i64,
+        #[serde(default)]
+        note: Option<String>,
+    },
+}
+
+impl From<RelatedEntry> for ExportedRelation {
+    fn from(entry: RelatedEntry) -> Self {
+        match entry {
+            RelatedEntry::Id(id) => ExportedRelation { id, note: None },
+            RelatedEntry::Full { id, note } => ExportedRelation { id, note },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
 pub struct ExportedComment {
     pub content: String,
     pub created_at: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct ExportedMilestone {
+    pub name: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub issues: Vec<i64>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct ExportedTimeEntry {
+    pub issue_id: i64,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub duration_seconds: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct ExportedSession {
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub active_issue_id: Option<i64>,
+    pub handoff_notes: Option<String>,
+    pub last_action: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, JsonSchema)]
 pub struct ExportData {
+    /// Version of chainlink that wrote the export
+    #[serde(default)]
+    pub chainlink_version: Option<String>,
+    /// Database schema version of the exporting database; lets import adapt to older or
+    /// newer layouts. Absent in exports written before it was recorded.
+    #[serde(default)]
+    pub schema_version: Option<i32>,
     pub version: i32,
     pub exported_at: String,
     pub issues: Vec<ExportedIssue>,
+    // Absent in exports written before time tracking was included
+    #[serde(default)]
+    pub time_entries: Vec<ExportedTimeEntry>,
+    #[serde(default)]
+    pub sessions: Vec<ExportedSession>,
+    #[serde(default)]
+    pub milestones: Vec<ExportedMilestone>,
 }
 
 fn export_issue(db: &Database, issue: &Issue) -> Result<ExportedIssue> {
     let labels = db.get_labels(issue.id)?;
-    let comments = db.get_comments(issue.id)?;
+    let comments = db.get_comments(issue.id, None)?;
 
     Ok(ExportedIssue {
         id: issue.id,
@@ -53,13 +140,170 @@ fn export_issue(db: &Database, issue: &Issue) -> Result<ExportedIssue> {
                 created_at: c.created_at.to_rfc3339(),
             })
             .collect(),
+        blocked_by: db.get_blockers(issue.id)?,
+        related: {
+            let mut notes = db.relation_notes(issue.id)?;
+            db.get_related_issues(issue.id)?
+                .into_iter()
+                .map(|i| ExportedRelation {
+                    note: notes.remove(&i.id),
+                    id: i.id,
+                })
+                .collect()
+        },
         created_at: issue.created_at.to_rfc3339(),
         updated_at: issue.updated_at.to_rfc3339(),
         closed_at: issue.closed_at.map(|dt| dt.to_rfc3339()),
+        resolution: db.get_resolution(issue.id)?,
+        due_at: issue.due_at.map(|dt| dt.to_rfc3339()),
+        recurrence: db.get_recurrence(issue.id)?,
     })
 }
 
-pub fn run_json(db: &Database, output_path: Option<&str>) -> Result<()> {
+fn export_milestones(db: &Database) -> Result<Vec<ExportedMilestone>> {
+    db.list_milestones(Some("all"))?
+        .into_iter()
+        .rev()
+        .map(|m| {
+            Ok(ExportedMilestone {
+                issues: db
+                    .get_milestone_issues(m.id)?
+                    .into_iter()
+                    .map(|i| i.id)
+                    .collect(),
+                name: m.name,
+                description: m.description,
+                status: m.status,
+            })
+        })
+        .collect()
+}
+
+fn export_time_entry(entry: TimeEntry) -> ExportedTimeEntry {
+    ExportedTimeEntry {
+        issue_id: entry.issue_id,
+        started_at: entry.started_at.to_rfc3339(),
+        ended_at: entry.ended_at.map(|dt| dt.to_rfc3339()),
+        duration_seconds: entry.duration_seconds,
+    }
+}
+
+fn export_session(session: Session) -> ExportedSession {
+    ExportedSession {
+        started_at: session.started_at.to_rfc3339(),
+        ended_at: session.ended_at.map(|dt| dt.to_rfc3339()),
+        active_issue_id: session.active_issue_id,
+        handoff_notes: session.handoff_notes,
+        last_action: session.last_action,
+    }
+}
+
+const REDACTED: &str = "[redacted]";
+
+/// Replace an issue's free text with placeholders, keeping ids, labels,
+/// priorities, statuses, timestamps and relationships intact.
+fn anonymize_issue(issue: &mut ExportedIssue) {
+    issue.title = format!("Issue #{}", issue.id);
+    if issue.description.is_some() {
+        issue.description = Some(REDACTED.to_string());
+    }
+    for comment in &mut issue.comments {
+        comment.content = REDACTED.to_string();
+    }
+    for relation in &mut issue.related {
+        if relation.note.is_some() {
+            relation.note = Some(REDACTED.to_string());
+        }
+    }
+}
+
+/// Redaction pass over a whole export: issue text, milestone names and
+/// descriptions, and session notes.
+fn anonymize(data: &mut ExportData) {
+    data.issues.iter_mut().for_each(anonymize_issue);
+    for (i, milestone) in data.milestones.iter_mut().enumerate() {
+        milestone.name = format!("Milestone {}", i + 1);
+        if milestone.description.is_some() {
+            milestone.description = Some(REDACTED.to_string());
+        }
+    }
+    for session in &mut data.sessions {
+        if session.handoff_notes.is_some() {
+            session.handoff_notes = Some(REDACTED.to_string());
+        }
+        if session.last_action.is_some() {
+            session.last_action = Some(REDACTED.to_string());
+        }
+    }
+}
+
+/// Destination for an export: a file or stdout, gzip-compressed when asked
+/// for or when the file name ends in `.gz`.
+enum ExportWriter {
+    Plain(BufWriter<Box<dyn Write>>),
+    Gzip(GzEncoder<BufWriter<Box<dyn Write>>>),
+}
+
+impl ExportWriter {
+    fn open(output_path: Option<&str>, gzip: bool) -> Result<Self> {
+        let sink: Box<dyn Write> = match output_path {
+            Some(path) => Box::new(fs::File::create(path).context("Failed to create export file")?),
+            None => Box::new(io::stdout()),
+        };
+        let sink = BufWriter::new(sink);
+        if gzip || output_path.is_some_and(|p| p.ends_with(".gz")) {
+            Ok(ExportWriter::Gzip(GzEncoder::new(
+                sink,
+                Compression::default(),
+            )))
+        } else {
+            Ok(ExportWriter::Plain(sink))
+        }
+    }
+
+    /// Write the gzip trailer (if any) and flush everything to the sink.
+    fn finish(self) -> Result<()> {
+        match self {
+            ExportWriter::Plain(mut out) => out.flush()?,
+            ExportWriter::Gzip(out) => out.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+impl Write for ExportWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            ExportWriter::Plain(out) => out.write(buf),
+            ExportWriter::Gzip(out) => out.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            ExportWriter::Plain(out) => out.flush(),
+            ExportWriter::Gzip(out) => out.flush(),
+        }
+    }
+}
+
+/// Write a whole export document. Stdout output gets a trailing newline.
+fn write_export(output_path: Option<&str>, gzip: bool, content: &str) -> Result<()> {
+    let mut out = ExportWriter::open(output_path, gzip)?;
+    out.write_all(content.as_bytes())
+        .context("Failed to write export file")?;
+    if output_path.is_none() {
+        out.write_all(b"\n")?;
+    }
+    out.finish().context("Failed to write export file")
+}
+
+pub fn run_json(
+    db: &Database,
+    output_path: Option<&str>,
+    gzip: bool,
+    anonymized: bool,
+) -> Result<()> {
     let issues = db.list_issues(Some("all"), None, None)?;
 
     let exported: Vec<ExportedIssue> = issues
@@ -67,28 +311,73 @@ pub fn run_json(db: &Database, output_path: Option<&str>) -> Result<()> {
         .map(|i| export_issue(db, i))
         .collect::<Result<Vec<_>>>()?;
 
-    let data = ExportData {
+    let mut data = ExportData {
+        chainlink_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        schema_version: Some(SCHEMA_VERSION),
         version: 1,
         exported_at: chrono::Utc::now().to_rfc3339(),
         issues: exported,
+        time_entries: db
+            .all_time_entries()?
+            .into_iter()
+            .map(export_time_entry)
+            .collect(),
+        sessions: db.all_sessions()?.into_iter().map(export_session).collect(),
+        milestones: export_milestones(db)?,
     };
+    if anonymized {
+        anonymize(&mut data);
+    }
 
     let json = serde_json::to_string_pretty(&data)?;
+    write_export(output_path, gzip, &json)?;
+    if let Some(path) = output_path {
+        eprintln!("Exported {} issues to {}", data.issues.len(), path);
+    }
+    Ok(())
+}
 
-    match output_path {
-        Some(path) => {
-            fs::write(path, json).context("Failed to write export file")?;
-            eprintln!("Exported {} issues to {}", data.issues.len(), path);
-        }
-        None => {
-            let mut stdout = io::stdout().lock();
-            writeln!(stdout, "{}", json)?;
+/// Write one JSON object per issue per line. Returns the number of issues.
+fn write_jsonl(db: &Database, out: &mut impl Write, anonymized: bool) -> Result<usize> {
+    let mut count = 0;
+    db.for_each_issue(|issue| {
+        let mut exported = export_issue(db, &issue)?;
+        if anonymized {
+            anonymize_issue(&mut exported);
         }
+        serde_json::to_writer(&mut *out, &exported)?;
+        out.write_all(b"\n")?;
+        count += 1;
+        Ok(())
+    })?;
+    out.flush()?;
+    Ok(count)
+}
+
+/// Stream issues as JSON Lines, so exports of large databases never hold
+/// every issue in memory at once. Only issues are written; use the JSON
+/// format for a full backup with sessions, time entries and milestones.
+pub fn run_jsonl(
+    db: &Database,
+    output_path: Option<&str>,
+    gzip: bool,
+    anonymized: bool,
+) -> Result<()> {
+    let mut out = ExportWriter::open(output_path, gzip)?;
+    let count = write_jsonl(db, &mut out, anonymized)?;
+    out.finish().context("Failed to write export file")?;
+    if let Some(path) = output_path {
+        eprintln!("Exported {} issues to {}", count, path);
     }
     Ok(())
 }
 
-pub fn run_markdown(db: &Database, output_path: Option<&str>) -> Result<()> {
+pub fn run_markdown(
+    db: &Database,
+    output_path: Option<&str>,
+    repo_url: Option<&str>,
+    gzip: bool,
+) -> Result<()> {
     let issues = db.list_issues(Some("all"), None, None)?;
     let mut md = String::new();
 
@@ -97,57 +386,107 @@ pub fn run_markdown(db: &Database, output_path: Option<&str>) -> Result<()> {
         "Exported: {}\n\n",
         chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
     ));
+    if let Some(url) = repo_url {
+        md.push_str(&format!("Repository: {}\n\n", url));
+    }
 
     // Group by status
-    let open: Vec<_> = issues.iter().filter(|i| i.status == "open").collect();
-    let closed: Vec<_> = issues.iter().filter(|i| i.status == "closed").collect();
-    let archived: Vec<_> = issues.iter().filter(|i| i.status == "archived").collect();
-
-    if !open.is_empty() {
-        md.push_str("## Open Issues\n\n");
-        for issue in &open {
-            write_issue_md(&mut md, db, issue)?;
+    let sections = [
+        ("Open Issues", "open"),
+        ("Closed Issues", "closed"),
+        ("Archived Issues", "archived"),
+    ]
+    .map(|(heading, status)| {
+        let members: Vec<&Issue> = issues.iter().filter(|i| i.status == status).collect();
+        (heading, members)
+    });
+
+    // Issues are numbered in document order; the TOC uses the same numbers
+    if !issues.is_empty() {
+        md.push_str("## Contents\n\n");
+        let mut number = 0;
+        for (heading, members) in &sections {
+            if members.is_empty() {
+                continue;
+            }
+            md.push_str(&format!("- {}\n", heading));
+            for issue in members {
+                number += 1;
+                md.push_str(&format!(
+                    "  {}. [#{} {}](#{})\n",
+                    number,
+                    issue.id,
+                    issue.title,
+                    issue_anchor(issue)
+                ));
+            }
         }
+        md.push('\n');
     }
 
-    if !closed.is_empty() {
-        md.push_str("## Closed Issues\n\n");
-        for issue in &closed {
-            write_issue_md(&mut md, db, issue)?;
+    let mut number = 0;
+    for (heading, members) in &sections {
+        if members.is_empty() {
+            continue;
+        }
+        md.push_str(&format!("## {}\n\n", heading));
+        for issue in members {
+            number += 1;
+            write_issue_md(&mut md, db, issue, number)?;
         }
     }
 
-    if !archived.is_empty() {
-        md.push_str("## Archived Issues\n\n");
-        for issue in &archived {
-            write_issue_md(&mut md, db, issue)?;
-        }
+    write_export(output_path, gzip, &md)?;
+    if let Some(path) = output_path {
+        eprintln!("Exported {} issues to {}", issues.len(), path);
     }
+    Ok(())
+}
 
-    match output_path {
-        Some(path) => {
-            fs::write(path, md).context("Failed to write export file")?;
-            eprintln!("Exported {} issues to {}", issues.len(), path);
-        }
-        None => {
-            let mut stdout = io::stdout().lock();
-            writeln!(stdout, "{}", md)?;
+/// GitHub-style anchor slug: lowercase, punctuation dropped, runs of
+/// whitespace and hyphens collapsed to a single hyphen. Non-ASCII letters
+/// and digits are kept.
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
         }
     }
-    Ok(())
+    slug.trim_end_matches('-').to_string()
 }
 
-fn write_issue_md(md: &mut String, db: &Database, issue: &Issue) -> Result<()> {
+/// Anchor for an issue's section; the id keeps it unique when titles repeat.
+fn issue_anchor(issue: &Issue) -> String {
+    let slug = slugify(&issue.title);
+    if slug.is_empty() {
+        format!("issue-{}", issue.id)
+    } else {
+        format!("issue-{}-{}", issue.id, slug)
+    }
+}
+
+fn write_issue_md(md: &mut String, db: &Database, issue: &Issue, number: usize) -> Result<()> {
     let checkbox = if issue.status == "closed" {
         "[x]"
     } else {
         "[ ]"
     };
 
+    md.push_str(&format!("<a id=\"{}\"></a>\n\n", issue_anchor(issue)));
     md.push_str(&format!(
-        "### {} #{}: {}\n\n",
-        checkbox, issue.id, issue.title
+        "### {}. {} #{}: {}\n\n",
+        number, checkbox, issue.id, issue.title
     ));
+    write_issue_details(md, db, issue)?;
+    md.push_str("\n---\n\n");
+    Ok(())
+}
+
+/// Metadata list, description and comments for one issue.
+fn write_issue_details(md: &mut String, db: &Database, issue: &Issue) -> Result<()> {
     md.push_str(&format!("- **Priority:** {}\n", issue.priority));
     md.push_str(&format!("- **Status:** {}\n", issue.status));
 
@@ -171,7 +510,7 @@ fn write_issue_md(md: &mut String, db: &Database, issue: &Issue) -> Result<()> {
         }
     }
 
-    let comments = db.get_comments(issue.id)?;
+    let comments = db.get_comments(issue.id, None)?;
     if !comments.is_empty() {
         md.push_str("\n**Comments:**\n");
         for comment in comments {
@@ -182,14 +521,67 @@ fn write_issue_md(md: &mut String, db: &Database, issue: &Issue) -> Result<()> {
             ));
         }
     }
+    Ok(())
+}
+
+/// File name for an issue in a split export: zero-padded id plus title slug.
+fn split_file_name(issue: &Issue) -> String {
+    let slug = slugify(&issue.title);
+    if slug.is_empty() {
+        format!("{:03}.md", issue.id)
+    } else {
+        format!("{:03}-{}.md", issue.id, slug)
+    }
+}
 
-    md.push_str("\n---\n\n");
+/// Whether `name` looks like a file written by `run_split`.
+fn is_split_file_name(name: &str) -> bool {
+    let Some(stem) = name.strip_suffix(".md") else {
+        return false;
+    };
+    let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+    digits >= 3 && (digits == stem.len() || stem.as_bytes()[digits] == b'-')
+}
+
+/// Write each issue to its own markdown file in `dir`, e.g. for a wiki.
+/// The directory is created if needed; a non-empty one is only written
+/// into with `force`, which also removes issue files from an earlier export
+/// that no longer match an issue (e.g. after a retitle).
+pub fn run_split(db: &Database, dir: &Path, force: bool) -> Result<()> {
+    if dir.is_dir() && !force && fs::read_dir(dir)?.next().is_some() {
+        bail!(
+            "{} is not empty; use --force to write into it anyway",
+            dir.display()
+        );
+    }
+    fs::create_dir_all(dir).context("Failed to create export directory")?;
+
+    let issues = db.list_issues(Some("all"), None, None)?;
+    let mut written = HashSet::new();
+    for issue in &issues {
+        let mut md = format!("# #{}: {}\n\n", issue.id, issue.title);
+        write_issue_details(&mut md, db, issue)?;
+        let name = split_file_name(issue);
+        fs::write(dir.join(&name), md).context("Failed to write export file")?;
+        written.insert(name);
+    }
+    if force {
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_split_file_name(&name) && !written.contains(&name) {
+                fs::remove_file(entry.path()).context("Failed to remove stale export file")?;
+            }
+        }
+    }
+    eprintln!("Exported {} issues to {}", issues.len(), dir.display());
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::FieldUpdate;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -200,6 +592,33 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_jsonl_one_issue_per_line() {
+        let (db, dir) = setup_test_db();
+        let first = db
+            .create_issue("First", Some("line one\nline two"), "high")
+            .unwrap();
+        db.add_label(first, "bug").unwrap();
+        db.create_subissue(first, "Child", None, "low").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        let path = dir.path().join("export.jsonl");
+        run_jsonl(&db, Some(path.to_str().unwrap()), false, false).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let issues: Vec<ExportedIssue> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(issues[0].title, "First");
+        assert_eq!(issues[0].labels, vec!["bug"]);
+        assert_eq!(issues[1].parent_id, Some(first));
+        assert_eq!(issues[2].status, "closed");
+    }
+
     #[test]
     fn test_export_issue_basic() {
         let (db, _dir) = setup_test_db();
@@ -234,11 +653,45 @@ mod tests {
         assert_eq!(exported.comments.len(), 2);
     }
 
+    #[test]
+    fn test_export_issue_with_dependencies_and_relations() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        db.add_dependency(a, b).unwrap();
+        db.add_relation(a, c, Some("same root cause")).unwrap();
+        let issue = db.get_issue(a).unwrap().unwrap();
+        let exported = export_issue(&db, &issue).unwrap();
+        assert_eq!(exported.blocked_by, vec![b]);
+        assert_eq!(
+            exported.related,
+            vec![ExportedRelation {
+                id: c,
+                note: Some("same root cause".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_export_milestones() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        let m1 = db.create_milestone("v1", None).unwrap();
+        db.create_milestone("v2", Some("Later")).unwrap();
+        db.add_issue_to_milestone(m1, id).unwrap();
+        let milestones = export_milestones(&db).unwrap();
+        assert_eq!(milestones.len(), 2);
+        assert_eq!(milestones[0].name, "v1");
+        assert_eq!(milestones[0].issues, vec![id]);
+        assert_eq!(milestones[1].description, Some("Later".to_string()));
+    }
+
     #[test]
     fn test_export_closed_issue() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
         let issue = db.get_issue(id).unwrap().unwrap();
         let exported = export_issue(&db, &issue).unwrap();
         assert_eq!(exported.status, "closed");
@@ -252,7 +705,7 @@ mod tests {
         db.create_issue("Issue 2", Some("Description"), "low")
             .unwrap();
         let output_path = dir.path().join("export.json");
-        let result = run_json(&db, Some(output_path.to_str().unwrap()));
+        let result = run_json(&db, Some(output_path.to_str().unwrap()), false, false);
         assert!(result.is_ok());
         let content = fs::read_to_string(&output_path).unwrap();
         let data: ExportData = serde_json::from_str(&content).unwrap();
@@ -260,41 +713,229 @@ mod tests {
         assert_eq!(data.issues.len(), 2);
     }
 
+    #[test]
+    fn test_anonymized_export_keeps_structure_but_no_text() {
+        let (db, dir) = setup_test_db();
+        let secret = db
+            .create_issue(
+                "Payroll outage at Acme",
+                Some("Root password leaked"),
+                "high",
+            )
+            .unwrap();
+        let child = db
+            .create_subissue(secret, "Rotate Acme keys", None, "low")
+            .unwrap();
+        let blocker = db.create_issue("Call Acme legal", None, "medium").unwrap();
+        db.add_dependency(secret, blocker).unwrap();
+        db.add_relation(child, blocker, None).unwrap();
+        db.add_label(secret, "security").unwrap();
+        db.add_comment(secret, "Acme's CFO is furious").unwrap();
+        let milestone = db.create_milestone("Acme Q3", Some("Acme only")).unwrap();
+        db.add_issue_to_milestone(milestone, secret).unwrap();
+
+        let plain_path = dir.path().join("plain.json");
+        run_json(&db, Some(plain_path.to_str().unwrap()), false, false).unwrap();
+        let anon_path = dir.path().join("anon.json");
+        run_json(&db, Some(anon_path.to_str().unwrap()), false, true).unwrap();
+
+        let content = fs::read_to_string(&anon_path).unwrap();
+        assert!(!content.contains("Acme"), "got: {}", content);
+        assert!(!content.contains("password"));
+
+        let plain: ExportData =
+            serde_json::from_str(&fs::read_to_string(&plain_path).unwrap()).unwrap();
+        let anon: ExportData = serde_json::from_str(&content).unwrap();
+        assert_eq!(anon.issues.len(), plain.issues.len());
+        for (a, p) in anon.issues.iter().zip(&plain.issues) {
+            assert_eq!(a.id, p.id);
+            assert_eq!(a.parent_id, p.parent_id);
+            assert_eq!(a.blocked_by, p.blocked_by);
+            assert_eq!(a.related, p.related);
+            assert_eq!(a.labels, p.labels);
+            assert_eq!(a.priority, p.priority);
+            assert_eq!(a.comments.len(), p.comments.len());
+        }
+        assert_eq!(anon.milestones[0].issues, vec![secret]);
+    }
+
+    #[test]
+    fn test_anonymized_jsonl_redacts_each_line() {
+        let (db, dir) = setup_test_db();
+        db.create_issue("Acme churn", Some("Acme again"), "high")
+            .unwrap();
+        let path = dir.path().join("anon.jsonl");
+        run_jsonl(&db, Some(path.to_str().unwrap()), false, true).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("Acme"));
+        let issue: ExportedIssue = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(issue.title, "Issue #1");
+        assert_eq!(issue.description.as_deref(), Some(REDACTED));
+    }
+
     #[test]
     fn test_run_json_empty_database() {
         let (db, dir) = setup_test_db();
         let output_path = dir.path().join("export.json");
-        let result = run_json(&db, Some(output_path.to_str().unwrap()));
+        let result = run_json(&db, Some(output_path.to_str().unwrap()), false, false);
         assert!(result.is_ok());
         let content = fs::read_to_string(&output_path).unwrap();
         let data: ExportData = serde_json::from_str(&content).unwrap();
         assert_eq!(data.issues.len(), 0);
     }
 
+    #[test]
+    fn test_run_json_includes_time_entries_and_sessions() {
+        let (db, dir) = setup_test_db();
+        let id = db.create_issue("Tracked", None, "medium").unwrap();
+        db.start_timer(id).unwrap();
+        db.stop_timer(id).unwrap();
+        let session_id = db.start_session().unwrap();
+        db.set_session_issue(session_id, id).unwrap();
+
+        let output_path = dir.path().join("export.json");
+        run_json(&db, Some(output_path.to_str().unwrap()), false, false).unwrap();
+        let content = fs::read_to_string(&output_path).unwrap();
+        let data: ExportData = serde_json::from_str(&content).unwrap();
+        assert_eq!(data.time_entries.len(), 1);
+        assert_eq!(data.time_entries[0].issue_id, id);
+        assert_eq!(data.sessions.len(), 1);
+        assert_eq!(data.sessions[0].active_issue_id, Some(id));
+    }
+
+    #[test]
+    fn test_export_data_without_time_entries_parses() {
+        let json = r#"{"version":1,"exported_at":"2024-01-01T00:00:00Z","issues":[]}"#;
+        let data: ExportData = serde_json::from_str(json).unwrap();
+        assert!(data.time_entries.is_empty());
+        assert!(data.sessions.is_empty());
+    }
+
     #[test]
     fn test_run_markdown_to_file() {
         let (db, dir) = setup_test_db();
         db.create_issue("Issue 1", None, "high").unwrap();
         let output_path = dir.path().join("export.md");
-        let result = run_markdown(&db, Some(output_path.to_str().unwrap()));
+        let result = run_markdown(&db, Some(output_path.to_str().unwrap()), None, false);
         assert!(result.is_ok());
         let content = fs::read_to_string(&output_path).unwrap();
         assert!(content.contains("# Chainlink Issues Export"));
     }
 
+    #[test]
+    fn test_run_markdown_gzip() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let (db, dir) = setup_test_db();
+        db.create_issue("Issue 1", None, "high").unwrap();
+        let output_path = dir.path().join("export.md");
+        run_markdown(&db, Some(output_path.to_str().unwrap()), None, true).unwrap();
+
+        let bytes = fs::read(&output_path).unwrap();
+        let mut content = String::new();
+        GzDecoder::new(&bytes[..])
+            .read_to_string(&mut content)
+            .unwrap();
+        assert!(content.contains("# Chainlink Issues Export"));
+    }
+
+    #[test]
+    fn test_split_export_writes_one_file_per_issue() {
+        let (db, dir) = setup_test_db();
+        db.create_issue("Fix login bug", None, "high").unwrap();
+        db.create_issue("Add dark mode!", Some("Please"), "low")
+            .unwrap();
+        let out = dir.path().join("wiki");
+
+        run_split(&db, &out, false).unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&out)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["001-fix-login-bug.md", "002-add-dark-mode.md"]);
+        let content = fs::read_to_string(out.join("002-add-dark-mode.md")).unwrap();
+        assert!(content.starts_with("# #2: Add dark mode!"));
+        assert!(content.contains("Please"));
+
+        // Refuses to write into a non-empty directory unless forced
+        assert!(run_split(&db, &out, false).is_err());
+        assert!(run_split(&db, &out, true).is_ok());
+    }
+
+    #[test]
+    fn test_forced_split_export_removes_stale_issue_files() {
+        let (db, dir) = setup_test_db();
+        let id = db.create_issue("Old title", None, "medium").unwrap();
+        let out = dir.path().join("wiki");
+        run_split(&db, &out, false).unwrap();
+        fs::write(out.join("notes.md"), "keep me").unwrap();
+
+        db.update_issue(id, Some("New title"), FieldUpdate::Keep, None)
+            .unwrap();
+        run_split(&db, &out, true).unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&out)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["001-new-title.md", "notes.md"]);
+    }
+
     #[test]
     fn test_markdown_groups_by_status() {
         let (db, dir) = setup_test_db();
         db.create_issue("Open issue", None, "medium").unwrap();
         let closed_id = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(closed_id).unwrap();
+        db.close_issue(closed_id, None).unwrap();
         let output_path = dir.path().join("export.md");
-        run_markdown(&db, Some(output_path.to_str().unwrap())).unwrap();
+        run_markdown(&db, Some(output_path.to_str().unwrap()), None, false).unwrap();
         let content = fs::read_to_string(&output_path).unwrap();
         assert!(content.contains("## Open Issues"));
         assert!(content.contains("## Closed Issues"));
     }
 
+    #[test]
+    fn test_slugify_spaces_and_punctuation() {
+        assert_eq!(slugify("Fix login bug"), "fix-login-bug");
+        assert_eq!(slugify("Crash: on start-up (v2)!"), "crash-on-start-up-v2");
+        assert_eq!(
+            slugify("  leading -- and  trailing  "),
+            "leading-and-trailing"
+        );
+        assert_eq!(slugify("snake_case_name"), "snake_case_name");
+        assert_eq!(slugify("!!!"), "");
+    }
+
+    #[test]
+    fn test_slugify_unicode() {
+        assert_eq!(slugify("Ünïcödé Tïtle"), "ünïcödé-tïtle");
+        assert_eq!(slugify("バグ 修正"), "バグ-修正");
+        assert_eq!(slugify("Emoji 🐛 bug"), "emoji-bug");
+    }
+
+    #[test]
+    fn test_markdown_toc_links_to_numbered_sections() {
+        let (db, dir) = setup_test_db();
+        db.create_issue("Fix login bug", None, "high").unwrap();
+        let closed_id = db.create_issue("Old task", None, "low").unwrap();
+        db.close_issue(closed_id, None).unwrap();
+        let output_path = dir.path().join("export.md");
+        run_markdown(&db, Some(output_path.to_str().unwrap()), None, false).unwrap();
+        let content = fs::read_to_string(&output_path).unwrap();
+
+        assert!(content.contains("## Contents"));
+        assert!(content.contains("1. [#1 Fix login bug](#issue-1-fix-login-bug)"));
+        assert!(content.contains("2. [#2 Old task](#issue-2-old-task)"));
+        assert!(content.contains("<a id=\"issue-1-fix-login-bug\"></a>"));
+        assert!(content.contains("### 1. [ ] #1: Fix login bug"));
+        assert!(content.contains("### 2. [x] #2: Old task"));
+    }
+
     #[test]
     fn test_export_unicode_content() {
         let (db, dir) = setup_test_db();
@@ -303,7 +944,7 @@ mod tests {
             .unwrap();
         db.add_label(id, "バグ").unwrap();
         let output_path = dir.path().join("export.json");
-        run_json(&db, Some(output_path.to_str().unwrap())).unwrap();
+        run_json(&db, Some(output_path.to_str().unwrap()), false, false).unwrap();
         let content = fs::read_to_string(&output_path).unwrap();
         let data: ExportData = serde_json::from_str(&content).unwrap();
         assert_eq!(data.issues[0].title, "Test 🐛");
@@ -312,6 +953,8 @@ mod tests {
     #[test]
     fn test_export_data_roundtrip() {
         let data = ExportData {
+            chainlink_version: None,
+            schema_version: None,
             version: 1,
             exported_at: "2024-01-01T00:00:00Z".to_string(),
             issues: vec![ExportedIssue {
@@ -326,10 +969,18 @@ mod tests {
                     content: "Comment".to_string(),
                     created_at: "2024-01-01T00:00:00Z".to_string(),
                 }],
+                blocked_by: vec![],
+                related: vec![],
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
                 closed_at: None,
+                resolution: None,
+                due_at: None,
+                recurrence: None,
             }],
+            time_entries: vec![],
+            sessions: vec![],
+            milestones: vec![],
         };
         let json = serde_json::to_string(&data).unwrap();
         let parsed: ExportData = serde_json::from_str(&json).unwrap();
@@ -343,7 +994,7 @@ mod tests {
             let (db, dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
             let output_path = dir.path().join("export.json");
-            let result = run_json(&db, Some(output_path.to_str().unwrap()));
+            let result = run_json(&db, Some(output_path.to_str().unwrap()), false, false);
             prop_assert!(result.is_ok());
         }
 
@@ -352,7 +1003,7 @@ mod tests {
             let (db, dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
             let output_path = dir.path().join("export.json");
-            run_json(&db, Some(output_path.to_str().unwrap())).unwrap();
+            run_json(&db, Some(output_path.to_str().unwrap()), false, false).unwrap();
             let content = fs::read_to_string(&output_path).unwrap();
             let result: Result<ExportData, _> = serde_json::from_str(&content);
             prop_assert!(result.is_ok());
@@ -1,31 +1,95 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
 use std::collections::HashMap;
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 
-use super::export::{ExportData, ExportedIssue};
-use crate::db::Database;
+use super::export::{
+    ExportData, ExportedIssue, ExportedMilestone, ExportedSession, ExportedTimeEntry,
+};
+use crate::db::{Database, SCHEMA_VERSION};
+use crate::models::{Session, TimeEntry};
+use crate::utils::{info, Recurrence};
 
-pub fn run_json(db: &Database, input_path: &Path) -> Result<()> {
-    let content = fs::read_to_string(input_path).context("Failed to read import file")?;
+/// First two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
 
-    let data: ExportData = serde_json::from_str(&content).context("Failed to parse JSON")?;
+/// Read an export file, decompressing it first if it is gzip-compressed.
+fn read_export_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).context("Failed to read import file")?;
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut content = String::new();
+        GzDecoder::new(&bytes[..])
+            .read_to_string(&mut content)
+            .context("Failed to decompress import file")?;
+        return Ok(content);
+    }
+    String::from_utf8(bytes).context("Import file is not valid UTF-8")
+}
+
+/// Parse an export file: the versioned object written by `export`, or a bare array of
+/// issues as produced by hand or by older tooling.
+fn parse_export(content: &str) -> Result<ExportData> {
+    let value: serde_json::Value = serde_json::from_str(content).context("Failed to parse JSON")?;
+    if value.is_array() {
+        let issues = serde_json::from_value(value).context("Failed to parse issue array")?;
+        return Ok(ExportData {
+            issues,
+            ..Default::default()
+        });
+    }
+    serde_json::from_value(value).context("Failed to parse JSON")
+}
+
+/// Warn when the export came from a newer schema than this build understands.
+fn schema_warning(schema_version: Option<i32>) -> Option<String> {
+    match schema_version {
+        Some(v) if v > SCHEMA_VERSION => Some(format!(
+            "export was written with schema version {} but this chainlink supports up to {}; \
+             fields it does not recognise will be ignored",
+            v, SCHEMA_VERSION
+        )),
+        _ => None,
+    }
+}
 
-    println!(
+/// Import issues from a JSON export.
+///
+/// By default every issue gets a fresh id and all references (parents,
+/// dependencies, relations, milestones, time entries, sessions) are rewritten
+/// through an old→new id map, so importing into a populated database never
+/// overwrites existing issues. With `preserve_ids` the exported ids are kept
+/// as-is, which is only allowed into an empty database.
+pub fn run_json(db: &Database, input_path: &Path, preserve_ids: bool) -> Result<()> {
+    let content = read_export_file(input_path)?;
+
+    let data = parse_export(&content)?;
+    if let Some(warning) = schema_warning(data.schema_version) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if preserve_ids && db.count_issues()? > 0 {
+        bail!("--preserve-ids requires an empty database; drop the flag to import with fresh ids");
+    }
+
+    info(format_args!(
         "Importing {} issues from {}",
         data.issues.len(),
         input_path.display()
-    );
+    ));
 
     // Wrap entire import in a transaction for atomicity
-    // If any part fails, all changes are rolled back
-    let count = db.transaction(|| {
-        // Map old IDs to new IDs for parent relationships
+    // If any part fails, all changes are rolled back. Taking the write lock
+    // up front keeps a concurrent import or daemon write from interleaving.
+    let count = db.transaction_immediate(|| {
+        // Map old IDs to new IDs so every reference can be rewritten
         let mut id_map: HashMap<i64, i64> = HashMap::new();
 
         // First pass: create all issues without parent relationships
         for issue in &data.issues {
-            let new_id = import_issue(db, issue, None)?;
+            let new_id = import_issue(db, issue, preserve_ids)?;
             id_map.insert(issue.id, new_id);
         }
 
@@ -41,17 +105,57 @@ pub fn run_json(db: &Database, input_path: &Path) -> Result<()> {
             }
         }
 
+        // Third pass: dependencies and relations between imported issues
+        for issue in &data.issues {
+            let new_id = id_map[&issue.id];
+            for old_blocker in &issue.blocked_by {
+                match id_map.get(old_blocker) {
+                    Some(&blocker) => {
+                        db.add_dependency(new_id, blocker)?;
+                    }
+                    None => eprintln!(
+                        "Warning: Skipping dependency of #{} on unknown issue #{}",
+                        issue.id, old_blocker
+                    ),
+                }
+            }
+            for relation in &issue.related {
+                match id_map.get(&relation.id) {
+                    Some(&related) => {
+                        db.add_relation(new_id, related, relation.note.as_deref())?;
+                    }
+                    None => eprintln!(
+                        "Warning: Skipping relation of #{} to unknown issue #{}",
+                        issue.id, relation.id
+                    ),
+                }
+            }
+        }
+
+        for milestone in &data.milestones {
+            import_milestone(db, milestone, &id_map)?;
+        }
+
+        for entry in &data.time_entries {
+            import_time_entry(db, entry, &id_map)?;
+        }
+
+        for session in &data.sessions {
+            import_session(db, session, &id_map)?;
+        }
+
         Ok(data.issues.len())
     })?;
 
-    println!("Successfully imported {} issues", count);
+    info(format_args!("Successfully imported {} issues", count));
+    super::integrity::warn_if_dangling(db)?;
     Ok(())
 }
 
-fn import_issue(db: &Database, issue: &ExportedIssue, parent_id: Option<i64>) -> Result<i64> {
-    let id = if let Some(pid) = parent_id {
-        db.create_subissue(
-            pid,
+fn import_issue(db: &Database, issue: &ExportedIssue, preserve_ids: bool) -> Result<i64> {
+    let id = if preserve_ids {
+        db.create_issue_with_id(
+            issue.id,
             &issue.title,
             issue.description.as_deref(),
             &issue.priority,
@@ -59,10 +163,13 @@ fn import_issue(db: &Database, issue: &ExportedIssue, parent_id: Option<i64>) ->
     } else {
         db.create_issue(&issue.title, issue.description.as_deref(), &issue.priority)?
     };
+    db.set_issue_source(id, "import")?;
 
-    // Add labels
+    // Add labels; one the label policy refuses shouldn't sink the whole import
     for label in &issue.labels {
-        db.add_label(id, label)?;
+        if let Err(e) = db.add_label(id, label) {
+            eprintln!("Warning: skipped a label on #{}: {}", id, e);
+        }
     }
 
     // Add comments
@@ -70,18 +177,114 @@ fn import_issue(db: &Database, issue: &ExportedIssue, parent_id: Option<i64>) ->
         db.add_comment(id, &comment.content)?;
     }
 
-    // Close if needed
-    if issue.status == "closed" {
-        db.close_issue(id)?;
+    if let Some(due_at) = &issue.due_at {
+        db.set_due_date(id, Some(parse_timestamp(due_at)?))?;
+    }
+    if let Some(recurrence) = &issue.recurrence {
+        recurrence.parse::<Recurrence>()?;
+        db.set_recurrence(id, Some(recurrence))?;
+    }
+
+    // Restore the status; imported issues start out open
+    let resolution = issue.resolution.as_deref();
+    match issue.status.as_str() {
+        "closed" => {
+            db.close_issue(id, resolution)?;
+        }
+        "wontdo" => {
+            db.drop_issue(id)?;
+        }
+        "archived" => {
+            db.close_issue(id, resolution)?;
+            db.archive_issue(id)?;
+        }
+        _ => {}
     }
 
-    println!("  Imported: #{} -> #{} {}", issue.id, id, issue.title);
+    info(format_args!(
+        "  Imported: #{} -> #{} {}",
+        issue.id, id, issue.title
+    ));
     Ok(id)
 }
 
+fn import_milestone(
+    db: &Database,
+    milestone: &ExportedMilestone,
+    id_map: &HashMap<i64, i64>,
+) -> Result<()> {
+    let id = db.create_milestone(&milestone.name, milestone.description.as_deref())?;
+    for old_id in &milestone.issues {
+        match id_map.get(old_id) {
+            Some(&issue_id) => {
+                db.add_issue_to_milestone(id, issue_id)?;
+            }
+            None => eprintln!(
+                "Warning: Skipping unknown issue #{} in milestone '{}'",
+                old_id, milestone.name
+            ),
+        }
+    }
+    if milestone.status == "closed" {
+        db.close_milestone(id)?;
+    }
+    Ok(())
+}
+
+fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(s)
+        .with_context(|| format!("Invalid timestamp '{}'", s))?
+        .with_timezone(&Utc))
+}
+
+fn import_time_entry(
+    db: &Database,
+    entry: &ExportedTimeEntry,
+    id_map: &HashMap<i64, i64>,
+) -> Result<()> {
+    let Some(&issue_id) = id_map.get(&entry.issue_id) else {
+        eprintln!(
+            "Warning: Skipping time entry for unknown issue #{}",
+            entry.issue_id
+        );
+        return Ok(());
+    };
+
+    db.insert_time_entry(&TimeEntry {
+        id: 0,
+        issue_id,
+        started_at: parse_timestamp(&entry.started_at)?,
+        ended_at: entry.ended_at.as_deref().map(parse_timestamp).transpose()?,
+        duration_seconds: entry.duration_seconds,
+    })?;
+    Ok(())
+}
+
+fn import_session(
+    db: &Database,
+    session: &ExportedSession,
+    id_map: &HashMap<i64, i64>,
+) -> Result<()> {
+    db.insert_session(&Session {
+        id: 0,
+        started_at: parse_timestamp(&session.started_at)?,
+        ended_at: session
+            .ended_at
+            .as_deref()
+            .map(parse_timestamp)
+            .transpose()?,
+        active_issue_id: session
+            .active_issue_id
+            .and_then(|old| id_map.get(&old).copied()),
+        handoff_notes: session.handoff_notes.clone(),
+        last_action: session.last_action.clone(),
+    })?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::export::{ExportData, ExportedIssue};
+    use super::super::export::{ExportData, ExportedIssue, ExportedRelation};
     use super::*;
     use proptest::prelude::*;
     use tempfile::tempdir;
@@ -95,9 +298,14 @@ mod tests {
 
     fn create_test_export(issues: Vec<ExportedIssue>) -> String {
         let data = ExportData {
+            chainlink_version: None,
+            schema_version: None,
             version: 1,
             exported_at: "2024-01-01T00:00:00Z".to_string(),
             issues,
+            time_entries: vec![],
+            sessions: vec![],
+            milestones: vec![],
         };
         serde_json::to_string_pretty(&data).unwrap()
     }
@@ -112,9 +320,14 @@ mod tests {
             parent_id,
             labels: vec![],
             comments: vec![],
+            blocked_by: vec![],
+            related: vec![],
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
             closed_at: None,
+            resolution: None,
+            due_at: None,
+            recurrence: None,
         }
     }
 
@@ -124,7 +337,7 @@ mod tests {
         let json = create_test_export(vec![make_issue(1, "Test issue", None, "open")]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_ok());
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(issues.len(), 1);
@@ -139,7 +352,7 @@ mod tests {
         ]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        run_json(&db, &import_path).unwrap();
+        run_json(&db, &import_path, false).unwrap();
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(issues.len(), 2);
     }
@@ -150,7 +363,7 @@ mod tests {
         let json = create_test_export(vec![make_issue(1, "Closed", None, "closed")]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        run_json(&db, &import_path).unwrap();
+        run_json(&db, &import_path, false).unwrap();
         let issues = db.list_issues(Some("closed"), None, None).unwrap();
         assert_eq!(issues.len(), 1);
     }
@@ -163,26 +376,363 @@ mod tests {
         let json = create_test_export(vec![issue]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        run_json(&db, &import_path).unwrap();
+        run_json(&db, &import_path, false).unwrap();
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         let labels = db.get_labels(issues[0].id).unwrap();
         assert!(labels.contains(&"bug".to_string()));
     }
 
+    #[test]
+    fn test_import_remaps_time_entries_and_sessions() {
+        let (db, dir) = setup_test_db();
+        // Occupy id 1 so imported issues get new ids
+        db.create_issue("Existing", None, "medium").unwrap();
+
+        let data = ExportData {
+            chainlink_version: None,
+            schema_version: None,
+            version: 1,
+            exported_at: "2024-01-01T00:00:00Z".to_string(),
+            issues: vec![make_issue(1, "Tracked", None, "open")],
+            time_entries: vec![
+                ExportedTimeEntry {
+                    issue_id: 1,
+                    started_at: "2024-01-01T00:00:00Z".to_string(),
+                    ended_at: Some("2024-01-01T01:00:00Z".to_string()),
+                    duration_seconds: Some(3600),
+                },
+                ExportedTimeEntry {
+                    issue_id: 42,
+                    started_at: "2024-01-01T00:00:00Z".to_string(),
+                    ended_at: None,
+                    duration_seconds: None,
+                },
+            ],
+            sessions: vec![ExportedSession {
+                started_at: "2024-01-01T00:00:00Z".to_string(),
+                ended_at: Some("2024-01-01T02:00:00Z".to_string()),
+                active_issue_id: Some(1),
+                handoff_notes: Some("Notes".to_string()),
+                last_action: None,
+            }],
+            milestones: vec![],
+        };
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, serde_json::to_string(&data).unwrap()).unwrap();
+        run_json(&db, &import_path, false).unwrap();
+
+        let entries = db.all_time_entries().unwrap();
+        assert_eq!(entries.len(), 1, "entry for unknown issue is skipped");
+        assert_eq!(entries[0].issue_id, 2);
+        assert_eq!(db.get_total_time(2).unwrap(), 3600);
+
+        let sessions = db.all_sessions().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].active_issue_id, Some(2));
+        assert_eq!(sessions[0].handoff_notes, Some("Notes".to_string()));
+    }
+
+    #[test]
+    fn test_import_into_populated_db_remaps_ids() {
+        let (db, dir) = setup_test_db();
+        let existing = db
+            .create_issue("Existing", Some("Keep me"), "high")
+            .unwrap();
+        assert_eq!(existing, 1);
+
+        let mut parent = make_issue(1, "Imported parent", None, "open");
+        parent.related = vec![ExportedRelation { id: 3, note: None }];
+        let child = make_issue(2, "Imported child", Some(1), "open");
+        let mut blocked = make_issue(3, "Imported blocked", None, "open");
+        blocked.blocked_by = vec![1];
+        let data = ExportData {
+            chainlink_version: None,
+            schema_version: None,
+            version: 1,
+            exported_at: "2024-01-01T00:00:00Z".to_string(),
+            issues: vec![parent, child, blocked],
+            time_entries: vec![],
+            sessions: vec![],
+            milestones: vec![ExportedMilestone {
+                name: "v1".to_string(),
+                description: None,
+                status: "open".to_string(),
+                issues: vec![2],
+            }],
+        };
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, serde_json::to_string(&data).unwrap()).unwrap();
+        run_json(&db, &import_path, false).unwrap();
+
+        // The pre-existing issue is untouched
+        let kept = db.get_issue(existing).unwrap().unwrap();
+        assert_eq!(kept.title, "Existing");
+        assert_eq!(kept.description, Some("Keep me".to_string()));
+        assert!(db.get_blockers(existing).unwrap().is_empty());
+
+        // Imported issues got fresh ids 2..=4 with references rewritten
+        assert_eq!(db.get_issue(2).unwrap().unwrap().title, "Imported parent");
+        assert_eq!(db.get_issue(3).unwrap().unwrap().parent_id, Some(2));
+        assert_eq!(db.get_blockers(4).unwrap(), vec![2]);
+        let related: Vec<i64> = db
+            .get_related_issues(2)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(related, vec![4]);
+        let milestones = db.get_issue_milestones(3).unwrap();
+        assert_eq!(milestones[0].name, "v1");
+    }
+
+    #[test]
+    fn test_import_preserve_ids() {
+        let (db, dir) = setup_test_db();
+        let json = create_test_export(vec![
+            make_issue(5, "Five", None, "open"),
+            make_issue(9, "Nine", Some(5), "closed"),
+        ]);
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, json).unwrap();
+        run_json(&db, &import_path, true).unwrap();
+
+        assert_eq!(db.get_issue(5).unwrap().unwrap().title, "Five");
+        let nine = db.get_issue(9).unwrap().unwrap();
+        assert_eq!(nine.parent_id, Some(5));
+        assert_eq!(nine.status, "closed");
+    }
+
+    #[test]
+    fn test_import_preserve_ids_requires_empty_db() {
+        let (db, dir) = setup_test_db();
+        db.create_issue("Existing", None, "medium").unwrap();
+        let json = create_test_export(vec![make_issue(1, "Clash", None, "open")]);
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, json).unwrap();
+
+        let result = run_json(&db, &import_path, true);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("empty database"));
+        assert_eq!(db.get_issue(1).unwrap().unwrap().title, "Existing");
+    }
+
+    #[test]
+    fn test_import_sets_source() {
+        let (db, dir) = setup_test_db();
+        let manual = db.create_issue("Manual", None, "medium").unwrap();
+        let json = create_test_export(vec![make_issue(1, "Imported", None, "open")]);
+        let import_path = dir.path().join("import.json");
+        fs::write(&import_path, json).unwrap();
+        run_json(&db, &import_path, false).unwrap();
+
+        assert_eq!(db.get_issue(manual).unwrap().unwrap().source, "manual");
+        assert_eq!(db.get_issue(2).unwrap().unwrap().source, "import");
+    }
+
     #[test]
     fn test_import_invalid_json() {
         let (db, dir) = setup_test_db();
         let import_path = dir.path().join("invalid.json");
         fs::write(&import_path, "not valid json").unwrap();
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_gzip_export_reimports_identically() {
+        let (source, dir) = setup_test_db();
+        let parent = source
+            .create_issue("Compressed", Some("Body"), "high")
+            .unwrap();
+        source
+            .create_subissue(parent, "Child", None, "low")
+            .unwrap();
+        source.add_label(parent, "bug").unwrap();
+        source.add_comment(parent, "A note").unwrap();
+
+        let path = dir.path().join("export.json.gz");
+        super::super::export::run_json(&source, Some(path.to_str().unwrap()), false, false)
+            .unwrap();
+        assert!(fs::read(&path).unwrap().starts_with(&GZIP_MAGIC));
+
+        let (target, _target_dir) = setup_test_db();
+        run_json(&target, &path, true).unwrap();
+
+        let snapshot = |db: &Database| {
+            db.list_issues(Some("all"), None, None)
+                .unwrap()
+                .into_iter()
+                .map(|i| {
+                    let comments: Vec<String> = db
+                        .get_comments(i.id, None)
+                        .unwrap()
+                        .into_iter()
+                        .map(|c| c.content)
+                        .collect();
+                    (
+                        i.id,
+                        i.title,
+                        i.description,
+                        i.priority,
+                        i.parent_id,
+                        db.get_labels(i.id).unwrap(),
+                        comments,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(snapshot(&source), snapshot(&target));
+    }
+
+    #[test]
+    fn test_versioned_export_round_trips() {
+        let (source, dir) = setup_test_db();
+        source.create_issue("Versioned", None, "high").unwrap();
+        let path = dir.path().join("export.json");
+        super::super::export::run_json(&source, Some(path.to_str().unwrap()), false, false)
+            .unwrap();
+
+        let data = parse_export(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(data.schema_version, Some(SCHEMA_VERSION));
+        assert_eq!(
+            data.chainlink_version.as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+        assert!(schema_warning(data.schema_version).is_none());
+
+        let (target, _target_dir) = setup_test_db();
+        run_json(&target, &path, false).unwrap();
+        let issues = target.list_issues(Some("all"), None, None).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title, "Versioned");
+    }
+
+    #[test]
+    fn test_dropped_and_archived_issues_round_trip() {
+        let (source, dir) = setup_test_db();
+        let dropped = source.create_issue("Won't do", None, "low").unwrap();
+        source.drop_issue(dropped).unwrap();
+        let archived = source.create_issue("Old", None, "low").unwrap();
+        source.close_issue(archived, None).unwrap();
+        source.archive_issue(archived).unwrap();
+        let path = dir.path().join("export.json");
+        super::super::export::run_json(&source, Some(path.to_str().unwrap()), false, false)
+            .unwrap();
+
+        let (target, _target_dir) = setup_test_db();
+        run_json(&target, &path, true).unwrap();
+        assert_eq!(target.get_issue(dropped).unwrap().unwrap().status, "wontdo");
+        assert_eq!(
+            target.get_issue(archived).unwrap().unwrap().status,
+            "archived"
+        );
+    }
+
+    #[test]
+    fn test_issue_fields_round_trip() {
+        let (source, dir) = setup_test_db();
+        let dup = source.create_issue("Dup", None, "low").unwrap();
+        source.close_issue(dup, Some("duplicate")).unwrap();
+        let chore = source.create_issue("Rotate logs", None, "low").unwrap();
+        source
+            .add_relation(dup, chore, Some("same log volume"))
+            .unwrap();
+        let due = DateTime::parse_from_rfc3339("2026-03-15T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        source.set_due_date(chore, Some(due)).unwrap();
+        source.set_recurrence(chore, Some("weekly")).unwrap();
+        let path = dir.path().join("export.json");
+        super::super::export::run_json(&source, Some(path.to_str().unwrap()), false, false)
+            .unwrap();
+
+        let (target, _target_dir) = setup_test_db();
+        run_json(&target, &path, true).unwrap();
+        assert_eq!(
+            target.get_resolution(dup).unwrap().as_deref(),
+            Some("duplicate")
+        );
+        assert_eq!(target.get_issue(chore).unwrap().unwrap().due_at, Some(due));
+        assert_eq!(
+            target.get_recurrence(chore).unwrap().as_deref(),
+            Some("weekly")
+        );
+        assert_eq!(
+            target
+                .relation_notes(dup)
+                .unwrap()
+                .get(&chore)
+                .map(String::as_str),
+            Some("same log volume")
+        );
+    }
+
+    #[test]
+    fn test_import_accepts_bare_related_ids() {
+        let (db, dir) = setup_test_db();
+        let mut value: serde_json::Value = serde_json::from_str(&create_test_export(vec![
+            make_issue(1, "A", None, "open"),
+            make_issue(2, "B", None, "open"),
+        ]))
+        .unwrap();
+        value["issues"][0]["related"] = serde_json::json!([2]);
+        let path = dir.path().join("old.json");
+        fs::write(&path, value.to_string()).unwrap();
+
+        run_json(&db, &path, true).unwrap();
+        let related: Vec<i64> = db
+            .get_related_issues(1)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(related, vec![2]);
+        assert!(db.relation_notes(1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_future_schema_warns() {
+        let (db, dir) = setup_test_db();
+        let mut value: serde_json::Value =
+            serde_json::from_str(&create_test_export(vec![make_issue(
+                1,
+                "From the future",
+                None,
+                "open",
+            )]))
+            .unwrap();
+        value["schema_version"] = serde_json::json!(SCHEMA_VERSION + 5);
+        value["hologram_attachments"] = serde_json::json!([]);
+        let import_path = dir.path().join("future.json");
+        fs::write(&import_path, value.to_string()).unwrap();
+
+        let warning = schema_warning(Some(SCHEMA_VERSION + 5)).unwrap();
+        assert!(warning.contains(&format!("schema version {}", SCHEMA_VERSION + 5)));
+        run_json(&db, &import_path, false).unwrap();
+        assert_eq!(db.list_issues(Some("all"), None, None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_import_headerless_array() {
+        let (db, dir) = setup_test_db();
+        let issues = vec![
+            make_issue(1, "Bare one", None, "open"),
+            make_issue(2, "Bare two", Some(1), "open"),
+        ];
+        let import_path = dir.path().join("bare.json");
+        fs::write(&import_path, serde_json::to_string(&issues).unwrap()).unwrap();
+
+        run_json(&db, &import_path, false).unwrap();
+        let imported = db.list_issues(Some("all"), None, None).unwrap();
+        assert_eq!(imported.len(), 2);
+        assert!(imported.iter().any(|i| i.parent_id.is_some()));
+    }
+
     #[test]
     fn test_import_missing_file() {
         let (db, dir) = setup_test_db();
         let import_path = dir.path().join("nonexistent.json");
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_err());
     }
 
@@ -192,7 +742,7 @@ mod tests {
         let json = create_test_export(vec![]);
         let import_path = dir.path().join("import.json");
         fs::write(&import_path, json).unwrap();
-        let result = run_json(&db, &import_path);
+        let result = run_json(&db, &import_path, false);
         assert!(result.is_ok());
     }
 
@@ -203,7 +753,7 @@ mod tests {
             let json = create_test_export(vec![make_issue(1, &title, None, "open")]);
             let import_path = dir.path().join("import.json");
             fs::write(&import_path, json).unwrap();
-            let result = run_json(&db, &import_path);
+            let result = run_json(&db, &import_path, false);
             prop_assert!(result.is_ok());
         }
     }
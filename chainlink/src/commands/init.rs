@@ -3,6 +3,7 @@ use std::fs;
 use std::path::Path;
 
 use crate::db::Database;
+use crate::utils::info;
 
 // Embed hook files at compile time from resources/ (packaged with the crate)
 const SETTINGS_JSON: &str = include_str!("../../resources/claude/settings.json");
@@ -89,6 +90,12 @@ const RULE_FILES: &[(&str, &str)] = &[
     ("tracking-relaxed.md", RULE_TRACKING_RELAXED),
 ];
 
+/// Returns true if no filename appears more than once in `RULE_FILES`.
+fn rule_filenames_unique() -> bool {
+    let mut seen = std::collections::HashSet::new();
+    RULE_FILES.iter().all(|(name, _)| seen.insert(*name))
+}
+
 /// Merge chainlink's MCP server entries into an existing `.mcp.json`, or create it fresh.
 /// Returns a list of warnings (e.g. overwritten keys) for the caller to display.
 fn write_mcp_json_merged(mcp_path: &Path) -> Result<Vec<String>> {
@@ -150,7 +157,55 @@ fn write_mcp_json_merged(mcp_path: &Path) -> Result<Vec<String>> {
     Ok(warnings)
 }
 
-pub fn run(path: &Path, force: bool) -> Result<()> {
+/// The database and its WAL sidecars; none of them belong in version control.
+const GITIGNORE_ENTRIES: [&str; 3] = [
+    "/.chainlink/issues.db",
+    "/.chainlink/issues.db-wal",
+    "/.chainlink/issues.db-shm",
+];
+
+/// Append whichever `GITIGNORE_ENTRIES` are missing to the project's `.gitignore`,
+/// creating it if needed. Nothing is added if the whole `.chainlink` directory is
+/// already ignored. Returns the entries that were added.
+fn update_gitignore(path: &Path) -> Result<Vec<&'static str>> {
+    let gitignore = path.join(".gitignore");
+    let existing = if gitignore.exists() {
+        fs::read_to_string(&gitignore).context("Failed to read .gitignore")?
+    } else {
+        String::new()
+    };
+    let lines: Vec<&str> = existing
+        .lines()
+        .map(|l| l.trim().trim_start_matches('/'))
+        .collect();
+    if lines
+        .iter()
+        .any(|l| l.trim_end_matches('/') == ".chainlink")
+    {
+        return Ok(Vec::new());
+    }
+
+    let missing: Vec<&'static str> = GITIGNORE_ENTRIES
+        .into_iter()
+        .filter(|entry| !lines.contains(&entry.trim_start_matches('/')))
+        .collect();
+    if missing.is_empty() {
+        return Ok(missing);
+    }
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in &missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+    fs::write(&gitignore, content).context("Failed to write .gitignore")?;
+    Ok(missing)
+}
+
+pub fn run(path: &Path, force: bool, gitignore: bool) -> Result<()> {
     let chainlink_dir = path.join(".chainlink");
     let claude_dir = path.join(".claude");
     let hooks_dir = claude_dir.join("hooks");
@@ -159,21 +214,34 @@ pub fn run(path: &Path, force: bool) -> Result<()> {
     let chainlink_exists = chainlink_dir.exists();
     let claude_exists = claude_dir.exists();
 
+    // Done even for an existing setup, so older projects pick up the entries
+    if gitignore {
+        let added = update_gitignore(path)?;
+        if !added.is_empty() {
+            info(format_args!("Added {} to .gitignore", added.join(", ")));
+        }
+    }
+
     if chainlink_exists && claude_exists && !force {
-        println!("Already initialized at {}", path.display());
-        println!("Use --force to update hooks to latest version.");
+        info(format_args!("Already initialized at {}", path.display()));
+        info("Use --force to update hooks to latest version.");
         return Ok(());
     }
 
     let rules_dir = chainlink_dir.join("rules");
 
+    debug_assert!(
+        rule_filenames_unique(),
+        "RULE_FILES contains duplicate filenames"
+    );
+
     // Create .chainlink directory and database
     if !chainlink_exists {
         fs::create_dir_all(&chainlink_dir).context("Failed to create .chainlink directory")?;
 
         let db_path = chainlink_dir.join("issues.db");
         Database::open(&db_path)?;
-        println!("Created {}", chainlink_dir.display());
+        info(format_args!("Created {}", chainlink_dir.display()));
     }
 
     // Write hook config (create or update)
@@ -193,9 +261,15 @@ pub fn run(path: &Path, force: bool) -> Result<()> {
         }
 
         if force && rules_exist {
-            println!("Updated {} with latest rules", rules_dir.display());
+            info(format_args!(
+                "Updated {} with latest rules",
+                rules_dir.display()
+            ));
         } else {
-            println!("Created {} with default rules", rules_dir.display());
+            info(format_args!(
+                "Created {} with default rules",
+                rules_dir.display()
+            ));
         }
     }
 
@@ -237,16 +311,22 @@ pub fn run(path: &Path, force: bool) -> Result<()> {
         }
 
         if force && claude_exists {
-            println!("Updated {} with latest hooks", claude_dir.display());
+            info(format_args!(
+                "Updated {} with latest hooks",
+                claude_dir.display()
+            ));
         } else {
-            println!("Created {} with Claude Code hooks", claude_dir.display());
+            info(format_args!(
+                "Created {} with Claude Code hooks",
+                claude_dir.display()
+            ));
         }
     }
 
-    println!("Chainlink initialized successfully!");
-    println!("\nNext steps:");
-    println!("  chainlink session start     # Start a session");
-    println!("  chainlink create \"Task\"     # Create an issue");
+    info("Chainlink initialized successfully!");
+    info("\nNext steps:");
+    info("  chainlink session start     # Start a session");
+    info("  chainlink create \"Task\"     # Create an issue");
 
     Ok(())
 }
@@ -259,7 +339,7 @@ mod tests {
     #[test]
     fn test_run_fresh_init() {
         let dir = tempdir().unwrap();
-        let result = run(dir.path(), false);
+        let result = run(dir.path(), false, true);
         assert!(result.is_ok());
 
         // Verify directories created
@@ -272,10 +352,48 @@ mod tests {
         assert!(dir.path().join(".chainlink/hook-config.json").exists());
     }
 
+    #[test]
+    fn test_init_adds_gitignore_entries_once() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/").unwrap();
+
+        run(dir.path(), false, true).unwrap();
+        let first = fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert_eq!(
+            first,
+            "target/\n/.chainlink/issues.db\n/.chainlink/issues.db-wal\n/.chainlink/issues.db-shm\n"
+        );
+
+        run(dir.path(), false, true).unwrap();
+        run(dir.path(), true, true).unwrap();
+        let again = fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert_eq!(again, first);
+    }
+
+    #[test]
+    fn test_init_gitignore_respects_existing_rules() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), ".chainlink/\n").unwrap();
+        assert!(update_gitignore(dir.path()).unwrap().is_empty());
+
+        fs::write(dir.path().join(".gitignore"), ".chainlink/issues.db\n").unwrap();
+        assert_eq!(
+            update_gitignore(dir.path()).unwrap(),
+            vec!["/.chainlink/issues.db-wal", "/.chainlink/issues.db-shm"]
+        );
+    }
+
+    #[test]
+    fn test_init_without_gitignore() {
+        let dir = tempdir().unwrap();
+        run(dir.path(), false, false).unwrap();
+        assert!(!dir.path().join(".gitignore").exists());
+    }
+
     #[test]
     fn test_run_creates_hook_files() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Verify hook files
         assert!(dir.path().join(".claude/settings.json").exists());
@@ -291,7 +409,7 @@ mod tests {
     #[test]
     fn test_run_creates_rule_files() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         let rules_dir = dir.path().join(".chainlink/rules");
         assert!(rules_dir.join("global.md").exists());
@@ -310,10 +428,10 @@ mod tests {
         let dir = tempdir().unwrap();
 
         // First init
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Second init without force - should succeed but not recreate
-        let result = run(dir.path(), false);
+        let result = run(dir.path(), false, true);
         assert!(result.is_ok());
     }
 
@@ -322,14 +440,14 @@ mod tests {
         let dir = tempdir().unwrap();
 
         // First init
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Modify a hook file
         let hook_path = dir.path().join(".claude/hooks/prompt-guard.py");
         fs::write(&hook_path, "# modified").unwrap();
 
         // Force update
-        run(dir.path(), true).unwrap();
+        run(dir.path(), true, true).unwrap();
 
         // Verify file was restored
         let content = fs::read_to_string(&hook_path).unwrap();
@@ -351,7 +469,7 @@ mod tests {
     #[test]
     fn test_force_init_preserves_existing_mcp_servers() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Add a custom MCP server entry alongside the embedded ones
         let mcp_path = dir.path().join(".mcp.json");
@@ -364,7 +482,7 @@ mod tests {
         fs::write(&mcp_path, serde_json::to_string_pretty(&content).unwrap()).unwrap();
 
         // Force update
-        run(dir.path(), true).unwrap();
+        run(dir.path(), true, true).unwrap();
 
         // Verify all embedded keys and the custom key are present
         let result: serde_json::Value =
@@ -391,7 +509,7 @@ mod tests {
     #[test]
     fn test_force_init_returns_warnings_for_overwritten_keys() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // The first init created .mcp.json with the embedded keys.
         // A second force init should warn about overwriting each one.
@@ -446,14 +564,14 @@ mod tests {
     #[test]
     fn test_force_init_fails_on_malformed_mcp_json() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Write invalid JSON to .mcp.json
         let mcp_path = dir.path().join(".mcp.json");
         fs::write(&mcp_path, "not json {{{").unwrap();
 
         // Force init should fail, not silently overwrite
-        let result = run(dir.path(), true);
+        let result = run(dir.path(), true, true);
         assert!(result.is_err());
         let err = format!("{:#}", result.unwrap_err());
         assert!(
@@ -470,14 +588,14 @@ mod tests {
     #[test]
     fn test_force_init_fails_on_non_object_mcp_json() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Write a JSON array to .mcp.json
         let mcp_path = dir.path().join(".mcp.json");
         fs::write(&mcp_path, "[1, 2, 3]").unwrap();
 
         // Force init should fail, not silently overwrite
-        let result = run(dir.path(), true);
+        let result = run(dir.path(), true, true);
         assert!(result.is_err());
         let err = format!("{:#}", result.unwrap_err());
         assert!(
@@ -494,14 +612,14 @@ mod tests {
     #[test]
     fn test_force_init_handles_empty_mcp_json_file() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Write empty file
         let mcp_path = dir.path().join(".mcp.json");
         fs::write(&mcp_path, "").unwrap();
 
         // Should fail — empty file is not valid JSON
-        let result = run(dir.path(), true);
+        let result = run(dir.path(), true, true);
         assert!(result.is_err());
         let err = format!("{:#}", result.unwrap_err());
         assert!(
@@ -514,14 +632,14 @@ mod tests {
     #[test]
     fn test_force_init_fails_on_non_object_mcp_servers_value() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Write valid JSON where mcpServers is a string instead of object
         let mcp_path = dir.path().join(".mcp.json");
         fs::write(&mcp_path, r#"{"mcpServers": "banana"}"#).unwrap();
 
         // Should fail, not silently replace
-        let result = run(dir.path(), true);
+        let result = run(dir.path(), true, true);
         assert!(result.is_err());
         let err = format!("{:#}", result.unwrap_err());
         assert!(
@@ -538,14 +656,14 @@ mod tests {
     #[test]
     fn test_init_merges_into_mcp_json_without_mcp_servers_key() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Write a valid object with no mcpServers key
         let mcp_path = dir.path().join(".mcp.json");
         fs::write(&mcp_path, r#"{"someOtherKey": true}"#).unwrap();
 
         // Force init should add mcpServers, preserving the other key
-        run(dir.path(), true).unwrap();
+        run(dir.path(), true, true).unwrap();
 
         let content = fs::read_to_string(&mcp_path).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
@@ -560,7 +678,7 @@ mod tests {
         // Create only .chainlink directory
         fs::create_dir_all(dir.path().join(".chainlink")).unwrap();
 
-        let result = run(dir.path(), false);
+        let result = run(dir.path(), false, true);
         assert!(result.is_ok());
 
         // .claude should now exist
@@ -574,7 +692,7 @@ mod tests {
         // Create only .claude directory
         fs::create_dir_all(dir.path().join(".claude")).unwrap();
 
-        let result = run(dir.path(), false);
+        let result = run(dir.path(), false, true);
         assert!(result.is_ok());
 
         // .chainlink should now exist
@@ -584,7 +702,7 @@ mod tests {
     #[test]
     fn test_run_database_usable() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Open the created database and verify it works
         let db_path = dir.path().join(".chainlink/issues.db");
@@ -598,7 +716,7 @@ mod tests {
     #[test]
     fn test_run_rule_files_not_empty() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         let rules_dir = dir.path().join(".chainlink/rules");
 
@@ -613,14 +731,14 @@ mod tests {
     #[test]
     fn test_run_force_updates_rules() {
         let dir = tempdir().unwrap();
-        run(dir.path(), false).unwrap();
+        run(dir.path(), false, true).unwrap();
 
         // Modify a rule file
         let rule_path = dir.path().join(".chainlink/rules/global.md");
         fs::write(&rule_path, "# modified rule").unwrap();
 
         // Force update
-        run(dir.path(), true).unwrap();
+        run(dir.path(), true, true).unwrap();
 
         // Verify file was restored
         let content = fs::read_to_string(&rule_path).unwrap();
@@ -633,7 +751,7 @@ mod tests {
 
         // Multiple force runs should all succeed
         for _ in 0..3 {
-            let result = run(dir.path(), true);
+            let result = run(dir.path(), true, true);
             assert!(result.is_ok());
         }
 
@@ -677,4 +795,48 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_rule_filenames_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for (name, _) in RULE_FILES {
+            assert!(seen.insert(*name), "Duplicate rule filename: {}", name);
+        }
+        assert!(rule_filenames_unique());
+    }
+
+    #[test]
+    fn test_rule_files_match_resources_dir() {
+        // Every file under resources/chainlink/rules must be embedded exactly once,
+        // and each entry must carry the content of the file it is named after.
+        let rules_src = Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/chainlink/rules");
+        let on_disk: Vec<String> = fs::read_dir(&rules_src)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+
+        assert_eq!(
+            RULE_FILES.len(),
+            on_disk.len(),
+            "RULE_FILES has {} entries but {} has {} files",
+            RULE_FILES.len(),
+            rules_src.display(),
+            on_disk.len()
+        );
+
+        for (name, content) in RULE_FILES {
+            assert!(
+                on_disk.iter().any(|f| f == name),
+                "Rule file {} is not present in {}",
+                name,
+                rules_src.display()
+            );
+            let expected = fs::read_to_string(rules_src.join(name)).unwrap();
+            assert_eq!(
+                *content, expected,
+                "RULE_FILES entry {} embeds the wrong file",
+                name
+            );
+        }
+    }
 }
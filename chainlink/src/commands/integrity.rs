@@ -0,0 +1,55 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+
+pub fn check(db: &Database) -> Result<()> {
+    let problems = db.validate_integrity()?;
+
+    if problems.is_empty() {
+        println!("No integrity problems found.");
+        return Ok(());
+    }
+
+    println!("Found {} integrity problem(s):", problems.len());
+    for problem in &problems {
+        println!("  {}", problem);
+    }
+
+    bail!("Database integrity check failed");
+}
+
+/// Print integrity warnings without failing; used after bulk operations like import.
+pub fn warn_if_dangling(db: &Database) -> Result<()> {
+    let problems = db.validate_integrity()?;
+    if !problems.is_empty() {
+        eprintln!(
+            "Warning: {} integrity problem(s) found. Run 'chainlink db check' for details.",
+            problems.len()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_check_clean_db() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+
+        assert!(check(&db).is_ok());
+        assert!(warn_if_dangling(&db).is_ok());
+    }
+}
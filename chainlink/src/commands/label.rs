@@ -1,25 +1,40 @@
 use anyhow::Result;
 
 use crate::db::Database;
+use crate::utils::info;
 
 pub fn add(db: &Database, issue_id: i64, label: &str) -> Result<()> {
     db.require_issue(issue_id)?;
+    let label = &db.normalize_label(label)?;
 
     if db.add_label(issue_id, label)? {
-        println!("Added label '{}' to issue #{}", label, issue_id);
+        info(format_args!(
+            "Added label '{}' to issue #{}",
+            label, issue_id
+        ));
     } else {
-        println!("Label '{}' already exists on issue #{}", label, issue_id);
+        info(format_args!(
+            "Label '{}' already exists on issue #{}",
+            label, issue_id
+        ));
     }
     Ok(())
 }
 
 pub fn remove(db: &Database, issue_id: i64, label: &str) -> Result<()> {
     db.require_issue(issue_id)?;
+    let label = &db.label_key(label);
 
     if db.remove_label(issue_id, label)? {
-        println!("Removed label '{}' from issue #{}", label, issue_id);
+        info(format_args!(
+            "Removed label '{}' from issue #{}",
+            label, issue_id
+        ));
     } else {
-        println!("Label '{}' not found on issue #{}", label, issue_id);
+        info(format_args!(
+            "Label '{}' not found on issue #{}",
+            label, issue_id
+        ));
     }
     Ok(())
 }
@@ -94,11 +109,11 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = add(&db, issue_id, "");
-        assert!(result.is_ok());
-
-        let labels = db.get_labels(issue_id).unwrap();
-        assert!(labels.contains(&"".to_string()));
+        for blank in ["", "   "] {
+            let err = add(&db, issue_id, blank).unwrap_err();
+            assert!(err.to_string().contains("can't be empty"));
+        }
+        assert!(db.get_labels(issue_id).unwrap().is_empty());
     }
 
     #[test]
@@ -140,9 +155,9 @@ mod tests {
         let result = add(&db, issue_id, malicious);
         assert!(result.is_ok());
 
-        // Verify label was stored literally
+        // Verify label was stored literally, apart from the spaces becoming hyphens
         let labels = db.get_labels(issue_id).unwrap();
-        assert!(labels.contains(&malicious.to_string()));
+        assert!(labels.contains(&"';-DROP-TABLE-labels;---".to_string()));
 
         // Verify database integrity
         let issues = db.list_issues(None, None, None).unwrap();
@@ -204,7 +219,7 @@ mod tests {
     fn test_add_label_to_closed_issue() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
         let result = add(&db, issue_id, "bug");
         assert!(result.is_ok());
@@ -1,52 +1,266 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde_json;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::commands::create::validate_priority;
+use crate::config::DEFAULT_DATE_FORMAT;
+use crate::db::{priority_rank, Comparison, IssueOrder, PriorityFilter};
+use crate::models::Issue;
+use crate::store::Store;
+use crate::utils::{display_width, format_timestamp, pad_display, truncate_display};
+
+/// Restricts `list` to one level of the issue hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentFilter {
+    /// Only issues without a parent
+    Root,
+    /// Only direct subissues of this issue
+    Of(i64),
+}
+
+impl FromStr for ParentFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("none") {
+            return Ok(ParentFilter::Root);
+        }
+        s.trim_start_matches('#')
+            .parse()
+            .map(ParentFilter::Of)
+            .map_err(|_| format!("expected an issue id or 'none', got '{}'", s))
+    }
+}
+
+/// Parse a `--priority` filter: a priority for an exact match, or one prefixed
+/// with `>=`, `<=`, `>`, `<` or `=` to compare by rank (`>=high` is high and
+/// critical).
+pub fn parse_priority_filter(spec: &str) -> Result<PriorityFilter<'_>> {
+    let comparisons = [
+        (">=", Some(Comparison::GreaterOrEqual)),
+        ("<=", Some(Comparison::LessOrEqual)),
+        (">", Some(Comparison::Greater)),
+        ("<", Some(Comparison::Less)),
+        ("=", None),
+    ];
+    for (prefix, comparison) in comparisons {
+        if let Some(priority) = spec.strip_prefix(prefix) {
+            let priority = priority.trim();
+            validate_priority(priority)?;
+            return Ok(match comparison {
+                Some(comparison) => PriorityFilter::Rank(comparison, priority_rank(priority)),
+                None => PriorityFilter::Is(priority),
+            });
+        }
+    }
+    Ok(PriorityFilter::Is(spec))
+}
+
+/// Filters accepted by `list`.
+#[derive(Default)]
+pub struct ListFilter<'a> {
+    pub status: Option<&'a str>,
+    pub label: Option<&'a str>,
+    /// Hide issues carrying any of these labels
+    pub not_labels: &'a [String],
+    pub priority: Option<PriorityFilter<'a>>,
+    pub source: Option<&'a str>,
+    pub parent: Option<ParentFilter>,
+    /// Row order: newest first, or manual ranking
+    pub order: IssueOrder,
+}
 
-use crate::db::Database;
-use crate::utils::truncate;
-
-pub fn run_json(
-    db: &Database,
-    status: Option<&str>,
-    label: Option<&str>,
-    priority: Option<&str>,
-) -> Result<()> {
-    let issues = db.list_issues(status, label, priority)?;
-    println!("{}", serde_json::to_string_pretty(&issues)?);
+impl ListFilter<'_> {
+    fn fetch(&self, db: &dyn Store) -> Result<Vec<Issue>> {
+        let mut issues = db.list_issues_excluding(
+            self.status,
+            self.label,
+            self.priority,
+            self.not_labels,
+            self.order,
+        )?;
+        if let Some(source) = self.source {
+            issues.retain(|i| i.source == source);
+        }
+        match self.parent {
+            Some(ParentFilter::Root) => issues.retain(|i| i.parent_id.is_none()),
+            Some(ParentFilter::Of(id)) => issues.retain(|i| i.parent_id == Some(id)),
+            None => {}
+        }
+        Ok(issues)
+    }
+}
+
+/// Keys `list --json --fields` can select, as serialized from `Issue`.
+pub const ISSUE_FIELDS: &[&str] = &[
+    "id",
+    "title",
+    "description",
+    "status",
+    "priority",
+    "parent_id",
+    "created_at",
+    "updated_at",
+    "closed_at",
+    "source",
+    "due_at",
+];
+
+/// Serialize `issues` as a JSON array, keeping only `fields` in each object
+/// when given. Unknown field names are an error.
+fn project(issues: &[Issue], fields: Option<&[String]>) -> Result<serde_json::Value> {
+    let Some(fields) = fields else {
+        return Ok(serde_json::to_value(issues)?);
+    };
+    if let Some(unknown) = fields.iter().find(|f| !ISSUE_FIELDS.contains(&f.as_str())) {
+        bail!(
+            "Unknown field '{}'. Known fields: {}",
+            unknown,
+            ISSUE_FIELDS.join(", ")
+        );
+    }
+    let projected = issues
+        .iter()
+        .map(|issue| {
+            let serde_json::Value::Object(mut all) = serde_json::to_value(issue)? else {
+                unreachable!("issues serialize as objects");
+            };
+            Ok(fields
+                .iter()
+                .map(|f| (f.clone(), all.remove(f).unwrap_or_default()))
+                .collect())
+        })
+        .collect::<Result<Vec<serde_json::Map<_, _>>>>()?;
+    Ok(serde_json::to_value(projected)?)
+}
+
+pub fn run_json(db: &dyn Store, filter: &ListFilter<'_>, fields: Option<&[String]>) -> Result<()> {
+    let issues = filter.fetch(db)?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&project(&issues, fields)?)?
+    );
     Ok(())
 }
 
-pub fn run(
-    db: &Database,
-    status: Option<&str>,
-    label: Option<&str>,
-    priority: Option<&str>,
-) -> Result<()> {
-    let issues = db.list_issues(status, label, priority)?;
+/// How `list` renders each row.
+pub struct ListDisplay<'a> {
+    /// Show exact creation dates instead of relative ages
+    pub absolute: bool,
+    /// strftime format for creation dates when `absolute` is set
+    pub date_format: &'a str,
+    /// Append the parent's id and title to subissue rows
+    pub show_parent: bool,
+    /// Terminal width to fit rows into, when known
+    pub width: Option<usize>,
+    /// Never shorten titles (`--full`)
+    pub full: bool,
+}
+
+impl Default for ListDisplay<'_> {
+    fn default() -> Self {
+        ListDisplay {
+            absolute: false,
+            date_format: DEFAULT_DATE_FORMAT,
+            show_parent: false,
+            width: None,
+            full: false,
+        }
+    }
+}
+
+pub fn run(db: &dyn Store, filter: &ListFilter<'_>, display: &ListDisplay<'_>) -> Result<()> {
+    let issues = filter.fetch(db)?;
 
     if issues.is_empty() {
         println!("No issues found.");
         return Ok(());
     }
 
+    let blocked = db.blocked_ids()?;
+    let parent_titles = if display.show_parent {
+        db.parent_titles()?
+    } else {
+        HashMap::new()
+    };
     for issue in issues {
-        let status_display = format!("[{}]", issue.status);
-        let date = issue.created_at.format("%Y-%m-%d");
+        let parent = parent_of(&issue, &parent_titles);
         println!(
-            "#{:<4} {:8} {:<40} {:8} {}",
-            issue.id,
-            status_display,
-            truncate(&issue.title, 40),
-            issue.priority,
-            date
+            "{}",
+            format_row(&issue, blocked.contains(&issue.id), parent, display)
         );
     }
 
     Ok(())
 }
 
+/// The id and title of `issue`'s parent, if it has one in `parent_titles`.
+fn parent_of<'a>(issue: &Issue, parent_titles: &'a HashMap<i64, String>) -> Option<(i64, &'a str)> {
+    let pid = issue.parent_id?;
+    parent_titles.get(&pid).map(|title| (pid, title.as_str()))
+}
+
+/// Title column width when the terminal width is unknown or wide enough
+const TITLE_WIDTH: usize = 40;
+/// Narrowest the title column gets on a small terminal
+const MIN_TITLE_WIDTH: usize = 10;
+/// Columns taken by the id, status and priority fields and their separators
+const FIXED_COLUMNS: usize = 25;
+
+/// How many columns of the title fit in a row whose other fields take
+/// `rest` columns. `None` when titles shouldn't be shortened.
+fn title_width(display: &ListDisplay<'_>, rest: usize) -> Option<usize> {
+    if display.full {
+        return None;
+    }
+    let fits = display
+        .width
+        .map_or(TITLE_WIDTH, |w| w.saturating_sub(FIXED_COLUMNS + rest));
+    Some(fits.clamp(MIN_TITLE_WIDTH, TITLE_WIDTH))
+}
+
+fn format_row(
+    issue: &Issue,
+    is_blocked: bool,
+    parent: Option<(i64, &str)>,
+    display: &ListDisplay<'_>,
+) -> String {
+    let status_display = format!("[{}]", issue.status);
+    let date = format_timestamp(issue.created_at, display.absolute, display.date_format);
+    let marker = if is_blocked { " [blocked]" } else { "" };
+    let parent = parent
+        .map(|(pid, title)| {
+            let title = if display.full {
+                title.to_string()
+            } else {
+                truncate_display(title, TITLE_WIDTH)
+            };
+            format!(" (under #{}: {})", pid, title)
+        })
+        .unwrap_or_default();
+    let rest = display_width(&date) + marker.len() + display_width(&parent);
+    let (title, pad) = match title_width(display, rest) {
+        Some(max) => (truncate_display(&issue.title, max), max),
+        None => (issue.title.clone(), TITLE_WIDTH),
+    };
+    format!(
+        "#{:<4} {:8} {} {:8} {}{}{}",
+        issue.id,
+        status_display,
+        pad_display(&title, pad),
+        issue.priority,
+        date,
+        marker,
+        parent
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::Database;
+    use crate::utils::truncate;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -107,7 +321,7 @@ mod tests {
     #[test]
     fn test_run_empty() {
         let (db, _dir) = setup_test_db();
-        run(&db, None, None, None).unwrap();
+        run(&db, &ListFilter::default(), &ListDisplay::default()).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert!(issues.is_empty());
     }
@@ -119,7 +333,7 @@ mod tests {
         db.create_issue("Issue 2", None, "medium").unwrap();
         db.create_issue("Issue 3", None, "low").unwrap();
 
-        run(&db, None, None, None).unwrap();
+        run(&db, &ListFilter::default(), &ListDisplay::default()).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert_eq!(issues.len(), 3);
     }
@@ -129,13 +343,20 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id1 = db.create_issue("Open issue", None, "medium").unwrap();
         let id2 = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(id2).unwrap();
+        db.close_issue(id2, None).unwrap();
 
         let issues = db.list_issues(Some("open"), None, None).unwrap();
         assert!(issues.iter().any(|i| i.id == id1));
         assert!(!issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, Some("open"), None, None);
+        let result = run(
+            &db,
+            &ListFilter {
+                status: Some("open"),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -144,13 +365,20 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id1 = db.create_issue("Open issue", None, "medium").unwrap();
         let id2 = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(id2).unwrap();
+        db.close_issue(id2, None).unwrap();
 
         let issues = db.list_issues(Some("closed"), None, None).unwrap();
         assert!(!issues.iter().any(|i| i.id == id1));
         assert!(issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, Some("closed"), None, None);
+        let result = run(
+            &db,
+            &ListFilter {
+                status: Some("closed"),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -159,9 +387,17 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id1 = db.create_issue("Open issue", None, "medium").unwrap();
         let id2 = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(id2).unwrap();
-
-        run(&db, Some("all"), None, None).unwrap();
+        db.close_issue(id2, None).unwrap();
+
+        run(
+            &db,
+            &ListFilter {
+                status: Some("all"),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        )
+        .unwrap();
         let issues = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(issues.len(), 2);
         assert!(issues.iter().any(|i| i.id == id1));
@@ -180,7 +416,14 @@ mod tests {
         assert!(issues.iter().any(|i| i.id == id1));
         assert!(!issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, None, Some("bug"), None);
+        let result = run(
+            &db,
+            &ListFilter {
+                label: Some("bug"),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -194,10 +437,60 @@ mod tests {
         assert!(issues.iter().any(|i| i.id == id1));
         assert!(!issues.iter().any(|i| i.id == id2));
 
-        let result = run(&db, None, None, Some("high"));
+        let result = run(
+            &db,
+            &ListFilter {
+                priority: Some(PriorityFilter::Is("high")),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        );
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_priority_range_filter() {
+        let (db, _dir) = setup_test_db();
+        let low = db.create_issue("Low", None, "low").unwrap();
+        let medium = db.create_issue("Medium", None, "medium").unwrap();
+        let high = db.create_issue("High", None, "high").unwrap();
+        let critical = db.create_issue("Critical", None, "critical").unwrap();
+
+        let ids = |spec: &str| -> Vec<i64> {
+            let filter = ListFilter {
+                priority: Some(parse_priority_filter(spec).unwrap()),
+                ..Default::default()
+            };
+            let mut ids: Vec<i64> = filter.fetch(&db).unwrap().iter().map(|i| i.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(">=high"), vec![high, critical]);
+        assert_eq!(ids("<medium"), vec![low]);
+        assert_eq!(ids("<=medium"), vec![low, medium]);
+        assert_eq!(ids(">high"), vec![critical]);
+        assert_eq!(ids("=medium"), vec![medium]);
+        assert_eq!(ids("high"), vec![high]);
+    }
+
+    #[test]
+    fn test_parse_priority_filter() {
+        assert_eq!(
+            parse_priority_filter(">= high").unwrap(),
+            PriorityFilter::Rank(Comparison::GreaterOrEqual, 3)
+        );
+        assert_eq!(
+            parse_priority_filter("<low").unwrap(),
+            PriorityFilter::Rank(Comparison::Less, 1)
+        );
+        assert_eq!(
+            parse_priority_filter("high").unwrap(),
+            PriorityFilter::Is("high")
+        );
+        assert!(parse_priority_filter(">=urgent").is_err());
+        assert!(parse_priority_filter("=").is_err());
+    }
+
     #[test]
     fn test_run_combined_filters() {
         let (db, _dir) = setup_test_db();
@@ -215,7 +508,16 @@ mod tests {
         assert!(!issues.iter().any(|i| i.id == id2));
         assert!(!issues.iter().any(|i| i.id == id3));
 
-        let result = run(&db, Some("open"), Some("bug"), Some("high"));
+        let result = run(
+            &db,
+            &ListFilter {
+                status: Some("open"),
+                label: Some("bug"),
+                priority: Some(PriorityFilter::Is("high")),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        );
         assert!(result.is_ok());
     }
 
@@ -225,7 +527,7 @@ mod tests {
         let long_title = "A".repeat(100);
         db.create_issue(&long_title, None, "medium").unwrap();
 
-        let result = run(&db, None, None, None);
+        let result = run(&db, &ListFilter::default(), &ListDisplay::default());
         assert!(result.is_ok());
     }
 
@@ -235,7 +537,7 @@ mod tests {
         db.create_issue("日本語タイトル 🎉", None, "medium")
             .unwrap();
 
-        let result = run(&db, None, None, None);
+        let result = run(&db, &ListFilter::default(), &ListDisplay::default());
         assert!(result.is_ok());
     }
 
@@ -244,7 +546,15 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Issue", None, "medium").unwrap();
 
-        run(&db, None, Some("nonexistent-label"), None).unwrap();
+        run(
+            &db,
+            &ListFilter {
+                label: Some("nonexistent-label"),
+                ..Default::default()
+            },
+            &ListDisplay::default(),
+        )
+        .unwrap();
         let issues = db
             .list_issues(None, Some("nonexistent-label"), None)
             .unwrap();
@@ -254,6 +564,226 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_source_filter() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("Manual", None, "medium").unwrap();
+        let imported = db.create_issue("Imported", None, "medium").unwrap();
+        db.set_issue_source(imported, "import").unwrap();
+
+        let filter = ListFilter {
+            source: Some("import"),
+            ..Default::default()
+        };
+        let issues = filter.fetch(&db).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].id, imported);
+        run(&db, &filter, &ListDisplay::default()).unwrap();
+    }
+
+    #[test]
+    fn test_fields_projection_keeps_only_requested_keys() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("One", Some("desc"), "high").unwrap();
+        db.create_issue("Two", None, "low").unwrap();
+        let issues = ListFilter::default().fetch(&db).unwrap();
+
+        let fields = vec!["id".to_string(), "title".to_string()];
+        let value = project(&issues, Some(&fields)).unwrap();
+        let objects = value.as_array().unwrap();
+        assert_eq!(objects.len(), 2);
+        for object in objects {
+            let keys: Vec<&String> = object.as_object().unwrap().keys().collect();
+            assert_eq!(keys, vec!["id", "title"]);
+        }
+
+        let err = project(&issues, Some(&["titel".to_string()])).unwrap_err();
+        assert!(err.to_string().contains("Unknown field 'titel'"));
+    }
+
+    #[test]
+    fn test_issue_fields_match_serialized_issue() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("One", None, "high").unwrap();
+        let value = project(&ListFilter::default().fetch(&db).unwrap(), None).unwrap();
+        let mut keys: Vec<&str> = value[0]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        let mut known = ISSUE_FIELDS.to_vec();
+        keys.sort();
+        known.sort();
+        assert_eq!(keys, known);
+    }
+
+    #[test]
+    fn test_not_label_filter() {
+        let (db, _dir) = setup_test_db();
+        let spam = db.create_issue("Spam", None, "medium").unwrap();
+        db.add_label(spam, "spam").unwrap();
+        let bug = db.create_issue("Bug", None, "medium").unwrap();
+        db.add_label(bug, "bug").unwrap();
+        let unlabeled = db.create_issue("Unlabeled", None, "medium").unwrap();
+
+        let not_labels = vec!["spam".to_string()];
+        let filter = ListFilter {
+            not_labels: &not_labels,
+            ..Default::default()
+        };
+        let ids: Vec<i64> = filter.fetch(&db).unwrap().iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![unlabeled, bug]);
+
+        let filter = ListFilter {
+            label: Some("bug"),
+            not_labels: &not_labels,
+            ..Default::default()
+        };
+        let ids: Vec<i64> = filter.fetch(&db).unwrap().iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![bug]);
+    }
+
+    #[test]
+    fn test_parent_filter() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let child = db.create_subissue(epic, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "low")
+            .unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+
+        let ids = |parent| -> Vec<i64> {
+            let filter = ListFilter {
+                parent: Some(parent),
+                ..Default::default()
+            };
+            filter.fetch(&db).unwrap().iter().map(|i| i.id).collect()
+        };
+        assert_eq!(ids(ParentFilter::Of(epic)), vec![child]);
+        assert_eq!(ids(ParentFilter::Of(child)), vec![grandchild]);
+        let mut roots = ids(ParentFilter::Root);
+        roots.sort();
+        assert_eq!(roots, vec![epic, other]);
+    }
+
+    #[test]
+    fn test_parent_filter_parse() {
+        assert_eq!("none".parse(), Ok(ParentFilter::Root));
+        assert_eq!("NONE".parse(), Ok(ParentFilter::Root));
+        assert_eq!("12".parse(), Ok(ParentFilter::Of(12)));
+        assert_eq!("#12".parse(), Ok(ParentFilter::Of(12)));
+        assert!("epic".parse::<ParentFilter>().is_err());
+    }
+
+    #[test]
+    fn test_blocked_marker() {
+        let (db, _dir) = setup_test_db();
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+
+        let blocked_ids = db.blocked_ids().unwrap();
+        let rows: Vec<String> = db
+            .list_issues(None, None, None)
+            .unwrap()
+            .iter()
+            .map(|i| {
+                format_row(
+                    i,
+                    blocked_ids.contains(&i.id),
+                    None,
+                    &ListDisplay {
+                        absolute: true,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        let row_for = |title: &str| rows.iter().find(|r| r.contains(title)).unwrap();
+        assert!(row_for("Blocked").ends_with("[blocked]"));
+        assert!(!row_for("Blocker").contains("[blocked]"));
+        run(&db, &ListFilter::default(), &ListDisplay::default()).unwrap();
+    }
+
+    #[test]
+    fn test_show_parent_title() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Auth epic", None, "high").unwrap();
+        let child = db
+            .create_subissue(epic, "Login form", None, "medium")
+            .unwrap();
+
+        let parents = db.parent_titles().unwrap();
+        assert_eq!(parents.len(), 1);
+        let display = ListDisplay {
+            show_parent: true,
+            ..Default::default()
+        };
+        let row = |id: i64| {
+            let issue = db.get_issue(id).unwrap().unwrap();
+            format_row(&issue, false, parent_of(&issue, &parents), &display)
+        };
+        assert!(row(child).ends_with(&format!("(under #{}: Auth epic)", epic)));
+        assert!(!row(epic).contains("under #"));
+        run(&db, &ListFilter::default(), &display).unwrap();
+    }
+
+    #[test]
+    fn test_title_fits_terminal_width() {
+        let (db, _dir) = setup_test_db();
+        let title = "A very long title that would wrap on a narrow terminal window";
+        let id = db.create_issue(title, None, "medium").unwrap();
+        let issue = db.get_issue(id).unwrap().unwrap();
+
+        let row = |width: Option<usize>, full: bool| {
+            format_row(
+                &issue,
+                false,
+                None,
+                &ListDisplay {
+                    width,
+                    full,
+                    ..Default::default()
+                },
+            )
+        };
+        let narrow = row(Some(60), false);
+        assert!(narrow.chars().count() <= 60, "{}", narrow);
+        assert!(narrow.contains("A very long title that w... medium"));
+        assert!(row(None, false).contains(&truncate_display(title, 40)));
+        assert!(row(Some(60), true).contains(title));
+        // Never narrower than the minimum, however small the terminal
+        assert!(row(Some(20), false).contains("A very ..."));
+    }
+
+    #[test]
+    fn test_wide_titles_stay_aligned() {
+        let (db, _dir) = setup_test_db();
+        let display = ListDisplay {
+            width: Some(60),
+            ..Default::default()
+        };
+        let rows: Vec<String> = [
+            "Fix login",
+            "ログイン画面の修正",
+            "日本語のとても長いタイトルがここにあります",
+        ]
+        .iter()
+        .map(|title| {
+            let id = db.create_issue(title, None, "medium").unwrap();
+            let issue = db.get_issue(id).unwrap().unwrap();
+            format_row(&issue, false, None, &display)
+        })
+        .collect();
+        let priority_column = |row: &str| display_width(&row[..row.find("medium").unwrap()]);
+        for row in &rows {
+            assert_eq!(priority_column(row), priority_column(&rows[0]), "{}", row);
+            assert!(display_width(row) <= 60, "{}", row);
+        }
+    }
+
     proptest! {
         #[test]
         fn truncate_respects_max_chars(s in ".{10,100}", max_chars in 5usize..50) {
@@ -281,7 +811,10 @@ mod tests {
             db.create_issue("Match", None, &priority).unwrap();
             db.create_issue("Other", None, "low").unwrap();
 
-            run(&db, None, None, Some(&priority)).unwrap();
+            run(&db, &ListFilter {
+                priority: Some(PriorityFilter::Is(&priority)),
+                ..Default::default()
+            }, &ListDisplay::default()).unwrap();
             let filtered = db.list_issues(None, None, Some(&priority)).unwrap();
             prop_assert!(filtered.iter().all(|i| i.priority == priority));
         }
@@ -1,33 +1,52 @@
 use anyhow::{bail, Result};
 
-use crate::db::Database;
+use super::next::priority_weight;
+use crate::db::{is_done, Database};
+use crate::models::{Issue, Milestone};
+use crate::utils::{display_width, info, pad_display};
 
 pub fn create(db: &Database, name: &str, description: Option<&str>) -> Result<()> {
     let id = db.create_milestone(name, description)?;
-    println!("Created milestone #{}: {}", id, name);
+    info(format_args!("Created milestone #{}: {}", id, name));
     Ok(())
 }
 
+/// "closed/total (pct%)", or "0/0" for a milestone with no issues.
+fn progress_label(closed: usize, total: usize) -> String {
+    if total == 0 {
+        return "0/0".to_string();
+    }
+    format!("{}/{} ({}%)", closed, total, closed * 100 / total)
+}
+
+/// One `milestone list` row, with the name padded to `name_width` so the
+/// progress lines up in its own column.
+fn format_row(m: &Milestone, closed: usize, total: usize, name_width: usize) -> String {
+    let status_marker = if m.status == "closed" { "✓" } else { " " };
+    format!(
+        "#{:<3} [{}] {}  {}",
+        m.id,
+        status_marker,
+        pad_display(&m.name, name_width),
+        progress_label(closed, total)
+    )
+}
+
 pub fn list(db: &Database, status: Option<&str>) -> Result<()> {
-    let milestones = db.list_milestones(status)?;
+    let milestones = db.list_milestones_with_progress(status)?;
 
     if milestones.is_empty() {
         println!("No milestones found.");
         return Ok(());
     }
 
-    for m in milestones {
-        let issues = db.get_milestone_issues(m.id)?;
-        let total = issues.len();
-        let closed = issues.iter().filter(|i| i.status == "closed").count();
-        let progress = if total > 0 {
-            format!("{}/{}", closed, total)
-        } else {
-            "0/0".to_string()
-        };
-
-        let status_marker = if m.status == "closed" { "✓" } else { " " };
-        println!("#{:<3} [{}] {} ({})", m.id, status_marker, m.name, progress);
+    let name_width = milestones
+        .iter()
+        .map(|(m, _, _)| display_width(&m.name))
+        .max()
+        .unwrap_or(0);
+    for (m, closed, total) in &milestones {
+        println!("{}", format_row(m, *closed, *total, name_width));
     }
 
     Ok(())
@@ -57,24 +76,44 @@ pub fn show(db: &Database, id: i64) -> Result<()> {
 
     let issues = db.get_milestone_issues(id)?;
     let total = issues.len();
-    let closed = issues.iter().filter(|i| i.status == "closed").count();
-
-    println!("\nProgress: {}/{} issues closed", closed, total);
-
-    if !issues.is_empty() {
-        println!("\nIssues:");
-        for issue in issues {
-            let status_marker = if issue.status == "closed" { "✓" } else { " " };
-            println!(
-                "  #{:<4} [{}] {:8} {}",
-                issue.id, status_marker, issue.priority, issue.title
-            );
-        }
+    let done = issues.iter().filter(|i| is_done(&i.status)).count();
+
+    println!("\nProgress: {}/{} issues done", done, total);
+
+    for line in issue_sections(issues) {
+        println!("{}", line);
     }
 
     Ok(())
 }
 
+/// Group milestone issues into Open / Closed / Won't do / Archived sections with counts,
+/// highest priority first within each section.
+fn issue_sections(mut issues: Vec<Issue>) -> Vec<String> {
+    issues.sort_by_key(|i| (std::cmp::Reverse(priority_weight(&i.priority)), i.id));
+
+    let mut lines = Vec::new();
+    for (status, heading) in [
+        ("open", "Open"),
+        ("closed", "Closed"),
+        ("wontdo", "Won't do"),
+        ("archived", "Archived"),
+    ] {
+        let group: Vec<&Issue> = issues.iter().filter(|i| i.status == status).collect();
+        if group.is_empty() {
+            continue;
+        }
+        lines.push(format!("\n{} ({}):", heading, group.len()));
+        for issue in group {
+            lines.push(format!(
+                "  #{:<4} {:8} {}",
+                issue.id, issue.priority, issue.title
+            ));
+        }
+    }
+    lines
+}
+
 pub fn add(db: &Database, milestone_id: i64, issue_ids: &[i64]) -> Result<()> {
     let milestone = db.get_milestone(milestone_id)?;
     if milestone.is_none() {
@@ -88,20 +127,72 @@ pub fn add(db: &Database, milestone_id: i64, issue_ids: &[i64]) -> Result<()> {
         }
 
         if db.add_issue_to_milestone(milestone_id, issue_id)? {
-            println!("Added #{} to milestone #{}", issue_id, milestone_id);
+            info(format_args!(
+                "Added #{} to milestone #{}",
+                issue_id, milestone_id
+            ));
         } else {
-            println!("Issue #{} already in milestone #{}", issue_id, milestone_id);
+            info(format_args!(
+                "Issue #{} already in milestone #{}",
+                issue_id, milestone_id
+            ));
         }
     }
 
     Ok(())
 }
 
+/// Add every open issue matching `label` and `priority` to a milestone, all
+/// or nothing.
+pub fn add_matching(
+    db: &Database,
+    milestone_id: i64,
+    label: Option<&str>,
+    priority: Option<&str>,
+) -> Result<()> {
+    if db.get_milestone(milestone_id)?.is_none() {
+        bail!("Milestone #{} not found", milestone_id);
+    }
+    let issues = db.list_issues(Some("open"), label, priority)?;
+
+    let added = db.transaction(|| {
+        let mut added = 0;
+        for issue in &issues {
+            if db.add_issue_to_milestone(milestone_id, issue.id)? {
+                added += 1;
+            }
+        }
+        Ok(added)
+    })?;
+
+    let already = issues.len() - added;
+    if issues.is_empty() {
+        info("No open issues match");
+    } else if already > 0 {
+        info(format_args!(
+            "Added {} issue(s) to milestone #{} ({} already in it)",
+            added, milestone_id, already
+        ));
+    } else {
+        info(format_args!(
+            "Added {} issue(s) to milestone #{}",
+            added, milestone_id
+        ));
+    }
+    Ok(())
+}
+
 pub fn remove(db: &Database, milestone_id: i64, issue_id: i64) -> Result<()> {
     if db.remove_issue_from_milestone(milestone_id, issue_id)? {
-        println!("Removed #{} from milestone #{}", issue_id, milestone_id);
+        info(format_args!(
+            "Removed #{} from milestone #{}",
+            issue_id, milestone_id
+        ));
     } else {
-        println!("Issue #{} not in milestone #{}", issue_id, milestone_id);
+        info(format_args!(
+            "Issue #{} not in milestone #{}",
+            issue_id, milestone_id
+        ));
     }
 
     Ok(())
@@ -109,9 +200,9 @@ pub fn remove(db: &Database, milestone_id: i64, issue_id: i64) -> Result<()> {
 
 pub fn close(db: &Database, id: i64) -> Result<()> {
     if db.close_milestone(id)? {
-        println!("Closed milestone #{}", id);
+        info(format_args!("Closed milestone #{}", id));
     } else {
-        println!("Milestone #{} not found", id);
+        info(format_args!("Milestone #{} not found", id));
     }
 
     Ok(())
@@ -119,9 +210,9 @@ pub fn close(db: &Database, id: i64) -> Result<()> {
 
 pub fn delete(db: &Database, id: i64) -> Result<()> {
     if db.delete_milestone(id)? {
-        println!("Deleted milestone #{}", id);
+        info(format_args!("Deleted milestone #{}", id));
     } else {
-        println!("Milestone #{} not found", id);
+        info(format_args!("Milestone #{} not found", id));
     }
 
     Ok(())
@@ -157,6 +248,59 @@ mod tests {
         assert_eq!(milestones[0].description, Some("First release".to_string()));
     }
 
+    #[test]
+    fn test_list_shows_progress() {
+        let (db, _dir) = setup_test_db();
+        let half = db.create_milestone("Secret Launch", None).unwrap();
+        let empty = db.create_milestone("v2.0", None).unwrap();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_issue_to_milestone(half, a).unwrap();
+        db.add_issue_to_milestone(half, b).unwrap();
+        db.close_issue(a, None).unwrap();
+
+        let listed = db.list_milestones_with_progress(Some("all")).unwrap();
+        let rows: Vec<String> = listed
+            .iter()
+            .map(|(m, closed, total)| format_row(m, *closed, *total, 13))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "#2   [ ] v2.0           0/0",
+                "#1   [ ] Secret Launch  1/2 (50%)",
+            ]
+        );
+
+        db.close_milestone(empty).unwrap();
+        assert_eq!(db.list_milestones_with_progress(None).unwrap().len(), 1);
+        list(&db, None).unwrap();
+    }
+
+    #[test]
+    fn test_dropped_issues_count_toward_progress() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_milestone("v1.0", None).unwrap();
+        let dropped = db.create_issue("Dropped", None, "medium").unwrap();
+        let open = db.create_issue("Open", None, "medium").unwrap();
+        add(&db, id, &[dropped, open]).unwrap();
+        db.drop_issue(dropped).unwrap();
+
+        let (_, done, total) = db
+            .list_milestones_with_progress(None)
+            .unwrap()
+            .into_iter()
+            .find(|(m, _, _)| m.id == id)
+            .unwrap();
+        assert_eq!(progress_label(done, total), "1/2 (50%)");
+
+        let lines = issue_sections(db.get_milestone_issues(id).unwrap());
+        let pos = |needle: &str| lines.iter().position(|l| l.contains(needle)).unwrap();
+        assert_eq!(lines[pos("Won't do (")].trim(), "Won't do (1):");
+        assert!(pos("Won't do (") < pos("Dropped"));
+        show(&db, id).unwrap();
+    }
+
     #[test]
     fn test_list_milestones_empty() {
         let (db, _dir) = setup_test_db();
@@ -185,6 +329,29 @@ mod tests {
         assert_eq!(m.description, Some("Description".to_string()));
     }
 
+    #[test]
+    fn test_show_groups_issues_by_status() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_milestone("v1.0", None).unwrap();
+        let open_low = db.create_issue("Open low", None, "low").unwrap();
+        let open_high = db.create_issue("Open high", None, "high").unwrap();
+        let closed = db.create_issue("Done", None, "medium").unwrap();
+        db.close_issue(closed, None).unwrap();
+        add(&db, id, &[open_low, open_high, closed]).unwrap();
+
+        show(&db, id).unwrap();
+        let lines = issue_sections(db.get_milestone_issues(id).unwrap());
+        let pos = |needle: &str| lines.iter().position(|l| l.contains(needle)).unwrap();
+
+        assert_eq!(lines[pos("Open (")].trim(), "Open (2):");
+        assert_eq!(lines[pos("Closed (")].trim(), "Closed (1):");
+        assert!(pos("Open (") < pos("Open high"));
+        assert!(pos("Open high") < pos("Open low"), "sorted by priority");
+        assert!(pos("Open low") < pos("Closed ("));
+        assert!(pos("Closed (") < pos("Done"));
+        assert!(!lines.iter().any(|l| l.contains("Archived")));
+    }
+
     #[test]
     fn test_show_nonexistent_milestone() {
         let (db, _dir) = setup_test_db();
@@ -212,6 +379,36 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_add_matching_adds_all_open_release_issues() {
+        let (db, _dir) = setup_test_db();
+        let milestone_id = db.create_milestone("v1.0", None).unwrap();
+        let first = db.create_issue("Ship docs", None, "medium").unwrap();
+        let second = db.create_issue("Tag build", None, "high").unwrap();
+        let done = db.create_issue("Already done", None, "medium").unwrap();
+        let other = db.create_issue("Unrelated", None, "medium").unwrap();
+        for id in [first, second, done] {
+            db.add_label(id, "release").unwrap();
+        }
+        db.close_issue(done, None).unwrap();
+        db.add_issue_to_milestone(milestone_id, second).unwrap();
+
+        add_matching(&db, milestone_id, Some("release"), None).unwrap();
+        let mut ids: Vec<i64> = db
+            .get_milestone_issues(milestone_id)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![first, second]);
+        assert!(!ids.contains(&other));
+
+        add_matching(&db, milestone_id, Some("release"), Some("medium")).unwrap();
+        assert_eq!(db.get_milestone_issues(milestone_id).unwrap().len(), 2);
+        assert!(add_matching(&db, 99999, Some("release"), None).is_err());
+    }
+
     #[test]
     fn test_remove_issue_from_milestone() {
         let (db, _dir) = setup_test_db();
@@ -250,7 +447,7 @@ mod tests {
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
         db.add_issue_to_milestone(milestone_id, issue1).unwrap();
         db.add_issue_to_milestone(milestone_id, issue2).unwrap();
-        db.close_issue(issue1).unwrap();
+        db.close_issue(issue1, None).unwrap();
         show(&db, milestone_id).unwrap();
         let issues = db.get_milestone_issues(milestone_id).unwrap();
         assert_eq!(issues.len(), 2);
@@ -1,22 +1,35 @@
+pub mod activity;
 pub mod archive;
+pub mod attach;
 pub mod comment;
 pub mod cpitd;
 pub mod create;
 pub mod delete;
 pub mod deps;
+pub mod doctor;
 pub mod export;
 pub mod import;
 pub mod init;
+pub mod integrity;
 pub mod label;
 pub mod list;
 pub mod milestone;
 pub mod next;
+pub mod open;
+pub mod rank;
 pub mod relate;
+pub mod reparent;
+pub mod schema;
 pub mod search;
 pub mod session;
 pub mod show;
+pub mod stats;
 pub mod status;
 pub mod tested;
 pub mod timer;
+pub mod trash;
 pub mod tree;
+#[cfg(feature = "tui")]
+pub mod ui;
 pub mod update;
+pub mod watch;
@@ -1,6 +1,7 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 
-use crate::db::Database;
+use crate::db::{is_done, Database};
 use crate::models::Issue;
 
 /// Progress tuple: (completed subissues, total subissues)
@@ -10,17 +11,11 @@ type Progress = Option<(i32, i32)>;
 type ScoredIssue = (Issue, i32, Progress);
 
 /// Priority order for sorting (higher = more important)
-fn priority_weight(priority: &str) -> i32 {
-    match priority {
-        "critical" => 4,
-        "high" => 3,
-        "medium" => 2,
-        "low" => 1,
-        _ => 0,
-    }
+pub fn priority_weight(priority: &str) -> i32 {
+    crate::db::priority_rank(priority)
 }
 
-/// Calculate progress for issues with subissues
+/// Calculate progress for issues with subissues; won't-do subissues count as finished
 fn calculate_progress(db: &Database, issue: &Issue) -> Result<Progress> {
     let subissues = db.get_subissues(issue.id)?;
     if subissues.is_empty() {
@@ -28,63 +23,211 @@ fn calculate_progress(db: &Database, issue: &Issue) -> Result<Progress> {
     }
 
     let total = subissues.len() as i32;
-    let closed = subissues.iter().filter(|s| s.status == "closed").count() as i32;
+    let closed = subissues.iter().filter(|s| is_done(&s.status)).count() as i32;
     Ok(Some((closed, total)))
 }
 
-pub fn run(db: &Database) -> Result<()> {
-    let ready = db.list_ready_issues()?;
+/// Explain open issues that can never become ready because they block each other.
+fn cycle_notice(db: &Database) -> Result<Option<String>> {
+    let mut stuck = Vec::new();
+    for cycle in db.find_dependency_cycles()? {
+        let mut all_open = true;
+        for &id in &cycle {
+            all_open &= db.get_issue(id)?.is_some_and(|i| i.status == "open");
+        }
+        if all_open {
+            stuck.push(cycle);
+        }
+    }
+    if stuck.is_empty() {
+        return Ok(None);
+    }
 
-    if ready.is_empty() {
-        println!("No issues ready to work on.");
-        println!(
-            "Use 'chainlink list' to see all issues or 'chainlink blocked' to see blocked issues."
-        );
-        return Ok(());
+    let count: usize = stuck.iter().map(Vec::len).sum();
+    let groups: Vec<String> = stuck
+        .iter()
+        .map(|cycle| {
+            let ids: Vec<String> = cycle.iter().map(|id| format!("#{}", id)).collect();
+            ids.join(", ")
+        })
+        .collect();
+    Ok(Some(format!(
+        "{} open issue(s) are stuck in dependency cycles: {}\nRun 'chainlink db check' for details and 'chainlink unblock' to break a cycle.",
+        count,
+        groups.join("; ")
+    )))
+}
+
+/// Points for an issue that is partially complete (finish what you started)
+const PROGRESS_BONUS: i32 = 50;
+
+/// Days overdue that keep adding to the boost; after that it stops growing.
+const MAX_OVERDUE_DAYS: i32 = 10;
+
+/// Days ahead of a due date that it starts to count.
+const DUE_SOON_DAYS: i32 = 7;
+
+/// Points from an issue's due date, in units of `weight` (one priority level
+/// by default). Due within a day or overdue is worth two levels, plus a tenth
+/// of a level per day overdue, so a medium issue that is due now beats a high
+/// one that isn't. A due date in the coming week adds up to one level, shrinking
+/// the further out it is; later due dates and no due date add nothing.
+pub fn due_score(issue: &Issue, weight: u16, now: DateTime<Utc>) -> i32 {
+    let Some(due) = issue.due_at else {
+        return 0;
+    };
+    let weight = i32::from(weight);
+    let hours_left = (due - now).num_hours();
+    // Day counts too large for i32 saturate; the score is bounded either way
+    let days = |hours: i64| i32::try_from(hours / 24).unwrap_or(i32::MAX);
+    if hours_left < 24 {
+        let days_overdue = days(hours_left.saturating_neg()).clamp(0, MAX_OVERDUE_DAYS);
+        weight
+            .saturating_mul(2)
+            .saturating_add(days_overdue.saturating_mul(weight) / 10)
+    } else {
+        (DUE_SOON_DAYS + 1)
+            .saturating_sub(days(hours_left))
+            .max(0)
+            .saturating_mul(weight)
+            / (DUE_SOON_DAYS + 1)
     }
+}
 
-    // Score and sort issues
-    let mut scored: Vec<ScoredIssue> = Vec::new();
+fn score_issue(
+    db: &Database,
+    issue: Issue,
+    due_weight: u16,
+    now: DateTime<Utc>,
+) -> Result<ScoredIssue> {
+    let priority_score = priority_weight(&issue.priority) * 100;
+    let progress = calculate_progress(db, &issue)?;
+
+    let progress_bonus = match &progress {
+        Some((closed, total)) if *closed > 0 && *closed < *total => PROGRESS_BONUS,
+        _ => 0,
+    };
+    let due_bonus = due_score(&issue, due_weight, now);
 
-    for issue in ready {
-        // Skip subissues - we want to recommend parent issues or standalone issues
-        if issue.parent_id.is_some() {
-            continue;
-        }
+    let score = priority_score
+        .saturating_add(progress_bonus)
+        .saturating_add(due_bonus);
+    Ok((issue, score, progress))
+}
 
-        let priority_score = priority_weight(&issue.priority) * 100;
-        let progress = calculate_progress(db, &issue)?;
+/// Ready top-level issues, highest score first. Subissues are left out so
+/// the recommendation is a parent or standalone issue.
+fn rank_ready(
+    db: &Database,
+    ready: Vec<Issue>,
+    due_weight: u16,
+    now: DateTime<Utc>,
+) -> Result<Vec<ScoredIssue>> {
+    let mut scored = ready
+        .into_iter()
+        .filter(|issue| issue.parent_id.is_none())
+        .map(|issue| score_issue(db, issue, due_weight, now))
+        .collect::<Result<Vec<_>>>()?;
+    // Stable, so equal scores keep ascending id order
+    scored.sort_by_key(|s| std::cmp::Reverse(s.1));
+    Ok(scored)
+}
 
-        // Boost score for issues that are partially complete (finish what you started)
-        let progress_bonus = match &progress {
-            Some((closed, total)) if *closed > 0 && *closed < *total => 50,
-            _ => 0,
-        };
+/// How `score` was reached, for `--explain`. `due` is the issue's `due_score`.
+fn explain_score(issue: &Issue, score: i32, due: i32) -> String {
+    let priority_score = priority_weight(&issue.priority) * 100;
+    let mut parts = vec![format!("{} priority", priority_score)];
+    if due > 0 {
+        parts.push(format!("{} due date", due));
+    }
+    let progress = score - priority_score - due;
+    if progress > 0 {
+        parts.push(format!("{} partly done", progress));
+    }
+    format!("score {} = {}", score, parts.join(" + "))
+}
+
+/// The `--count` worklist: one line per issue, best first.
+fn worklist_lines(
+    scored: &[ScoredIssue],
+    count: usize,
+    explain: Option<(u16, DateTime<Utc>)>,
+) -> Vec<String> {
+    scored
+        .iter()
+        .take(count)
+        .enumerate()
+        .map(|(n, (issue, score, progress))| {
+            let progress_str = match progress {
+                Some((c, t)) => format!(" ({}/{})", c, t),
+                None => String::new(),
+            };
+            let explanation = match explain {
+                Some((due_weight, now)) => format!(
+                    "  [{}]",
+                    explain_score(issue, *score, due_score(issue, due_weight, now))
+                ),
+                None => String::new(),
+            };
+            format!(
+                "{}. #{} [{}] {}{}{}",
+                n + 1,
+                issue.id,
+                issue.priority,
+                issue.title,
+                progress_str,
+                explanation
+            )
+        })
+        .collect()
+}
 
-        let score = priority_score + progress_bonus;
-        scored.push((issue, score, progress));
+/// Recommend what to work on next: the best ready issue, or the top `count`
+/// when more than one is asked for. `explain` shows how each was scored.
+/// `due_weight` scales how much due dates count against priority (see `due_score`).
+pub fn run(db: &Database, count: usize, explain: bool, due_weight: u16) -> Result<()> {
+    let ready = db.list_ready_issues()?;
+
+    if ready.is_empty() {
+        println!("No issues ready to work on.");
+        match cycle_notice(db)? {
+            Some(notice) => println!("{}", notice),
+            None => println!(
+                "Use 'chainlink list' to see all issues or 'chainlink blocked' to see blocked issues."
+            ),
+        }
+        return Ok(());
     }
 
-    // Sort by score descending
-    scored.sort_by(|a, b| b.1.cmp(&a.1));
+    let now = Utc::now();
+    let first_ready = ready[0].clone();
+    let scored = rank_ready(db, ready, due_weight, now)?;
 
     if scored.is_empty() {
         // All ready issues are subissues, show them instead
-        let ready = db.list_ready_issues()?;
-        if let Some(issue) = ready.first() {
-            println!("Next: #{} [{}] {}", issue.id, issue.priority, issue.title);
-            if let Some(parent_id) = issue.parent_id {
-                println!("       (subissue of #{})", parent_id);
-            }
-        } else {
-            println!("No issues ready to work on.");
+        let issue = first_ready;
+        println!("Next: #{} [{}] {}", issue.id, issue.priority, issue.title);
+        if let Some(parent_id) = issue.parent_id {
+            println!("       (subissue of #{})", parent_id);
+        }
+        return Ok(());
+    }
+
+    if count > 1 {
+        println!("Top {} ready issue(s):", count.min(scored.len()));
+        for line in worklist_lines(&scored, count, explain.then_some((due_weight, now))) {
+            println!("  {}", line);
         }
         return Ok(());
     }
 
     // Recommend the top issue
-    let (top, _score, progress) = &scored[0];
+    let (top, score, progress) = &scored[0];
     println!("Next: #{} [{}] {}", top.id, top.priority, top.title);
+    if explain {
+        let due = due_score(top, due_weight, now);
+        println!("       {}", explain_score(top, *score, due));
+    }
 
     if let Some((closed, total)) = progress {
         println!("       Progress: {}/{} subissues complete", closed, total);
@@ -123,6 +266,7 @@ pub fn run(db: &Database) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::DEFAULT_DUE_WEIGHT;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -158,10 +302,95 @@ mod tests {
         assert_eq!(priority_weight("unknown"), 0);
     }
 
+    #[test]
+    fn test_count_lists_top_scores_in_order() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("Low", None, "low").unwrap();
+        let critical = db.create_issue("Critical", None, "critical").unwrap();
+        db.create_issue("Medium", None, "medium").unwrap();
+        let high = db.create_issue("High", None, "high").unwrap();
+        let started = db.create_issue("Started", None, "medium").unwrap();
+        let done = db.create_subissue(started, "Done", None, "low").unwrap();
+        db.create_subissue(started, "Todo", None, "low").unwrap();
+        db.close_issue(done, None).unwrap();
+
+        let now = Utc::now();
+        let ready = db.list_ready_issues().unwrap();
+        let scored = rank_ready(&db, ready, DEFAULT_DUE_WEIGHT, now).unwrap();
+        let lines = worklist_lines(&scored, 3, Some((DEFAULT_DUE_WEIGHT, now)));
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            format!(
+                "1. #{} [critical] Critical  [score 400 = 400 priority]",
+                critical
+            )
+        );
+        assert!(lines[1].starts_with(&format!("2. #{} [high]", high)));
+        assert!(lines[2].starts_with(&format!("3. #{} [medium] Started (1/2)", started)));
+        assert!(lines[2].ends_with("[score 250 = 200 priority + 50 partly done]"));
+        run(&db, 3, true, DEFAULT_DUE_WEIGHT).unwrap();
+    }
+
+    #[test]
+    fn test_overdue_medium_outranks_high_without_due_date() {
+        let (db, _dir) = setup_test_db();
+        let high = db.create_issue("High", None, "high").unwrap();
+        let medium = db.create_issue("Overdue", None, "medium").unwrap();
+        let now = Utc::now();
+        db.set_due_date(medium, Some(now - chrono::Duration::days(3)))
+            .unwrap();
+
+        let ready = db.list_ready_issues().unwrap();
+        let scored = rank_ready(&db, ready, DEFAULT_DUE_WEIGHT, now).unwrap();
+        let order: Vec<i64> = scored.iter().map(|s| s.0.id).collect();
+        assert_eq!(order, vec![medium, high]);
+        let lines = worklist_lines(&scored, 1, Some((DEFAULT_DUE_WEIGHT, now)));
+        assert!(lines[0].ends_with("[score 430 = 200 priority + 230 due date]"));
+
+        // With due dates switched off, priority decides again
+        let ready = db.list_ready_issues().unwrap();
+        let scored = rank_ready(&db, ready, 0, now).unwrap();
+        assert_eq!(scored[0].0.id, high);
+    }
+
+    #[test]
+    fn test_due_score_grows_as_the_due_date_nears() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Due", None, "medium").unwrap();
+        let now = Utc::now();
+        let score_due_in = |days: i64| {
+            db.set_due_date(id, Some(now + chrono::Duration::days(days)))
+                .unwrap();
+            let issue = db.get_issue(id).unwrap().unwrap();
+            due_score(&issue, DEFAULT_DUE_WEIGHT, now)
+        };
+
+        assert_eq!(score_due_in(30), 0);
+        assert_eq!(score_due_in(8), 0);
+        assert!(score_due_in(6) > 0);
+        assert!(score_due_in(1) > score_due_in(6));
+        assert_eq!(score_due_in(0), 200);
+        assert!(score_due_in(-1) > score_due_in(0));
+        assert_eq!(
+            score_due_in(-30),
+            score_due_in(i64::from(-MAX_OVERDUE_DAYS))
+        );
+
+        db.set_due_date(id, Some(now - chrono::Duration::days(100_000)))
+            .unwrap();
+        let ancient = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(due_score(&ancient, u16::MAX, now), 3 * i32::from(u16::MAX));
+
+        let undated = db.create_issue("Undated", None, "medium").unwrap();
+        let issue = db.get_issue(undated).unwrap().unwrap();
+        assert_eq!(due_score(&issue, DEFAULT_DUE_WEIGHT, now), 0);
+    }
+
     #[test]
     fn test_run_no_issues() {
         let (db, _dir) = setup_test_db();
-        run(&db).unwrap();
+        run(&db, 1, false, DEFAULT_DUE_WEIGHT).unwrap();
         let ready = db.list_ready_issues().unwrap();
         assert!(ready.is_empty());
     }
@@ -171,7 +400,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue 1", None, "high").unwrap();
 
-        run(&db).unwrap();
+        run(&db, 1, false, DEFAULT_DUE_WEIGHT).unwrap();
         let ready = db.list_ready_issues().unwrap();
         assert_eq!(ready.len(), 1);
         assert_eq!(ready[0].id, id);
@@ -186,7 +415,7 @@ mod tests {
             .unwrap();
         db.create_issue("Medium priority", None, "medium").unwrap();
 
-        run(&db).unwrap();
+        run(&db, 1, false, DEFAULT_DUE_WEIGHT).unwrap();
         // Verify the critical issue has the highest weight via the scoring function
         let ready = db.list_ready_issues().unwrap();
         assert_eq!(ready.len(), 3);
@@ -217,7 +446,7 @@ mod tests {
             .unwrap();
         db.create_subissue(parent_id, "Child 2", None, "medium")
             .unwrap();
-        db.close_issue(child1).unwrap();
+        db.close_issue(child1, None).unwrap();
 
         let issue = db.get_issue(parent_id).unwrap().unwrap();
         let progress = calculate_progress(&db, &issue).unwrap();
@@ -228,6 +457,23 @@ mod tests {
         assert_eq!(total, 2);
     }
 
+    #[test]
+    fn test_calculate_progress_counts_dropped_subissues_as_done() {
+        let (db, _dir) = setup_test_db();
+        let parent_id = db.create_issue("Parent", None, "high").unwrap();
+        let closed = db
+            .create_subissue(parent_id, "Closed", None, "low")
+            .unwrap();
+        let dropped = db
+            .create_subissue(parent_id, "Dropped", None, "low")
+            .unwrap();
+        db.close_issue(closed, None).unwrap();
+        db.drop_issue(dropped).unwrap();
+
+        let issue = db.get_issue(parent_id).unwrap().unwrap();
+        assert_eq!(calculate_progress(&db, &issue).unwrap(), Some((2, 2)));
+    }
+
     #[test]
     fn test_run_skips_blocked() {
         let (db, _dir) = setup_test_db();
@@ -235,7 +481,7 @@ mod tests {
         let blocked = db.create_issue("Blocked", None, "critical").unwrap();
         db.add_dependency(blocked, blocker).unwrap();
 
-        run(&db).unwrap();
+        run(&db, 1, false, DEFAULT_DUE_WEIGHT).unwrap();
         let ready = db.list_ready_issues().unwrap();
         assert!(
             !ready.iter().any(|i| i.id == blocked),
@@ -251,9 +497,9 @@ mod tests {
     fn test_run_all_issues_closed() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Done", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
 
-        run(&db).unwrap();
+        run(&db, 1, false, DEFAULT_DUE_WEIGHT).unwrap();
         let ready = db.list_ready_issues().unwrap();
         assert!(
             ready.is_empty(),
@@ -261,6 +507,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_run_reports_dependency_cycle() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "high").unwrap();
+        let b = db.create_issue("B", None, "high").unwrap();
+        db.add_dependency(a, b).unwrap();
+        db.add_dependency_unchecked(b, a);
+
+        assert!(db.list_ready_issues().unwrap().is_empty());
+        let notice = cycle_notice(&db).unwrap().unwrap();
+        assert!(notice.contains(&format!("#{}, #{}", a, b)), "{}", notice);
+        assert!(notice.contains("chainlink db check"));
+        run(&db, 1, false, DEFAULT_DUE_WEIGHT).unwrap();
+
+        // Closing one side breaks the cycle, so there is nothing to report
+        db.close_issue(b, None).unwrap();
+        assert!(cycle_notice(&db).unwrap().is_none());
+    }
+
     proptest! {
         #[test]
         fn prop_priority_weight_valid(priority in "low|medium|high|critical") {
@@ -274,7 +539,7 @@ mod tests {
             for i in 0..count {
                 db.create_issue(&format!("Issue {}", i), None, "medium").unwrap();
             }
-            let result = run(&db);
+            let result = run(&db, 1, false, DEFAULT_DUE_WEIGHT);
             prop_assert!(result.is_ok());
         }
     }
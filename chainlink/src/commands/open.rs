@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+
+use crate::db::Database;
+use crate::utils::{info, issue_url};
+
+/// Launches URLs; stubbed out in tests so nothing opens a real browser.
+pub trait Opener {
+    fn open(&self, url: &str) -> Result<()>;
+}
+
+/// The system's default browser.
+pub struct Browser;
+
+impl Opener for Browser {
+    fn open(&self, url: &str) -> Result<()> {
+        open::that(url).with_context(|| format!("Failed to open {} in a browser", url))
+    }
+}
+
+/// Open issue `id` on the project's tracker, or print it like `show` when no
+/// `repo_url` is configured.
+pub fn run(
+    db: &Database,
+    id: i64,
+    repo_url: Option<&str>,
+    wrap_width: Option<usize>,
+    opener: &dyn Opener,
+) -> Result<()> {
+    let Some(repo_url) = repo_url else {
+        return crate::commands::show::run(db, id, false, None, wrap_width, None);
+    };
+    db.require_issue(id)?;
+    let url = issue_url(repo_url, id);
+    opener.open(&url)?;
+    info(format_args!("Opened {}", url));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<String>>);
+
+    impl Opener for Recorder {
+        fn open(&self, url: &str) -> Result<()> {
+            self.0.borrow_mut().push(url.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_opens_issue_url_under_repo_url() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Crash on start", None, "high").unwrap();
+        let recorder = Recorder::default();
+
+        run(
+            &db,
+            id,
+            Some("https://example.com/project/"),
+            None,
+            &recorder,
+        )
+        .unwrap();
+        assert_eq!(
+            *recorder.0.borrow(),
+            vec![format!("https://example.com/project/issues/{}", id)]
+        );
+    }
+
+    #[test]
+    fn test_without_repo_url_opens_nothing() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Local only", None, "medium").unwrap();
+        let recorder = Recorder::default();
+
+        run(&db, id, None, None, &recorder).unwrap();
+        assert!(recorder.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_missing_issue_is_an_error() {
+        let (db, _dir) = setup_test_db();
+        let recorder = Recorder::default();
+        assert!(run(
+            &db,
+            99,
+            Some("https://example.com/project"),
+            None,
+            &recorder
+        )
+        .is_err());
+        assert!(recorder.0.borrow().is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{bail, Result};
+
+use crate::db::Database;
+use crate::utils::info;
+
+/// Where `rank` moves an issue, relative to another issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    Before(i64),
+    After(i64),
+}
+
+impl Position {
+    fn anchor(self) -> i64 {
+        match self {
+            Position::Before(id) | Position::After(id) => id,
+        }
+    }
+}
+
+/// The sort order that slots an issue in at `index` of `siblings` (in manual
+/// order, without the issue being moved). None when a neighbour is unranked or
+/// the two neighbours are too close to fit anything between them.
+fn order_at(siblings: &[(i64, Option<f64>)], index: usize) -> Option<f64> {
+    let prev = index.checked_sub(1).map(|i| siblings[i].1);
+    let next = siblings.get(index).map(|s| s.1);
+    match (prev, next) {
+        (Some(None), _) | (_, Some(None)) => None,
+        (None, None) => Some(1.0),
+        (Some(Some(p)), None) => Some(p + 1.0),
+        (None, Some(Some(n))) => Some(n - 1.0),
+        (Some(Some(p)), Some(Some(n))) => {
+            let mid = (p + n) / 2.0;
+            (mid > p && mid < n).then_some(mid)
+        }
+    }
+}
+
+/// Move `id` just before or after another issue in manual order, among the
+/// anchor's siblings (issues with the same parent, or the top-level backlog).
+/// Both issues must share a parent.
+pub fn run(db: &Database, id: i64, position: Position) -> Result<()> {
+    let anchor_id = position.anchor();
+    if anchor_id == id {
+        bail!("Can't rank issue #{} relative to itself", id);
+    }
+    let Some(issue) = db.get_issue(id)? else {
+        bail!("Issue #{} not found", id);
+    };
+    let Some(anchor) = db.get_issue(anchor_id)? else {
+        bail!("Issue #{} not found", anchor_id);
+    };
+    if issue.parent_id != anchor.parent_id {
+        bail!(
+            "Issues #{} and #{} have different parents; move #{} with 'chainlink reparent-all' first",
+            id,
+            anchor_id,
+            id
+        );
+    }
+
+    db.transaction(|| {
+        let mut siblings = db.sibling_sort_orders(anchor.parent_id)?;
+        siblings.retain(|(sibling, _)| *sibling != id);
+        let anchor_index = siblings
+            .iter()
+            .position(|(sibling, _)| *sibling == anchor_id)
+            .expect("anchor is one of its own siblings");
+        let index = match position {
+            Position::Before(_) => anchor_index,
+            Position::After(_) => anchor_index + 1,
+        };
+
+        let order = match order_at(&siblings, index) {
+            Some(order) => order,
+            None => {
+                // Write the current order out as 1, 2, 3... to make room
+                for (i, (sibling, order)) in siblings.iter_mut().enumerate() {
+                    *order = Some((i + 1) as f64);
+                    db.set_sort_order(*sibling, (i + 1) as f64)?;
+                }
+                order_at(&siblings, index).expect("evenly spaced orders leave room")
+            }
+        };
+        db.set_sort_order(id, order)?;
+        Ok(())
+    })?;
+
+    let relation = match position {
+        Position::Before(_) => "before",
+        Position::After(_) => "after",
+    };
+    info(format_args!("Ranked #{} {} #{}", id, relation, anchor_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::IssueOrder;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    fn manual_ids(db: &Database) -> Vec<i64> {
+        db.list_issues_excluding(Some("all"), None, None, &[], IssueOrder::Manual)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_rank_before_puts_issue_first_regardless_of_id() {
+        let (db, _dir) = setup_test_db();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let a = db.create_issue("A", None, "medium").unwrap();
+
+        run(&db, a, Position::Before(b)).unwrap();
+        let order = manual_ids(&db);
+        let pos = |id| order.iter().position(|&x| x == id).unwrap();
+        assert!(pos(a) < pos(b), "got {:?}", order);
+
+        run(&db, c, Position::After(a)).unwrap();
+        assert_eq!(manual_ids(&db), vec![a, c, b]);
+    }
+
+    #[test]
+    fn test_rank_repeatedly_renumbers_when_out_of_room() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+        let third = db.create_issue("Third", None, "medium").unwrap();
+        run(&db, first, Position::Before(second)).unwrap();
+        run(&db, third, Position::After(second)).unwrap();
+
+        // Halving the gap this often exhausts f64 precision
+        for _ in 0..100 {
+            run(&db, third, Position::Before(second)).unwrap();
+            run(&db, third, Position::After(first)).unwrap();
+            run(&db, second, Position::After(third)).unwrap();
+            run(&db, second, Position::Before(third)).unwrap();
+        }
+        assert_eq!(manual_ids(&db), vec![first, second, third]);
+    }
+
+    #[test]
+    fn test_rank_among_subissues() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let x = db.create_subissue(parent, "X", None, "medium").unwrap();
+        let y = db.create_subissue(parent, "Y", None, "medium").unwrap();
+
+        run(&db, x, Position::After(y)).unwrap();
+        let subissue_order: Vec<i64> = manual_ids(&db)
+            .into_iter()
+            .filter(|id| *id != parent)
+            .collect();
+        assert_eq!(subissue_order, vec![y, x]);
+    }
+
+    #[test]
+    fn test_rank_errors() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Only", None, "medium").unwrap();
+        assert!(run(&db, id, Position::Before(id)).is_err());
+        assert!(run(&db, id, Position::After(99)).is_err());
+        assert!(run(&db, 99, Position::After(id)).is_err());
+    }
+
+    #[test]
+    fn test_rank_refuses_issue_under_another_parent() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        let top = db.create_issue("Top", None, "medium").unwrap();
+
+        let err = run(&db, top, Position::Before(child)).unwrap_err();
+        assert!(err.to_string().contains("different parents"), "{}", err);
+        assert!(run(&db, child, Position::After(top)).is_err());
+        assert!(db
+            .sibling_sort_orders(None)
+            .unwrap()
+            .iter()
+            .all(|(_, order)| order.is_none()));
+    }
+}
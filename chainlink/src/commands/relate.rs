@@ -1,18 +1,112 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 use crate::db::Database;
+use crate::models::Issue;
+use crate::utils::info;
+
+/// Whether `ancestor_id` appears in the parent chain of `issue_id`.
+pub fn is_ancestor(db: &Database, issue_id: i64, ancestor_id: i64) -> Result<bool> {
+    let mut seen = std::collections::HashSet::new();
+    let mut current = db.get_issue(issue_id)?.and_then(|i| i.parent_id);
+    while let Some(parent_id) = current {
+        if parent_id == ancestor_id {
+            return Ok(true);
+        }
+        if !seen.insert(parent_id) {
+            break;
+        }
+        current = db.get_issue(parent_id)?.and_then(|i| i.parent_id);
+    }
+    Ok(false)
+}
 
-pub fn add(db: &Database, issue_id: i64, related_id: i64) -> Result<()> {
-    db.require_issue(issue_id)?;
-    db.require_issue(related_id)?;
+/// Warning text when the two issues are already linked through the subissue hierarchy.
+fn hierarchy_warning(db: &Database, issue_id: i64, related_id: i64) -> Result<Option<String>> {
+    let (ancestor, descendant) = if is_ancestor(db, related_id, issue_id)? {
+        (issue_id, related_id)
+    } else if is_ancestor(db, issue_id, related_id)? {
+        (related_id, issue_id)
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(format!(
+        "#{} is a subissue of #{}; the hierarchy already links them. Use --force to relate them anyway.",
+        descendant, ancestor
+    )))
+}
+
+/// Relate `issue_id` to each of `others`, or every pair among all of them
+/// when `clique` is set. Pairs that are already related are left alone,
+/// except that `note` replaces their note.
+pub fn add_many(
+    db: &Database,
+    issue_id: i64,
+    others: &[i64],
+    clique: bool,
+    force: bool,
+    note: Option<&str>,
+) -> Result<()> {
+    let mut ids = vec![issue_id];
+    for &id in others {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.len() < 2 {
+        bail!("Give at least two different issue ids to relate");
+    }
+    for &id in &ids {
+        db.require_issue(id)?;
+    }
+
+    db.transaction(|| {
+        for (a, b) in relation_pairs(&ids, clique) {
+            link(db, a, b, force, note)?;
+        }
+        Ok(())
+    })
+}
 
-    if db.add_relation(issue_id, related_id)? {
-        println!("Linked #{} ↔ #{}", issue_id, related_id);
+/// The pairs to relate: the first id with each of the rest, or all pairs.
+fn relation_pairs(ids: &[i64], clique: bool) -> Vec<(i64, i64)> {
+    let Some((&first, rest)) = ids.split_first() else {
+        return Vec::new();
+    };
+    if !clique {
+        return rest.iter().map(|&id| (first, id)).collect();
+    }
+    ids.iter()
+        .enumerate()
+        .flat_map(|(i, &a)| ids[i + 1..].iter().map(move |&b| (a, b)))
+        .collect()
+}
+
+fn link(
+    db: &Database,
+    issue_id: i64,
+    related_id: i64,
+    force: bool,
+    note: Option<&str>,
+) -> Result<()> {
+    if !force {
+        if let Some(warning) = hierarchy_warning(db, issue_id, related_id)? {
+            eprintln!("Warning: {}", warning);
+            return Ok(());
+        }
+    }
+
+    if db.add_relation(issue_id, related_id, note)? {
+        info(format_args!("Linked #{} ↔ #{}", issue_id, related_id));
+    } else if note.is_some() {
+        info(format_args!(
+            "Issues #{} and #{} are already related; updated the note",
+            issue_id, related_id
+        ));
     } else {
-        println!(
+        info(format_args!(
             "Issues #{} and #{} are already related",
             issue_id, related_id
-        );
+        ));
     }
 
     Ok(())
@@ -20,12 +114,12 @@ pub fn add(db: &Database, issue_id: i64, related_id: i64) -> Result<()> {
 
 pub fn remove(db: &Database, issue_id: i64, related_id: i64) -> Result<()> {
     if db.remove_relation(issue_id, related_id)? {
-        println!("Unlinked #{} ↔ #{}", issue_id, related_id);
+        info(format_args!("Unlinked #{} ↔ #{}", issue_id, related_id));
     } else {
-        println!(
+        info(format_args!(
             "No relation found between #{} and #{}",
             issue_id, related_id
-        );
+        ));
     }
 
     Ok(())
@@ -41,18 +135,28 @@ pub fn list(db: &Database, issue_id: i64) -> Result<()> {
         return Ok(());
     }
 
+    let notes = db.relation_notes(issue_id)?;
     println!("Related to #{}:", issue_id);
     for r in related {
-        let status_marker = if r.status == "closed" { "✓" } else { " " };
-        println!(
-            "  #{:<4} [{}] {:8} {}",
-            r.id, status_marker, r.priority, r.title
-        );
+        println!("{}", related_line(&r, notes.get(&r.id)));
     }
 
     Ok(())
 }
 
+/// One row of the `related` listing, with the relation's note if it has one.
+fn related_line(issue: &Issue, note: Option<&String>) -> String {
+    let status_marker = if issue.status == "closed" { "✓" } else { " " };
+    let mut line = format!(
+        "  #{:<4} [{}] {:8} {}",
+        issue.id, status_marker, issue.priority, issue.title
+    );
+    if let Some(note) = note {
+        line.push_str(&format!(" — {}", note));
+    }
+    line
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,7 +176,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        let result = add(&db, id1, id2);
+        let result = add_many(&db, id1, &[id2], false, false, None);
         assert!(result.is_ok());
 
         let related = db.get_related_issues(id1).unwrap();
@@ -86,7 +190,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
+        add_many(&db, id1, &[id2], false, false, None).unwrap();
 
         let related1 = db.get_related_issues(id1).unwrap();
         let related2 = db.get_related_issues(id2).unwrap();
@@ -99,7 +203,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue 1", None, "medium").unwrap();
 
-        let result = add(&db, id, 99999);
+        let result = add_many(&db, id, &[99999], false, false, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -110,21 +214,122 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
-        let result = add(&db, id1, id2);
+        add_many(&db, id1, &[id2], false, false, None).unwrap();
+        let result = add_many(&db, id1, &[id2], false, false, None);
         assert!(result.is_ok());
 
         let related = db.get_related_issues(id1).unwrap();
         assert_eq!(related.len(), 1);
     }
 
+    #[test]
+    fn test_add_relation_parent_child_warns() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "low")
+            .unwrap();
+
+        let warning = hierarchy_warning(&db, parent, child).unwrap().unwrap();
+        assert!(warning.contains(&format!("#{} is a subissue of #{}", child, parent)));
+        assert!(hierarchy_warning(&db, grandchild, parent)
+            .unwrap()
+            .is_some());
+
+        add_many(&db, parent, &[child], false, false, None).unwrap();
+        assert!(db.get_related_issues(parent).unwrap().is_empty());
+
+        add_many(&db, parent, &[child], false, true, None).unwrap();
+        assert_eq!(db.get_related_issues(parent).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_add_relation_siblings_allowed() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let a = db.create_subissue(parent, "A", None, "medium").unwrap();
+        let b = db.create_subissue(parent, "B", None, "medium").unwrap();
+
+        assert!(hierarchy_warning(&db, a, b).unwrap().is_none());
+        add_many(&db, a, &[b], false, false, None).unwrap();
+        assert_eq!(db.get_related_issues(a).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_relate_with_reason_shows_note_in_listing() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        add_many(&db, a, &[b], false, false, Some("same root cause")).unwrap();
+
+        let notes = db.relation_notes(b).unwrap();
+        let issue = db.get_issue(a).unwrap().unwrap();
+        let line = related_line(&issue, notes.get(&a));
+        assert!(line.ends_with("A — same root cause"));
+
+        // Relating again with a new reason replaces the note.
+        add_many(&db, b, &[a], false, false, Some("duplicate")).unwrap();
+        assert_eq!(db.relation_notes(a).unwrap()[&b], "duplicate");
+    }
+
+    #[test]
+    fn test_add_many_relates_primary_to_each() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        db.add_relation(a, b, None).unwrap();
+
+        add_many(&db, a, &[b, c, a], false, false, None).unwrap();
+
+        let related: Vec<i64> = db
+            .get_related_issues(a)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(related, vec![b, c]);
+        assert!(db.get_related_issues(b).unwrap().iter().all(|i| i.id != c));
+    }
+
+    #[test]
+    fn test_add_many_clique_relates_all_pairs() {
+        let (db, _dir) = setup_test_db();
+        let ids: Vec<i64> = (0..4)
+            .map(|n| {
+                db.create_issue(&format!("Dup {}", n), None, "medium")
+                    .unwrap()
+            })
+            .collect();
+
+        add_many(&db, ids[0], &ids[1..], true, false, None).unwrap();
+
+        for &id in &ids {
+            assert_eq!(db.get_related_issues(id).unwrap().len(), 3);
+        }
+        assert_eq!(relation_pairs(&ids, true).len(), 6);
+    }
+
+    #[test]
+    fn test_add_many_missing_issue_relates_nothing() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        assert!(add_many(&db, a, &[b, 999], false, false, None).is_err());
+        assert!(db.get_related_issues(a).unwrap().is_empty());
+        assert!(add_many(&db, a, &[a], false, false, None).is_err());
+    }
+
     #[test]
     fn test_remove_relation() {
         let (db, _dir) = setup_test_db();
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
+        add_many(&db, id1, &[id2], false, false, None).unwrap();
         let result = remove(&db, id1, id2);
         assert!(result.is_ok());
 
@@ -149,8 +354,8 @@ mod tests {
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
         let id3 = db.create_issue("Issue 3", None, "medium").unwrap();
 
-        add(&db, id1, id2).unwrap();
-        add(&db, id1, id3).unwrap();
+        add_many(&db, id1, &[id2], false, false, None).unwrap();
+        add_many(&db, id1, &[id3], false, false, None).unwrap();
 
         let result = list(&db, id1);
         assert!(result.is_ok());
@@ -183,7 +388,7 @@ mod tests {
                 let id1 = ids[a as usize % ids.len()];
                 let id2 = ids[b as usize % ids.len()];
 
-                add(&db, id1, id2).unwrap();
+                add_many(&db, id1, &[id2], false, false, None).unwrap();
                 let related = db.get_related_issues(id1).unwrap();
                 prop_assert!(!related.is_empty());
 
@@ -0,0 +1,173 @@
+use anyhow::{bail, Result};
+use std::str::FromStr;
+
+use crate::commands::relate::is_ancestor;
+use crate::db::Database;
+use crate::utils::info;
+
+/// Where `reparent-all` moves issues to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewParent {
+    /// Make the issues top-level
+    Detach,
+    Under(i64),
+}
+
+impl FromStr for NewParent {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("none") {
+            return Ok(NewParent::Detach);
+        }
+        s.trim_start_matches('#')
+            .parse()
+            .map(NewParent::Under)
+            .map_err(|_| format!("expected an issue id or 'none', got '{}'", s))
+    }
+}
+
+/// Why `id` can't be moved under `new_parent`, if it can't.
+fn refusal(db: &Database, id: i64, new_parent: NewParent) -> Result<Option<String>> {
+    let Some(issue) = db.get_issue(id)? else {
+        return Ok(Some("not found".to_string()));
+    };
+    let reason = match new_parent {
+        NewParent::Detach if issue.parent_id.is_none() => Some("already top-level".to_string()),
+        NewParent::Detach => None,
+        NewParent::Under(pid) if pid == id => Some("an issue can't be its own parent".to_string()),
+        NewParent::Under(pid) if issue.parent_id == Some(pid) => {
+            Some(format!("already under #{}", pid))
+        }
+        NewParent::Under(pid) if is_ancestor(db, pid, id)? => Some(format!(
+            "#{} is a subissue of #{}; moving it would create a cycle",
+            pid, id
+        )),
+        NewParent::Under(_) => None,
+    };
+    Ok(reason)
+}
+
+/// Move each of `ids` under `new_parent` (or detach them), skipping any that
+/// would be a no-op or create a cycle. Returns how many were moved.
+pub fn run_all(db: &Database, new_parent: NewParent, ids: &[i64]) -> Result<usize> {
+    if let NewParent::Under(pid) = new_parent {
+        db.require_issue(pid)
+            .map_err(|_| anyhow::anyhow!("New parent #{} not found", pid))?;
+    }
+
+    let moved = db.transaction(|| {
+        let mut moved = 0;
+        for &id in ids {
+            // Checked against the current state, so earlier moves in this
+            // batch count towards cycle detection
+            if let Some(reason) = refusal(db, id, new_parent)? {
+                eprintln!("#{}: skipped ({})", id, reason);
+                continue;
+            }
+            let parent_id = match new_parent {
+                NewParent::Detach => None,
+                NewParent::Under(pid) => Some(pid),
+            };
+            db.update_parent(id, parent_id)?;
+            match new_parent {
+                NewParent::Detach => info(format_args!("#{}: detached (now top-level)", id)),
+                NewParent::Under(pid) => info(format_args!("#{}: moved under #{}", id, pid)),
+            }
+            moved += 1;
+        }
+        Ok(moved)
+    })?;
+
+    if moved == 0 {
+        bail!("No issues were reparented");
+    }
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_new_parent_from_str() {
+        assert_eq!("none".parse::<NewParent>(), Ok(NewParent::Detach));
+        assert_eq!("#7".parse::<NewParent>(), Ok(NewParent::Under(7)));
+        assert!("epic".parse::<NewParent>().is_err());
+    }
+
+    #[test]
+    fn test_move_two_children_to_new_parent() {
+        let (db, _dir) = setup_test_db();
+        let old = db.create_issue("Old epic", None, "medium").unwrap();
+        let new = db.create_issue("New epic", None, "medium").unwrap();
+        let a = db.create_subissue(old, "A", None, "low").unwrap();
+        let b = db.create_subissue(old, "B", None, "low").unwrap();
+        let c = db.create_subissue(old, "C", None, "low").unwrap();
+
+        assert_eq!(run_all(&db, NewParent::Under(new), &[a, b]).unwrap(), 2);
+
+        let moved: Vec<i64> = db
+            .get_subissues(new)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(moved, vec![a, b]);
+        let left: Vec<i64> = db
+            .get_subissues(old)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(left, vec![c]);
+    }
+
+    #[test]
+    fn test_detach_all() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Epic", None, "medium").unwrap();
+        let a = db.create_subissue(parent, "A", None, "low").unwrap();
+        let b = db.create_subissue(parent, "B", None, "low").unwrap();
+
+        run_all(&db, NewParent::Detach, &[a, b]).unwrap();
+
+        assert!(db.get_subissues(parent).unwrap().is_empty());
+        assert_eq!(db.get_issue(a).unwrap().unwrap().parent_id, None);
+    }
+
+    #[test]
+    fn test_cycle_and_missing_ids_are_skipped() {
+        let (db, _dir) = setup_test_db();
+        let top = db.create_issue("Top", None, "medium").unwrap();
+        let mid = db.create_subissue(top, "Mid", None, "medium").unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+
+        // Moving top under its own descendant is refused; other still moves
+        assert_eq!(
+            run_all(&db, NewParent::Under(mid), &[top, 999, other]).unwrap(),
+            1
+        );
+        assert_eq!(db.get_issue(top).unwrap().unwrap().parent_id, None);
+        assert_eq!(db.get_issue(other).unwrap().unwrap().parent_id, Some(mid));
+
+        let result = run_all(&db, NewParent::Under(mid), &[mid]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_missing_new_parent_fails() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let result = run_all(&db, NewParent::Under(42), &[a]);
+        assert!(result.unwrap_err().to_string().contains("#42 not found"));
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use schemars::schema_for;
+use serde_json::{json, Value};
+
+use super::export::ExportData;
+use super::show::IssueDetail;
+use crate::models::Issue;
+
+/// JSON schemas for the shapes `--json` output and `export` produce, keyed by
+/// where they appear. Generated from the serde structs, so they can't drift.
+fn schemas() -> Value {
+    json!({
+        "issue": schema_for!(Issue),
+        "issue_detail": schema_for!(IssueDetail),
+        "export": schema_for!(ExportData),
+    })
+}
+
+pub fn run() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&schemas())?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_output_is_json_describing_issue_fields() {
+        let output = serde_json::to_string_pretty(&schemas()).unwrap();
+        let parsed: Value = serde_json::from_str(&output).unwrap();
+
+        let properties = &parsed["issue"]["properties"];
+        assert!(properties.get("title").is_some());
+        assert!(properties.get("priority").is_some());
+        // Flattened issue fields appear directly on the detail object
+        assert!(parsed["issue_detail"]["properties"].get("title").is_some());
+        assert!(parsed["export"]["properties"].get("issues").is_some());
+    }
+}
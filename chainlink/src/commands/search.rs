@@ -1,23 +1,139 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use regex::Regex;
 use serde_json;
 
 use crate::db::Database;
+use crate::utils::color_enabled;
 
-pub fn run_json(db: &Database, query: &str) -> Result<()> {
-    let results = db.search_issues(query)?;
+const HIGHLIGHT_START: &str = "\x1b[1;33m";
+const HIGHLIGHT_END: &str = "\x1b[0m";
+
+/// Wrap each case-insensitive occurrence of `query` in `text` with ANSI highlighting.
+fn highlight(text: &str, query: &str) -> String {
+    let needle: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return text.to_string();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        // Lowercase forward from i until we have as many chars as the needle
+        let mut folded = Vec::with_capacity(needle.len());
+        let mut end = i;
+        while folded.len() < needle.len() && end < chars.len() {
+            folded.extend(chars[end].to_lowercase());
+            end += 1;
+        }
+
+        if folded == needle {
+            out.push_str(HIGHLIGHT_START);
+            out.extend(&chars[i..end]);
+            out.push_str(HIGHLIGHT_END);
+            i = end;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Wrap each match of `re` in `text` with ANSI highlighting.
+fn highlight_regex(text: &str, re: &Regex) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in re.find_iter(text).filter(|m| !m.is_empty()) {
+        out.push_str(&text[last..m.start()]);
+        out.push_str(HIGHLIGHT_START);
+        out.push_str(m.as_str());
+        out.push_str(HIGHLIGHT_END);
+        last = m.end();
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// What a search looks for: a case-insensitive substring (matched with
+/// LIKE) or a regular expression (`--regex`, matched with REGEXP).
+enum Matcher<'a> {
+    Text(&'a str),
+    Regex(&'a str, Regex),
+}
+
+impl<'a> Matcher<'a> {
+    fn new(query: &'a str, regex: bool) -> Result<Self> {
+        if regex {
+            let re = Regex::new(query).with_context(|| format!("Invalid regex '{}'", query))?;
+            Ok(Matcher::Regex(query, re))
+        } else {
+            Ok(Matcher::Text(query))
+        }
+    }
+
+    fn search(&self, db: &Database, limit: Option<usize>) -> Result<Vec<crate::models::Issue>> {
+        match self {
+            Matcher::Text(query) => db.search_issues(query, limit),
+            Matcher::Regex(pattern, _) => db.search_issues_regex(pattern, limit),
+        }
+    }
+
+    fn count(&self, db: &Database) -> Result<usize> {
+        match self {
+            Matcher::Text(query) => db.count_search_matches(query),
+            Matcher::Regex(pattern, _) => db.count_regex_matches(pattern),
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Text(query) => text.to_lowercase().contains(&query.to_lowercase()),
+            Matcher::Regex(_, re) => re.is_match(text),
+        }
+    }
+
+    fn highlight(&self, text: &str) -> String {
+        match self {
+            Matcher::Text(query) => highlight(text, query),
+            Matcher::Regex(_, re) => highlight_regex(text, re),
+        }
+    }
+}
+
+pub fn run_json(db: &Database, query: &str, limit: Option<usize>, regex: bool) -> Result<()> {
+    let results = Matcher::new(query, regex)?.search(db, limit)?;
     println!("{}", serde_json::to_string_pretty(&results)?);
     Ok(())
 }
 
-pub fn run(db: &Database, query: &str) -> Result<()> {
-    let results = db.search_issues(query)?;
+/// Print the best `limit` matches for `query`, noting how many were left out.
+/// With `regex`, `query` is a regular expression instead of a substring.
+pub fn run(db: &Database, query: &str, limit: Option<usize>, regex: bool) -> Result<()> {
+    let matcher = Matcher::new(query, regex)?;
+    let results = matcher.search(db, limit)?;
 
     if results.is_empty() {
         println!("No issues found matching '{}'", query);
         return Ok(());
     }
 
-    println!("Found {} issue(s) matching '{}':\n", results.len(), query);
+    let total = if limit.is_some_and(|n| results.len() >= n) {
+        matcher.count(db)?
+    } else {
+        results.len()
+    };
+    println!("Found {} issue(s) matching '{}':\n", total, query);
+    let shown = results.len();
+
+    let color = color_enabled();
+    let mark = |text: &str| {
+        if color {
+            matcher.highlight(text)
+        } else {
+            text.to_string()
+        }
+    };
 
     for issue in results {
         let status_marker = if issue.status == "closed" { "✓" } else { " " };
@@ -31,7 +147,7 @@ pub fn run(db: &Database, query: &str) -> Result<()> {
             issue.id,
             status_marker,
             issue.priority,
-            issue.title,
+            mark(&issue.title),
             parent_str,
             if issue.status == "closed" {
                 "(closed)"
@@ -42,20 +158,29 @@ pub fn run(db: &Database, query: &str) -> Result<()> {
 
         // Show snippet of description if it contains the query
         if let Some(ref desc) = issue.description {
-            if desc.to_lowercase().contains(&query.to_lowercase()) {
+            if matcher.is_match(desc) {
                 let preview: String = desc.chars().take(60).collect();
                 let suffix = if desc.chars().count() > 60 { "..." } else { "" };
-                println!("      └─ {}{}", preview.replace('\n', " "), suffix);
+                println!("      └─ {}{}", mark(&preview.replace('\n', " ")), suffix);
             }
         }
     }
 
+    if let Some(note) = truncation_note(shown, total) {
+        println!("\n{}", note);
+    }
+
     Ok(())
 }
 
+fn truncation_note(shown: usize, total: usize) -> Option<String> {
+    (shown < total).then(|| format!("Showing {} of {} (use --limit to see more)", shown, total))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::FieldUpdate;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -68,6 +193,125 @@ mod tests {
 
     // ==================== Unit Tests ====================
 
+    #[test]
+    fn test_limit_truncates_and_reports_total() {
+        let (db, _dir) = setup_test_db();
+        for i in 0..7 {
+            db.create_issue(&format!("Flaky test {}", i), None, "medium")
+                .unwrap();
+        }
+        db.create_issue("Unrelated", None, "medium").unwrap();
+
+        let results = db.search_issues("flaky", Some(5)).unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(db.count_search_matches("flaky").unwrap(), 7);
+        assert_eq!(
+            truncation_note(results.len(), 7).as_deref(),
+            Some("Showing 5 of 7 (use --limit to see more)")
+        );
+        assert_eq!(truncation_note(7, 7), None);
+        run(&db, "flaky", Some(5), false).unwrap();
+    }
+
+    #[test]
+    fn test_title_matches_rank_first_then_recently_updated() {
+        let (db, _dir) = setup_test_db();
+        let in_title = db.create_issue("Cache eviction", None, "medium").unwrap();
+        let older = db
+            .create_issue("Memory", Some("cache grows"), "medium")
+            .unwrap();
+        let newer = db
+            .create_issue("Startup", Some("cold cache"), "medium")
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        db.update_issue(older, Some("Memory leak"), FieldUpdate::Keep, None)
+            .unwrap();
+
+        let ids: Vec<i64> = db
+            .search_issues("cache", None)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids, vec![in_title, older, newer]);
+    }
+
+    #[test]
+    fn test_regex_matches_digits_but_not_word() {
+        let (db, _dir) = setup_test_db();
+        let numbered = db
+            .create_issue("Fix auth42 handshake", None, "high")
+            .unwrap();
+        db.create_issue("Improve authentication", None, "medium")
+            .unwrap();
+        let described = db
+            .create_issue("Token refresh", Some("see auth7 logs"), "low")
+            .unwrap();
+        let commented = db.create_issue("Session bug", None, "low").unwrap();
+        db.add_comment(commented, "related to auth314").unwrap();
+
+        let mut ids: Vec<i64> = db
+            .search_issues_regex(r"auth\d+", None)
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![numbered, described, commented]);
+        assert_eq!(db.count_regex_matches(r"auth\d+").unwrap(), 3);
+        assert!(run(&db, r"auth\d+", None, true).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_regex_errors_clearly() {
+        let (db, _dir) = setup_test_db();
+        let err = run(&db, "auth(", None, true).unwrap_err();
+        assert!(err.to_string().contains("Invalid regex 'auth('"));
+    }
+
+    #[test]
+    fn test_highlight_regex() {
+        let re = Regex::new(r"auth\d+").unwrap();
+        assert_eq!(
+            highlight_regex("auth1 and auth22", &re),
+            format!(
+                "{s}auth1{e} and {s}auth22{e}",
+                s = HIGHLIGHT_START,
+                e = HIGHLIGHT_END
+            )
+        );
+    }
+
+    #[test]
+    fn test_highlight_case_insensitive() {
+        assert_eq!(
+            highlight("Fix Auth bug in auth", "auth"),
+            format!(
+                "Fix {s}Auth{e} bug in {s}auth{e}",
+                s = HIGHLIGHT_START,
+                e = HIGHLIGHT_END
+            )
+        );
+    }
+
+    #[test]
+    fn test_highlight_no_match_or_empty_query() {
+        assert_eq!(highlight("nothing here", "zzz"), "nothing here");
+        assert_eq!(highlight("nothing here", ""), "nothing here");
+    }
+
+    #[test]
+    fn test_highlight_unicode() {
+        assert_eq!(
+            highlight("Überprüfung ÜBER", "über"),
+            format!(
+                "{s}Über{e}prüfung {s}ÜBER{e}",
+                s = HIGHLIGHT_START,
+                e = HIGHLIGHT_END
+            )
+        );
+    }
+
     #[test]
     fn test_search_finds_by_title() {
         let (db, _dir) = setup_test_db();
@@ -76,8 +320,8 @@ mod tests {
             .unwrap();
         db.create_issue("Add dark mode", None, "medium").unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", None, false).unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -89,8 +333,8 @@ mod tests {
             .create_issue("Feature A", Some("This relates to user login"), "medium")
             .unwrap();
 
-        run(&db, "login").unwrap();
-        let results = db.search_issues("login").unwrap();
+        run(&db, "login", None, false).unwrap();
+        let results = db.search_issues("login", None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -102,8 +346,8 @@ mod tests {
             .create_issue("Fix AUTHENTICATION Bug", None, "high")
             .unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", None, false).unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
         assert_eq!(
             results.len(),
             1,
@@ -117,8 +361,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Some issue", None, "medium").unwrap();
 
-        run(&db, "nonexistent").unwrap();
-        let results = db.search_issues("nonexistent").unwrap();
+        run(&db, "nonexistent", None, false).unwrap();
+        let results = db.search_issues("nonexistent", None).unwrap();
         assert!(
             results.is_empty(),
             "Search for nonexistent term should return empty"
@@ -129,8 +373,8 @@ mod tests {
     fn test_search_empty_database() {
         let (db, _dir) = setup_test_db();
 
-        run(&db, "anything").unwrap();
-        let results = db.search_issues("anything").unwrap();
+        run(&db, "anything", None, false).unwrap();
+        let results = db.search_issues("anything", None).unwrap();
         assert!(results.is_empty());
     }
 
@@ -139,8 +383,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Test issue", None, "medium").unwrap();
 
-        run(&db, "").unwrap();
-        let _results = db.search_issues("").unwrap();
+        run(&db, "", None, false).unwrap();
+        let _results = db.search_issues("", None).unwrap();
         // Empty query behavior: may match all or none depending on implementation
         // Just verify it doesn't error
     }
@@ -152,8 +396,8 @@ mod tests {
             .create_issue("Fix bug with @mentions", None, "medium")
             .unwrap();
 
-        run(&db, "@mentions").unwrap();
-        let results = db.search_issues("@mentions").unwrap();
+        run(&db, "@mentions", None, false).unwrap();
+        let results = db.search_issues("@mentions", None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -165,8 +409,8 @@ mod tests {
             .create_issue("Fix 日本語 support", None, "medium")
             .unwrap();
 
-        run(&db, "日本語").unwrap();
-        let results = db.search_issues("日本語").unwrap();
+        run(&db, "日本語", None, false).unwrap();
+        let results = db.search_issues("日本語", None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
@@ -176,7 +420,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Normal issue", None, "medium").unwrap();
 
-        run(&db, "'; DROP TABLE issues; --").unwrap();
+        run(&db, "'; DROP TABLE issues; --", None, false).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert_eq!(
             issues.len(),
@@ -191,8 +435,8 @@ mod tests {
         db.create_issue("Test issue with pattern", None, "medium")
             .unwrap();
 
-        run(&db, "%pattern%").unwrap();
-        let results = db.search_issues("%pattern%").unwrap();
+        run(&db, "%pattern%", None, false).unwrap();
+        let results = db.search_issues("%pattern%", None).unwrap();
         // SQL wildcards should be escaped -- literal "%pattern%" should NOT match "pattern"
         assert!(
             results.is_empty(),
@@ -207,8 +451,8 @@ mod tests {
         db.add_comment(id, "Found the root cause in authentication module")
             .unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", None, false).unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
         assert_eq!(
             results.len(),
             1,
@@ -225,8 +469,8 @@ mod tests {
             .create_subissue(parent_id, "Sub task authentication", None, "medium")
             .unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", None, false).unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, sub_id);
         assert_eq!(results[0].parent_id, Some(parent_id));
@@ -238,10 +482,10 @@ mod tests {
         let id = db
             .create_issue("Fix authentication bug", None, "high")
             .unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
 
-        run(&db, "authentication").unwrap();
-        let results = db.search_issues("authentication").unwrap();
+        run(&db, "authentication", None, false).unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
         assert_eq!(results.len(), 1, "Search should find closed issues too");
         assert_eq!(results[0].status, "closed");
     }
@@ -253,7 +497,7 @@ mod tests {
         fn prop_search_never_panics(query in ".*") {
             let (db, _dir) = setup_test_db();
             db.create_issue("Test issue", None, "medium").unwrap();
-            let _ = run(&db, &query);
+            let _ = run(&db, &query, None, false);
         }
 
         #[test]
@@ -263,7 +507,7 @@ mod tests {
         ) {
             let (db, _dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
-            let result = run(&db, &query);
+            let result = run(&db, &query, None, false);
             prop_assert!(result.is_ok());
         }
 
@@ -274,7 +518,7 @@ mod tests {
         ) {
             let (db, _dir) = setup_test_db();
             db.create_issue(&title, None, "medium").unwrap();
-            let result = run(&db, &query);
+            let result = run(&db, &query, None, false);
             prop_assert!(result.is_ok());
         }
     }
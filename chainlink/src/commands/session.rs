@@ -2,49 +2,60 @@ use anyhow::{bail, Result};
 use chrono::Utc;
 
 use crate::db::Database;
+use crate::utils::{humanize_age, info};
 
 pub fn start(db: &Database) -> Result<()> {
     // Check if there's already an active session
     if let Some(current) = db.get_current_session()? {
-        println!(
+        info(format_args!(
             "Session #{} is already active (started {})",
             current.id,
             current.started_at.format("%Y-%m-%d %H:%M")
-        );
+        ));
         return Ok(());
     }
 
     // Show previous session's handoff notes
     if let Some(last) = db.get_last_session()? {
         if let Some(ended) = last.ended_at {
-            println!("Previous session ended: {}", ended.format("%Y-%m-%d %H:%M"));
+            info(format_args!(
+                "Previous session ended: {}",
+                ended.format("%Y-%m-%d %H:%M")
+            ));
         }
         if let Some(notes) = &last.handoff_notes {
             if !notes.is_empty() {
-                println!("Handoff notes:");
+                info("Handoff notes:");
                 for line in notes.lines() {
-                    println!("  {}", line);
+                    info(format_args!("  {}", line));
                 }
-                println!();
+                info("");
             }
         }
     }
 
     let id = db.start_session()?;
-    println!("Session #{} started.", id);
+    info(format_args!("Session #{} started.", id));
     Ok(())
 }
 
-pub fn end(db: &Database, notes: Option<&str>) -> Result<()> {
+/// End the current session, stopping the running timer too when `stop_timer` is set.
+pub fn end(db: &Database, notes: Option<&str>, stop_timer: bool) -> Result<()> {
     let session = match db.get_current_session()? {
         Some(s) => s,
         None => bail!("No active session"),
     };
 
     db.end_session(session.id, notes)?;
-    println!("Session #{} ended.", session.id);
+    info(format_args!("Session #{} ended.", session.id));
+    if stop_timer {
+        if let Some((issue_id, _)) = db.get_active_timer()? {
+            db.stop_timer(issue_id)?;
+            info(format_args!("Stopped timer for #{}", issue_id));
+        }
+    }
     if notes.is_some() {
-        println!("Handoff notes saved.");
+        info("Handoff notes saved.");
     }
     Ok(())
 }
@@ -82,10 +93,18 @@ pub fn status(db: &Database) -> Result<()> {
     }
 
     println!("Duration: {} minutes", minutes);
+
+    let tested = match db.get_last_tested()? {
+        Some(at) => humanize_age(at, Utc::now()),
+        None => "never".to_string(),
+    };
+    println!("Tests last run: {}", tested);
     Ok(())
 }
 
-pub fn work(db: &Database, issue_id: i64) -> Result<()> {
+/// Set the issue being worked on. With `timer`, also start timing it,
+/// stopping whatever timer was running for another issue.
+pub fn work(db: &Database, issue_id: i64, timer: bool) -> Result<()> {
     let session = match db.get_current_session()? {
         Some(s) => s,
         None => bail!("No active session. Use 'chainlink session start' first."),
@@ -97,22 +116,41 @@ pub fn work(db: &Database, issue_id: i64) -> Result<()> {
     };
 
     db.set_session_issue(session.id, issue_id)?;
-    println!("Now working on: #{} {}", issue.id, issue.title);
+    info(format_args!(
+        "Now working on: #{} {}",
+        issue.id, issue.title
+    ));
+
+    if timer {
+        match db.get_active_timer()? {
+            Some((active_id, _)) if active_id == issue_id => {
+                info(format_args!("Timer already running for #{}", issue_id));
+                return Ok(());
+            }
+            Some((active_id, _)) => {
+                db.stop_timer(active_id)?;
+                info(format_args!("Stopped timer for #{}", active_id));
+            }
+            None => {}
+        }
+        db.start_timer(issue_id)?;
+        info(format_args!("Started timer for #{}", issue_id));
+    }
     Ok(())
 }
 
-pub fn action(db: &Database, text: &str) -> Result<()> {
+pub fn action(db: &Database, text: &str, author: Option<&str>) -> Result<()> {
     let session = match db.get_current_session()? {
         Some(s) => s,
         None => bail!("No active session. Use 'chainlink session start' first."),
     };
 
     db.set_session_action(session.id, text)?;
-    println!("Action recorded: {}", text);
+    info(format_args!("Action recorded: {}", text));
 
     // Auto-comment on the active issue if one is set
     if let Some(issue_id) = session.active_issue_id {
-        db.add_comment(issue_id, &format!("[action] {}", text))?;
+        db.add_comment_by(issue_id, &format!("[action] {}", text), author)?;
     }
 
     Ok(())
@@ -184,7 +222,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         start(&db).unwrap();
-        let result = end(&db, None);
+        let result = end(&db, None, true);
         assert!(result.is_ok());
 
         let session = db.get_current_session().unwrap();
@@ -196,7 +234,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         start(&db).unwrap();
-        let result = end(&db, Some("Completed auth feature"));
+        let result = end(&db, Some("Completed auth feature"), true);
         assert!(result.is_ok());
 
         let last = db.get_last_session().unwrap().unwrap();
@@ -210,7 +248,7 @@ mod tests {
     fn test_end_no_active_session() {
         let (db, _dir) = setup_test_db();
 
-        let result = end(&db, None);
+        let result = end(&db, None, true);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -243,7 +281,7 @@ mod tests {
 
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
         start(&db).unwrap();
-        work(&db, issue_id).unwrap();
+        work(&db, issue_id, false).unwrap();
 
         let result = status(&db);
         assert!(result.is_ok());
@@ -258,7 +296,7 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
         start(&db).unwrap();
 
-        let result = work(&db, issue_id);
+        let result = work(&db, issue_id, false);
         assert!(result.is_ok());
 
         let session = db.get_current_session().unwrap().unwrap();
@@ -271,7 +309,7 @@ mod tests {
 
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = work(&db, issue_id);
+        let result = work(&db, issue_id, false);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -285,7 +323,7 @@ mod tests {
 
         start(&db).unwrap();
 
-        let result = work(&db, 99999);
+        let result = work(&db, 99999, false);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -298,15 +336,49 @@ mod tests {
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
         start(&db).unwrap();
 
-        work(&db, issue1).unwrap();
+        work(&db, issue1, false).unwrap();
         let session = db.get_current_session().unwrap().unwrap();
         assert_eq!(session.active_issue_id, Some(issue1));
 
-        work(&db, issue2).unwrap();
+        work(&db, issue2, false).unwrap();
         let session = db.get_current_session().unwrap().unwrap();
         assert_eq!(session.active_issue_id, Some(issue2));
     }
 
+    #[test]
+    fn test_work_with_timer_switches_running_timer() {
+        let (db, _dir) = setup_test_db();
+
+        let issue1 = db.create_issue("Issue 1", None, "medium").unwrap();
+        let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
+        start(&db).unwrap();
+
+        work(&db, issue1, true).unwrap();
+        assert_eq!(db.get_active_timer().unwrap().unwrap().0, issue1);
+        // Working on the same issue again keeps the timer going
+        work(&db, issue1, true).unwrap();
+        assert_eq!(db.get_time_entries(issue1).unwrap().len(), 1);
+
+        work(&db, issue2, true).unwrap();
+        assert_eq!(db.get_active_timer().unwrap().unwrap().0, issue2);
+        assert!(db.get_time_entries(issue1).unwrap()[0].ended_at.is_some());
+
+        end(&db, None, false).unwrap();
+        assert!(db.get_active_timer().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_end_stops_running_timer() {
+        let (db, _dir) = setup_test_db();
+
+        let issue = db.create_issue("Issue", None, "medium").unwrap();
+        start(&db).unwrap();
+        work(&db, issue, true).unwrap();
+
+        end(&db, None, true).unwrap();
+        assert!(db.get_active_timer().unwrap().is_none());
+    }
+
     // ==================== Last Handoff Tests ====================
 
     #[test]
@@ -323,7 +395,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         start(&db).unwrap();
-        end(&db, None).unwrap();
+        end(&db, None, true).unwrap();
 
         let result = last_handoff(&db);
         assert!(result.is_ok());
@@ -335,7 +407,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         start(&db).unwrap();
-        end(&db, Some("Important handoff notes")).unwrap();
+        end(&db, Some("Important handoff notes"), true).unwrap();
 
         let result = last_handoff(&db);
         assert!(result.is_ok());
@@ -359,13 +431,13 @@ mod tests {
 
         // Create and work on issue
         let issue_id = db.create_issue("Feature", None, "high").unwrap();
-        work(&db, issue_id).unwrap();
+        work(&db, issue_id, false).unwrap();
 
         // Check status
         status(&db).unwrap();
 
         // End with notes
-        end(&db, Some("Made progress on feature")).unwrap();
+        end(&db, Some("Made progress on feature"), true).unwrap();
         assert!(db.get_current_session().unwrap().is_none());
 
         // Start new session
@@ -387,7 +459,7 @@ mod tests {
             for _ in 0..iterations {
                 start(&db).unwrap();
                 prop_assert!(db.get_current_session().unwrap().is_some());
-                end(&db, None).unwrap();
+                end(&db, None, true).unwrap();
                 prop_assert!(db.get_current_session().unwrap().is_none());
             }
         }
@@ -397,7 +469,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
 
             start(&db).unwrap();
-            end(&db, Some(&notes)).unwrap();
+            end(&db, Some(&notes), true).unwrap();
 
             let last = db.get_last_session().unwrap().unwrap();
             prop_assert_eq!(last.handoff_notes, Some(notes));
@@ -408,7 +480,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
 
             start(&db).unwrap();
-            let result = work(&db, issue_id);
+            let result = work(&db, issue_id, false);
             prop_assert!(result.is_err());
         }
     }
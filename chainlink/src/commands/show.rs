@@ -1,44 +1,215 @@
 use anyhow::{bail, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use schemars::JsonSchema;
 use serde::Serialize;
 use serde_json;
+use std::collections::HashMap;
 
-use crate::db::Database;
+use crate::commands::{attach, cpitd};
+use crate::models::{Attachment, Comment, Issue, Milestone};
+use crate::store::Store;
+use crate::utils::{format_duration_secs, format_timestamp, humanize_age, wrap};
 
-#[derive(Serialize)]
-struct IssueDetail {
+/// The `show --json` payload.
+#[derive(Serialize, JsonSchema)]
+pub struct IssueDetail {
     #[serde(flatten)]
-    issue: crate::models::Issue,
+    issue: Issue,
     labels: Vec<String>,
-    milestone: Option<crate::models::Milestone>,
+    milestone: Option<Milestone>,
+    milestones: Vec<Milestone>,
+    /// How the issue was resolved, if it was closed with one
+    resolution: Option<String>,
     comments: Vec<crate::models::Comment>,
     blocked_by: Vec<i64>,
     blocking: Vec<i64>,
-    subissues: Vec<crate::models::Issue>,
-    related: Vec<crate::models::Issue>,
+    subissues: Vec<Issue>,
+    related: Vec<Issue>,
+    /// Notes on relations, keyed by the related issue's id
+    relation_notes: HashMap<i64, String>,
+    attachments: Vec<Attachment>,
+    /// Seconds since creation, for issues that are not closed
+    age_seconds: Option<i64>,
+    /// Seconds from creation to the latest close
+    resolution_seconds: Option<i64>,
 }
 
-pub fn run_json(db: &Database, id: i64) -> Result<()> {
+/// (age, resolution time) in seconds; exactly one is set.
+fn timing(issue: &Issue, now: DateTime<Utc>) -> (Option<i64>, Option<i64>) {
+    match issue.closed_at {
+        Some(closed) => (None, Some((closed - issue.created_at).num_seconds())),
+        None => (Some((now - issue.created_at).num_seconds()), None),
+    }
+}
+
+pub fn run_json(db: &dyn Store, id: i64) -> Result<()> {
     let issue = match db.get_issue(id)? {
         Some(i) => i,
         None => bail!("Issue #{} not found", id),
     };
 
+    let (age_seconds, resolution_seconds) = timing(&issue, Utc::now());
+    let milestones = db.get_issue_milestones(id)?;
     let detail = IssueDetail {
         issue,
         labels: db.get_labels(id)?,
-        milestone: db.get_issue_milestone(id)?,
-        comments: db.get_comments(id)?,
+        milestone: milestones.first().cloned(),
+        milestones,
+        resolution: db.get_resolution(id)?,
+        comments: db.get_comments(id, None)?,
         blocked_by: db.get_blockers(id)?,
         blocking: db.get_blocking(id)?,
         subissues: db.get_subissues(id)?,
         related: db.get_related_issues(id)?,
+        relation_notes: db.relation_notes(id)?,
+        attachments: db.get_attachments(id)?,
+        age_seconds,
+        resolution_seconds,
     };
 
     println!("{}", serde_json::to_string_pretty(&detail)?);
     Ok(())
 }
 
-pub fn run(db: &Database, id: i64) -> Result<()> {
+fn format_comment(comment: &Comment) -> String {
+    let author = comment
+        .author
+        .as_ref()
+        .map(|a| format!(" {}:", a))
+        .unwrap_or_default();
+    format!(
+        "  [{}]{} {}",
+        comment.created_at.format("%Y-%m-%d %H:%M"),
+        author,
+        comment.content
+    )
+}
+
+/// One comment under its day header: the time of day with `absolute`, else
+/// how long ago it was written.
+fn format_dated_comment(comment: &Comment, absolute: bool, now: DateTime<Utc>) -> String {
+    let author = comment
+        .author
+        .as_ref()
+        .map(|a| format!(" {}:", a))
+        .unwrap_or_default();
+    let when = if absolute {
+        comment.created_at.format("%H:%M").to_string()
+    } else {
+        humanize_age(comment.created_at, now)
+    };
+    format!("  [{}]{} {}", when, author, comment.content)
+}
+
+fn day_header(day: NaiveDate) -> String {
+    format!("  ── {} ──", day.format("%Y-%m-%d"))
+}
+
+/// Render comments (oldest first) with a date header before the first comment of
+/// each day.
+fn group_by_day(comments: &[&Comment], absolute: bool, now: DateTime<Utc>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current_day = None;
+    for comment in comments {
+        let day = comment.created_at.date_naive();
+        if current_day != Some(day) {
+            lines.push(day_header(day));
+            current_day = Some(day);
+        }
+        lines.push(format_dated_comment(comment, absolute, now));
+    }
+    lines
+}
+
+fn earlier_line(earlier: usize) -> String {
+    format!(
+        "  … {} earlier comment{}",
+        earlier,
+        if earlier == 1 { "" } else { "s" }
+    )
+}
+
+/// Render the pinned comments, oldest first.
+fn pinned_lines(db: &dyn Store, id: i64) -> Result<Vec<String>> {
+    Ok(db
+        .get_comments(id, None)?
+        .iter()
+        .filter(|c| c.pinned)
+        .map(format_comment)
+        .collect())
+}
+
+/// Render the comments section grouped by day, keeping only the most recent `limit`
+/// comments if given, and only those by `author` if given. Pinned comments are left
+/// out; they get their own section.
+fn comment_lines(
+    db: &dyn Store,
+    id: i64,
+    limit: Option<usize>,
+    author: Option<&str>,
+    absolute: bool,
+) -> Result<Vec<String>> {
+    let now = Utc::now();
+    if let Some(author) = author {
+        let by_author: Vec<Comment> = db
+            .get_comments(id, None)?
+            .into_iter()
+            .filter(|c| !c.pinned && c.author.as_deref() == Some(author))
+            .collect();
+        let earlier = limit.map_or(0, |n| by_author.len().saturating_sub(n));
+        let mut lines = Vec::new();
+        if earlier > 0 {
+            lines.push(earlier_line(earlier));
+        }
+        let shown: Vec<&Comment> = by_author[earlier..].iter().collect();
+        lines.extend(group_by_day(&shown, absolute, now));
+        return Ok(lines);
+    }
+
+    let comments = db.get_comments(id, limit)?;
+    let mut lines = Vec::new();
+    if comments.is_empty() {
+        return Ok(lines);
+    }
+
+    let mut earlier = db.count_comments(id)? - comments.len();
+    if earlier > 0 {
+        // Pinned comments outside the window are already shown under "Pinned:"
+        earlier -= db
+            .get_comments(id, None)?
+            .iter()
+            .filter(|c| c.pinned && !comments.iter().any(|shown| shown.id == c.id))
+            .count();
+    }
+    if earlier > 0 {
+        lines.push(earlier_line(earlier));
+    }
+    let shown: Vec<&Comment> = comments.iter().filter(|c| !c.pinned).collect();
+    lines.extend(group_by_day(&shown, absolute, now));
+    Ok(lines)
+}
+
+/// Summarise milestone memberships on one line, so it's clear what a delete would touch.
+fn milestone_line(milestones: &[Milestone]) -> Option<String> {
+    if milestones.is_empty() {
+        return None;
+    }
+    let names: Vec<String> = milestones
+        .iter()
+        .map(|m| format!("#{} {}", m.id, m.name))
+        .collect();
+    Some(format!("Milestones: {}", names.join(", ")))
+}
+
+/// With `wrap_width`, the description is wrapped to fit that many columns.
+pub fn run(
+    db: &dyn Store,
+    id: i64,
+    absolute: bool,
+    comment_limit: Option<usize>,
+    wrap_width: Option<usize>,
+    comment_author: Option<&str>,
+) -> Result<()> {
     let issue = match db.get_issue(id)? {
         Some(i) => i,
         None => bail!("Issue #{} not found", id),
@@ -47,14 +218,39 @@ pub fn run(db: &Database, id: i64) -> Result<()> {
     println!("Issue #{}: {}", issue.id, issue.title);
     println!("Status: {}", issue.status);
     println!("Priority: {}", issue.priority);
+    println!("Source: {}", issue.source);
     if let Some(parent_id) = issue.parent_id {
         println!("Parent: #{}", parent_id);
     }
-    println!("Created: {}", issue.created_at.format("%Y-%m-%d %H:%M:%S"));
-    println!("Updated: {}", issue.updated_at.format("%Y-%m-%d %H:%M:%S"));
+    if let Some(due) = issue.due_at {
+        println!("Due: {}", due.format("%Y-%m-%d"));
+    }
+    if let Some(recurrence) = db.get_recurrence(id)? {
+        println!("Repeats: {}", recurrence);
+    }
+    const TIMESTAMP_FMT: &str = "%Y-%m-%d %H:%M:%S";
+    println!(
+        "Created: {}",
+        format_timestamp(issue.created_at, absolute, TIMESTAMP_FMT)
+    );
+    println!(
+        "Updated: {}",
+        format_timestamp(issue.updated_at, absolute, TIMESTAMP_FMT)
+    );
 
     if let Some(closed) = issue.closed_at {
-        println!("Closed: {}", closed.format("%Y-%m-%d %H:%M:%S"));
+        println!(
+            "Closed: {}",
+            format_timestamp(closed, absolute, TIMESTAMP_FMT)
+        );
+        if let Some(resolution) = db.get_resolution(id)? {
+            println!("Resolution: {}", resolution);
+        }
+    }
+    match timing(&issue, Utc::now()) {
+        (_, Some(secs)) => println!("Time to close: {}", format_duration_secs(secs)),
+        (Some(secs), _) => println!("Age: {}", format_duration_secs(secs)),
+        (None, None) => {}
     }
 
     // Labels
@@ -63,31 +259,45 @@ pub fn run(db: &Database, id: i64) -> Result<()> {
         println!("Labels: {}", labels.join(", "));
     }
 
-    // Milestone
-    if let Some(milestone) = db.get_issue_milestone(id)? {
-        println!("Milestone: #{} {}", milestone.id, milestone.name);
+    // Milestones
+    if let Some(line) = milestone_line(&db.get_issue_milestones(id)?) {
+        println!("{}", line);
     }
 
-    // Description
+    // Description; clone-detection issues carry a dedup marker that's noise here
     if let Some(desc) = &issue.description {
+        let desc = match cpitd::marker_files(desc) {
+            Some((file_a, file_b)) => {
+                println!("Clone detection issue (files: {}, {})", file_a, file_b);
+                cpitd::strip_marker(desc)
+            }
+            None => desc.clone(),
+        };
         if !desc.is_empty() {
             println!("\nDescription:");
-            for line in desc.lines() {
+            let lines = match wrap_width {
+                Some(width) => wrap(&desc, width.saturating_sub(2)),
+                None => desc.lines().map(str::to_string).collect(),
+            };
+            for line in lines {
                 println!("  {}", line);
             }
         }
     }
 
     // Comments
-    let comments = db.get_comments(id)?;
+    let pinned = pinned_lines(db, id)?;
+    if !pinned.is_empty() {
+        println!("\nPinned:");
+        for line in pinned {
+            println!("{}", line);
+        }
+    }
+    let comments = comment_lines(db, id, comment_limit, comment_author, absolute)?;
     if !comments.is_empty() {
         println!("\nComments:");
-        for comment in comments {
-            println!(
-                "  [{}] {}",
-                comment.created_at.format("%Y-%m-%d %H:%M"),
-                comment.content
-            );
+        for line in comments {
+            println!("{}", line);
         }
     }
 
@@ -125,23 +335,41 @@ pub fn run(db: &Database, id: i64) -> Result<()> {
     // Related issues
     let related = db.get_related_issues(id)?;
     if !related.is_empty() {
-        println!("\nRelated:");
+        let notes = db.relation_notes(id)?;
+        println!("\nRelated ({}):", db.count_relations(id)?);
         for rel in related {
             let status_marker = if rel.status == "closed" { "✓" } else { " " };
+            let note = notes
+                .get(&rel.id)
+                .map(|n| format!(" — {}", n))
+                .unwrap_or_default();
             println!(
-                "  #{} [{}] {} - {}",
-                rel.id, status_marker, rel.priority, rel.title
+                "  #{} [{}] {} - {}{}",
+                rel.id, status_marker, rel.priority, rel.title, note
             );
         }
     }
 
+    let attachments = db.get_attachments(id)?;
+    if !attachments.is_empty() {
+        println!("\nAttachments:");
+        for attachment in &attachments {
+            println!("{}", attach::format_attachment(attachment));
+        }
+    }
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::{Database, IssueOrder, PriorityFilter};
+    use crate::models::Session;
+    use chrono::TimeZone;
     use proptest::prelude::*;
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
     use tempfile::tempdir;
 
     fn setup_test_db() -> (Database, tempfile::TempDir) {
@@ -151,6 +379,56 @@ mod tests {
         (db, dir)
     }
 
+    /// `comment_lines` without the day headers.
+    fn comment_entries(
+        db: &dyn Store,
+        id: i64,
+        limit: Option<usize>,
+        author: Option<&str>,
+    ) -> Result<Vec<String>> {
+        Ok(comment_lines(db, id, limit, author, true)?
+            .into_iter()
+            .filter(|line| !line.starts_with("  ──"))
+            .collect())
+    }
+
+    fn comment_at(content: &str, created_at: DateTime<Utc>) -> Comment {
+        Comment {
+            id: 0,
+            issue_id: 1,
+            content: content.to_string(),
+            created_at,
+            pinned: false,
+            author: None,
+        }
+    }
+
+    #[test]
+    fn test_comments_grouped_under_day_headers() {
+        let day = |d: u32, h: u32| Utc.with_ymd_and_hms(2024, 1, d, h, 0, 0).unwrap();
+        let first = comment_at("Found it", day(5, 9));
+        let second = comment_at("Fixed it", day(5, 17));
+        let third = comment_at("Verified", day(6, 10));
+        let now = day(8, 10);
+
+        let lines = group_by_day(&[&first, &second, &third], false, now);
+        assert_eq!(
+            lines,
+            vec![
+                "  ── 2024-01-05 ──",
+                "  [3 days ago] Found it",
+                "  [2 days ago] Fixed it",
+                "  ── 2024-01-06 ──",
+                "  [2 days ago] Verified",
+            ]
+        );
+        let headers = lines.iter().filter(|l| l.starts_with("  ──")).count();
+        assert_eq!(headers, 2);
+
+        let lines = group_by_day(&[&first], true, now);
+        assert_eq!(lines[1], "  [09:00] Found it");
+    }
+
     // ==================== Unit Tests ====================
 
     #[test]
@@ -158,7 +436,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.title, "Test issue");
         assert_eq!(issue.priority, "medium");
@@ -169,7 +447,7 @@ mod tests {
     fn test_show_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = run(&db, 99999);
+        let result = run(&db, 99999, false, None, None, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -181,7 +459,7 @@ mod tests {
             .create_issue("Test issue", Some("A detailed description"), "high")
             .unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(
             issue.description,
@@ -196,7 +474,7 @@ mod tests {
         db.add_label(issue_id, "bug").unwrap();
         db.add_label(issue_id, "urgent").unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let labels = db.get_labels(issue_id).unwrap();
         assert_eq!(labels.len(), 2);
         assert!(labels.contains(&"bug".to_string()));
@@ -210,13 +488,87 @@ mod tests {
         db.add_comment(issue_id, "First comment").unwrap();
         db.add_comment(issue_id, "Second comment").unwrap();
 
-        run(&db, issue_id).unwrap();
-        let comments = db.get_comments(issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
+        let comments = db.get_comments(issue_id, None).unwrap();
         assert_eq!(comments.len(), 2);
         assert_eq!(comments[0].content, "First comment");
         assert_eq!(comments[1].content, "Second comment");
     }
 
+    #[test]
+    fn test_pinned_comment_listed_first_until_unpinned() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+        db.add_comment(issue_id, "First").unwrap();
+        let key = db.add_comment(issue_id, "Key finding").unwrap();
+        db.add_comment(issue_id, "Third").unwrap();
+
+        db.set_comment_pinned(issue_id, key, true).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
+        let pinned = pinned_lines(&db, issue_id).unwrap();
+        assert_eq!(pinned.len(), 1);
+        assert!(pinned[0].ends_with("Key finding"));
+        let rest = comment_entries(&db, issue_id, None, None).unwrap();
+        assert_eq!(rest.len(), 2);
+        assert!(rest[0].ends_with("First"));
+        assert!(rest[1].ends_with("Third"));
+
+        // A pinned comment outside the --comments window isn't counted as hidden
+        let rest = comment_entries(&db, issue_id, Some(1), None).unwrap();
+        assert_eq!(rest[0], "  … 1 earlier comment");
+
+        db.set_comment_pinned(issue_id, key, false).unwrap();
+        assert!(pinned_lines(&db, issue_id).unwrap().is_empty());
+        let rest = comment_entries(&db, issue_id, None, None).unwrap();
+        assert_eq!(rest.len(), 3);
+        assert!(rest[1].ends_with("Key finding"));
+    }
+
+    #[test]
+    fn test_comment_author_filter() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+        db.add_comment_by(issue_id, "One", Some("alice")).unwrap();
+        db.add_comment_by(issue_id, "Two", Some("bob")).unwrap();
+        db.add_comment_by(issue_id, "Three", Some("alice")).unwrap();
+
+        let lines = comment_entries(&db, issue_id, None, Some("alice")).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("alice: One"));
+        assert!(lines[1].ends_with("alice: Three"));
+
+        let lines = comment_entries(&db, issue_id, Some(1), Some("alice")).unwrap();
+        assert_eq!(
+            lines,
+            vec!["  … 1 earlier comment".to_string(), lines[1].clone()]
+        );
+        assert!(lines[1].ends_with("Three"));
+        assert!(comment_entries(&db, issue_id, None, Some("carol"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_show_comment_limit() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+        for n in 1..=5 {
+            db.add_comment(issue_id, &format!("Comment {}", n)).unwrap();
+        }
+
+        run(&db, issue_id, false, Some(2), None, None).unwrap();
+        let lines = comment_entries(&db, issue_id, Some(2), None).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "  … 3 earlier comments");
+        assert!(lines[1].ends_with("Comment 4"));
+        assert!(lines[2].ends_with("Comment 5"));
+
+        // Without a limit every comment is shown and there is no note
+        let lines = comment_entries(&db, issue_id, None, None).unwrap();
+        assert_eq!(lines.len(), 5);
+        assert!(!lines[0].contains("earlier"));
+    }
+
     #[test]
     fn test_show_issue_with_blockers() {
         let (db, _dir) = setup_test_db();
@@ -224,7 +576,7 @@ mod tests {
         let issue_id = db.create_issue("Blocked issue", None, "medium").unwrap();
         db.add_dependency(issue_id, blocker_id).unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let blockers = db.get_blockers(issue_id).unwrap();
         assert_eq!(blockers.len(), 1);
         assert!(blockers.contains(&blocker_id));
@@ -241,7 +593,7 @@ mod tests {
             .create_subissue(parent_id, "Child 2", None, "low")
             .unwrap();
 
-        run(&db, parent_id).unwrap();
+        run(&db, parent_id, false, None, None, None).unwrap();
         let subs = db.get_subissues(parent_id).unwrap();
         assert_eq!(subs.len(), 2);
         assert!(subs.iter().any(|s| s.id == c1 && s.title == "Child 1"));
@@ -256,7 +608,7 @@ mod tests {
             .create_subissue(parent_id, "Child", None, "medium")
             .unwrap();
 
-        run(&db, child_id).unwrap();
+        run(&db, child_id, false, None, None, None).unwrap();
         let child = db.get_issue(child_id).unwrap().unwrap();
         assert_eq!(child.parent_id, Some(parent_id));
     }
@@ -266,9 +618,9 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let issue2 = db.create_issue("Issue 2", None, "medium").unwrap();
-        db.add_relation(issue1, issue2).unwrap();
+        db.add_relation(issue1, issue2, None).unwrap();
 
-        run(&db, issue1).unwrap();
+        run(&db, issue1, false, None, None, None).unwrap();
         let related = db.get_related_issues(issue1).unwrap();
         assert_eq!(related.len(), 1);
         assert_eq!(related[0].id, issue2);
@@ -278,9 +630,9 @@ mod tests {
     fn test_show_closed_issue() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "closed");
         assert!(issue.closed_at.is_some());
@@ -293,10 +645,32 @@ mod tests {
         let milestone_id = db.create_milestone("v1.0", None).unwrap();
         db.add_issue_to_milestone(milestone_id, issue_id).unwrap();
 
-        run(&db, issue_id).unwrap();
-        let milestone = db.get_issue_milestone(issue_id).unwrap();
-        assert!(milestone.is_some());
-        assert_eq!(milestone.unwrap().name, "v1.0");
+        run(&db, issue_id, false, None, None, None).unwrap();
+        let milestones = db.get_issue_milestones(issue_id).unwrap();
+        assert_eq!(milestones.len(), 1);
+        assert_eq!(milestones[0].name, "v1.0");
+    }
+
+    #[test]
+    fn test_show_issue_in_two_milestones_lists_both() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
+        let first = db.create_milestone("v1.0", None).unwrap();
+        let second = db.create_milestone("v2.0", None).unwrap();
+        db.add_issue_to_milestone(first, issue_id).unwrap();
+        db.add_issue_to_milestone(second, issue_id).unwrap();
+
+        run(&db, issue_id, false, None, None, None).unwrap();
+        let milestones = db.get_issue_milestones(issue_id).unwrap();
+        assert_eq!(
+            milestone_line(&milestones).unwrap(),
+            format!("Milestones: #{} v1.0, #{} v2.0", first, second)
+        );
+    }
+
+    #[test]
+    fn test_milestone_line_empty() {
+        assert_eq!(milestone_line(&[]), None);
     }
 
     #[test]
@@ -308,7 +682,7 @@ mod tests {
         db.add_comment(issue_id, "评论 🎉").unwrap();
         db.add_label(issue_id, "バグ").unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.title, "测试问题 🐛");
         assert_eq!(issue.description, Some("描述 αβγ".to_string()));
@@ -322,7 +696,7 @@ mod tests {
         let desc = "Line 1\nLine 2\n\nLine 4 after blank";
         let issue_id = db.create_issue("Test", Some(desc), "medium").unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.description, Some(desc.to_string()));
     }
@@ -332,11 +706,292 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", Some(""), "medium").unwrap();
 
-        run(&db, issue_id).unwrap();
+        run(&db, issue_id, false, None, None, None).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.description, Some("".to_string()));
     }
 
+    #[test]
+    fn test_show_absolute_timestamps() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Test", None, "medium").unwrap();
+        db.close_issue(issue_id, None).unwrap();
+
+        assert!(run(&db, issue_id, true, None, None, None).is_ok());
+    }
+
+    #[test]
+    fn test_timing_open_and_closed() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Timed", None, "medium").unwrap();
+        let issue = db.get_issue(id).unwrap().unwrap();
+        let later = issue.created_at + chrono::Duration::hours(5);
+        assert_eq!(timing(&issue, later), (Some(5 * 3600), None));
+
+        let closed = Issue {
+            closed_at: Some(issue.created_at + chrono::Duration::minutes(90)),
+            status: "closed".to_string(),
+            ..issue
+        };
+        assert_eq!(timing(&closed, later), (None, Some(90 * 60)));
+    }
+
+    // ==================== Mock Store Tests ====================
+
+    /// In-memory `Store` with just enough behaviour to drive `show`.
+    #[derive(Default)]
+    struct MockStore {
+        issues: RefCell<Vec<Issue>>,
+        labels: RefCell<Vec<(i64, String)>>,
+        comments: Vec<Comment>,
+    }
+
+    impl MockStore {
+        fn issue(id: i64, title: &str, parent_id: Option<i64>) -> Issue {
+            let now = Utc::now();
+            Issue {
+                id,
+                title: title.to_string(),
+                description: None,
+                status: "open".to_string(),
+                priority: "medium".to_string(),
+                parent_id,
+                created_at: now,
+                updated_at: now,
+                closed_at: None,
+                source: "manual".to_string(),
+                due_at: None,
+            }
+        }
+
+        fn insert(&self, title: &str, parent_id: Option<i64>) -> i64 {
+            let mut issues = self.issues.borrow_mut();
+            let id = issues.len() as i64 + 1;
+            issues.push(Self::issue(id, title, parent_id));
+            id
+        }
+
+        fn set_status(&self, id: i64, status: &str) -> bool {
+            match self.issues.borrow_mut().iter_mut().find(|i| i.id == id) {
+                Some(issue) => {
+                    issue.status = status.to_string();
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    impl Store for MockStore {
+        fn create_issue(&self, title: &str, _: Option<&str>, _: &str) -> Result<i64> {
+            Ok(self.insert(title, None))
+        }
+
+        fn create_subissue(
+            &self,
+            parent_id: i64,
+            title: &str,
+            _: Option<&str>,
+            _: &str,
+        ) -> Result<i64> {
+            Ok(self.insert(title, Some(parent_id)))
+        }
+
+        fn get_issue(&self, id: i64) -> Result<Option<Issue>> {
+            Ok(self.issues.borrow().iter().find(|i| i.id == id).cloned())
+        }
+
+        fn list_issues(
+            &self,
+            _: Option<&str>,
+            _: Option<&str>,
+            _: Option<&str>,
+        ) -> Result<Vec<Issue>> {
+            Ok(self.issues.borrow().clone())
+        }
+
+        fn list_issues_excluding(
+            &self,
+            _: Option<&str>,
+            _: Option<&str>,
+            _: Option<PriorityFilter<'_>>,
+            _: &[String],
+            _: IssueOrder,
+        ) -> Result<Vec<Issue>> {
+            Ok(self.issues.borrow().clone())
+        }
+
+        fn find_similar_titles(&self, _: &str) -> Result<Vec<(i64, String)>> {
+            Ok(Vec::new())
+        }
+
+        fn atomically(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+            f()
+        }
+
+        fn close_issue_by(&self, id: i64, _: Option<&str>, _: Option<&str>) -> Result<bool> {
+            Ok(self.set_status(id, "closed"))
+        }
+
+        fn drop_issue_by(&self, id: i64, _: Option<&str>) -> Result<bool> {
+            Ok(self.set_status(id, "wontdo"))
+        }
+
+        fn reopen_issue(&self, id: i64) -> Result<bool> {
+            Ok(self.set_status(id, "open"))
+        }
+
+        fn get_resolution(&self, _: i64) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn set_due_date(&self, _: i64, _: Option<DateTime<Utc>>) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn set_recurrence(&self, _: i64, _: Option<&str>) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn get_recurrence(&self, _: i64) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn normalize_label(&self, label: &str) -> Result<String> {
+            crate::utils::normalize_label(label, Default::default())
+        }
+
+        fn add_label(&self, issue_id: i64, label: &str) -> Result<bool> {
+            self.labels.borrow_mut().push((issue_id, label.to_string()));
+            Ok(true)
+        }
+
+        fn get_labels(&self, issue_id: i64) -> Result<Vec<String>> {
+            Ok(self
+                .labels
+                .borrow()
+                .iter()
+                .filter(|(id, _)| *id == issue_id)
+                .map(|(_, l)| l.clone())
+                .collect())
+        }
+
+        fn get_comments(&self, issue_id: i64, limit: Option<usize>) -> Result<Vec<Comment>> {
+            let all: Vec<Comment> = self
+                .comments
+                .iter()
+                .filter(|c| c.issue_id == issue_id)
+                .cloned()
+                .collect();
+            let skip = limit.map_or(0, |n| all.len().saturating_sub(n));
+            Ok(all.into_iter().skip(skip).collect())
+        }
+
+        fn count_comments(&self, issue_id: i64) -> Result<usize> {
+            Ok(self
+                .comments
+                .iter()
+                .filter(|c| c.issue_id == issue_id)
+                .count())
+        }
+
+        fn get_subissues(&self, parent_id: i64) -> Result<Vec<Issue>> {
+            Ok(self
+                .issues
+                .borrow()
+                .iter()
+                .filter(|i| i.parent_id == Some(parent_id))
+                .cloned()
+                .collect())
+        }
+
+        fn get_blockers(&self, _: i64) -> Result<Vec<i64>> {
+            Ok(Vec::new())
+        }
+
+        fn get_blocking(&self, _: i64) -> Result<Vec<i64>> {
+            Ok(Vec::new())
+        }
+
+        fn blocked_ids(&self) -> Result<HashSet<i64>> {
+            Ok(HashSet::new())
+        }
+
+        fn parent_titles(&self) -> Result<HashMap<i64, String>> {
+            let issues = self.issues.borrow();
+            Ok(issues
+                .iter()
+                .filter(|p| issues.iter().any(|c| c.parent_id == Some(p.id)))
+                .map(|p| (p.id, p.title.clone()))
+                .collect())
+        }
+
+        fn get_related_issues(&self, _: i64) -> Result<Vec<Issue>> {
+            Ok(Vec::new())
+        }
+
+        fn relation_notes(&self, _: i64) -> Result<HashMap<i64, String>> {
+            Ok(HashMap::new())
+        }
+
+        fn get_attachments(&self, _: i64) -> Result<Vec<Attachment>> {
+            Ok(Vec::new())
+        }
+
+        fn count_relations(&self, _: i64) -> Result<i64> {
+            Ok(0)
+        }
+
+        fn get_issue_milestones(&self, _: i64) -> Result<Vec<Milestone>> {
+            Ok(Vec::new())
+        }
+
+        fn add_issue_to_milestone(&self, _: i64, _: i64) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn get_current_session(&self) -> Result<Option<Session>> {
+            Ok(None)
+        }
+
+        fn set_session_issue(&self, _: i64, _: i64) -> Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn test_show_against_mock_store() {
+        let mut store = MockStore::default();
+        let id = store.insert("Mock issue", None);
+        store.insert("Mock child", Some(id));
+        store.add_label(id, "bug").unwrap();
+        store.comments = (1..=3)
+            .map(|n| Comment {
+                id: n,
+                issue_id: id,
+                content: format!("note {}", n),
+                created_at: Utc::now(),
+                pinned: false,
+                author: None,
+            })
+            .collect();
+
+        run(&store, id, false, None, None, None).unwrap();
+        run_json(&store, id).unwrap();
+
+        let lines = comment_entries(&store, id, Some(1), None).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("2 earlier comments"));
+        assert!(lines[1].ends_with("note 3"));
+    }
+
+    #[test]
+    fn test_show_missing_issue_in_mock_store() {
+        let store = MockStore::default();
+        let err = run(&store, 42, false, None, None, None).unwrap_err();
+        assert!(err.to_string().contains("#42 not found"));
+    }
+
     // ==================== Property-Based Tests ====================
 
     proptest! {
@@ -344,14 +999,14 @@ mod tests {
         fn prop_show_never_panics(title in "[a-zA-Z0-9 ]{1,50}") {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
-            let result = run(&db, issue_id);
+            let result = run(&db, issue_id, false, None, None, None);
             prop_assert!(result.is_ok());
         }
 
         #[test]
         fn prop_show_nonexistent_always_fails(issue_id in 1000i64..10000) {
             let (db, _dir) = setup_test_db();
-            let result = run(&db, issue_id);
+            let result = run(&db, issue_id, false, None, None, None);
             prop_assert!(result.is_err());
         }
 
@@ -362,7 +1017,7 @@ mod tests {
         ) {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&title, Some(&desc), "medium").unwrap();
-            let result = run(&db, issue_id);
+            let result = run(&db, issue_id, false, None, None, None);
             prop_assert!(result.is_ok());
         }
 
@@ -372,7 +1027,7 @@ mod tests {
         ) {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
-            let result = run(&db, issue_id);
+            let result = run(&db, issue_id, false, None, None, None);
             prop_assert!(result.is_ok());
         }
     }
@@ -0,0 +1,144 @@
+use anyhow::Result;
+
+use crate::commands::next::priority_weight;
+use crate::db::Database;
+use crate::utils::format_duration_secs;
+
+/// (key, count) pairs in display order
+type Counts = Vec<(String, usize)>;
+
+/// Issue counts per status and open counts per priority (highest first).
+fn counts(db: &Database) -> Result<(Counts, Counts)> {
+    let issues = db.list_issues(Some("all"), None, None)?;
+
+    let mut by_status = Counts::new();
+    let mut by_priority = Counts::new();
+    for issue in &issues {
+        bump(&mut by_status, &issue.status);
+        if issue.status == "open" {
+            bump(&mut by_priority, &issue.priority);
+        }
+    }
+    by_status.sort();
+    by_priority.sort_by_key(|(p, _)| std::cmp::Reverse(priority_weight(p)));
+    Ok((by_status, by_priority))
+}
+
+fn bump(counts: &mut Counts, key: &str) {
+    match counts.iter_mut().find(|(k, _)| k == key) {
+        Some((_, n)) => *n += 1,
+        None => counts.push((key.to_string(), 1)),
+    }
+}
+
+pub fn run(db: &Database) -> Result<()> {
+    let (by_status, by_priority) = counts(db)?;
+
+    if by_status.is_empty() {
+        println!("No issues yet.");
+        return Ok(());
+    }
+
+    println!("Issues:");
+    for (status, n) in &by_status {
+        println!("  {:<10} {}", status, n);
+    }
+
+    if !by_priority.is_empty() {
+        println!("\nOpen by priority:");
+        for (priority, n) in &by_priority {
+            println!("  {:<10} {}", priority, n);
+        }
+    }
+
+    match db.avg_resolution_seconds()? {
+        Some(avg) => println!(
+            "\nAverage time to close: {}",
+            format_duration_secs(avg.round() as i64)
+        ),
+        None => println!("\nAverage time to close: (no closed issues)"),
+    }
+
+    let by_label = db.time_by_label()?;
+    if !by_label.is_empty() {
+        println!("\nTime tracked by label:");
+        for (label, secs) in &by_label {
+            println!("  {:<10} {}", label, format_duration_secs(*secs));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TimeEntry;
+    use chrono::{Duration, Utc};
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_run_empty() {
+        let (db, _dir) = setup_test_db();
+        run(&db).unwrap();
+    }
+
+    #[test]
+    fn test_counts() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("Low", None, "low").unwrap();
+        db.create_issue("Critical", None, "critical").unwrap();
+        db.create_issue("Another low", None, "low").unwrap();
+        let done = db.create_issue("Done", None, "high").unwrap();
+        db.close_issue(done, None).unwrap();
+
+        let (by_status, by_priority) = counts(&db).unwrap();
+        assert_eq!(
+            by_status,
+            vec![("closed".to_string(), 1), ("open".to_string(), 3)]
+        );
+        assert_eq!(
+            by_priority,
+            vec![("critical".to_string(), 1), ("low".to_string(), 2)]
+        );
+        run(&db).unwrap();
+    }
+
+    fn log_time(db: &Database, issue_id: i64, secs: i64) {
+        let ended = Utc::now();
+        db.insert_time_entry(&TimeEntry {
+            id: 0,
+            issue_id,
+            started_at: ended - Duration::seconds(secs),
+            ended_at: Some(ended),
+            duration_seconds: Some(secs),
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_time_by_label_counts_every_label() {
+        let (db, _dir) = setup_test_db();
+        let ui = db.create_issue("Button styling", None, "medium").unwrap();
+        db.add_label(ui, "frontend").unwrap();
+        db.add_label(ui, "bug").unwrap();
+        log_time(&db, ui, 3600);
+        let api = db.create_issue("Endpoint", None, "medium").unwrap();
+        db.add_label(api, "bug").unwrap();
+        log_time(&db, api, 1800);
+        let unlabeled = db.create_issue("Misc", None, "medium").unwrap();
+        log_time(&db, unlabeled, 600);
+
+        assert_eq!(
+            db.time_by_label().unwrap(),
+            vec![("bug".to_string(), 5400), ("frontend".to_string(), 3600)]
+        );
+        run(&db).unwrap();
+    }
+}
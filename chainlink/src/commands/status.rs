@@ -1,29 +1,118 @@
 use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
 use std::fs;
 use std::path::Path;
 
 use crate::db::Database;
+use crate::models::Issue;
+use crate::store::Store;
+use crate::utils::{info, Recurrence};
+
+/// Accepted values for `close --resolution`.
+pub const RESOLUTIONS: [&str; 4] = ["fixed", "wontfix", "duplicate", "invalid"];
+
+pub fn validate_resolution(resolution: &str) -> Result<()> {
+    if !RESOLUTIONS.contains(&resolution) {
+        bail!(
+            "Invalid resolution '{}'. Must be one of: {}",
+            resolution,
+            RESOLUTIONS.join(", ")
+        );
+    }
+    Ok(())
+}
 
-pub fn close(db: &Database, id: i64, update_changelog: bool, chainlink_dir: &Path) -> Result<()> {
-    close_inner(db, id, update_changelog, chainlink_dir, false)
+/// What `close` does when the issue still has open subissues.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpenSubissues {
+    /// Refuse to close and list the open subissues
+    #[default]
+    Refuse,
+    /// Close the parent and leave the subissues open
+    Ignore,
+    /// Close the open subissues as well
+    Cascade,
 }
 
-pub fn close_quiet(
-    db: &Database,
+/// How `close` treats subissues, the changelog and the closed issue's record.
+pub struct CloseOpts<'a> {
+    pub update_changelog: bool,
+    pub chainlink_dir: &'a Path,
+    pub subissues: OpenSubissues,
+    pub resolution: Option<&'a str>,
+    /// Recorded as the closer; resolved by the caller (see `utils::current_user`)
+    pub closed_by: Option<&'a str>,
+}
+
+/// Close one issue without recording a closer; a shorthand for tests.
+#[cfg(test)]
+pub fn close(
+    db: &dyn Store,
     id: i64,
     update_changelog: bool,
     chainlink_dir: &Path,
+    subissues: OpenSubissues,
 ) -> Result<()> {
-    close_inner(db, id, update_changelog, chainlink_dir, true)
+    let opts = CloseOpts {
+        update_changelog,
+        chainlink_dir,
+        subissues,
+        resolution: None,
+        closed_by: None,
+    };
+    close_inner(db, id, false, false, &opts)
 }
 
+/// Close several issues in one pass. A single id behaves exactly like `close`;
+/// with several ids, failures are reported as warnings and a summary is printed.
+pub fn close_many(db: &dyn Store, ids: &[i64], opts: &CloseOpts) -> Result<()> {
+    if let Some(resolution) = opts.resolution {
+        validate_resolution(resolution)?;
+    }
+
+    if let [id] = ids {
+        return close_inner(db, *id, false, false, opts);
+    }
+
+    let mut closed = Vec::new();
+    for &id in ids {
+        match close_inner(db, id, true, false, opts) {
+            Ok(()) => closed.push(format!("#{}", id)),
+            Err(e) => eprintln!("Warning: {}", e),
+        }
+    }
+
+    if closed.is_empty() {
+        bail!("No issues were closed");
+    }
+
+    info(format_args!(
+        "Closed {} issue(s): {}",
+        closed.len(),
+        closed.join(", ")
+    ));
+    Ok(())
+}
+
+/// `batch` leaves the per-issue messages out when `close_many` prints a summary
+/// instead; `cascaded` is set for subissues closed along with their parent.
 fn close_inner(
-    db: &Database,
+    db: &dyn Store,
     id: i64,
-    update_changelog: bool,
-    chainlink_dir: &Path,
-    quiet: bool,
+    batch: bool,
+    cascaded: bool,
+    opts: &CloseOpts,
 ) -> Result<()> {
+    let CloseOpts {
+        update_changelog,
+        chainlink_dir,
+        subissues,
+        resolution,
+        closed_by,
+    } = *opts;
+    // The resolution describes the issue that was asked for, not the
+    // subissues swept up with it.
+    let resolution = if cascaded { None } else { resolution };
     // Get issue details before closing
     let issue = db.get_issue(id)?;
     let issue = match issue {
@@ -32,12 +121,52 @@ fn close_inner(
     };
     let labels = db.get_labels(id)?;
 
-    if db.close_issue(id)? {
-        if !quiet {
-            println!("Closed issue #{}", id);
+    if subissues != OpenSubissues::Ignore {
+        let open: Vec<_> = db
+            .get_subissues(id)?
+            .into_iter()
+            .filter(|s| s.status == "open")
+            .collect();
+        if !open.is_empty() && subissues == OpenSubissues::Refuse {
+            let listing: Vec<String> = open
+                .iter()
+                .map(|s| format!("  #{} {}", s.id, s.title))
+                .collect();
+            bail!(
+                "Issue #{} has {} open subissue(s):\n{}\nUse --force to close it anyway or --cascade to close them too",
+                id,
+                open.len(),
+                listing.join("\n")
+            );
+        }
+        for sub in open {
+            close_inner(db, sub.id, batch, true, opts)?;
+        }
+    }
+
+    // A recurring chore comes back unless it was closed as not needed. A
+    // subissue closed with its parent doesn't: the copy would sit under a
+    // closed parent, so it keeps its recurrence for if it's reopened. The
+    // close is undone if the next occurrence can't be scheduled.
+    let mut next = None;
+    db.atomically(&mut || {
+        if !db.close_issue_by(id, resolution, closed_by)? {
+            bail!("Issue #{} not found", id);
+        }
+        if !cascaded && matches!(resolution, None | Some("fixed")) {
+            next = schedule_next_occurrence(db, &issue, &labels, Utc::now())?;
+        }
+        Ok(())
+    })?;
+    if !batch {
+        info(format_args!("Closed issue #{}", id));
+        if let Some((next_id, due)) = next {
+            info(format_args!(
+                "Created #{} for the next occurrence (due {})",
+                next_id,
+                due.format("%Y-%m-%d")
+            ));
         }
-    } else {
-        bail!("Issue #{} not found", id);
     }
 
     // Update changelog if requested
@@ -50,18 +179,29 @@ fn close_inner(
             if let Err(e) = create_changelog(&changelog_path) {
                 eprintln!("Warning: Could not create CHANGELOG.md: {}", e);
             } else {
-                println!("Created CHANGELOG.md");
+                info("Created CHANGELOG.md");
             }
         }
 
         if changelog_path.exists() {
-            let category = determine_changelog_category(&labels);
-            let entry = format!("- {} (#{})\n", issue.title, id);
-
-            if let Err(e) = append_to_changelog(&changelog_path, &category, &entry) {
-                eprintln!("Warning: Could not update CHANGELOG.md: {}", e);
-            } else if !quiet {
-                println!("Added to CHANGELOG.md under {}", category);
+            match determine_changelog_category(&labels, resolution) {
+                Some(category) => {
+                    let entry = format!("- {} (#{})\n", issue.title, id);
+
+                    if let Err(e) = append_to_changelog(&changelog_path, &category, &entry) {
+                        eprintln!("Warning: Could not update CHANGELOG.md: {}", e);
+                    } else if !batch {
+                        info(format_args!("Added to CHANGELOG.md under {}", category));
+                    }
+                }
+                None => {
+                    if !batch {
+                        info(format_args!(
+                            "Not added to CHANGELOG.md (resolved as {})",
+                            resolution.unwrap_or_default()
+                        ));
+                    }
+                }
             }
         }
     }
@@ -69,6 +209,41 @@ fn close_inner(
     Ok(())
 }
 
+/// If `issue` recurs, open a fresh copy due one interval after its due date (or
+/// after `now` if it had none), with the same labels and in the same open
+/// milestones. The recurrence moves to the copy, so reopening and closing the
+/// old issue doesn't schedule it twice.
+fn schedule_next_occurrence(
+    db: &dyn Store,
+    issue: &Issue,
+    labels: &[String],
+    now: DateTime<Utc>,
+) -> Result<Option<(i64, DateTime<Utc>)>> {
+    let Some(spec) = db.get_recurrence(issue.id)? else {
+        return Ok(None);
+    };
+    let recurrence: Recurrence = spec.parse()?;
+    let due = recurrence.next_due(issue.due_at.unwrap_or(now))?;
+
+    let description = issue.description.as_deref();
+    let next_id = match issue.parent_id {
+        Some(parent) => db.create_subissue(parent, &issue.title, description, &issue.priority)?,
+        None => db.create_issue(&issue.title, description, &issue.priority)?,
+    };
+    for label in labels {
+        db.add_label(next_id, label)?;
+    }
+    for milestone in db.get_issue_milestones(issue.id)? {
+        if milestone.status == "open" {
+            db.add_issue_to_milestone(milestone.id, next_id)?;
+        }
+    }
+    db.set_due_date(next_id, Some(due))?;
+    db.set_recurrence(next_id, Some(&spec))?;
+    db.set_recurrence(issue.id, None)?;
+    Ok(Some((next_id, due)))
+}
+
 fn create_changelog(path: &Path) -> Result<()> {
     let template = r#"# Changelog
 
@@ -88,7 +263,16 @@ The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.0.0/).
     Ok(())
 }
 
-fn determine_changelog_category(labels: &[String]) -> String {
+/// The changelog heading for a closed issue, or None when its resolution
+/// means nothing actually changed (wontfix, duplicate, invalid).
+fn determine_changelog_category(labels: &[String], resolution: Option<&str>) -> Option<String> {
+    match resolution {
+        None | Some("fixed") => Some(category_from_labels(labels)),
+        Some(_) => None,
+    }
+}
+
+fn category_from_labels(labels: &[String]) -> String {
     for label in labels {
         match label.to_lowercase().as_str() {
             "bug" | "fix" | "bugfix" => return "Fixed".to_string(),
@@ -147,43 +331,89 @@ fn append_to_changelog(path: &Path, category: &str, entry: &str) -> Result<()> {
 }
 
 pub fn close_all(
-    db: &Database,
+    db: &dyn Store,
     label_filter: Option<&str>,
     priority_filter: Option<&str>,
     update_changelog: bool,
     chainlink_dir: &Path,
+    closed_by: Option<&str>,
 ) -> Result<()> {
     let issues = db.list_issues(Some("open"), label_filter, priority_filter)?;
 
     if issues.is_empty() {
-        println!("No matching open issues found.");
+        info("No matching open issues found.");
         return Ok(());
     }
 
+    // Every matching issue is being closed anyway, so open subissues don't block their parent
+    let opts = CloseOpts {
+        update_changelog,
+        chainlink_dir,
+        subissues: OpenSubissues::Ignore,
+        resolution: None,
+        closed_by,
+    };
     let mut closed_count = 0;
     for issue in &issues {
-        match close(db, issue.id, update_changelog, chainlink_dir) {
+        match close_inner(db, issue.id, false, false, &opts) {
             Ok(()) => closed_count += 1,
             Err(e) => eprintln!("Warning: Failed to close #{}: {}", issue.id, e),
         }
     }
 
-    println!("Closed {} issue(s).", closed_count);
+    info(format_args!("Closed {} issue(s).", closed_count));
+    Ok(())
+}
+
+/// Mark issues as won't do. They leave the open list like closed issues, but
+/// never count as resolved and are never added to the changelog.
+pub fn drop_issues(db: &dyn Store, ids: &[i64], closed_by: Option<&str>) -> Result<()> {
+    for &id in ids {
+        if db.drop_issue_by(id, closed_by)? {
+            info(format_args!("Dropped issue #{} (won't do)", id));
+        } else {
+            bail!("Issue #{} not found", id);
+        }
+    }
     Ok(())
 }
 
-pub fn reopen(db: &Database, id: i64) -> Result<()> {
+pub fn reopen(db: &dyn Store, id: i64) -> Result<()> {
     if db.reopen_issue(id)? {
-        println!("Reopened issue #{}", id);
+        info(format_args!("Reopened issue #{}", id));
     } else {
         bail!("Issue #{} not found", id);
     }
     Ok(())
 }
 
+/// Reopen an issue and every closed subissue below it, at any depth, all or
+/// nothing. Open, won't-do and archived subissues are left as they are.
+pub fn reopen_with_subissues(db: &Database, id: i64) -> Result<()> {
+    db.require_issue(id)?;
+    let reopened = db.transaction(|| {
+        db.reopen_issue(id)?;
+        let mut reopened = 0;
+        let mut pending = db.get_subissues(id)?;
+        while let Some(sub) = pending.pop() {
+            pending.extend(db.get_subissues(sub.id)?);
+            if sub.status == "closed" && db.reopen_issue(sub.id)? {
+                reopened += 1;
+            }
+        }
+        Ok(reopened)
+    })?;
+    info(format_args!(
+        "Reopened issue #{} and {} subissue(s)",
+        id, reopened
+    ));
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::Database;
     use proptest::prelude::*;
     use tempfile::tempdir;
 
@@ -194,6 +424,22 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_drop_issues_marks_wontdo() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("Abandon me", None, "low").unwrap();
+        let b = db.create_issue("And me", None, "low").unwrap();
+        let keep = db.create_issue("Keep me", None, "low").unwrap();
+
+        drop_issues(&db, &[a, b], None).unwrap();
+
+        let open = db.list_issues(Some("open"), None, None).unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, keep);
+        assert_eq!(db.get_issue(a).unwrap().unwrap().status, "wontdo");
+        assert!(drop_issues(&db, &[99999], None).is_err());
+    }
+
     // ==================== Close Tests ====================
 
     #[test]
@@ -204,7 +450,7 @@ mod tests {
 
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let result = close(&db, issue_id, false, &chainlink_dir);
+        let result = close(&db, issue_id, false, &chainlink_dir, OpenSubissues::Refuse);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -212,13 +458,220 @@ mod tests {
         assert!(issue.closed_at.is_some());
     }
 
+    #[test]
+    fn test_close_weekly_recurring_issue_creates_next() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let id = db.create_issue("Rotate logs", None, "low").unwrap();
+        db.add_label(id, "chore").unwrap();
+        db.set_recurrence(id, Some("weekly")).unwrap();
+
+        let before = Utc::now();
+        close(&db, id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
+
+        let open = db.list_issues(Some("open"), None, None).unwrap();
+        assert_eq!(open.len(), 1);
+        let next = &open[0];
+        assert_ne!(next.id, id);
+        assert_eq!(next.title, "Rotate logs");
+        assert_eq!(next.priority, "low");
+        assert_eq!(db.get_labels(next.id).unwrap(), vec!["chore"]);
+        assert_eq!(
+            db.get_recurrence(next.id).unwrap().as_deref(),
+            Some("weekly")
+        );
+        assert_eq!(db.get_recurrence(id).unwrap(), None);
+        let days = (next.due_at.unwrap() - before).num_hours() as f64 / 24.0;
+        assert!((days - 7.0).abs() < 0.1, "due {} days out", days);
+    }
+
+    #[test]
+    fn test_recurring_issue_advances_from_its_due_date() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Pay invoice", None, "medium").unwrap();
+        db.set_recurrence(id, Some("monthly")).unwrap();
+        let due = DateTime::parse_from_rfc3339("2026-03-15T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        db.set_due_date(id, Some(due)).unwrap();
+
+        let issue = db.get_issue(id).unwrap().unwrap();
+        let (next_id, next_due) = schedule_next_occurrence(&db, &issue, &[], Utc::now())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next_due.to_rfc3339(), "2026-04-15T00:00:00+00:00");
+        assert_eq!(
+            db.get_issue(next_id).unwrap().unwrap().due_at,
+            Some(next_due)
+        );
+    }
+
+    #[test]
+    fn test_close_fails_cleanly_when_next_occurrence_is_out_of_range() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let id = db.create_issue("End of time", None, "low").unwrap();
+        db.set_recurrence(id, Some("999999999d")).unwrap();
+
+        let err = close(&db, id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+        let open = db.list_issues(Some("open"), None, None).unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, id);
+        assert_eq!(
+            db.get_recurrence(id).unwrap().as_deref(),
+            Some("999999999d")
+        );
+    }
+
+    #[test]
+    fn test_close_non_recurring_issue_creates_nothing() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let id = db.create_issue("One-off", None, "medium").unwrap();
+
+        close(&db, id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
+        assert!(db.list_issues(Some("open"), None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cascaded_close_does_not_schedule_recurring_subissue() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let parent = db.create_issue("Quarterly ops", None, "medium").unwrap();
+        let child = db
+            .create_subissue(parent, "Rotate logs", None, "low")
+            .unwrap();
+        db.set_recurrence(child, Some("weekly")).unwrap();
+
+        close(&db, parent, false, &chainlink_dir, OpenSubissues::Cascade).unwrap();
+        assert!(db.list_issues(Some("open"), None, None).unwrap().is_empty());
+        assert_eq!(db.get_recurrence(child).unwrap().as_deref(), Some("weekly"));
+    }
+
+    #[test]
+    fn test_next_occurrence_joins_open_milestones() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let open_ms = db.create_milestone("v1.0", None).unwrap();
+        let closed_ms = db.create_milestone("v0.9", None).unwrap();
+        let id = db.create_issue("Rotate logs", None, "low").unwrap();
+        db.set_recurrence(id, Some("weekly")).unwrap();
+        db.add_issue_to_milestone(open_ms, id).unwrap();
+        db.add_issue_to_milestone(closed_ms, id).unwrap();
+        db.close_milestone(closed_ms).unwrap();
+
+        close(&db, id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
+        let next = db.list_issues(Some("open"), None, None).unwrap()[0].id;
+        let names: Vec<String> = db
+            .get_issue_milestones(next)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["v1.0"]);
+    }
+
+    #[test]
+    fn test_close_recurring_as_wontfix_stops_recurrence() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let id = db.create_issue("Weekly sync", None, "medium").unwrap();
+        db.set_recurrence(id, Some("weekly")).unwrap();
+
+        close_many(
+            &db,
+            &[id],
+            &CloseOpts {
+                update_changelog: false,
+                chainlink_dir: &chainlink_dir,
+                subissues: OpenSubissues::Refuse,
+                resolution: Some("wontfix"),
+                closed_by: None,
+            },
+        )
+        .unwrap();
+        assert!(db.list_issues(Some("open"), None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_close_with_resolution_persists() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let issue_id = db.create_issue("Dup", None, "medium").unwrap();
+
+        close_many(
+            &db,
+            &[issue_id],
+            &CloseOpts {
+                update_changelog: false,
+                chainlink_dir: &chainlink_dir,
+                subissues: OpenSubissues::Refuse,
+                resolution: Some("duplicate"),
+                closed_by: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.get_resolution(issue_id).unwrap(),
+            Some("duplicate".to_string())
+        );
+        reopen(&db, issue_id).unwrap();
+        assert_eq!(db.get_resolution(issue_id).unwrap(), None);
+    }
+
+    #[test]
+    fn test_close_with_unknown_resolution_rejected() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        let issue_id = db.create_issue("Test", None, "medium").unwrap();
+
+        let err = close_many(
+            &db,
+            &[issue_id],
+            &CloseOpts {
+                update_changelog: false,
+                chainlink_dir: &chainlink_dir,
+                subissues: OpenSubissues::Refuse,
+                resolution: Some("meh"),
+                closed_by: None,
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid resolution 'meh'"));
+        assert_eq!(db.get_issue(issue_id).unwrap().unwrap().status, "open");
+    }
+
+    #[test]
+    fn test_changelog_skips_unfixed_resolutions() {
+        let labels = vec!["bug".to_string()];
+        assert_eq!(
+            determine_changelog_category(&labels, Some("fixed")),
+            Some("Fixed".to_string())
+        );
+        assert_eq!(
+            determine_changelog_category(&labels, None),
+            Some("Fixed".to_string())
+        );
+        assert_eq!(determine_changelog_category(&labels, Some("wontfix")), None);
+    }
+
     #[test]
     fn test_close_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
         let chainlink_dir = _dir.path().join(".chainlink");
         std::fs::create_dir_all(&chainlink_dir).unwrap();
 
-        let result = close(&db, 99999, false, &chainlink_dir);
+        let result = close(&db, 99999, false, &chainlink_dir, OpenSubissues::Refuse);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
@@ -230,13 +683,126 @@ mod tests {
         std::fs::create_dir_all(&chainlink_dir).unwrap();
 
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
         // Closing again should be fine (idempotent at db level)
-        let result = close(&db, issue_id, false, &chainlink_dir);
+        let result = close(&db, issue_id, false, &chainlink_dir, OpenSubissues::Refuse);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_close_refuses_with_open_subissues() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+
+        let err = close(&db, parent, false, &chainlink_dir, OpenSubissues::Refuse).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("1 open subissue"));
+        assert!(message.contains(&format!("#{} Child", child)));
+        assert_eq!(db.get_issue(parent).unwrap().unwrap().status, "open");
+
+        // A closed subissue no longer blocks the parent
+        db.close_issue(child, None).unwrap();
+        close(&db, parent, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
+        assert_eq!(db.get_issue(parent).unwrap().unwrap().status, "closed");
+    }
+
+    #[test]
+    fn test_close_force_leaves_subissues_open() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+
+        close(&db, parent, false, &chainlink_dir, OpenSubissues::Ignore).unwrap();
+        assert_eq!(db.get_issue(parent).unwrap().unwrap().status, "closed");
+        assert_eq!(db.get_issue(child).unwrap().unwrap().status, "open");
+    }
+
+    #[test]
+    fn test_close_cascade_closes_nested_subissues() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+        let grandchild = db
+            .create_subissue(child, "Grandchild", None, "low")
+            .unwrap();
+
+        close(&db, parent, false, &chainlink_dir, OpenSubissues::Cascade).unwrap();
+        for id in [parent, child, grandchild] {
+            assert_eq!(db.get_issue(id).unwrap().unwrap().status, "closed");
+        }
+    }
+
+    #[test]
+    fn test_close_cascade_keeps_resolution_on_parent_only() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let child = db.create_subissue(parent, "Child", None, "medium").unwrap();
+
+        close_many(
+            &db,
+            &[parent],
+            &CloseOpts {
+                update_changelog: false,
+                chainlink_dir: &chainlink_dir,
+                subissues: OpenSubissues::Cascade,
+                resolution: Some("duplicate"),
+                closed_by: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            db.get_resolution(parent).unwrap().as_deref(),
+            Some("duplicate")
+        );
+        assert_eq!(db.get_issue(child).unwrap().unwrap().status, "closed");
+        assert_eq!(db.get_resolution(child).unwrap(), None);
+    }
+
+    #[test]
+    fn test_close_records_closer_on_cascaded_subissues() {
+        let (db, _dir) = setup_test_db();
+        let chainlink_dir = _dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        db.create_subissue(parent, "Child", None, "medium").unwrap();
+
+        close_many(
+            &db,
+            &[parent],
+            &CloseOpts {
+                update_changelog: false,
+                chainlink_dir: &chainlink_dir,
+                subissues: OpenSubissues::Cascade,
+                resolution: None,
+                closed_by: Some("alice"),
+            },
+        )
+        .unwrap();
+        let closers: Vec<Option<String>> = db
+            .activity_feed(Utc::now() - chrono::Duration::hours(1))
+            .unwrap()
+            .into_iter()
+            .filter(|item| item.kind == "closed")
+            .map(|item| item.author)
+            .collect();
+        assert_eq!(closers, vec![Some("alice".to_string()); 2]);
+    }
+
     // ==================== Reopen Tests ====================
 
     #[test]
@@ -244,7 +810,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
         let result = reopen(&db, issue_id);
         assert!(result.is_ok());
@@ -254,6 +820,45 @@ mod tests {
         assert!(issue.closed_at.is_none());
     }
 
+    #[test]
+    fn test_reopen_with_and_without_subissues() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "medium").unwrap();
+        let first = db.create_subissue(parent, "First", None, "medium").unwrap();
+        let second = db
+            .create_subissue(parent, "Second", None, "medium")
+            .unwrap();
+        let status = |id| db.get_issue(id).unwrap().unwrap().status;
+        let close_all = || {
+            for id in [first, second, parent] {
+                db.close_issue(id, None).unwrap();
+            }
+        };
+
+        close_all();
+        reopen(&db, parent).unwrap();
+        assert_eq!(status(parent), "open");
+        assert_eq!(status(first), "closed");
+        assert_eq!(status(second), "closed");
+
+        close_all();
+        reopen_with_subissues(&db, parent).unwrap();
+        for id in [parent, first, second] {
+            assert_eq!(status(id), "open");
+        }
+
+        // Reaches grandchildren, but leaves dropped subissues alone
+        let grandchild = db
+            .create_subissue(first, "Grandchild", None, "low")
+            .unwrap();
+        db.close_issue(grandchild, None).unwrap();
+        db.drop_issue(second).unwrap();
+        reopen_with_subissues(&db, parent).unwrap();
+        assert_eq!(status(grandchild), "open");
+        assert_eq!(status(second), "wontdo");
+        assert!(reopen_with_subissues(&db, 99999).is_err());
+    }
+
     #[test]
     fn test_reopen_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
@@ -281,34 +886,22 @@ mod tests {
 
     #[test]
     fn test_determine_changelog_category_bug() {
-        assert_eq!(determine_changelog_category(&["bug".to_string()]), "Fixed");
-        assert_eq!(determine_changelog_category(&["fix".to_string()]), "Fixed");
-        assert_eq!(
-            determine_changelog_category(&["bugfix".to_string()]),
-            "Fixed"
-        );
+        assert_eq!(category_from_labels(&["bug".to_string()]), "Fixed");
+        assert_eq!(category_from_labels(&["fix".to_string()]), "Fixed");
+        assert_eq!(category_from_labels(&["bugfix".to_string()]), "Fixed");
     }
 
     #[test]
     fn test_determine_changelog_category_feature() {
-        assert_eq!(
-            determine_changelog_category(&["feature".to_string()]),
-            "Added"
-        );
-        assert_eq!(
-            determine_changelog_category(&["enhancement".to_string()]),
-            "Added"
-        );
+        assert_eq!(category_from_labels(&["feature".to_string()]), "Added");
+        assert_eq!(category_from_labels(&["enhancement".to_string()]), "Added");
     }
 
     #[test]
     fn test_determine_changelog_category_breaking() {
+        assert_eq!(category_from_labels(&["breaking".to_string()]), "Changed");
         assert_eq!(
-            determine_changelog_category(&["breaking".to_string()]),
-            "Changed"
-        );
-        assert_eq!(
-            determine_changelog_category(&["breaking-change".to_string()]),
+            category_from_labels(&["breaking-change".to_string()]),
             "Changed"
         );
     }
@@ -316,44 +909,32 @@ mod tests {
     #[test]
     fn test_determine_changelog_category_other() {
         assert_eq!(
-            determine_changelog_category(&["deprecated".to_string()]),
+            category_from_labels(&["deprecated".to_string()]),
             "Deprecated"
         );
-        assert_eq!(
-            determine_changelog_category(&["removed".to_string()]),
-            "Removed"
-        );
-        assert_eq!(
-            determine_changelog_category(&["security".to_string()]),
-            "Security"
-        );
+        assert_eq!(category_from_labels(&["removed".to_string()]), "Removed");
+        assert_eq!(category_from_labels(&["security".to_string()]), "Security");
     }
 
     #[test]
     fn test_determine_changelog_category_default() {
-        assert_eq!(
-            determine_changelog_category(&["unknown".to_string()]),
-            "Changed"
-        );
-        assert_eq!(determine_changelog_category(&[]), "Changed");
+        assert_eq!(category_from_labels(&["unknown".to_string()]), "Changed");
+        assert_eq!(category_from_labels(&[]), "Changed");
     }
 
     #[test]
     fn test_determine_changelog_category_first_match_wins() {
         // Bug comes before feature, so Fixed should win
         assert_eq!(
-            determine_changelog_category(&["bug".to_string(), "feature".to_string()]),
+            category_from_labels(&["bug".to_string(), "feature".to_string()]),
             "Fixed"
         );
     }
 
     #[test]
     fn test_determine_changelog_category_case_insensitive() {
-        assert_eq!(determine_changelog_category(&["BUG".to_string()]), "Fixed");
-        assert_eq!(
-            determine_changelog_category(&["Feature".to_string()]),
-            "Added"
-        );
+        assert_eq!(category_from_labels(&["BUG".to_string()]), "Fixed");
+        assert_eq!(category_from_labels(&["Feature".to_string()]), "Added");
     }
 
     // ==================== Close/Reopen Cycle Tests ====================
@@ -367,7 +948,7 @@ mod tests {
         let issue_id = db.create_issue("Test issue", None, "medium").unwrap();
 
         // Close
-        close(&db, issue_id, false, &chainlink_dir).unwrap();
+        close(&db, issue_id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "closed");
 
@@ -377,7 +958,7 @@ mod tests {
         assert_eq!(issue.status, "open");
 
         // Close again
-        close(&db, issue_id, false, &chainlink_dir).unwrap();
+        close(&db, issue_id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.status, "closed");
     }
@@ -392,7 +973,7 @@ mod tests {
             std::fs::create_dir_all(&chainlink_dir).unwrap();
 
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
-            close(&db, issue_id, false, &chainlink_dir).unwrap();
+            close(&db, issue_id, false, &chainlink_dir, OpenSubissues::Refuse).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.status, "closed");
@@ -403,7 +984,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
 
             let issue_id = db.create_issue(&title, None, "medium").unwrap();
-            db.close_issue(issue_id).unwrap();
+            db.close_issue(issue_id, None).unwrap();
 
             reopen(&db, issue_id).unwrap();
 
@@ -417,7 +998,7 @@ mod tests {
             let chainlink_dir = _dir.path().join(".chainlink");
             std::fs::create_dir_all(&chainlink_dir).unwrap();
 
-            let result = close(&db, issue_id, false, &chainlink_dir);
+            let result = close(&db, issue_id, false, &chainlink_dir, OpenSubissues::Refuse);
             prop_assert!(result.is_err());
         }
 
@@ -434,7 +1015,7 @@ mod tests {
             labels in proptest::collection::vec("[a-zA-Z]{1,20}", 0..5)
         ) {
             let valid_categories = ["Fixed", "Added", "Changed", "Deprecated", "Removed", "Security"];
-            let category = determine_changelog_category(&labels);
+            let category = category_from_labels(&labels);
             prop_assert!(
                 valid_categories.contains(&category.as_str()),
                 "Got unknown category: {}", category
@@ -1,15 +1,20 @@
 use anyhow::{Context, Result};
+use chrono::Utc;
 use std::fs;
 use std::path::Path;
 
-pub fn run(chainlink_dir: &Path) -> Result<()> {
+use crate::db::Database;
+use crate::utils::info;
+
+pub fn run(db: &Database, chainlink_dir: &Path) -> Result<()> {
     let marker_file = chainlink_dir.join("last_test_run");
 
     // Create or update the marker file
     fs::write(&marker_file, "").context("Failed to update test marker")?;
+    db.set_last_tested(Utc::now())?;
 
-    println!("✓ Marked tests as run");
-    println!("  Test reminder will reset on next code change.");
+    info("✓ Marked tests as run");
+    info("  Test reminder will reset on next code change.");
 
     Ok(())
 }
@@ -20,13 +25,20 @@ mod tests {
     use proptest::prelude::*;
     use tempfile::tempdir;
 
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
     #[test]
     fn test_run_creates_marker_file() {
-        let dir = tempdir().unwrap();
+        let (db, dir) = setup_test_db();
         let chainlink_dir = dir.path().join(".chainlink");
         std::fs::create_dir_all(&chainlink_dir).unwrap();
 
-        let result = run(&chainlink_dir);
+        let result = run(&db, &chainlink_dir);
         assert!(result.is_ok());
 
         let marker_path = chainlink_dir.join("last_test_run");
@@ -35,14 +47,14 @@ mod tests {
 
     #[test]
     fn test_run_updates_existing_marker() {
-        let dir = tempdir().unwrap();
+        let (db, dir) = setup_test_db();
         let chainlink_dir = dir.path().join(".chainlink");
         std::fs::create_dir_all(&chainlink_dir).unwrap();
 
         let marker_path = chainlink_dir.join("last_test_run");
         std::fs::write(&marker_path, "old content").unwrap();
 
-        let result = run(&chainlink_dir);
+        let result = run(&db, &chainlink_dir);
         assert!(result.is_ok());
 
         let content = std::fs::read_to_string(&marker_path).unwrap();
@@ -51,10 +63,10 @@ mod tests {
 
     #[test]
     fn test_run_fails_on_nonexistent_dir() {
-        let dir = tempdir().unwrap();
+        let (db, dir) = setup_test_db();
         let nonexistent = dir.path().join("nonexistent");
 
-        let result = run(&nonexistent);
+        let result = run(&db, &nonexistent);
         assert!(result.is_err());
     }
 
@@ -64,7 +76,7 @@ mod tests {
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
-            let dir = tempdir().unwrap();
+            let (db, dir) = setup_test_db();
             let chainlink_dir = dir.path().join(".chainlink");
             std::fs::create_dir_all(&chainlink_dir).unwrap();
 
@@ -73,7 +85,7 @@ mod tests {
             perms.set_mode(0o444);
             std::fs::set_permissions(&chainlink_dir, perms).unwrap();
 
-            let result = run(&chainlink_dir);
+            let result = run(&db, &chainlink_dir);
             assert!(result.is_err());
 
             // Restore permissions for cleanup
@@ -85,13 +97,13 @@ mod tests {
 
     #[test]
     fn test_run_idempotent() {
-        let dir = tempdir().unwrap();
+        let (db, dir) = setup_test_db();
         let chainlink_dir = dir.path().join(".chainlink");
         std::fs::create_dir_all(&chainlink_dir).unwrap();
 
         // Run multiple times
         for _ in 0..3 {
-            let result = run(&chainlink_dir);
+            let result = run(&db, &chainlink_dir);
             assert!(result.is_ok());
         }
 
@@ -99,14 +111,27 @@ mod tests {
         assert!(marker_path.exists());
     }
 
+    #[test]
+    fn test_run_records_last_tested() {
+        let (db, dir) = setup_test_db();
+        let chainlink_dir = dir.path().join(".chainlink");
+        std::fs::create_dir_all(&chainlink_dir).unwrap();
+        assert!(db.get_last_tested().unwrap().is_none());
+
+        let before = Utc::now();
+        run(&db, &chainlink_dir).unwrap();
+        let recorded = db.get_last_tested().unwrap().unwrap();
+        assert!(recorded >= before && recorded <= Utc::now());
+    }
+
     proptest! {
         #[test]
         fn prop_run_never_panics_with_valid_dir(subdir in "[a-z]{1,10}") {
-            let dir = tempdir().unwrap();
+            let (db, dir) = setup_test_db();
             let chainlink_dir = dir.path().join(&subdir);
             std::fs::create_dir_all(&chainlink_dir).unwrap();
 
-            let result = run(&chainlink_dir);
+            let result = run(&db, &chainlink_dir);
             prop_assert!(result.is_ok());
         }
     }
@@ -1,7 +1,101 @@
 use anyhow::{bail, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 use crate::db::Database;
+use crate::models::TimeEntry;
+use crate::utils::{format_duration_secs, info};
+
+/// The `timer --json` payload.
+#[derive(Debug, Serialize, PartialEq)]
+struct TimerStatus {
+    /// Issue the timer is running for, if any
+    issue_id: Option<i64>,
+    running: bool,
+    elapsed_seconds: i64,
+}
+
+fn timer_status(db: &Database) -> Result<TimerStatus> {
+    Ok(match db.get_active_timer()? {
+        Some((issue_id, started_at)) => TimerStatus {
+            issue_id: Some(issue_id),
+            running: true,
+            elapsed_seconds: Utc::now().signed_duration_since(started_at).num_seconds(),
+        },
+        None => TimerStatus {
+            issue_id: None,
+            running: false,
+            elapsed_seconds: 0,
+        },
+    })
+}
+
+/// The `timer --issue <id> --json` payload.
+#[derive(Debug, Serialize)]
+struct TimerHistory {
+    issue_id: i64,
+    entries: Vec<TimeEntry>,
+    /// Sum of the completed entries
+    total_seconds: i64,
+}
+
+/// Seconds logged by completed entries; a running entry doesn't count yet.
+fn completed_total(entries: &[TimeEntry]) -> i64 {
+    entries.iter().filter_map(|e| e.duration_seconds).sum()
+}
+
+/// One row per entry: start, end (or "(running)") and duration so far.
+fn history_lines(entries: &[TimeEntry], now: DateTime<Utc>) -> Vec<String> {
+    const FMT: &str = "%Y-%m-%d %H:%M";
+    entries
+        .iter()
+        .map(|e| {
+            let (end, secs) = match (e.ended_at, e.duration_seconds) {
+                (Some(end), Some(secs)) => (end.format(FMT).to_string(), secs),
+                (Some(end), None) => (
+                    end.format(FMT).to_string(),
+                    (end - e.started_at).num_seconds(),
+                ),
+                (None, _) => ("(running)".to_string(), (now - e.started_at).num_seconds()),
+            };
+            format!(
+                "  {} → {:<16}  {}",
+                e.started_at.format(FMT),
+                end,
+                format_duration_secs(secs)
+            )
+        })
+        .collect()
+}
+
+/// List every time entry recorded for an issue, with the total.
+pub fn history(db: &Database, issue_id: i64) -> Result<()> {
+    let issue = db.require_issue(issue_id)?;
+    let entries = db.get_time_entries(issue_id)?;
+
+    println!("Time entries for #{}: {}", issue_id, issue.title);
+    if entries.is_empty() {
+        println!("  (none)");
+        return Ok(());
+    }
+    for line in history_lines(&entries, Utc::now()) {
+        println!("{}", line);
+    }
+    println!("Total: {}", format_duration_secs(completed_total(&entries)));
+    Ok(())
+}
+
+pub fn history_json(db: &Database, issue_id: i64) -> Result<()> {
+    db.require_issue(issue_id)?;
+    let entries = db.get_time_entries(issue_id)?;
+    let history = TimerHistory {
+        issue_id,
+        total_seconds: completed_total(&entries),
+        entries,
+    };
+    println!("{}", serde_json::to_string_pretty(&history)?);
+    Ok(())
+}
 
 pub fn start(db: &Database, issue_id: i64) -> Result<()> {
     // Verify issue exists
@@ -23,8 +117,11 @@ pub fn start(db: &Database, issue_id: i64) -> Result<()> {
     }
 
     db.start_timer(issue_id)?;
-    println!("Started timer for #{}: {}", issue_id, issue.title);
-    println!("Run 'chainlink stop' when done.");
+    info(format_args!(
+        "Started timer for #{}: {}",
+        issue_id, issue.title
+    ));
+    info("Run 'chainlink stop' when done.");
 
     Ok(())
 }
@@ -47,17 +144,20 @@ pub fn stop(db: &Database) -> Result<()> {
     let minutes = duration.num_minutes() % 60;
     let seconds = duration.num_seconds() % 60;
 
-    println!("Stopped timer for #{}: {}", issue_id, title);
-    println!("Time spent: {}h {}m {}s", hours, minutes, seconds);
+    info(format_args!("Stopped timer for #{}: {}", issue_id, title));
+    info(format_args!(
+        "Time spent: {}h {}m {}s",
+        hours, minutes, seconds
+    ));
 
     // Show total time for this issue
     let total = db.get_total_time(issue_id)?;
     let total_hours = total / 3600;
     let total_minutes = (total % 3600) / 60;
-    println!(
+    info(format_args!(
         "Total time on this issue: {}h {}m",
         total_hours, total_minutes
-    );
+    ));
 
     Ok(())
 }
@@ -88,6 +188,11 @@ pub fn status(db: &Database) -> Result<()> {
     Ok(())
 }
 
+pub fn status_json(db: &Database) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&timer_status(db)?)?);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +206,52 @@ mod tests {
         (db, dir)
     }
 
+    #[test]
+    fn test_history_lists_entries_and_total() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Tracked", None, "medium").unwrap();
+        let start = |h: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 3, 1)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        for (from, to) in [(9, 10), (13, 15)] {
+            db.insert_time_entry(&TimeEntry {
+                id: 0,
+                issue_id: id,
+                started_at: start(from),
+                ended_at: Some(start(to)),
+                duration_seconds: Some(i64::from(to - from) * 3600),
+            })
+            .unwrap();
+        }
+
+        let entries = db.get_time_entries(id).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(completed_total(&entries), 3 * 3600);
+
+        let lines = history_lines(&entries, Utc::now());
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("2024-03-01 09:00 → 2024-03-01 10:00"));
+        assert!(lines[0].ends_with("1h 0m"));
+        assert!(lines[1].ends_with("2h 0m"));
+        assert!(history(&db, id).is_ok());
+    }
+
+    #[test]
+    fn test_history_marks_running_entry() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Tracked", None, "medium").unwrap();
+        db.start_timer(id).unwrap();
+
+        let entries = db.get_time_entries(id).unwrap();
+        let lines = history_lines(&entries, Utc::now());
+        assert!(lines[0].contains("(running)"));
+        assert_eq!(completed_total(&entries), 0);
+    }
+
     #[test]
     fn test_start_timer() {
         let (db, _dir) = setup_test_db();
@@ -186,6 +337,29 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_timer_status_json() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        let idle = timer_status(&db).unwrap();
+        assert_eq!(
+            idle,
+            TimerStatus {
+                issue_id: None,
+                running: false,
+                elapsed_seconds: 0,
+            }
+        );
+
+        start(&db, id).unwrap();
+        let running = timer_status(&db).unwrap();
+        assert!(running.running);
+        assert_eq!(running.issue_id, Some(id));
+        assert!(running.elapsed_seconds >= 0);
+        status_json(&db).unwrap();
+    }
+
     #[test]
     fn test_timer_workflow() {
         let (db, _dir) = setup_test_db();
@@ -0,0 +1,131 @@
+use anyhow::{bail, Result};
+use chrono::Utc;
+
+use crate::db::Database;
+use crate::utils::{humanize_age, info};
+
+pub fn list(db: &Database) -> Result<()> {
+    let trashed = db.list_trashed()?;
+
+    if trashed.is_empty() {
+        println!("Trash is empty.");
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    for entry in trashed {
+        let subs = match entry.subissue_count {
+            0 => String::new(),
+            1 => " (+1 subissue)".to_string(),
+            n => format!(" (+{} subissues)", n),
+        };
+        println!(
+            "{:<4} was #{:<4} {}{}  deleted {}",
+            entry.id,
+            entry.issue_id,
+            entry.title,
+            subs,
+            humanize_age(entry.deleted_at, now)
+        );
+    }
+    println!("\nRestore one with 'chainlink restore <trash id>'.");
+
+    Ok(())
+}
+
+pub fn list_json(db: &Database) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&db.list_trashed()?)?);
+    Ok(())
+}
+
+pub fn restore(db: &Database, trash_id: i64) -> Result<()> {
+    let restored = db.transaction(|| db.restore_trashed(trash_id))?;
+    match restored {
+        Some(id) => {
+            info(format_args!(
+                "Restored trash entry {} as issue #{}",
+                trash_id, id
+            ));
+            Ok(())
+        }
+        None => bail!("Trash entry {} not found", trash_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_list_multiple_trashed_issues() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", None, "medium").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+        db.create_subissue(second, "Child", None, "low").unwrap();
+
+        db.trash_issue(first).unwrap().unwrap();
+        db.trash_issue(second).unwrap().unwrap();
+        list(&db).unwrap();
+
+        let trashed = db.list_trashed().unwrap();
+        assert_eq!(trashed.len(), 2);
+        assert_eq!(trashed[0].issue_id, second);
+        assert_eq!(trashed[0].subissue_count, 1);
+        assert_eq!(trashed[1].issue_id, first);
+        assert!(db.get_issue(first).unwrap().is_none());
+        assert!(db.get_issue(second).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_restore_specific_non_latest_entry() {
+        let (db, _dir) = setup_test_db();
+        let first = db.create_issue("First", Some("keep me"), "high").unwrap();
+        db.add_label(first, "bug").unwrap();
+        db.add_comment(first, "a note").unwrap();
+        db.create_subissue(first, "Child", None, "low").unwrap();
+        let second = db.create_issue("Second", None, "medium").unwrap();
+
+        let first_trash = db.trash_issue(first).unwrap().unwrap();
+        db.trash_issue(second).unwrap().unwrap();
+
+        restore(&db, first_trash).unwrap();
+
+        let trashed = db.list_trashed().unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].issue_id, second);
+
+        let restored = db
+            .list_issues(Some("all"), None, None)
+            .unwrap()
+            .into_iter()
+            .find(|i| i.title == "First")
+            .unwrap();
+        assert_ne!(restored.id, first);
+        assert_eq!(restored.description.as_deref(), Some("keep me"));
+        assert_eq!(restored.priority, "high");
+        assert_eq!(db.get_labels(restored.id).unwrap(), vec!["bug"]);
+        assert_eq!(
+            db.get_comments(restored.id, None).unwrap()[0].content,
+            "a note"
+        );
+        let subs = db.get_subissues(restored.id).unwrap();
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs[0].title, "Child");
+    }
+
+    #[test]
+    fn test_restore_missing_entry_fails() {
+        let (db, _dir) = setup_test_db();
+        let result = restore(&db, 42);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+}
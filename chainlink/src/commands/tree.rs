@@ -1,23 +1,67 @@
 use anyhow::Result;
+use std::collections::HashSet;
 
 use crate::db::Database;
 use crate::models::Issue;
+use crate::utils::truncate_display;
 
 fn status_icon(status: &str) -> &'static str {
     match status {
         "open" => " ",
         "closed" => "x",
+        "wontdo" => "-",
         _ => "?",
     }
 }
 
-fn print_issue(issue: &Issue, indent: usize) {
-    let prefix = "  ".repeat(indent);
-    let icon = status_icon(&issue.status);
-    println!(
-        "{}[{}] #{} {} - {}",
-        prefix, icon, issue.id, issue.priority, issue.title
+/// Whether an issue can be worked on now, given the ids of blocked issues.
+fn annotation(issue: &Issue, blocked: &HashSet<i64>) -> &'static str {
+    if issue.status == "closed" {
+        "[closed]"
+    } else if issue.status == "wontdo" {
+        "[won't do]"
+    } else if blocked.contains(&issue.id) {
+        "[blocked]"
+    } else {
+        "[ready]"
+    }
+}
+
+/// Shortest a title gets, however deeply it is nested
+const MIN_TITLE_WIDTH: usize = 10;
+
+/// How tree lines are rendered.
+#[derive(Clone, Copy, Default)]
+struct TreeStyle<'a> {
+    /// Ids of blocked issues, set when `--annotate` asked for markers
+    blocked: Option<&'a HashSet<i64>>,
+    /// Terminal width to fit lines into; `None` leaves titles whole
+    width: Option<usize>,
+}
+
+fn format_issue(issue: &Issue, indent: usize, style: TreeStyle<'_>) -> String {
+    let head = format!(
+        "{}[{}] #{} {} - ",
+        "  ".repeat(indent),
+        status_icon(&issue.status),
+        issue.id,
+        issue.priority
     );
+    let marker = style
+        .blocked
+        .map(|ids| format!(" {}", annotation(issue, ids)))
+        .unwrap_or_default();
+    let title = match style.width {
+        Some(width) => {
+            let used = head.chars().count() + marker.len();
+            truncate_display(
+                &issue.title,
+                width.saturating_sub(used).max(MIN_TITLE_WIDTH),
+            )
+        }
+        None => issue.title.clone(),
+    };
+    format!("{}{}{}", head, title, marker)
 }
 
 fn print_tree_recursive(
@@ -25,6 +69,7 @@ fn print_tree_recursive(
     parent_id: i64,
     indent: usize,
     status_filter: Option<&str>,
+    style: TreeStyle<'_>,
 ) -> Result<()> {
     let subissues = db.get_subissues(parent_id)?;
     for sub in subissues {
@@ -35,13 +80,20 @@ fn print_tree_recursive(
         if dominated_by_filter {
             continue;
         }
-        print_issue(&sub, indent);
-        print_tree_recursive(db, sub.id, indent + 1, status_filter)?;
+        println!("{}", format_issue(&sub, indent, style));
+        print_tree_recursive(db, sub.id, indent + 1, status_filter, style)?;
     }
     Ok(())
 }
 
-pub fn run(db: &Database, status_filter: Option<&str>) -> Result<()> {
+/// Print the issue hierarchy. With `annotate`, each issue is also marked
+/// `[ready]`, `[blocked]` or `[closed]`. Titles are shortened to fit `width`.
+pub fn run(
+    db: &Database,
+    status_filter: Option<&str>,
+    annotate: bool,
+    width: Option<usize>,
+) -> Result<()> {
     // Get all top-level issues (no parent)
     let all_issues = db.list_issues(status_filter, None, None)?;
     let top_level: Vec<_> = all_issues
@@ -54,9 +106,18 @@ pub fn run(db: &Database, status_filter: Option<&str>) -> Result<()> {
         return Ok(());
     }
 
+    let blocked = if annotate {
+        Some(db.blocked_ids()?)
+    } else {
+        None
+    };
+    let style = TreeStyle {
+        blocked: blocked.as_ref(),
+        width,
+    };
     for issue in top_level {
-        print_issue(&issue, 0);
-        print_tree_recursive(db, issue.id, 1, status_filter)?;
+        println!("{}", format_issue(&issue, 0, style));
+        print_tree_recursive(db, issue.id, 1, status_filter, style)?;
     }
 
     // Legend
@@ -94,10 +155,62 @@ mod tests {
         assert_eq!(status_icon("archived"), "?");
     }
 
+    #[test]
+    fn test_annotations() {
+        let (db, _dir) = setup_test_db();
+        let epic = db.create_issue("Epic", None, "high").unwrap();
+        let blocked = db.create_subissue(epic, "Blocked", None, "medium").unwrap();
+        let ready = db.create_subissue(epic, "Ready", None, "medium").unwrap();
+        let done = db.create_subissue(epic, "Done", None, "low").unwrap();
+        db.add_dependency(blocked, ready).unwrap();
+        db.close_issue(done, None).unwrap();
+
+        let blocked_ids = db.blocked_ids().unwrap();
+        let line = |id: i64| {
+            let issue = db.get_issue(id).unwrap().unwrap();
+            let style = TreeStyle {
+                blocked: Some(&blocked_ids),
+                width: None,
+            };
+            format_issue(&issue, 1, style)
+        };
+        assert!(line(blocked).ends_with("Blocked [blocked]"));
+        assert!(line(ready).ends_with("Ready [ready]"));
+        assert!(line(done).ends_with("Done [closed]"));
+
+        let issue = db.get_issue(ready).unwrap().unwrap();
+        assert!(format_issue(&issue, 1, TreeStyle::default()).ends_with("- Ready"));
+        run(&db, None, true, None).unwrap();
+    }
+
+    #[test]
+    fn test_titles_fit_width() {
+        let (db, _dir) = setup_test_db();
+        let parent = db.create_issue("Parent", None, "high").unwrap();
+        let child = db
+            .create_subissue(
+                parent,
+                "A rather long subissue title for a tree",
+                None,
+                "low",
+            )
+            .unwrap();
+        let issue = db.get_issue(child).unwrap().unwrap();
+
+        let style = TreeStyle {
+            blocked: None,
+            width: Some(30),
+        };
+        let line = format_issue(&issue, 2, style);
+        assert_eq!(line.chars().count(), 30);
+        assert!(line.ends_with("A rather l..."));
+        assert!(format_issue(&issue, 2, TreeStyle::default()).ends_with("for a tree"));
+    }
+
     #[test]
     fn test_run_empty() {
         let (db, _dir) = setup_test_db();
-        run(&db, None).unwrap();
+        run(&db, None, false, None).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert!(issues.is_empty());
     }
@@ -106,7 +219,7 @@ mod tests {
     fn test_run_single_issue() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Test issue", None, "medium").unwrap();
-        run(&db, None).unwrap();
+        run(&db, None, false, None).unwrap();
         let issues = db.list_issues(None, None, None).unwrap();
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].id, id);
@@ -120,7 +233,7 @@ mod tests {
             .create_subissue(parent, "Child 1", None, "medium")
             .unwrap();
         let c2 = db.create_subissue(parent, "Child 2", None, "low").unwrap();
-        run(&db, None).unwrap();
+        run(&db, None, false, None).unwrap();
         let subs = db.get_subissues(parent).unwrap();
         assert_eq!(subs.len(), 2);
         assert!(subs.iter().any(|s| s.id == c1));
@@ -135,7 +248,7 @@ mod tests {
             .create_subissue(grandparent, "Parent", None, "medium")
             .unwrap();
         let child = db.create_subissue(parent, "Child", None, "low").unwrap();
-        run(&db, None).unwrap();
+        run(&db, None, false, None).unwrap();
         let child_issue = db.get_issue(child).unwrap().unwrap();
         assert_eq!(child_issue.parent_id, Some(parent));
         let parent_issue = db.get_issue(parent).unwrap().unwrap();
@@ -147,8 +260,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let closed_id = db.create_issue("Closed issue", None, "medium").unwrap();
         let open_id = db.create_issue("Open issue", None, "medium").unwrap();
-        db.close_issue(closed_id).unwrap();
-        run(&db, Some("open")).unwrap();
+        db.close_issue(closed_id, None).unwrap();
+        run(&db, Some("open"), false, None).unwrap();
         let open_issues = db.list_issues(Some("open"), None, None).unwrap();
         assert_eq!(open_issues.len(), 1);
         assert_eq!(open_issues[0].id, open_id);
@@ -158,8 +271,8 @@ mod tests {
     fn test_run_closed_filter() {
         let (db, _dir) = setup_test_db();
         let id = db.create_issue("Issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
-        run(&db, Some("closed")).unwrap();
+        db.close_issue(id, None).unwrap();
+        run(&db, Some("closed"), false, None).unwrap();
         let closed = db.list_issues(Some("closed"), None, None).unwrap();
         assert_eq!(closed.len(), 1);
         assert_eq!(closed[0].id, id);
@@ -170,8 +283,8 @@ mod tests {
         let (db, _dir) = setup_test_db();
         db.create_issue("Open issue", None, "medium").unwrap();
         let id = db.create_issue("Closed issue", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
-        run(&db, Some("all")).unwrap();
+        db.close_issue(id, None).unwrap();
+        run(&db, Some("all"), false, None).unwrap();
         let all = db.list_issues(Some("all"), None, None).unwrap();
         assert_eq!(all.len(), 2);
     }
@@ -183,7 +296,7 @@ mod tests {
             for i in 0..count {
                 db.create_issue(&format!("Issue {}", i), None, "medium").unwrap();
             }
-            let result = run(&db, None);
+            let result = run(&db, None, false, None);
             prop_assert!(result.is_ok());
         }
 
@@ -194,7 +307,7 @@ mod tests {
             for i in 0..depth {
                 parent_id = db.create_subissue(parent_id, &format!("Child {}", i), None, "medium").unwrap();
             }
-            let result = run(&db, None);
+            let result = run(&db, None, false, None);
             prop_assert!(result.is_ok());
         }
     }
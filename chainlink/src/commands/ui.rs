@@ -0,0 +1,298 @@
+//! Keyboard-driven issue list for triage (`chainlink ui`, `tui` feature only).
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::Path;
+
+use crate::commands::{label, status};
+use crate::db::Database;
+use crate::models::Issue;
+use crate::utils::{self, Verbosity};
+
+const HELP: &str = "↑/↓ move  c close  o reopen  l label  r reload  q quit";
+
+/// The issues shown in the list and which one is selected.
+pub struct ListModel {
+    status: String,
+    issues: Vec<Issue>,
+    selected: usize,
+}
+
+impl ListModel {
+    pub fn load(db: &Database, status: &str) -> Result<Self> {
+        let mut model = ListModel {
+            status: status.to_string(),
+            issues: Vec::new(),
+            selected: 0,
+        };
+        model.reload(db)?;
+        Ok(model)
+    }
+
+    /// Re-read the issues, keeping the selection on the same row where possible.
+    pub fn reload(&mut self, db: &Database) -> Result<()> {
+        self.issues = db.list_issues(Some(&self.status), None, None)?;
+        self.selected = self.selected.min(self.issues.len().saturating_sub(1));
+        Ok(())
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        self.issues
+            .iter()
+            .map(|i| format!("#{:<4} {:8} {:6} {}", i.id, i.priority, i.status, i.title))
+            .collect()
+    }
+
+    pub fn selected(&self) -> Option<&Issue> {
+        self.issues.get(self.selected)
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.issues.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+enum Mode {
+    Browse,
+    /// Typing a label for the selected issue
+    Label(String),
+}
+
+/// List state plus the footer line; key handling lives here so it can be
+/// exercised without a terminal.
+pub struct App<'a> {
+    db: &'a Database,
+    chainlink_dir: &'a Path,
+    update_changelog: bool,
+    /// Recorded as the closer of issues closed from the list
+    closed_by: Option<&'a str>,
+    pub model: ListModel,
+    mode: Mode,
+    pub message: Option<String>,
+}
+
+impl<'a> App<'a> {
+    pub fn new(
+        db: &'a Database,
+        chainlink_dir: &'a Path,
+        update_changelog: bool,
+        closed_by: Option<&'a str>,
+        status: &str,
+    ) -> Result<Self> {
+        Ok(App {
+            db,
+            chainlink_dir,
+            update_changelog,
+            closed_by,
+            model: ListModel::load(db, status)?,
+            mode: Mode::Browse,
+            message: None,
+        })
+    }
+
+    /// Handle one key press. Returns false when the user asked to quit.
+    pub fn handle_key(&mut self, key: KeyCode) -> Result<bool> {
+        if let Mode::Label(input) = &mut self.mode {
+            match key {
+                KeyCode::Enter => {
+                    let text = input.trim().to_string();
+                    self.mode = Mode::Browse;
+                    if !text.is_empty() {
+                        self.on_selected(|db, id| label::add(db, id, &text), "Labeled")?;
+                    }
+                }
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Down | KeyCode::Char('j') => self.model.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => self.model.move_up(),
+            KeyCode::Char('c') => {
+                let (dir, changelog, closed_by) =
+                    (self.chainlink_dir, self.update_changelog, self.closed_by);
+                self.on_selected(
+                    |db, id| {
+                        status::close_many(
+                            db,
+                            &[id],
+                            &status::CloseOpts {
+                                update_changelog: changelog,
+                                chainlink_dir: dir,
+                                subissues: status::OpenSubissues::Refuse,
+                                resolution: None,
+                                closed_by,
+                            },
+                        )
+                    },
+                    "Closed",
+                )?;
+            }
+            KeyCode::Char('o') => self.on_selected(|db, id| status::reopen(db, id), "Reopened")?,
+            KeyCode::Char('l') if self.model.selected().is_some() => {
+                self.mode = Mode::Label(String::new());
+            }
+            KeyCode::Char('r') => {
+                self.model.reload(self.db)?;
+                self.message = None;
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Run a command against the selected issue and report the outcome in the
+    /// footer. Command errors are shown rather than ending the session.
+    fn on_selected(
+        &mut self,
+        action: impl FnOnce(&Database, i64) -> Result<()>,
+        done: &str,
+    ) -> Result<()> {
+        let Some(id) = self.model.selected().map(|i| i.id) else {
+            return Ok(());
+        };
+        self.message = Some(match action(self.db, id) {
+            Ok(()) => format!("{} #{}", done, id),
+            Err(e) => format!("Error: {}", e),
+        });
+        self.model.reload(self.db)
+    }
+
+    fn footer(&self) -> String {
+        match &self.mode {
+            Mode::Label(input) => format!("Label: {}▏ (Enter to add, Esc to cancel)", input),
+            Mode::Browse => match &self.message {
+                Some(message) => format!("{}  |  {}", message, HELP),
+                None => HELP.to_string(),
+            },
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [list_area, footer_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+        let items: Vec<ListItem> = self.model.rows().into_iter().map(ListItem::new).collect();
+        let title = format!(
+            " chainlink: {} issues ({}) ",
+            self.model.status,
+            items.len()
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state =
+            ListState::default().with_selected(self.model.selected().map(|_| self.model.selected));
+        frame.render_stateful_widget(list, list_area, &mut state);
+        frame.render_widget(Paragraph::new(self.footer()), footer_area);
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(key.code)? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub fn run(
+    db: &Database,
+    chainlink_dir: &Path,
+    update_changelog: bool,
+    closed_by: Option<&str>,
+    status: &str,
+) -> Result<()> {
+    let mut app = App::new(db, chainlink_dir, update_changelog, closed_by, status)?;
+
+    // Command functions report to stdout, which would scribble over the screen
+    let previous = utils::verbosity();
+    utils::set_verbosity(Verbosity::Quiet);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    utils::set_verbosity(previous);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_list_model_builds_from_db() {
+        let (db, _dir) = setup_test_db();
+        db.create_issue("First", None, "high").unwrap();
+        db.create_issue("Second", None, "low").unwrap();
+        let closed = db.create_issue("Done", None, "medium").unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        let mut model = ListModel::load(&db, "open").unwrap();
+        let rows = model.rows();
+        assert_eq!(rows.len(), 2);
+        assert!(rows
+            .iter()
+            .any(|r| r.contains("First") && r.contains("high")));
+        assert!(!rows.iter().any(|r| r.contains("Done")));
+
+        let first = model.selected().unwrap().id;
+        model.move_up();
+        assert_eq!(model.selected().unwrap().id, first);
+        model.move_down();
+        model.move_down();
+        assert_ne!(model.selected().unwrap().id, first);
+    }
+
+    #[test]
+    fn test_keys_close_and_label_the_selected_issue() {
+        let (db, dir) = setup_test_db();
+        let id = db.create_issue("Triage me", None, "medium").unwrap();
+        let mut app = App::new(&db, dir.path(), false, None, "open").unwrap();
+
+        for key in [
+            KeyCode::Char('l'),
+            KeyCode::Char('b'),
+            KeyCode::Char('u'),
+            KeyCode::Char('g'),
+            KeyCode::Enter,
+        ] {
+            assert!(app.handle_key(key).unwrap());
+        }
+        assert_eq!(db.get_labels(id).unwrap(), vec!["bug"]);
+
+        app.handle_key(KeyCode::Char('c')).unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "closed");
+        assert_eq!(app.message.as_deref(), Some("Closed #1"));
+        assert!(app.model.selected().is_none());
+
+        assert!(!app.handle_key(KeyCode::Char('q')).unwrap());
+    }
+}
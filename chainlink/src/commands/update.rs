@@ -1,37 +1,83 @@
 use anyhow::{bail, Result};
+use chrono::Utc;
 
 use crate::commands::create::validate_priority;
-use crate::db::Database;
+use crate::db::{Database, FieldUpdate};
+use crate::utils::{info, parse_due_date, Recurrence};
 
 pub fn run(
     db: &Database,
     id: i64,
     title: Option<&str>,
-    description: Option<&str>,
+    description: FieldUpdate<&str>,
     priority: Option<&str>,
 ) -> Result<()> {
-    if title.is_none() && description.is_none() && priority.is_none() {
-        bail!("Nothing to update. Use --title, --description, or --priority");
+    if title.is_none() && description.is_keep() && priority.is_none() {
+        bail!("Nothing to update. Use --title, --description, --clear-description, --priority, --due or --recur");
     }
 
     if let Some(p) = priority {
-        if !validate_priority(p) {
-            bail!(
-                "Invalid priority '{}'. Must be one of: low, medium, high, critical",
-                p
-            );
-        }
+        validate_priority(p)?;
     }
 
+    db.require_issue(id)?;
     if db.update_issue(id, title, description, priority)? {
-        println!("Updated issue #{}", id);
+        info(format_args!("Updated issue #{}", id));
     } else {
-        bail!("Issue #{} not found", id);
+        info(format_args!(
+            "Issue #{} already matches; nothing changed",
+            id
+        ));
     }
 
     Ok(())
 }
 
+/// Set or clear an issue's due date and recurrence (`--due`, `--recur` and their `--clear-` forms).
+pub fn schedule(
+    db: &Database,
+    id: i64,
+    due: FieldUpdate<&str>,
+    recurrence: FieldUpdate<&str>,
+) -> Result<()> {
+    db.require_issue(id)?;
+    match due {
+        FieldUpdate::Keep => {}
+        FieldUpdate::Set(spec) => {
+            let due_at = parse_due_date(spec, Utc::now())?;
+            db.set_due_date(id, Some(due_at))?;
+            info(format_args!(
+                "Issue #{} is due {}",
+                id,
+                due_at.format("%Y-%m-%d")
+            ));
+        }
+        FieldUpdate::Clear => {
+            db.set_due_date(id, None)?;
+            info(format_args!("Cleared the due date on issue #{}", id));
+        }
+    }
+    match recurrence {
+        FieldUpdate::Keep => {}
+        FieldUpdate::Set(spec) => {
+            // Check the first occurrence fits, so closing the issue can't fail later
+            let from = db.get_issue(id)?.and_then(|i| i.due_at);
+            spec.parse::<Recurrence>()?
+                .next_due(from.unwrap_or_else(Utc::now))?;
+            db.set_recurrence(id, Some(spec))?;
+            info(format_args!(
+                "Issue #{} now recurs {} (a new copy opens when it is closed)",
+                id, spec
+            ));
+        }
+        FieldUpdate::Clear => {
+            db.set_recurrence(id, None)?;
+            info(format_args!("Issue #{} no longer recurs", id));
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,7 +98,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Original title", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, Some("New title"), None, None);
+        let result = run(&db, issue_id, Some("New title"), FieldUpdate::Keep, None);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -64,7 +110,13 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, Some("New description"), None);
+        let result = run(
+            &db,
+            issue_id,
+            None,
+            FieldUpdate::Set("New description"),
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -76,7 +128,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, None, Some("critical"));
+        let result = run(&db, issue_id, None, FieldUpdate::Keep, Some("critical"));
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -94,7 +146,7 @@ mod tests {
             &db,
             issue_id,
             Some("New title"),
-            Some("New description"),
+            FieldUpdate::Set("New description"),
             Some("high"),
         );
         assert!(result.is_ok());
@@ -110,7 +162,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, None, None);
+        let result = run(&db, issue_id, None, FieldUpdate::Keep, None);
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
@@ -118,23 +170,60 @@ mod tests {
             .contains("Nothing to update"));
     }
 
+    #[test]
+    fn test_update_to_existing_values_succeeds_without_touching_issue() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Same", None, "medium").unwrap();
+        let before = db.get_issue(issue_id).unwrap().unwrap();
+
+        run(
+            &db,
+            issue_id,
+            Some("Same"),
+            FieldUpdate::Keep,
+            Some("medium"),
+        )
+        .unwrap();
+
+        let after = db.get_issue(issue_id).unwrap().unwrap();
+        assert_eq!(after.updated_at, before.updated_at);
+    }
+
     #[test]
     fn test_update_nonexistent_issue() {
         let (db, _dir) = setup_test_db();
 
-        let result = run(&db, 99999, Some("New title"), None, None);
+        let result = run(&db, 99999, Some("New title"), FieldUpdate::Keep, None);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_schedule_rejects_out_of_range_due_and_recurrence() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Far off", None, "medium").unwrap();
+
+        let due = schedule(&db, id, FieldUpdate::Set("99999999999d"), FieldUpdate::Keep);
+        assert!(due.unwrap_err().to_string().contains("out of range"));
+        let recur = schedule(&db, id, FieldUpdate::Keep, FieldUpdate::Set("999999999d"));
+        assert!(recur.unwrap_err().to_string().contains("out of range"));
+
+        let issue = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(issue.due_at, None);
+        assert_eq!(db.get_recurrence(id).unwrap(), None);
+    }
+
     #[test]
     fn test_update_invalid_priority() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, None, None, Some("urgent"));
+        let result = run(&db, issue_id, None, FieldUpdate::Keep, Some("urgent"));
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("Invalid priority"));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "invalid priority 'urgent'; valid: low, medium, high, critical"
+        );
     }
 
     #[test]
@@ -145,7 +234,7 @@ mod tests {
             .unwrap();
 
         // Only update title
-        run(&db, issue_id, Some("New title"), None, None).unwrap();
+        run(&db, issue_id, Some("New title"), FieldUpdate::Keep, None).unwrap();
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.title, "New title");
@@ -158,7 +247,13 @@ mod tests {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Original", None, "medium").unwrap();
 
-        let result = run(&db, issue_id, Some("新しいタイトル 🎉"), None, None);
+        let result = run(
+            &db,
+            issue_id,
+            Some("新しいタイトル 🎉"),
+            FieldUpdate::Keep,
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -172,20 +267,34 @@ mod tests {
             .create_issue("Test", Some("Has description"), "medium")
             .unwrap();
 
-        let result = run(&db, issue_id, None, Some(""), None);
+        let result = run(&db, issue_id, None, FieldUpdate::Set(""), None);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
         assert_eq!(issue.description, Some("".to_string()));
     }
 
+    #[test]
+    fn test_update_clear_description() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db
+            .create_issue("Test", Some("Has description"), "medium")
+            .unwrap();
+
+        let result = run(&db, issue_id, None, FieldUpdate::Clear, None);
+        assert!(result.is_ok());
+
+        let issue = db.get_issue(issue_id).unwrap().unwrap();
+        assert_eq!(issue.description, None);
+    }
+
     #[test]
     fn test_update_sql_injection() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Original", None, "medium").unwrap();
 
         let malicious = "'; DROP TABLE issues; --";
-        let result = run(&db, issue_id, Some(malicious), None, None);
+        let result = run(&db, issue_id, Some(malicious), FieldUpdate::Keep, None);
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -200,9 +309,15 @@ mod tests {
     fn test_update_closed_issue() {
         let (db, _dir) = setup_test_db();
         let issue_id = db.create_issue("Test", None, "medium").unwrap();
-        db.close_issue(issue_id).unwrap();
+        db.close_issue(issue_id, None).unwrap();
 
-        let result = run(&db, issue_id, Some("Updated closed issue"), None, None);
+        let result = run(
+            &db,
+            issue_id,
+            Some("Updated closed issue"),
+            FieldUpdate::Keep,
+            None,
+        );
         assert!(result.is_ok());
 
         let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -210,6 +325,36 @@ mod tests {
         assert_eq!(issue.status, "closed"); // Status should remain closed
     }
 
+    #[test]
+    fn test_schedule_sets_and_clears() {
+        let (db, _dir) = setup_test_db();
+        let issue_id = db.create_issue("Chore", None, "medium").unwrap();
+
+        schedule(
+            &db,
+            issue_id,
+            FieldUpdate::Set("2026-05-01"),
+            FieldUpdate::Set("weekly"),
+        )
+        .unwrap();
+        let issue = db.get_issue(issue_id).unwrap().unwrap();
+        assert_eq!(
+            issue.due_at.unwrap().to_rfc3339(),
+            "2026-05-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            db.get_recurrence(issue_id).unwrap().as_deref(),
+            Some("weekly")
+        );
+
+        let err = schedule(&db, issue_id, FieldUpdate::Keep, FieldUpdate::Set("yearly"));
+        assert!(err.unwrap_err().to_string().contains("Invalid recurrence"));
+
+        schedule(&db, issue_id, FieldUpdate::Clear, FieldUpdate::Clear).unwrap();
+        assert_eq!(db.get_issue(issue_id).unwrap().unwrap().due_at, None);
+        assert_eq!(db.get_recurrence(issue_id).unwrap(), None);
+    }
+
     // ==================== Property-Based Tests ====================
 
     proptest! {
@@ -221,7 +366,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue(&original, None, "medium").unwrap();
 
-            run(&db, issue_id, Some(&new_title), None, None).unwrap();
+            run(&db, issue_id, Some(&new_title), FieldUpdate::Keep, None).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.title, new_title);
@@ -232,7 +377,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            let result = run(&db, issue_id, None, None, Some(&priority));
+            let result = run(&db, issue_id, None, FieldUpdate::Keep, Some(&priority));
             prop_assert!(result.is_ok());
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
@@ -249,7 +394,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            let result = run(&db, issue_id, None, None, Some(&priority));
+            let result = run(&db, issue_id, None, FieldUpdate::Keep, Some(&priority));
             prop_assert!(result.is_err());
         }
 
@@ -257,7 +402,7 @@ mod tests {
         fn prop_nonexistent_issue_fails(issue_id in 1000i64..10000) {
             let (db, _dir) = setup_test_db();
 
-            let result = run(&db, issue_id, Some("New title"), None, None);
+            let result = run(&db, issue_id, Some("New title"), FieldUpdate::Keep, None);
             prop_assert!(result.is_err());
         }
 
@@ -266,7 +411,7 @@ mod tests {
             let (db, _dir) = setup_test_db();
             let issue_id = db.create_issue("Test", None, "medium").unwrap();
 
-            run(&db, issue_id, None, Some(&desc), None).unwrap();
+            run(&db, issue_id, None, FieldUpdate::Set(&desc), None).unwrap();
 
             let issue = db.get_issue(issue_id).unwrap().unwrap();
             prop_assert_eq!(issue.description, Some(desc));
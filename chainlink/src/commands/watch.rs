@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+use crate::db::Database;
+use crate::utils::info;
+
+/// Start watching issues, so the daemon reports their status changes and new
+/// comments (see `notifications` in config.toml).
+pub fn watch(db: &Database, ids: &[i64]) -> Result<()> {
+    for &id in ids {
+        db.require_issue(id)?;
+    }
+    for &id in ids {
+        if db.watch_issue(id)? {
+            info(format_args!("Watching #{}", id));
+        } else {
+            info(format_args!("Already watching #{}", id));
+        }
+    }
+    Ok(())
+}
+
+pub fn unwatch(db: &Database, ids: &[i64]) -> Result<()> {
+    for &id in ids {
+        if db.unwatch_issue(id)? {
+            info(format_args!("Stopped watching #{}", id));
+        } else {
+            info(format_args!("Not watching #{}", id));
+        }
+    }
+    Ok(())
+}
+
+pub fn list(db: &Database) -> Result<()> {
+    let watched = db.watched_issues()?;
+    if watched.is_empty() {
+        println!("No watched issues.");
+        return Ok(());
+    }
+    for issue in watched {
+        println!("#{:<4} {:8} {}", issue.id, issue.status, issue.title);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_test_db() -> (Database, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let db = Database::open(&db_path).unwrap();
+        (db, dir)
+    }
+
+    #[test]
+    fn test_watch_and_unwatch() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+
+        watch(&db, &[a, b]).unwrap();
+        watch(&db, &[a]).unwrap();
+        let ids: Vec<i64> = db.watched_issues().unwrap().iter().map(|w| w.id).collect();
+        assert_eq!(ids, vec![a, b]);
+
+        unwatch(&db, &[a, 99]).unwrap();
+        let ids: Vec<i64> = db.watched_issues().unwrap().iter().map(|w| w.id).collect();
+        assert_eq!(ids, vec![b]);
+        list(&db).unwrap();
+    }
+
+    #[test]
+    fn test_watch_missing_issue_watches_nothing() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        assert!(watch(&db, &[a, 99]).is_err());
+        assert!(db.watched_issues().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+//! User settings read from the `.chainlink` directory.
+//!
+//! `config.toml` is the general config file. `default_priority` and
+//! `default_list_status` may also live in `hook-config.json`, which the hooks
+//! own; only those two keys are picked out of it and the rest are ignored.
+//! When both files set a key, `config.toml` wins.
+
+use anyhow::{bail, Context, Result};
+use chrono::format::{Item, StrftimeItems};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+use crate::utils::LabelPolicy;
+
+const FALLBACK_PRIORITY: &str = "medium";
+const FALLBACK_LIST_STATUS: &str = "open";
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// Points per priority level that a due date is worth in `next`
+pub const DEFAULT_DUE_WEIGHT: u16 = 100;
+/// Largest `due_weight` accepted in config.toml
+pub const MAX_DUE_WEIGHT: u16 = 1000;
+
+/// What `delete` does with an issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeleteMode {
+    /// Remove it permanently
+    #[default]
+    Hard,
+    /// Move it to the trash, restorable with `chainlink restore`
+    Trash,
+    /// Close and archive it, keeping all of its data in place
+    Archive,
+}
+
+/// Where the daemon reports changes to watched issues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Notifications {
+    /// Append a line to `.chainlink/notifications.log`
+    Log,
+    /// Show a desktop notification (needs the `notify` feature; logs otherwise)
+    Desktop,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Priority for new issues when `--priority` is not given
+    pub default_priority: Option<String>,
+    /// Status filter for `list` when `--status` is not given
+    pub default_list_status: Option<String>,
+    /// strftime format for absolute dates in `list --absolute`
+    pub date_format: Option<String>,
+    /// `false` disables colored output, like `--no-color`
+    pub color: Option<bool>,
+    /// Project repository, shown in markdown exports and used by `open`
+    pub repo_url: Option<String>,
+    /// Threshold for `archive older` when no day count is given
+    pub auto_archive_days: Option<i64>,
+    /// Most relations `relate` allows per issue; unset means no limit
+    pub max_relations_per_issue: Option<i64>,
+    /// What `delete` does without `--hard` or `--trash`
+    pub delete_behavior: Option<DeleteMode>,
+    /// Whether whitespace in labels becomes hyphens or is refused
+    pub label_policy: Option<LabelPolicy>,
+    /// Points a due date is worth in `next`, per priority level; 0 ignores due dates
+    pub due_weight: Option<u16>,
+    /// How the daemon reports changes to watched issues; unset turns it off
+    pub notifications: Option<Notifications>,
+}
+
+/// The keys of `hook-config.json` that the CLI reads.
+#[derive(Default, Deserialize)]
+struct HookDefaults {
+    default_priority: Option<String>,
+    default_list_status: Option<String>,
+}
+
+impl Config {
+    /// Load the config from `chainlink_dir`. Missing files yield the defaults.
+    pub fn load(chainlink_dir: &Path) -> Result<Config> {
+        let hook_path = chainlink_dir.join("hook-config.json");
+        let hook: HookDefaults = match read(&hook_path)? {
+            Some(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", hook_path.display()))?,
+            None => HookDefaults::default(),
+        };
+        let toml_path = chainlink_dir.join("config.toml");
+        let mut config: Config = match read(&toml_path)? {
+            Some(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", toml_path.display()))?,
+            None => Config::default(),
+        };
+        config.default_priority = config.default_priority.or(hook.default_priority);
+        config.default_list_status = config.default_list_status.or(hook.default_list_status);
+        if let Some(format) = &config.date_format {
+            if !valid_date_format(format) {
+                eprintln!(
+                    "Warning: date_format '{}' in {} is not a valid strftime format; using '{}'",
+                    format,
+                    toml_path.display(),
+                    DEFAULT_DATE_FORMAT
+                );
+                config.date_format = None;
+            }
+        }
+        if let Some(weight) = config.due_weight {
+            if weight > MAX_DUE_WEIGHT {
+                bail!(
+                    "due_weight in {} must be between 0 and {}, got {}",
+                    toml_path.display(),
+                    MAX_DUE_WEIGHT,
+                    weight
+                );
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn priority(&self) -> &str {
+        self.default_priority
+            .as_deref()
+            .unwrap_or(FALLBACK_PRIORITY)
+    }
+
+    pub fn list_status(&self) -> &str {
+        self.default_list_status
+            .as_deref()
+            .unwrap_or(FALLBACK_LIST_STATUS)
+    }
+
+    pub fn date_format(&self) -> &str {
+        self.date_format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT)
+    }
+
+    pub fn delete_mode(&self) -> DeleteMode {
+        self.delete_behavior.unwrap_or_default()
+    }
+
+    pub fn due_weight(&self) -> u16 {
+        self.due_weight.unwrap_or(DEFAULT_DUE_WEIGHT)
+    }
+}
+
+/// Whether chrono can format dates with `format` (formatting an invalid one panics).
+fn valid_date_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+fn read(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    fs::read_to_string(path)
+        .map(Some)
+        .with_context(|| format!("Failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_missing_file_uses_fallbacks() {
+        let dir = tempdir().unwrap();
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.priority(), "medium");
+        assert_eq!(config.list_status(), "open");
+        assert_eq!(config.date_format(), DEFAULT_DATE_FORMAT);
+        assert_eq!(config.color, None);
+        assert_eq!(config.repo_url, None);
+        assert_eq!(config.auto_archive_days, None);
+    }
+
+    #[test]
+    fn test_reads_defaults_and_ignores_hook_keys() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("hook-config.json"),
+            r#"{"tracking_mode": "strict", "default_priority": "high", "default_list_status": "all"}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.priority(), "high");
+        assert_eq!(config.list_status(), "all");
+    }
+
+    #[test]
+    fn test_malformed_file_is_an_error() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("hook-config.json"), "{ not json").unwrap();
+        assert!(Config::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_parses_config_toml() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            r#"
+default_priority = "low"
+date_format = "%d/%m/%Y"
+color = false
+repo_url = "https://example.com/project"
+auto_archive_days = 30
+max_relations_per_issue = 50
+delete_behavior = "archive"
+label_policy = "reject"
+due_weight = 50
+notifications = "log"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.priority(), "low");
+        assert_eq!(config.list_status(), "open");
+        assert_eq!(config.date_format(), "%d/%m/%Y");
+        assert_eq!(config.color, Some(false));
+        assert_eq!(
+            config.repo_url.as_deref(),
+            Some("https://example.com/project")
+        );
+        assert_eq!(config.auto_archive_days, Some(30));
+        assert_eq!(config.max_relations_per_issue, Some(50));
+        assert_eq!(config.delete_mode(), DeleteMode::Archive);
+        assert_eq!(config.label_policy, Some(LabelPolicy::Reject));
+        assert_eq!(config.due_weight(), 50);
+        assert_eq!(config.notifications, Some(Notifications::Log));
+    }
+
+    #[test]
+    fn test_out_of_range_due_weight_is_rejected() {
+        let dir = tempdir().unwrap();
+        for weight in ["-5", "1001", "70000"] {
+            fs::write(
+                dir.path().join("config.toml"),
+                format!("due_weight = {}\n", weight),
+            )
+            .unwrap();
+            assert!(Config::load(dir.path()).is_err(), "accepted {}", weight);
+        }
+    }
+
+    #[test]
+    fn test_config_toml_overrides_hook_config() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("hook-config.json"),
+            r#"{"default_priority": "high", "default_list_status": "all"}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            "default_priority = \"low\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.priority(), "low");
+        assert_eq!(config.list_status(), "all");
+        assert_eq!(config.delete_mode(), DeleteMode::Hard);
+        assert_eq!(config.notifications, None);
+    }
+
+    #[test]
+    fn test_invalid_date_format_falls_back_to_default() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("config.toml"), "date_format = \"%Q\"\n").unwrap();
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config.date_format(), DEFAULT_DATE_FORMAT);
+        assert!(valid_date_format("%d/%m/%Y %H:%M"));
+        assert!(!valid_date_format("%Y-%"));
+    }
+
+    #[test]
+    fn test_unknown_delete_behavior_is_an_error() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("config.toml"),
+            "delete_behavior = \"shred\"\n",
+        )
+        .unwrap();
+        assert!(Config::load(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_malformed_toml_is_an_error() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("config.toml"), "color = maybe").unwrap();
+        assert!(Config::load(dir.path()).is_err());
+    }
+}
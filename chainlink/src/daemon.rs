@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use std::fs;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -8,9 +9,14 @@ use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+use crate::config::{Config, Notifications};
 use crate::db::Database;
+use crate::models::WatchedIssue;
+use crate::utils::humanize_age;
 
 const FLUSH_INTERVAL_SECS: u64 = 30;
+/// Hours since the last `chainlink tested` before the daemon starts nagging.
+const TEST_REMINDER_HOURS: i64 = 4;
 
 pub fn start(chainlink_dir: &Path) -> Result<()> {
     let pid_file = chainlink_dir.join("daemon.pid");
@@ -82,6 +88,11 @@ pub fn stop(chainlink_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// PID of the daemon for `chainlink_dir`, if one is running.
+pub fn running_pid(chainlink_dir: &Path) -> Option<u32> {
+    read_pid(&chainlink_dir.join("daemon.pid")).filter(|&pid| is_process_running(pid))
+}
+
 pub fn status(chainlink_dir: &Path) -> Result<()> {
     let pid_file = chainlink_dir.join("daemon.pid");
 
@@ -116,6 +127,22 @@ pub fn run_daemon(chainlink_dir: &Path) -> Result<()> {
     println!("Watching: {}", chainlink_dir.display());
     println!("Flush interval: {} seconds", FLUSH_INTERVAL_SECS);
 
+    let notifications = match Config::load(chainlink_dir) {
+        Ok(config) => config.notifications,
+        Err(e) => {
+            eprintln!("Watch notifications off: {:#}", e);
+            None
+        }
+    };
+    // Last seen state of watched issues, compared against on every poll
+    let mut watched: Option<Vec<WatchedIssue>> = None;
+    if let Some(mode) = notifications {
+        println!("Watch notifications: {:?}", mode);
+        watched = Database::open(&db_path)
+            .and_then(|db| db.watched_issues())
+            .ok();
+    }
+
     // Zombie prevention: Monitor stdin for closure.
     // When the parent process (VS Code) dies, stdin will be closed.
     // This thread detects that and signals the main loop to exit.
@@ -149,6 +176,8 @@ pub fn run_daemon(chainlink_dir: &Path) -> Result<()> {
         }
     });
 
+    let mut last_reminder: Option<DateTime<Utc>> = None;
+
     loop {
         // Check if we should exit (stdin closed)
         if should_exit.load(Ordering::SeqCst) {
@@ -177,19 +206,108 @@ pub fn run_daemon(chainlink_dir: &Path) -> Result<()> {
                     if let Err(e) = fs::write(&session_file, json) {
                         eprintln!("Failed to write session file: {}", e);
                     } else {
-                        println!(
-                            "Session flushed at {}",
-                            chrono::Utc::now().format("%H:%M:%S")
-                        );
+                        println!("Session flushed at {}", Utc::now().format("%H:%M:%S"));
                     }
                 }
             }
+
+            // Remind about stale test runs, at most once per reminder window
+            let now = Utc::now();
+            let recently_reminded = last_reminder
+                .is_some_and(|at| now - at < chrono::Duration::hours(TEST_REMINDER_HOURS));
+            if !recently_reminded {
+                if let Some(message) = test_reminder(db.get_last_tested().ok().flatten(), now) {
+                    println!("{}", message);
+                    last_reminder = Some(now);
+                }
+            }
+
+            if let Some(mode) = notifications {
+                match db.watched_issues() {
+                    Ok(current) => {
+                        if let Some(before) = &watched {
+                            for change in watch_changes(before, &current) {
+                                notify(chainlink_dir, mode, &change);
+                            }
+                        }
+                        watched = Some(current);
+                    }
+                    Err(e) => eprintln!("Failed to read watched issues: {}", e),
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// One message per status change or batch of new comments on a watched issue
+/// between two polls. Issues watched since `before` was taken are only
+/// reported from the next poll on.
+fn watch_changes(before: &[WatchedIssue], after: &[WatchedIssue]) -> Vec<String> {
+    let mut changes = Vec::new();
+    for issue in after {
+        let Some(prev) = before.iter().find(|prev| prev.id == issue.id) else {
+            continue;
+        };
+        if issue.status != prev.status {
+            changes.push(format!(
+                "#{} {}: {} → {}",
+                issue.id, issue.title, prev.status, issue.status
+            ));
+        }
+        if issue.comment_count > prev.comment_count {
+            changes.push(format!(
+                "#{} {}: {} new comment(s)",
+                issue.id,
+                issue.title,
+                issue.comment_count - prev.comment_count
+            ));
+        }
+    }
+    changes
+}
+
+fn notify(chainlink_dir: &Path, mode: Notifications, message: &str) {
+    println!("{}", message);
+    #[cfg(feature = "notify")]
+    if mode == Notifications::Desktop {
+        match notify_rust::Notification::new()
+            .summary("chainlink")
+            .body(message)
+            .show()
+        {
+            Ok(_) => return,
+            Err(e) => eprintln!("Desktop notification failed, logging instead: {}", e),
+        }
+    }
+    #[cfg(not(feature = "notify"))]
+    let _ = mode;
+
+    let log_path = chainlink_dir.join("notifications.log");
+    let line = format!("{} {}\n", Utc::now().to_rfc3339(), message);
+    let written = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+    if let Err(e) = written {
+        eprintln!("Failed to write {}: {}", log_path.display(), e);
+    }
+}
+
+/// Reminder text when tests haven't been marked as run within `TEST_REMINDER_HOURS`.
+fn test_reminder(last_tested: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<String> {
+    match last_tested {
+        None => Some("Reminder: tests have never been marked as run".to_string()),
+        Some(at) if now - at >= chrono::Duration::hours(TEST_REMINDER_HOURS) => Some(format!(
+            "Reminder: tests last run {}",
+            humanize_age(at, now)
+        )),
+        Some(_) => None,
+    }
+}
+
 fn read_pid(pid_file: &Path) -> Option<u32> {
     let mut file = fs::File::open(pid_file).ok()?;
     let mut contents = String::new();
@@ -239,3 +357,58 @@ fn kill_process(pid: u32) -> Result<()> {
         .context("Failed to kill process")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(id: i64, status: &str, comment_count: usize) -> WatchedIssue {
+        WatchedIssue {
+            id,
+            title: format!("Issue {}", id),
+            status: status.to_string(),
+            comment_count,
+        }
+    }
+
+    #[test]
+    fn test_watch_changes_reports_status_and_new_comments() {
+        let before = vec![
+            snapshot(1, "open", 0),
+            snapshot(2, "open", 3),
+            snapshot(3, "open", 1),
+        ];
+        let after = vec![
+            snapshot(1, "closed", 0),
+            snapshot(2, "open", 5),
+            snapshot(3, "open", 1),
+            // Watched after the last poll
+            snapshot(4, "closed", 2),
+        ];
+        assert_eq!(
+            watch_changes(&before, &after),
+            vec![
+                "#1 Issue 1: open → closed".to_string(),
+                "#2 Issue 2: 2 new comment(s)".to_string(),
+            ]
+        );
+        assert!(watch_changes(&after, &after).is_empty());
+        // Unwatched or deleted issues aren't reported
+        assert!(watch_changes(&before, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_notify_appends_to_log() {
+        let dir = tempfile::tempdir().unwrap();
+        notify(dir.path(), Notifications::Log, "#1 Issue 1: open → closed");
+        notify(
+            dir.path(),
+            Notifications::Log,
+            "#1 Issue 1: 1 new comment(s)",
+        );
+        let log = fs::read_to_string(dir.path().join("notifications.log")).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" #1 Issue 1: open → closed"));
+    }
+}
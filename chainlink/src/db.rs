@@ -1,24 +1,433 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use regex::Regex;
+use rusqlite::functions::FunctionFlags;
 use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use crate::models::{Comment, Issue, Session};
+use crate::models::{
+    ActivityItem, Attachment, Comment, Issue, Session, TimeEntry, TrashedIssue, WatchedIssue,
+};
+use crate::utils::{normalize_label, title_similarity, LabelPolicy};
 
-const SCHEMA_VERSION: i32 = 8;
+pub const SCHEMA_VERSION: i32 = 22;
+
+/// Minimum `title_similarity` for an open issue to count as a likely duplicate.
+const SIMILAR_TITLE_THRESHOLD: f64 = 0.6;
 
 pub struct Database {
     conn: Connection,
+    /// Most relations `add_relation` allows per issue; None means no limit
+    max_relations: Option<i64>,
+    /// How `add_label` treats whitespace in labels
+    label_policy: LabelPolicy,
+}
+
+/// The order `list_issues_excluding` returns issues in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IssueOrder {
+    /// Highest id first
+    #[default]
+    Newest,
+    /// By `sort_order` as set with `rank`; unranked issues follow, newest first
+    Manual,
+}
+
+impl std::str::FromStr for IssueOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "newest" => Ok(IssueOrder::Newest),
+            "manual" => Ok(IssueOrder::Manual),
+            _ => Err(format!("expected 'newest' or 'manual', got '{}'", s)),
+        }
+    }
+}
+
+/// Rank of a priority, higher is more important; 0 for unknown priorities.
+pub fn priority_rank(priority: &str) -> i32 {
+    match priority {
+        "critical" => 4,
+        "high" => 3,
+        "medium" => 2,
+        "low" => 1,
+        _ => 0,
+    }
+}
+
+/// Whether an issue with `status` is finished with: closed or won't do.
+/// Milestone and subissue progress count both as done.
+pub fn is_done(status: &str) -> bool {
+    matches!(status, "closed" | "wontdo")
+}
+
+/// The statuses `is_done` accepts, as an SQL list.
+const DONE_STATUSES_SQL: &str = "('closed', 'wontdo')";
+
+/// `priority_rank(i.priority)` in SQL.
+const PRIORITY_RANK_SQL: &str = "CASE i.priority WHEN 'critical' THEN 4 WHEN 'high' THEN 3 WHEN 'medium' THEN 2 WHEN 'low' THEN 1 ELSE 0 END";
+
+/// How a `PriorityFilter::Rank` compares an issue's priority rank to its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn sql(self) -> &'static str {
+        match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+        }
+    }
+}
+
+/// Which priorities `list_issues_excluding` keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityFilter<'a> {
+    /// Exactly this priority
+    Is(&'a str),
+    /// Priorities whose `priority_rank` compares this way to the given rank
+    Rank(Comparison, i32),
+}
+
+/// How an optional column should be changed by an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldUpdate<T> {
+    /// Leave the current value untouched
+    Keep,
+    /// Replace the value
+    Set(T),
+    /// Set the column to NULL
+    Clear,
+}
+
+impl<T> FieldUpdate<T> {
+    pub fn is_keep(&self) -> bool {
+        matches!(self, FieldUpdate::Keep)
+    }
+}
+
+impl<T> From<Option<T>> for FieldUpdate<T> {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(FieldUpdate::Keep, FieldUpdate::Set)
+    }
+}
+
+/// The `FROM`/`WHERE` part shared by the search queries; `?1` is the
+/// pattern from `search_pattern`.
+const SEARCH_MATCHES_SQL: &str = r#"
+    FROM issues i
+    LEFT JOIN comments c ON i.id = c.issue_id
+    LEFT JOIN labels l ON i.id = l.issue_id
+    WHERE i.title LIKE ?1 ESCAPE '\' COLLATE NOCASE
+       OR i.description LIKE ?1 ESCAPE '\' COLLATE NOCASE
+       OR c.content LIKE ?1 ESCAPE '\' COLLATE NOCASE
+       OR l.label LIKE ?1 ESCAPE '\' COLLATE NOCASE
+"#;
+
+/// Issues whose title, description or any comment matches `?1` as a regex.
+const REGEX_MATCHES_SQL: &str = r#"
+    FROM issues i
+    LEFT JOIN comments c ON i.id = c.issue_id
+    WHERE i.title REGEXP ?1
+       OR i.description REGEXP ?1
+       OR c.content REGEXP ?1
+"#;
+
+/// Define SQLite's `REGEXP` operator (`text REGEXP pattern` calls
+/// `regexp(pattern, text)`), compiling each pattern once per statement.
+fn register_regexp(conn: &Connection) -> Result<()> {
+    type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+    conn.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let re: std::sync::Arc<Regex> = ctx
+                .get_or_create_aux(0, |vr| -> std::result::Result<_, BoxError> {
+                    Ok(Regex::new(vr.as_str()?)?)
+                })?;
+            let text = ctx
+                .get_raw(1)
+                .as_str_or_null()
+                .map_err(|e| rusqlite::Error::UserFunctionError(e.into()))?;
+            Ok(text.is_some_and(|t| re.is_match(t)))
+        },
+    )?;
+    Ok(())
+}
+
+/// A LIKE pattern matching `query` anywhere, with its wildcards escaped
+/// so they match literally.
+fn search_pattern(query: &str) -> String {
+    let escaped = query.replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Open issues with at least one open blocker. `CROSS JOIN` pins the join
+/// order so blockers are looked up through `idx_deps_blocked_blocker`.
+const BLOCKED_ISSUES_SQL: &str = r#"
+    SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at
+    FROM issues i
+    CROSS JOIN dependencies d ON i.id = d.blocked_id
+    CROSS JOIN issues blocker ON d.blocker_id = blocker.id
+    WHERE i.status = 'open' AND blocker.status = 'open'
+    ORDER BY i.id
+"#;
+
+/// Open issues with no open blocker. The inner join means a dependency row
+/// pointing at a missing blocker (left behind if foreign keys were ever off)
+/// never blocks; `CROSS JOIN` makes SQLite drive the subquery from
+/// `idx_deps_blocked_blocker` instead of scanning every open issue.
+const READY_ISSUES_SQL: &str = r#"
+    SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at
+    FROM issues i
+    WHERE i.status = 'open'
+    AND NOT EXISTS (
+        SELECT 1 FROM dependencies d
+        CROSS JOIN issues blocker ON d.blocker_id = blocker.id
+        WHERE d.blocked_id = i.id
+        AND blocker.status = 'open'
+    )
+    ORDER BY i.id
+"#;
+
+/// A schema upgrade step, run when the database is older than its version.
+/// Fresh databases run every step too, so each must be a no-op on a schema
+/// that already has the change.
+type Migration = (i32, fn(&Connection) -> Result<()>);
+
+/// Upgrade steps in the order they are applied.
+const MIGRATIONS: &[Migration] = &[
+    (2, migrate_add_parent_id),
+    (7, migrate_sessions_set_null),
+    (8, migrate_add_last_action),
+    (9, migrate_add_source),
+    (11, migrate_drop_deps_blocked_index),
+    (13, migrate_add_resolution),
+    (14, migrate_add_comment_pinned),
+    (15, migrate_add_recurrence),
+    (16, migrate_add_relation_note),
+    (18, migrate_add_authors),
+    (19, migrate_add_sort_order),
+    (21, migrate_add_archived_from),
+    (22, migrate_add_archived_at),
+];
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(names.iter().any(|name| name == column))
+}
+
+fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    if !has_column(conn, table, column)? {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// v2: subissues
+fn migrate_add_parent_id(conn: &Connection) -> Result<()> {
+    add_column(
+        conn,
+        "issues",
+        "parent_id",
+        "INTEGER REFERENCES issues(id) ON DELETE CASCADE",
+    )
+}
+
+/// v7: recreate sessions with ON DELETE SET NULL for active_issue_id, so
+/// deleting an issue clears the session reference instead of failing.
+/// Skipped when the reference already does that, since the rebuild would
+/// drop columns added by later migrations (e.g. `last_action`).
+fn migrate_sessions_set_null(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA foreign_key_list(sessions)")?;
+    let on_delete = stmt
+        .query_map([], |row| row.get::<_, String>(6))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if on_delete.iter().any(|action| action == "SET NULL") {
+        return Ok(());
+    }
+    conn.execute_batch(
+        r#"
+        DROP TABLE IF EXISTS sessions_new;
+        CREATE TABLE sessions_new (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            started_at TEXT NOT NULL,
+            ended_at TEXT,
+            active_issue_id INTEGER,
+            handoff_notes TEXT,
+            FOREIGN KEY (active_issue_id) REFERENCES issues(id) ON DELETE SET NULL
+        );
+        INSERT INTO sessions_new (id, started_at, ended_at, active_issue_id, handoff_notes)
+            SELECT id, started_at, ended_at, active_issue_id, handoff_notes FROM sessions;
+        DROP TABLE sessions;
+        ALTER TABLE sessions_new RENAME TO sessions;
+        "#,
+    )?;
+    Ok(())
+}
+
+/// v8: last_action on sessions
+fn migrate_add_last_action(conn: &Connection) -> Result<()> {
+    add_column(conn, "sessions", "last_action", "TEXT")
+}
+
+/// v9: record where each issue came from (manual, import, cpitd)
+fn migrate_add_source(conn: &Connection) -> Result<()> {
+    add_column(conn, "issues", "source", "TEXT NOT NULL DEFAULT 'manual'")
+}
+
+/// v11: idx_deps_blocked is a prefix of idx_deps_blocked_blocker
+fn migrate_drop_deps_blocked_index(conn: &Connection) -> Result<()> {
+    conn.execute("DROP INDEX IF EXISTS idx_deps_blocked", [])?;
+    Ok(())
+}
+
+/// v13: how a closed issue was resolved (fixed, wontfix, ...)
+fn migrate_add_resolution(conn: &Connection) -> Result<()> {
+    add_column(conn, "issues", "resolution", "TEXT")
+}
+
+/// v14: pinned comments
+fn migrate_add_comment_pinned(conn: &Connection) -> Result<()> {
+    add_column(conn, "comments", "pinned", "INTEGER NOT NULL DEFAULT 0")
+}
+
+/// v15: due dates and recurring issues
+fn migrate_add_recurrence(conn: &Connection) -> Result<()> {
+    add_column(conn, "issues", "due_at", "TEXT")?;
+    add_column(conn, "issues", "recurrence", "TEXT")
+}
+
+/// v16: a note on why two issues are related
+fn migrate_add_relation_note(conn: &Connection) -> Result<()> {
+    add_column(conn, "relations", "note", "TEXT")
+}
+
+/// v18: who wrote each comment and who closed each issue
+fn migrate_add_authors(conn: &Connection) -> Result<()> {
+    add_column(conn, "comments", "author", "TEXT")?;
+    add_column(conn, "issues", "closed_by", "TEXT")
+}
+
+/// v19: manual ranking (`rank`, `list --sort manual`)
+fn migrate_add_sort_order(conn: &Connection) -> Result<()> {
+    add_column(conn, "issues", "sort_order", "REAL")
+}
+
+/// v21: remember whether an archived issue was closed or won't do
+fn migrate_add_archived_from(conn: &Connection) -> Result<()> {
+    add_column(conn, "issues", "archived_from", "TEXT")
+}
+
+/// v22: record when an issue was archived, so later edits don't reset the
+/// `archive purge --older` clock. Issues archived earlier fall back to their
+/// last update.
+fn migrate_add_archived_at(conn: &Connection) -> Result<()> {
+    add_column(conn, "issues", "archived_at", "TEXT")?;
+    conn.execute(
+        "UPDATE issues SET archived_at = updated_at WHERE status = 'archived' AND archived_at IS NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Cutoff timestamp for issues archived more than `days` ago. Counts too large
+/// to subtract from today reach back to the earliest representable date.
+fn archived_before(days: u32) -> String {
+    Utc::now()
+        .checked_sub_signed(chrono::Duration::days(i64::from(days)))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+        .to_rfc3339()
+}
+
+/// Archived issues that `purge_archived` may delete: archived (more than ?1 days
+/// ago, when ?1 is set) and with no kept issue below them, since deleting an
+/// issue deletes its subissues too.
+const PURGEABLE_CTE: &str = r#"
+    WITH RECURSIVE kept(id, parent_id) AS (
+        SELECT id, parent_id FROM issues
+            WHERE status != 'archived' OR (?1 IS NOT NULL AND archived_at >= ?1)
+        UNION
+        SELECT p.id, p.parent_id FROM issues p JOIN kept k ON p.id = k.parent_id
+    ),
+    purgeable(id) AS (
+        SELECT id FROM issues
+            WHERE status = 'archived' AND (?1 IS NULL OR archived_at < ?1)
+            AND id NOT IN (SELECT id FROM kept)
+    )
+"#;
+
+/// Whether `e` means another connection holds a lock we need.
+fn is_busy(e: &rusqlite::Error) -> bool {
+    matches!(
+        e,
+        rusqlite::Error::SqliteFailure(err, _)
+            if matches!(err.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+    )
 }
 
 impl Database {
     pub fn open(path: &Path) -> Result<Self> {
         let conn = Connection::open(path).context("Failed to open database")?;
-        let db = Database { conn };
+        register_regexp(&conn)?;
+        let db = Database {
+            conn,
+            max_relations: None,
+            label_policy: LabelPolicy::default(),
+        };
+        // Tables from a newer schema may have columns or meanings this build
+        // doesn't know about, so don't touch them at all
+        let version = db.schema_version()?;
+        if version > SCHEMA_VERSION {
+            anyhow::bail!(
+                "{} was created by a newer chainlink (schema v{}, this build supports up to v{}); upgrade chainlink to use it",
+                path.display(),
+                version,
+                SCHEMA_VERSION
+            );
+        }
         db.init_schema()?;
         Ok(db)
     }
 
+    /// Cap the number of relations any one issue may have (see `add_relation`).
+    pub fn set_max_relations(&mut self, max: Option<i64>) {
+        self.max_relations = max;
+    }
+
+    pub fn set_label_policy(&mut self, policy: LabelPolicy) {
+        self.label_policy = policy;
+    }
+
+    /// The form `add_label` stores `label` in, or why it refuses it.
+    pub fn normalize_label(&self, label: &str) -> Result<String> {
+        normalize_label(label, self.label_policy)
+    }
+
+    /// The stored form to look `label` up by. A label `add_label` would refuse
+    /// is looked up as given, so labels stored before validation can still be found.
+    pub fn label_key(&self, label: &str) -> String {
+        self.normalize_label(label)
+            .unwrap_or_else(|_| label.to_string())
+    }
+
     /// Execute a closure within a database transaction.
     /// If the closure returns Ok, the transaction is committed.
     /// If the closure returns Err, the transaction is rolled back.
@@ -27,6 +436,32 @@ impl Database {
         F: FnOnce() -> Result<T>,
     {
         self.conn.execute("BEGIN TRANSACTION", [])?;
+        self.finish_transaction(f)
+    }
+
+    /// Like `transaction`, but takes the write lock up front (`BEGIN IMMEDIATE`)
+    /// so another process can't write between our reads and writes. Waits for
+    /// the busy timeout if someone else holds the lock, then gives up with a
+    /// "database busy" error.
+    pub fn transaction_immediate<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T>,
+    {
+        if let Err(e) = self.conn.execute("BEGIN IMMEDIATE", []) {
+            if is_busy(&e) {
+                anyhow::bail!(
+                    "Database is busy (another chainlink command is writing to it); try again"
+                );
+            }
+            return Err(e.into());
+        }
+        self.finish_transaction(f)
+    }
+
+    fn finish_transaction<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T>,
+    {
         match f() {
             Ok(result) => {
                 self.conn.execute("COMMIT", [])?;
@@ -41,158 +476,181 @@ impl Database {
 
     fn init_schema(&self) -> Result<()> {
         // Check if we need to initialize
-        let version: i32 = self
-            .conn
-            .query_row(
-                "SELECT COALESCE(MAX(version), 0) FROM pragma_user_version",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or(0);
+        let version = self.schema_version()?;
 
         if version < SCHEMA_VERSION {
-            self.conn.execute_batch(
-                r#"
-                -- Core issues table
-                CREATE TABLE IF NOT EXISTS issues (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    title TEXT NOT NULL,
-                    description TEXT,
-                    status TEXT NOT NULL DEFAULT 'open',
-                    priority TEXT NOT NULL DEFAULT 'medium',
-                    parent_id INTEGER,
-                    created_at TEXT NOT NULL,
-                    updated_at TEXT NOT NULL,
-                    closed_at TEXT,
-                    FOREIGN KEY (parent_id) REFERENCES issues(id) ON DELETE CASCADE
-                );
-
-                -- Labels (many-to-many)
-                CREATE TABLE IF NOT EXISTS labels (
-                    issue_id INTEGER NOT NULL,
-                    label TEXT NOT NULL,
-                    PRIMARY KEY (issue_id, label),
-                    FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
-                );
+            self.transaction(|| {
+                self.conn.execute_batch(
+                    r#"
+                    -- Core issues table
+                    CREATE TABLE IF NOT EXISTS issues (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        title TEXT NOT NULL,
+                        description TEXT,
+                        status TEXT NOT NULL DEFAULT 'open',
+                        priority TEXT NOT NULL DEFAULT 'medium',
+                        parent_id INTEGER,
+                        created_at TEXT NOT NULL,
+                        updated_at TEXT NOT NULL,
+                        closed_at TEXT,
+                        source TEXT NOT NULL DEFAULT 'manual',
+                        resolution TEXT,
+                        due_at TEXT,
+                        recurrence TEXT,
+                        closed_by TEXT,
+                        sort_order REAL,
+                        archived_from TEXT,
+                        archived_at TEXT,
+                        FOREIGN KEY (parent_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-                -- Dependencies (blocker blocks blocked)
-                CREATE TABLE IF NOT EXISTS dependencies (
-                    blocker_id INTEGER NOT NULL,
-                    blocked_id INTEGER NOT NULL,
-                    PRIMARY KEY (blocker_id, blocked_id),
-                    FOREIGN KEY (blocker_id) REFERENCES issues(id) ON DELETE CASCADE,
-                    FOREIGN KEY (blocked_id) REFERENCES issues(id) ON DELETE CASCADE
-                );
+                    -- Labels (many-to-many)
+                    CREATE TABLE IF NOT EXISTS labels (
+                        issue_id INTEGER NOT NULL,
+                        label TEXT NOT NULL,
+                        PRIMARY KEY (issue_id, label),
+                        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-                -- Comments
-                CREATE TABLE IF NOT EXISTS comments (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    issue_id INTEGER NOT NULL,
-                    content TEXT NOT NULL,
-                    created_at TEXT NOT NULL,
-                    FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
-                );
+                    -- Dependencies (blocker blocks blocked)
+                    CREATE TABLE IF NOT EXISTS dependencies (
+                        blocker_id INTEGER NOT NULL,
+                        blocked_id INTEGER NOT NULL,
+                        PRIMARY KEY (blocker_id, blocked_id),
+                        FOREIGN KEY (blocker_id) REFERENCES issues(id) ON DELETE CASCADE,
+                        FOREIGN KEY (blocked_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-                -- Sessions (for context preservation)
-                CREATE TABLE IF NOT EXISTS sessions (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    started_at TEXT NOT NULL,
-                    ended_at TEXT,
-                    active_issue_id INTEGER,
-                    handoff_notes TEXT,
-                    FOREIGN KEY (active_issue_id) REFERENCES issues(id) ON DELETE SET NULL
-                );
+                    -- Comments
+                    CREATE TABLE IF NOT EXISTS comments (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        issue_id INTEGER NOT NULL,
+                        content TEXT NOT NULL,
+                        created_at TEXT NOT NULL,
+                        pinned INTEGER NOT NULL DEFAULT 0,
+                        author TEXT,
+                        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-                -- Time tracking
-                CREATE TABLE IF NOT EXISTS time_entries (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    issue_id INTEGER NOT NULL,
-                    started_at TEXT NOT NULL,
-                    ended_at TEXT,
-                    duration_seconds INTEGER,
-                    FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
-                );
+                    -- Sessions (for context preservation)
+                    CREATE TABLE IF NOT EXISTS sessions (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        started_at TEXT NOT NULL,
+                        ended_at TEXT,
+                        active_issue_id INTEGER,
+                        handoff_notes TEXT,
+                        FOREIGN KEY (active_issue_id) REFERENCES issues(id) ON DELETE SET NULL
+                    );
 
-                -- Relations (related issues, bidirectional)
-                CREATE TABLE IF NOT EXISTS relations (
-                    issue_id_1 INTEGER NOT NULL,
-                    issue_id_2 INTEGER NOT NULL,
-                    created_at TEXT NOT NULL,
-                    PRIMARY KEY (issue_id_1, issue_id_2),
-                    FOREIGN KEY (issue_id_1) REFERENCES issues(id) ON DELETE CASCADE,
-                    FOREIGN KEY (issue_id_2) REFERENCES issues(id) ON DELETE CASCADE
-                );
+                    -- Time tracking
+                    CREATE TABLE IF NOT EXISTS time_entries (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        issue_id INTEGER NOT NULL,
+                        started_at TEXT NOT NULL,
+                        ended_at TEXT,
+                        duration_seconds INTEGER,
+                        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-                -- Milestones
-                CREATE TABLE IF NOT EXISTS milestones (
-                    id INTEGER PRIMARY KEY AUTOINCREMENT,
-                    name TEXT NOT NULL,
-                    description TEXT,
-                    status TEXT NOT NULL DEFAULT 'open',
-                    created_at TEXT NOT NULL,
-                    closed_at TEXT
-                );
+                    -- Relations (related issues, bidirectional)
+                    CREATE TABLE IF NOT EXISTS relations (
+                        issue_id_1 INTEGER NOT NULL,
+                        issue_id_2 INTEGER NOT NULL,
+                        created_at TEXT NOT NULL,
+                        note TEXT,
+                        PRIMARY KEY (issue_id_1, issue_id_2),
+                        FOREIGN KEY (issue_id_1) REFERENCES issues(id) ON DELETE CASCADE,
+                        FOREIGN KEY (issue_id_2) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-                -- Milestone-Issue relationship (many-to-many)
-                CREATE TABLE IF NOT EXISTS milestone_issues (
-                    milestone_id INTEGER NOT NULL,
-                    issue_id INTEGER NOT NULL,
-                    PRIMARY KEY (milestone_id, issue_id),
-                    FOREIGN KEY (milestone_id) REFERENCES milestones(id) ON DELETE CASCADE,
-                    FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
-                );
+                    -- Milestones
+                    CREATE TABLE IF NOT EXISTS milestones (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        name TEXT NOT NULL,
+                        description TEXT,
+                        status TEXT NOT NULL DEFAULT 'open',
+                        created_at TEXT NOT NULL,
+                        closed_at TEXT
+                    );
 
-                -- Indexes
-                CREATE INDEX IF NOT EXISTS idx_issues_status ON issues(status);
-                CREATE INDEX IF NOT EXISTS idx_issues_priority ON issues(priority);
-                CREATE INDEX IF NOT EXISTS idx_labels_issue ON labels(issue_id);
-                CREATE INDEX IF NOT EXISTS idx_comments_issue ON comments(issue_id);
-                CREATE INDEX IF NOT EXISTS idx_deps_blocker ON dependencies(blocker_id);
-                CREATE INDEX IF NOT EXISTS idx_deps_blocked ON dependencies(blocked_id);
-                CREATE INDEX IF NOT EXISTS idx_issues_parent ON issues(parent_id);
-                CREATE INDEX IF NOT EXISTS idx_time_entries_issue ON time_entries(issue_id);
-                CREATE INDEX IF NOT EXISTS idx_relations_1 ON relations(issue_id_1);
-                CREATE INDEX IF NOT EXISTS idx_relations_2 ON relations(issue_id_2);
-                CREATE INDEX IF NOT EXISTS idx_milestone_issues_m ON milestone_issues(milestone_id);
-                CREATE INDEX IF NOT EXISTS idx_milestone_issues_i ON milestone_issues(issue_id);
-                "#,
-            )?;
+                    -- Milestone-Issue relationship (many-to-many)
+                    CREATE TABLE IF NOT EXISTS milestone_issues (
+                        milestone_id INTEGER NOT NULL,
+                        issue_id INTEGER NOT NULL,
+                        PRIMARY KEY (milestone_id, issue_id),
+                        FOREIGN KEY (milestone_id) REFERENCES milestones(id) ON DELETE CASCADE,
+                        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-            // Migration: add parent_id column if upgrading from v1
-            let _ = self.conn.execute(
-                "ALTER TABLE issues ADD COLUMN parent_id INTEGER REFERENCES issues(id) ON DELETE CASCADE",
-                [],
-            );
+                    -- Files and links attached to issues (stored as given, not copied)
+                    CREATE TABLE IF NOT EXISTS attachments (
+                        id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        issue_id INTEGER NOT NULL,
+                        path_or_url TEXT NOT NULL,
+                        description TEXT,
+                        created_at TEXT NOT NULL,
+                        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
 
-            // Migration v7: Recreate sessions table with ON DELETE SET NULL for active_issue_id
-            // This ensures deleting an issue clears the session reference instead of failing
-            if version < 7 {
-                let _ = self.conn.execute_batch(
-                    r#"
-                    CREATE TABLE IF NOT EXISTS sessions_new (
+                    -- Deleted issues, restorable with `chainlink restore`
+                    CREATE TABLE IF NOT EXISTS trash (
                         id INTEGER PRIMARY KEY AUTOINCREMENT,
-                        started_at TEXT NOT NULL,
-                        ended_at TEXT,
-                        active_issue_id INTEGER,
-                        handoff_notes TEXT,
-                        FOREIGN KEY (active_issue_id) REFERENCES issues(id) ON DELETE SET NULL
+                        issue_id INTEGER NOT NULL,
+                        title TEXT NOT NULL,
+                        subissue_count INTEGER NOT NULL DEFAULT 0,
+                        snapshot TEXT NOT NULL,
+                        deleted_at TEXT NOT NULL
+                    );
+
+                    -- Issues the daemon reports changes to (`chainlink watch`)
+                    CREATE TABLE IF NOT EXISTS watches (
+                        issue_id INTEGER PRIMARY KEY,
+                        created_at TEXT NOT NULL,
+                        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+                    );
+
+                    -- Key/value store for tracker-wide state (e.g. last test run)
+                    CREATE TABLE IF NOT EXISTS metadata (
+                        key TEXT PRIMARY KEY,
+                        value TEXT NOT NULL
                     );
-                    INSERT OR IGNORE INTO sessions_new SELECT * FROM sessions;
-                    DROP TABLE IF EXISTS sessions;
-                    ALTER TABLE sessions_new RENAME TO sessions;
                     "#,
-                );
-            }
+                )?;
+
+                // Bring tables that predate the current schema up to date before
+                // indexing columns they may not have had yet
+                for (to_version, migrate) in MIGRATIONS {
+                    if version < *to_version {
+                        migrate(&self.conn).with_context(|| {
+                            format!("Migration to schema v{} failed", to_version)
+                        })?;
+                    }
+                }
 
-            // Migration v8: Add last_action column to sessions table
-            if version < 8 {
-                let _ = self
-                    .conn
-                    .execute("ALTER TABLE sessions ADD COLUMN last_action TEXT", []);
-            }
+                self.conn.execute_batch(
+                    r#"
+                    -- Indexes
+                    CREATE INDEX IF NOT EXISTS idx_issues_status ON issues(status);
+                    CREATE INDEX IF NOT EXISTS idx_issues_priority ON issues(priority);
+                    CREATE INDEX IF NOT EXISTS idx_labels_issue ON labels(issue_id);
+                    CREATE INDEX IF NOT EXISTS idx_comments_issue ON comments(issue_id);
+                    CREATE INDEX IF NOT EXISTS idx_deps_blocker ON dependencies(blocker_id);
+                    -- Covers "what blocks X" lookups in ready/blocked without touching the table
+                    CREATE INDEX IF NOT EXISTS idx_deps_blocked_blocker ON dependencies(blocked_id, blocker_id);
+                    CREATE INDEX IF NOT EXISTS idx_issues_parent ON issues(parent_id);
+                    CREATE INDEX IF NOT EXISTS idx_time_entries_issue ON time_entries(issue_id);
+                    CREATE INDEX IF NOT EXISTS idx_relations_1 ON relations(issue_id_1);
+                    CREATE INDEX IF NOT EXISTS idx_relations_2 ON relations(issue_id_2);
+                    CREATE INDEX IF NOT EXISTS idx_milestone_issues_m ON milestone_issues(milestone_id);
+                    CREATE INDEX IF NOT EXISTS idx_milestone_issues_i ON milestone_issues(issue_id);
+                    CREATE INDEX IF NOT EXISTS idx_attachments_issue ON attachments(issue_id);
+                    "#,
+                )?;
 
-            self.conn
-                .execute(&format!("PRAGMA user_version = {}", SCHEMA_VERSION), [])?;
+                self.conn
+                    .execute(&format!("PRAGMA user_version = {}", SCHEMA_VERSION), [])?;
+                Ok(())
+            })?;
         }
 
         // Enable foreign keys
@@ -229,16 +687,87 @@ impl Database {
         parent_id: Option<i64>,
     ) -> Result<i64> {
         let now = Utc::now().to_rfc3339();
-        self.conn.execute(
-            "INSERT INTO issues (title, description, priority, parent_id, status, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, 'open', ?5, ?5)",
-            params![title, description, priority, parent_id, now],
-        )?;
+        // Cached: imports and bulk operations create issues in tight loops
+        self.conn
+            .prepare_cached(
+                "INSERT INTO issues (title, description, priority, parent_id, status, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, 'open', ?5, ?5)",
+            )?
+            .execute(params![title, description, priority, parent_id, now])?;
         Ok(self.conn.last_insert_rowid())
     }
 
+    /// Create an issue with an explicit id (used by `import --preserve-ids`).
+    pub fn create_issue_with_id(
+        &self,
+        id: i64,
+        title: &str,
+        description: Option<&str>,
+        priority: &str,
+    ) -> Result<i64> {
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .prepare_cached(
+                "INSERT INTO issues (id, title, description, priority, status, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, 'open', ?5, ?5)",
+            )?
+            .execute(params![id, title, description, priority, now])
+            .with_context(|| format!("Failed to create issue #{}", id))?;
+        Ok(id)
+    }
+
+    /// Record where an issue came from (e.g. "import", "cpitd"); new issues default to "manual".
+    pub fn set_issue_source(&self, id: i64, source: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE issues SET source = ?1 WHERE id = ?2",
+            params![source, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// The schema version recorded in the database (`PRAGMA user_version`).
+    pub fn schema_version(&self) -> Result<i32> {
+        let version = self
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        Ok(version)
+    }
+
+    pub fn count_all_comments(&self) -> Result<i64> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM comments", [], |row| row.get(0))?;
+        Ok(count)
+    }
+
+    pub fn count_issues(&self) -> Result<i64> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM issues", [], |row| row.get(0))?;
+        Ok(count)
+    }
+
+    /// Open issues whose titles closely match `title`, most similar first.
+    pub fn find_similar_titles(&self, title: &str) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, title FROM issues WHERE status = 'open'")?;
+        let mut scored: Vec<(f64, i64, String)> = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(|(id, existing)| {
+                let score = title_similarity(title, &existing);
+                (score >= SIMILAR_TITLE_THRESHOLD).then_some((score, id, existing))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        Ok(scored.into_iter().map(|(_, id, t)| (id, t)).collect())
+    }
+
     pub fn get_subissues(&self, parent_id: i64) -> Result<Vec<Issue>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at FROM issues WHERE parent_id = ?1 ORDER BY id",
+            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at FROM issues WHERE parent_id = ?1 ORDER BY id",
         )?;
 
         let issues = stmt
@@ -249,8 +778,8 @@ impl Database {
     }
 
     pub fn get_issue(&self, id: i64) -> Result<Option<Issue>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at FROM issues WHERE id = ?1",
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at FROM issues WHERE id = ?1",
         )?;
 
         let issue = stmt.query_row([id], issue_from_row).ok();
@@ -265,14 +794,49 @@ impl Database {
             .ok_or_else(|| anyhow::anyhow!("Issue #{} not found", id))
     }
 
+    /// Call `f` with every issue, oldest first, without loading them all
+    /// into memory. Stops at the first error `f` returns.
+    pub fn for_each_issue<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(Issue) -> Result<()>,
+    {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at FROM issues ORDER BY id",
+        )?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            f(issue_from_row(row)?)?;
+        }
+        Ok(())
+    }
+
     pub fn list_issues(
         &self,
         status_filter: Option<&str>,
         label_filter: Option<&str>,
         priority_filter: Option<&str>,
+    ) -> Result<Vec<Issue>> {
+        self.list_issues_excluding(
+            status_filter,
+            label_filter,
+            priority_filter.map(PriorityFilter::Is),
+            &[],
+            IssueOrder::Newest,
+        )
+    }
+
+    /// Like `list_issues`, but also drops issues carrying any of `excluded_labels`
+    /// and returns them in `order`.
+    pub fn list_issues_excluding(
+        &self,
+        status_filter: Option<&str>,
+        label_filter: Option<&str>,
+        priority_filter: Option<PriorityFilter<'_>>,
+        excluded_labels: &[String],
+        order: IssueOrder,
     ) -> Result<Vec<Issue>> {
         let mut sql = String::from(
-            "SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at FROM issues i",
+            "SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at FROM issues i",
         );
         let mut conditions = Vec::new();
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
@@ -290,12 +854,27 @@ impl Database {
 
         if let Some(label) = label_filter {
             conditions.push("l.label = ?".to_string());
-            params_vec.push(Box::new(label.to_string()));
+            params_vec.push(Box::new(self.label_key(label)));
+        }
+
+        match priority_filter {
+            Some(PriorityFilter::Is(priority)) => {
+                conditions.push("i.priority = ?".to_string());
+                params_vec.push(Box::new(priority.to_string()));
+            }
+            Some(PriorityFilter::Rank(comparison, rank)) => {
+                conditions.push(format!("({}) {} ?", PRIORITY_RANK_SQL, comparison.sql()));
+                params_vec.push(Box::new(rank));
+            }
+            None => {}
         }
 
-        if let Some(priority) = priority_filter {
-            conditions.push("i.priority = ?".to_string());
-            params_vec.push(Box::new(priority.to_string()));
+        for label in excluded_labels {
+            conditions.push(
+                "NOT EXISTS (SELECT 1 FROM labels x WHERE x.issue_id = i.id AND x.label = ?)"
+                    .to_string(),
+            );
+            params_vec.push(Box::new(self.label_key(label)));
         }
 
         if !conditions.is_empty() {
@@ -303,7 +882,10 @@ impl Database {
             sql.push_str(&conditions.join(" AND "));
         }
 
-        sql.push_str(" ORDER BY i.id DESC");
+        sql.push_str(match order {
+            IssueOrder::Newest => " ORDER BY i.id DESC",
+            IssueOrder::Manual => " ORDER BY i.sort_order IS NULL, i.sort_order, i.id DESC",
+        });
 
         let mut stmt = self.conn.prepare(&sql)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> =
@@ -316,32 +898,72 @@ impl Database {
         Ok(issues)
     }
 
+    /// Mean seconds from creation to the latest close, over every issue that has been
+    /// closed (including archived ones, but not won't-do ones). Reopening clears
+    /// `closed_at`, so only the most recent close counts. `None` when nothing has
+    /// been closed yet.
+    pub fn avg_resolution_seconds(&self) -> Result<Option<f64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT created_at, closed_at FROM issues WHERE closed_at IS NOT NULL AND COALESCE(archived_from, status) != 'wontdo'",
+        )?;
+        let durations = stmt
+            .query_map([], |row| {
+                let created = parse_datetime(row.get::<_, String>(0)?);
+                let closed = parse_datetime(row.get::<_, String>(1)?);
+                Ok((closed - created).num_seconds())
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        if durations.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            durations.iter().sum::<i64>() as f64 / durations.len() as f64,
+        ))
+    }
+
+    /// Returns false if the issue doesn't exist or nothing would change.
     pub fn update_issue(
         &self,
         id: i64,
         title: Option<&str>,
-        description: Option<&str>,
+        description: FieldUpdate<&str>,
         priority: Option<&str>,
     ) -> Result<bool> {
+        // Only fields that differ from the stored issue are written, so a
+        // no-op update leaves updated_at alone
+        let Some(current) = self.get_issue(id)? else {
+            return Ok(false);
+        };
         let now = Utc::now().to_rfc3339();
         let mut updates = vec!["updated_at = ?1".to_string()];
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(now)];
 
-        if let Some(t) = title {
+        if let Some(t) = title.filter(|t| *t != current.title) {
             updates.push(format!("title = ?{}", params_vec.len() + 1));
             params_vec.push(Box::new(t.to_string()));
         }
 
-        if let Some(d) = description {
-            updates.push(format!("description = ?{}", params_vec.len() + 1));
-            params_vec.push(Box::new(d.to_string()));
+        match description {
+            FieldUpdate::Keep => {}
+            FieldUpdate::Set(d) if current.description.as_deref() == Some(d) => {}
+            FieldUpdate::Set(d) => {
+                updates.push(format!("description = ?{}", params_vec.len() + 1));
+                params_vec.push(Box::new(d.to_string()));
+            }
+            FieldUpdate::Clear if current.description.is_none() => {}
+            FieldUpdate::Clear => updates.push("description = NULL".to_string()),
         }
 
-        if let Some(p) = priority {
+        if let Some(p) = priority.filter(|p| *p != current.priority) {
             updates.push(format!("priority = ?{}", params_vec.len() + 1));
             params_vec.push(Box::new(p.to_string()));
         }
 
+        if updates.len() == 1 {
+            return Ok(false);
+        }
+
         params_vec.push(Box::new(id));
         let sql = format!(
             "UPDATE issues SET {} WHERE id = ?{}",
@@ -355,24 +977,110 @@ impl Database {
         Ok(rows > 0)
     }
 
-    pub fn close_issue(&self, id: i64) -> Result<bool> {
+    /// Close an issue without recording who closed it (see `close_issue_by`).
+    pub fn close_issue(&self, id: i64, resolution: Option<&str>) -> Result<bool> {
+        self.close_issue_by(id, resolution, None)
+    }
+
+    pub fn close_issue_by(
+        &self,
+        id: i64,
+        resolution: Option<&str>,
+        closed_by: Option<&str>,
+    ) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'closed', closed_at = ?1, updated_at = ?1 WHERE id = ?2",
-            params![now, id],
+            "UPDATE issues SET status = 'closed', closed_at = ?1, updated_at = ?1, resolution = ?2, closed_by = ?3 WHERE id = ?4",
+            params![now, resolution, closed_by, id],
         )?;
         Ok(rows > 0)
     }
 
-    pub fn reopen_issue(&self, id: i64) -> Result<bool> {
-        let now = Utc::now().to_rfc3339();
+    pub fn set_sort_order(&self, id: i64, sort_order: f64) -> Result<bool> {
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'open', closed_at = NULL, updated_at = ?1 WHERE id = ?2",
-            params![now, id],
+            "UPDATE issues SET sort_order = ?1 WHERE id = ?2",
+            params![sort_order, id],
         )?;
         Ok(rows > 0)
     }
 
+    /// Ids and sort orders of the issues under `parent_id` (top-level issues for
+    /// None), in manual order.
+    pub fn sibling_sort_orders(&self, parent_id: Option<i64>) -> Result<Vec<(i64, Option<f64>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, sort_order FROM issues WHERE parent_id IS ?1 ORDER BY sort_order IS NULL, sort_order, id DESC",
+        )?;
+        let siblings = stmt
+            .query_map([parent_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(siblings)
+    }
+
+    /// Mark an issue as won't do (status `wontdo`): abandoned rather than completed.
+    pub fn drop_issue(&self, id: i64) -> Result<bool> {
+        self.drop_issue_by(id, None)
+    }
+
+    pub fn drop_issue_by(&self, id: i64, closed_by: Option<&str>) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET status = 'wontdo', closed_at = ?1, updated_at = ?1, resolution = NULL, closed_by = ?2 WHERE id = ?3",
+            params![now, closed_by, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn reopen_issue(&self, id: i64) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET status = 'open', closed_at = NULL, resolution = NULL, closed_by = NULL, updated_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// How the issue was resolved when it was closed, if that was recorded.
+    pub fn get_resolution(&self, id: i64) -> Result<Option<String>> {
+        let resolution = self
+            .conn
+            .query_row("SELECT resolution FROM issues WHERE id = ?1", [id], |row| {
+                row.get::<_, Option<String>>(0)
+            })
+            .ok()
+            .flatten();
+        Ok(resolution)
+    }
+
+    pub fn set_due_date(&self, id: i64, due_at: Option<DateTime<Utc>>) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET due_at = ?1, updated_at = ?2 WHERE id = ?3",
+            params![due_at.map(|d| d.to_rfc3339()), now, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Store how often the issue recurs (see `utils::Recurrence`), or stop it recurring.
+    pub fn set_recurrence(&self, id: i64, recurrence: Option<&str>) -> Result<bool> {
+        let now = Utc::now().to_rfc3339();
+        let rows = self.conn.execute(
+            "UPDATE issues SET recurrence = ?1, updated_at = ?2 WHERE id = ?3",
+            params![recurrence, now, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn get_recurrence(&self, id: i64) -> Result<Option<String>> {
+        let recurrence = self
+            .conn
+            .query_row("SELECT recurrence FROM issues WHERE id = ?1", [id], |row| {
+                row.get::<_, Option<String>>(0)
+            })
+            .ok()
+            .flatten();
+        Ok(recurrence)
+    }
+
     pub fn delete_issue(&self, id: i64) -> Result<bool> {
         let rows = self
             .conn
@@ -381,18 +1089,20 @@ impl Database {
     }
 
     // Labels
+    /// Add a label, normalized per the label policy (see `normalize_label`).
     pub fn add_label(&self, issue_id: i64, label: &str) -> Result<bool> {
-        let result = self.conn.execute(
-            "INSERT OR IGNORE INTO labels (issue_id, label) VALUES (?1, ?2)",
-            params![issue_id, label],
-        )?;
+        let label = self.normalize_label(label)?;
+        let result = self
+            .conn
+            .prepare_cached("INSERT OR IGNORE INTO labels (issue_id, label) VALUES (?1, ?2)")?
+            .execute(params![issue_id, label])?;
         Ok(result > 0)
     }
 
     pub fn remove_label(&self, issue_id: i64, label: &str) -> Result<bool> {
         let rows = self.conn.execute(
             "DELETE FROM labels WHERE issue_id = ?1 AND label = ?2",
-            params![issue_id, label],
+            params![issue_id, self.label_key(label)],
         )?;
         Ok(rows > 0)
     }
@@ -400,7 +1110,7 @@ impl Database {
     pub fn get_labels(&self, issue_id: i64) -> Result<Vec<String>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT label FROM labels WHERE issue_id = ?1 ORDER BY label")?;
+            .prepare_cached("SELECT label FROM labels WHERE issue_id = ?1 ORDER BY label")?;
         let labels = stmt
             .query_map([issue_id], |row| row.get(0))?
             .collect::<std::result::Result<Vec<String>, _>>()?;
@@ -408,32 +1118,93 @@ impl Database {
     }
 
     // Comments
+    /// Add a comment without an author (see `add_comment_by`).
     pub fn add_comment(&self, issue_id: i64, content: &str) -> Result<i64> {
+        self.add_comment_by(issue_id, content, None)
+    }
+
+    pub fn add_comment_by(
+        &self,
+        issue_id: i64,
+        content: &str,
+        author: Option<&str>,
+    ) -> Result<i64> {
         let now = Utc::now().to_rfc3339();
-        self.conn.execute(
-            "INSERT INTO comments (issue_id, content, created_at) VALUES (?1, ?2, ?3)",
-            params![issue_id, content, now],
-        )?;
+        self.conn
+            .prepare_cached(
+                "INSERT INTO comments (issue_id, content, created_at, author) VALUES (?1, ?2, ?3, ?4)",
+            )?
+            .execute(params![issue_id, content, now, author])?;
         Ok(self.conn.last_insert_rowid())
     }
 
-    pub fn get_comments(&self, issue_id: i64) -> Result<Vec<Comment>> {
+    /// Id of the most recent comment on an issue, if it has any.
+    pub fn last_comment_id(&self, issue_id: i64) -> Result<Option<i64>> {
+        let id = self.conn.query_row(
+            "SELECT MAX(id) FROM comments WHERE issue_id = ?1",
+            [issue_id],
+            |row| row.get(0),
+        )?;
+        Ok(id)
+    }
+
+    pub fn update_comment(&self, comment_id: i64, content: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE comments SET content = ?1 WHERE id = ?2",
+            params![content, comment_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Comments on an issue in chronological order.
+    /// With `limit`, only the most recent `limit` comments are returned.
+    pub fn get_comments(&self, issue_id: i64, limit: Option<usize>) -> Result<Vec<Comment>> {
+        // A negative LIMIT means no limit in SQLite
+        let limit = limit.map_or(-1, |n| n as i64);
         let mut stmt = self.conn.prepare(
-            "SELECT id, issue_id, content, created_at FROM comments WHERE issue_id = ?1 ORDER BY created_at",
+            r#"
+            SELECT id, issue_id, content, created_at, pinned, author FROM (
+                SELECT id, issue_id, content, created_at, pinned, author FROM comments
+                WHERE issue_id = ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT ?2
+            )
+            ORDER BY created_at, id
+            "#,
         )?;
         let comments = stmt
-            .query_map([issue_id], |row| {
+            .query_map(params![issue_id, limit], |row| {
                 Ok(Comment {
                     id: row.get(0)?,
                     issue_id: row.get(1)?,
                     content: row.get(2)?,
                     created_at: parse_datetime(row.get::<_, String>(3)?),
+                    pinned: row.get(4)?,
+                    author: row.get(5)?,
                 })
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
         Ok(comments)
     }
 
+    /// Pin or unpin one of an issue's comments. False if the issue has no such comment.
+    pub fn set_comment_pinned(&self, issue_id: i64, comment_id: i64, pinned: bool) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE comments SET pinned = ?1 WHERE id = ?2 AND issue_id = ?3",
+            params![pinned, comment_id, issue_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn count_comments(&self, issue_id: i64) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM comments WHERE issue_id = ?1",
+            [issue_id],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     // Dependencies
     pub fn add_dependency(&self, blocked_id: i64, blocker_id: i64) -> Result<bool> {
         // Prevent self-blocking
@@ -479,6 +1250,65 @@ impl Database {
         Ok(false)
     }
 
+    /// Groups of issues that block each other in a cycle, each sorted by id.
+    /// `add_dependency` refuses to create cycles, but older databases or direct
+    /// edits can still contain them.
+    pub fn find_dependency_cycles(&self) -> Result<Vec<Vec<i64>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT blocker_id, blocked_id FROM dependencies ORDER BY blocker_id")?;
+        let edges = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut graph: std::collections::BTreeMap<i64, Vec<i64>> = Default::default();
+        for (blocker, blocked) in edges {
+            graph.entry(blocker).or_default().push(blocked);
+            graph.entry(blocked).or_default();
+        }
+
+        let reachable = |start: i64| {
+            let mut seen = HashSet::new();
+            let mut stack = graph[&start].clone();
+            while let Some(node) = stack.pop() {
+                if seen.insert(node) {
+                    stack.extend(graph[&node].iter().copied());
+                }
+            }
+            seen
+        };
+        let reach: std::collections::BTreeMap<i64, HashSet<i64>> =
+            graph.keys().map(|&id| (id, reachable(id))).collect();
+
+        // Two issues share a cycle when each can reach the other
+        let mut cycles = Vec::new();
+        let mut assigned = HashSet::new();
+        for (&id, from_id) in &reach {
+            if assigned.contains(&id) || !from_id.contains(&id) {
+                continue;
+            }
+            let group: Vec<i64> = reach
+                .iter()
+                .filter(|(other, from_other)| from_id.contains(other) && from_other.contains(&id))
+                .map(|(&other, _)| other)
+                .collect();
+            assigned.extend(group.iter().copied());
+            cycles.push(group);
+        }
+        Ok(cycles)
+    }
+
+    /// Insert a dependency row without the self-block and cycle checks.
+    #[cfg(test)]
+    pub(crate) fn add_dependency_unchecked(&self, blocked_id: i64, blocker_id: i64) {
+        self.conn
+            .execute(
+                "INSERT INTO dependencies (blocker_id, blocked_id) VALUES (?1, ?2)",
+                params![blocker_id, blocked_id],
+            )
+            .unwrap();
+    }
+
     pub fn remove_dependency(&self, blocked_id: i64, blocker_id: i64) -> Result<bool> {
         let rows = self.conn.execute(
             "DELETE FROM dependencies WHERE blocker_id = ?1 AND blocked_id = ?2",
@@ -508,38 +1338,65 @@ impl Database {
     }
 
     pub fn list_blocked_issues(&self) -> Result<Vec<Issue>> {
+        let mut stmt = self.conn.prepare(BLOCKED_ISSUES_SQL)?;
+
+        let issues = stmt
+            .query_map([], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(issues)
+    }
+
+    /// Blockers of `issue_id` that are still open, in id order.
+    pub fn get_open_blockers(&self, issue_id: i64) -> Result<Vec<i64>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
-            FROM issues i
-            JOIN dependencies d ON i.id = d.blocked_id
+            SELECT d.blocker_id
+            FROM dependencies d
             JOIN issues blocker ON d.blocker_id = blocker.id
-            WHERE i.status = 'open' AND blocker.status = 'open'
-            ORDER BY i.id
+            WHERE d.blocked_id = ?1 AND blocker.status = 'open'
+            ORDER BY d.blocker_id
             "#,
         )?;
-
-        let issues = stmt
-            .query_map([], issue_from_row)?
+        let blockers = stmt
+            .query_map([issue_id], |row| row.get(0))?
             .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(blockers)
+    }
 
-        Ok(issues)
+    /// Ids of all issues with at least one open blocker, in a single query.
+    pub fn blocked_ids(&self) -> Result<HashSet<i64>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT DISTINCT d.blocked_id
+            FROM dependencies d
+            JOIN issues blocker ON d.blocker_id = blocker.id
+            WHERE blocker.status = 'open'
+            "#,
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<HashSet<i64>, _>>()?;
+        Ok(ids)
     }
 
-    pub fn list_ready_issues(&self) -> Result<Vec<Issue>> {
+    /// Titles of every issue that has subissues, keyed by id.
+    pub fn parent_titles(&self) -> Result<HashMap<i64, String>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
-            FROM issues i
-            WHERE i.status = 'open'
-            AND NOT EXISTS (
-                SELECT 1 FROM dependencies d
-                JOIN issues blocker ON d.blocker_id = blocker.id
-                WHERE d.blocked_id = i.id AND blocker.status = 'open'
-            )
-            ORDER BY i.id
+            SELECT DISTINCT p.id, p.title
+            FROM issues c
+            JOIN issues p ON c.parent_id = p.id
             "#,
         )?;
+        let titles = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<HashMap<i64, String>, _>>()?;
+        Ok(titles)
+    }
+
+    pub fn list_ready_issues(&self) -> Result<Vec<Issue>> {
+        let mut stmt = self.conn.prepare(READY_ISSUES_SQL)?;
 
         let issues = stmt
             .query_map([], issue_from_row)?
@@ -572,18 +1429,7 @@ impl Database {
             "SELECT id, started_at, ended_at, active_issue_id, handoff_notes, last_action FROM sessions WHERE ended_at IS NULL ORDER BY id DESC LIMIT 1",
         )?;
 
-        let session = stmt
-            .query_row([], |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    started_at: parse_datetime(row.get::<_, String>(1)?),
-                    ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
-                    active_issue_id: row.get(3)?,
-                    handoff_notes: row.get(4)?,
-                    last_action: row.get(5)?,
-                })
-            })
-            .ok();
+        let session = stmt.query_row([], session_from_row).ok();
 
         Ok(session)
     }
@@ -593,22 +1439,38 @@ impl Database {
             "SELECT id, started_at, ended_at, active_issue_id, handoff_notes, last_action FROM sessions WHERE ended_at IS NOT NULL ORDER BY id DESC LIMIT 1",
         )?;
 
-        let session = stmt
-            .query_row([], |row| {
-                Ok(Session {
-                    id: row.get(0)?,
-                    started_at: parse_datetime(row.get::<_, String>(1)?),
-                    ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
-                    active_issue_id: row.get(3)?,
-                    handoff_notes: row.get(4)?,
-                    last_action: row.get(5)?,
-                })
-            })
-            .ok();
+        let session = stmt.query_row([], session_from_row).ok();
 
         Ok(session)
     }
 
+    pub fn all_sessions(&self) -> Result<Vec<Session>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, started_at, ended_at, active_issue_id, handoff_notes, last_action FROM sessions ORDER BY id",
+        )?;
+
+        let sessions = stmt
+            .query_map([], session_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(sessions)
+    }
+
+    /// Insert a session with explicit timestamps (used by import).
+    pub fn insert_session(&self, session: &Session) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO sessions (started_at, ended_at, active_issue_id, handoff_notes, last_action) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                session.started_at.to_rfc3339(),
+                session.ended_at.map(|dt| dt.to_rfc3339()),
+                session.active_issue_id,
+                session.handoff_notes,
+                session.last_action,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
     pub fn set_session_issue(&self, session_id: i64, issue_id: i64) -> Result<bool> {
         let rows = self.conn.execute(
             "UPDATE sessions SET active_issue_id = ?1 WHERE id = ?2",
@@ -625,6 +1487,27 @@ impl Database {
         Ok(rows > 0)
     }
 
+    // Test runs
+    pub fn set_last_tested(&self, at: DateTime<Utc>) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES ('last_tested_at', ?1)",
+            [at.to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_last_tested(&self) -> Result<Option<DateTime<Utc>>> {
+        let value: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = 'last_tested_at'",
+                [],
+                |row| row.get(0),
+            )
+            .ok();
+        Ok(value.map(parse_datetime))
+    }
+
     // Time tracking
     pub fn start_timer(&self, issue_id: i64) -> Result<i64> {
         let now = Utc::now().to_rfc3339();
@@ -690,32 +1573,173 @@ impl Database {
         Ok(total)
     }
 
-    /// Search issues by query string across titles, descriptions, and comments
-    pub fn search_issues(&self, query: &str) -> Result<Vec<Issue>> {
-        // Escape SQL LIKE wildcards to prevent unintended pattern matching
-        let escaped = query.replace('%', "\\%").replace('_', "\\_");
-        let pattern = format!("%{}%", escaped);
+    /// Total tracked seconds per label, largest first. An issue's time counts
+    /// toward every label it has; running timers are not included.
+    pub fn time_by_label(&self) -> Result<Vec<(String, i64)>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
-            FROM issues i
-            LEFT JOIN comments c ON i.id = c.issue_id
-            WHERE i.title LIKE ?1 ESCAPE '\' COLLATE NOCASE
-               OR i.description LIKE ?1 ESCAPE '\' COLLATE NOCASE
-               OR c.content LIKE ?1 ESCAPE '\' COLLATE NOCASE
-            ORDER BY i.id DESC
+            SELECT l.label, SUM(t.duration_seconds) AS total
+            FROM time_entries t
+            JOIN labels l ON l.issue_id = t.issue_id
+            WHERE t.duration_seconds IS NOT NULL
+            GROUP BY l.label
+            ORDER BY total DESC, l.label
+            "#,
+        )?;
+        let totals = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(totals)
+    }
+
+    pub fn all_time_entries(&self) -> Result<Vec<TimeEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, issue_id, started_at, ended_at, duration_seconds FROM time_entries ORDER BY id",
+        )?;
+
+        let entries = stmt
+            .query_map([], time_entry_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// Time entries for one issue, oldest first, including a running one.
+    pub fn get_time_entries(&self, issue_id: i64) -> Result<Vec<TimeEntry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, issue_id, started_at, ended_at, duration_seconds FROM time_entries WHERE issue_id = ?1 ORDER BY started_at, id",
+        )?;
+
+        let entries = stmt
+            .query_map([issue_id], time_entry_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// Insert a time entry with explicit timestamps (used by import).
+    pub fn insert_time_entry(&self, entry: &TimeEntry) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO time_entries (issue_id, started_at, ended_at, duration_seconds) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                entry.issue_id,
+                entry.started_at.to_rfc3339(),
+                entry.ended_at.map(|dt| dt.to_rfc3339()),
+                entry.duration_seconds,
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Search issues by query string across titles, descriptions, and comments
+    /// Issues whose title, description, comments or labels contain `query`,
+    /// title matches first and then most recently updated. At most `limit`
+    /// results when given.
+    pub fn search_issues(&self, query: &str, limit: Option<usize>) -> Result<Vec<Issue>> {
+        let sql = format!(
+            r#"
+            SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at
+            {}
+            ORDER BY i.title LIKE ?1 ESCAPE '\' COLLATE NOCASE DESC, i.updated_at DESC, i.id DESC
+            LIMIT ?2
             "#,
+            SEARCH_MATCHES_SQL
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        // A negative LIMIT means no limit in SQLite
+        let limit = limit.map_or(-1, |n| n as i64);
+        let issues = stmt
+            .query_map(params![search_pattern(query), limit], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(issues)
+    }
+
+    /// How many issues `search_issues` would return without a limit.
+    pub fn count_search_matches(&self, query: &str) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(DISTINCT i.id) {}", SEARCH_MATCHES_SQL),
+            [search_pattern(query)],
+            |row| row.get(0),
         )?;
+        Ok(count as usize)
+    }
 
+    /// Like `search_issues`, but `pattern` is a regular expression matched
+    /// against titles, descriptions and comments.
+    pub fn search_issues_regex(&self, pattern: &str, limit: Option<usize>) -> Result<Vec<Issue>> {
+        Regex::new(pattern).with_context(|| format!("Invalid regex '{}'", pattern))?;
+        let sql = format!(
+            r#"
+            SELECT DISTINCT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at
+            {}
+            ORDER BY i.title REGEXP ?1 DESC, i.updated_at DESC, i.id DESC
+            LIMIT ?2
+            "#,
+            REGEX_MATCHES_SQL
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let limit = limit.map_or(-1, |n| n as i64);
         let issues = stmt
-            .query_map([&pattern], issue_from_row)?
+            .query_map(params![pattern, limit], issue_from_row)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
         Ok(issues)
     }
 
+    /// How many issues `search_issues_regex` would return without a limit.
+    pub fn count_regex_matches(&self, pattern: &str) -> Result<usize> {
+        Regex::new(pattern).with_context(|| format!("Invalid regex '{}'", pattern))?;
+        let count: i64 = self.conn.query_row(
+            &format!("SELECT COUNT(DISTINCT i.id) {}", REGEX_MATCHES_SQL),
+            [pattern],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    // Attachments
+    pub fn add_attachment(
+        &self,
+        issue_id: i64,
+        path_or_url: &str,
+        description: Option<&str>,
+    ) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO attachments (issue_id, path_or_url, description, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![issue_id, path_or_url, description, Utc::now().to_rfc3339()],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Attachments on an issue, oldest first.
+    pub fn get_attachments(&self, issue_id: i64) -> Result<Vec<Attachment>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, issue_id, path_or_url, description, created_at FROM attachments WHERE issue_id = ?1 ORDER BY id",
+        )?;
+        let attachments = stmt
+            .query_map([issue_id], |row| {
+                Ok(Attachment {
+                    id: row.get(0)?,
+                    issue_id: row.get(1)?,
+                    path_or_url: row.get(2)?,
+                    description: row.get(3)?,
+                    created_at: parse_datetime(row.get::<_, String>(4)?),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(attachments)
+    }
+
     // Relations (bidirectional)
-    pub fn add_relation(&self, issue_id_1: i64, issue_id_2: i64) -> Result<bool> {
+    /// Relate two issues, optionally noting why. Returns false if they were
+    /// already related; a given note still replaces the old one.
+    pub fn add_relation(
+        &self,
+        issue_id_1: i64,
+        issue_id_2: i64,
+        note: Option<&str>,
+    ) -> Result<bool> {
         if issue_id_1 == issue_id_2 {
             anyhow::bail!("Cannot relate an issue to itself");
         }
@@ -725,14 +1749,50 @@ impl Database {
         } else {
             (issue_id_2, issue_id_1)
         };
+        if let Some(max) = self.max_relations {
+            let exists = self.conn.query_row(
+                "SELECT COUNT(*) FROM relations WHERE issue_id_1 = ?1 AND issue_id_2 = ?2",
+                params![a, b],
+                |row| row.get::<_, i64>(0),
+            )? > 0;
+            if !exists {
+                for id in [a, b] {
+                    let count = self.count_relations(id)?;
+                    if count >= max {
+                        anyhow::bail!(
+                            "Issue #{} already has {} relations (max_relations_per_issue is {})",
+                            id,
+                            count,
+                            max
+                        );
+                    }
+                }
+            }
+        }
         let now = Utc::now().to_rfc3339();
         let result = self.conn.execute(
-            "INSERT OR IGNORE INTO relations (issue_id_1, issue_id_2, created_at) VALUES (?1, ?2, ?3)",
-            params![a, b, now],
+            "INSERT OR IGNORE INTO relations (issue_id_1, issue_id_2, created_at, note) VALUES (?1, ?2, ?3, ?4)",
+            params![a, b, now, note],
         )?;
+        if result == 0 && note.is_some() {
+            self.conn.execute(
+                "UPDATE relations SET note = ?1 WHERE issue_id_1 = ?2 AND issue_id_2 = ?3",
+                params![note, a, b],
+            )?;
+        }
         Ok(result > 0)
     }
 
+    /// Number of relations involving `issue_id`, in either direction.
+    pub fn count_relations(&self, issue_id: i64) -> Result<i64> {
+        let count = self.conn.query_row(
+            "SELECT COUNT(*) FROM relations WHERE issue_id_1 = ?1 OR issue_id_2 = ?1",
+            [issue_id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
     pub fn remove_relation(&self, issue_id_1: i64, issue_id_2: i64) -> Result<bool> {
         let (a, b) = if issue_id_1 < issue_id_2 {
             (issue_id_1, issue_id_2)
@@ -746,6 +1806,21 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// Notes on `issue_id`'s relations, keyed by the other issue's id.
+    pub fn relation_notes(&self, issue_id: i64) -> Result<HashMap<i64, String>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT issue_id_2, note FROM relations WHERE issue_id_1 = ?1 AND note IS NOT NULL
+            UNION ALL
+            SELECT issue_id_1, note FROM relations WHERE issue_id_2 = ?1 AND note IS NOT NULL
+            "#,
+        )?;
+        let notes = stmt
+            .query_map([issue_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<HashMap<_, _>, _>>()?;
+        Ok(notes)
+    }
+
     pub fn update_parent(&self, id: i64, parent_id: Option<i64>) -> Result<bool> {
         let now = chrono::Utc::now().to_rfc3339();
         let rows = self.conn.execute(
@@ -758,7 +1833,7 @@ impl Database {
     pub fn get_related_issues(&self, issue_id: i64) -> Result<Vec<Issue>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
+            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at
             FROM issues i
             WHERE i.id IN (
                 SELECT issue_id_2 FROM relations WHERE issue_id_1 = ?1
@@ -839,6 +1914,45 @@ impl Database {
         Ok(milestones)
     }
 
+    /// Like `list_milestones`, with each milestone's closed and total issue
+    /// counts, fetched in one query. Won't-do issues count as closed.
+    pub fn list_milestones_with_progress(
+        &self,
+        status: Option<&str>,
+    ) -> Result<Vec<(crate::models::Milestone, usize, usize)>> {
+        let status = status.unwrap_or("open");
+        let mut stmt = self.conn.prepare(&format!(
+            r#"
+            SELECT m.id, m.name, m.description, m.status, m.created_at, m.closed_at,
+                   COUNT(i.id), COUNT(CASE WHEN i.status IN {} THEN 1 END)
+            FROM milestones m
+            LEFT JOIN milestone_issues mi ON mi.milestone_id = m.id
+            LEFT JOIN issues i ON i.id = mi.issue_id
+            WHERE ?1 = 'all' OR m.status = ?1
+            GROUP BY m.id
+            ORDER BY m.id DESC
+            "#,
+            DONE_STATUSES_SQL
+        ))?;
+        let milestones = stmt
+            .query_map([status], |row| {
+                let milestone = crate::models::Milestone {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    status: row.get(3)?,
+                    created_at: parse_datetime(row.get::<_, String>(4)?),
+                    closed_at: row.get::<_, Option<String>>(5)?.map(parse_datetime),
+                };
+                let total: i64 = row.get(6)?;
+                let closed: i64 = row.get(7)?;
+                Ok((milestone, closed as usize, total as usize))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(milestones)
+    }
+
     pub fn add_issue_to_milestone(&self, milestone_id: i64, issue_id: i64) -> Result<bool> {
         let result = self.conn.execute(
             "INSERT OR IGNORE INTO milestone_issues (milestone_id, issue_id) VALUES (?1, ?2)",
@@ -858,7 +1972,7 @@ impl Database {
     pub fn get_milestone_issues(&self, milestone_id: i64) -> Result<Vec<Issue>> {
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at
+            SELECT i.id, i.title, i.description, i.status, i.priority, i.parent_id, i.created_at, i.updated_at, i.closed_at, i.source, i.due_at
             FROM issues i
             JOIN milestone_issues mi ON i.id = mi.issue_id
             WHERE mi.milestone_id = ?1
@@ -889,19 +2003,20 @@ impl Database {
         Ok(rows > 0)
     }
 
-    pub fn get_issue_milestone(&self, issue_id: i64) -> Result<Option<crate::models::Milestone>> {
+    /// Every milestone the issue belongs to, oldest first.
+    pub fn get_issue_milestones(&self, issue_id: i64) -> Result<Vec<crate::models::Milestone>> {
         let mut stmt = self.conn.prepare(
             r#"
             SELECT m.id, m.name, m.description, m.status, m.created_at, m.closed_at
             FROM milestones m
             JOIN milestone_issues mi ON m.id = mi.milestone_id
             WHERE mi.issue_id = ?1
-            LIMIT 1
+            ORDER BY m.id
             "#,
         )?;
 
-        let milestone = stmt
-            .query_row([issue_id], |row| {
+        let milestones = stmt
+            .query_map([issue_id], |row| {
                 Ok(crate::models::Milestone {
                     id: row.get(0)?,
                     name: row.get(1)?,
@@ -910,17 +2025,63 @@ impl Database {
                     created_at: parse_datetime(row.get::<_, String>(4)?),
                     closed_at: row.get::<_, Option<String>>(5)?.map(parse_datetime),
                 })
-            })
-            .ok();
-
-        Ok(milestone)
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(milestones)
+    }
+
+    // Watches
+    /// Returns false if the issue was already watched.
+    pub fn watch_issue(&self, issue_id: i64) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR IGNORE INTO watches (issue_id, created_at) VALUES (?1, ?2)",
+            params![issue_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    pub fn unwatch_issue(&self, issue_id: i64) -> Result<bool> {
+        let rows = self
+            .conn
+            .execute("DELETE FROM watches WHERE issue_id = ?1", [issue_id])?;
+        Ok(rows > 0)
+    }
+
+    /// Current state of every watched issue, by id.
+    pub fn watched_issues(&self) -> Result<Vec<WatchedIssue>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT i.id, i.title, i.status,
+                (SELECT COUNT(*) FROM comments c WHERE c.issue_id = i.id)
+            FROM watches w
+            JOIN issues i ON i.id = w.issue_id
+            ORDER BY i.id
+            "#,
+        )?;
+        let watched = stmt
+            .query_map([], |row| {
+                Ok(WatchedIssue {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    status: row.get(2)?,
+                    comment_count: row.get::<_, i64>(3)? as usize,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(watched)
     }
 
     // Archiving
+    /// Archive a closed or won't-do issue; `unarchive_issue` puts it back in
+    /// whichever of the two it was.
     pub fn archive_issue(&self, id: i64) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'archived', updated_at = ?1 WHERE id = ?2 AND status = 'closed'",
+            &format!(
+                "UPDATE issues SET status = 'archived', archived_from = status, archived_at = ?1, updated_at = ?1 WHERE id = ?2 AND status IN {}",
+                DONE_STATUSES_SQL
+            ),
             params![now, id],
         )?;
         Ok(rows > 0)
@@ -929,7 +2090,7 @@ impl Database {
     pub fn unarchive_issue(&self, id: i64) -> Result<bool> {
         let now = Utc::now().to_rfc3339();
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'closed', updated_at = ?1 WHERE id = ?2 AND status = 'archived'",
+            "UPDATE issues SET status = COALESCE(archived_from, 'closed'), archived_from = NULL, archived_at = NULL, updated_at = ?1 WHERE id = ?2 AND status = 'archived'",
             params![now, id],
         )?;
         Ok(rows > 0)
@@ -937,7 +2098,7 @@ impl Database {
 
     pub fn list_archived_issues(&self) -> Result<Vec<Issue>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at FROM issues WHERE status = 'archived' ORDER BY id DESC",
+            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at FROM issues WHERE status = 'archived' ORDER BY id DESC",
         )?;
 
         let issues = stmt
@@ -947,18 +2108,355 @@ impl Database {
         Ok(issues)
     }
 
+    /// Closed and won't-do issues that `archive_older_than(days)` would archive,
+    /// oldest close first.
+    pub fn closed_older_than(&self, days: i64) -> Result<Vec<Issue>> {
+        let cutoff = (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at FROM issues WHERE status IN {} AND closed_at < ?1 ORDER BY closed_at, id",
+            DONE_STATUSES_SQL
+        ))?;
+
+        let issues = stmt
+            .query_map([cutoff], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(issues)
+    }
+
     pub fn archive_older_than(&self, days: i64) -> Result<i32> {
         let cutoff = Utc::now() - chrono::Duration::days(days);
         let cutoff_str = cutoff.to_rfc3339();
         let now = Utc::now().to_rfc3339();
 
         let rows = self.conn.execute(
-            "UPDATE issues SET status = 'archived', updated_at = ?1 WHERE status = 'closed' AND closed_at < ?2",
+            &format!(
+                "UPDATE issues SET status = 'archived', archived_from = status, archived_at = ?1, updated_at = ?1 WHERE status IN {} AND closed_at < ?2",
+                DONE_STATUSES_SQL
+            ),
             params![now, cutoff_str],
         )?;
 
         Ok(rows as i32)
     }
+
+    /// Archived issues `purge_archived(older_than_days)` would delete, by id.
+    pub fn purgeable_archived(&self, older_than_days: Option<u32>) -> Result<Vec<Issue>> {
+        let cutoff = older_than_days.map(archived_before);
+        let mut stmt = self.conn.prepare(&format!(
+            "{} SELECT id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at FROM issues WHERE id IN (SELECT id FROM purgeable) ORDER BY id",
+            PURGEABLE_CTE
+        ))?;
+        let issues = stmt
+            .query_map([cutoff], issue_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(issues)
+    }
+
+    /// Permanently delete archived issues, only those archived more than
+    /// `older_than_days` ago if given. An archived issue with an unarchived (or
+    /// too recently archived) subissue is kept. Returns how many were deleted.
+    pub fn purge_archived(&self, older_than_days: Option<u32>) -> Result<usize> {
+        let cutoff = older_than_days.map(archived_before);
+        let rows = self.conn.execute(
+            &format!(
+                "{} DELETE FROM issues WHERE id IN (SELECT id FROM purgeable)",
+                PURGEABLE_CTE
+            ),
+            [cutoff],
+        )?;
+        Ok(rows)
+    }
+
+    /// Overwrite `archived_at`, to make an issue look archived long ago.
+    #[cfg(test)]
+    pub(crate) fn backdate_archived_at(&self, id: i64, archived_at: &str) {
+        self.conn
+            .execute(
+                "UPDATE issues SET archived_at = ?1 WHERE id = ?2",
+                params![archived_at, id],
+            )
+            .unwrap();
+    }
+
+    // Trash
+    /// Delete an issue (and its subissues), keeping a snapshot in the trash.
+    /// Returns the trash id, or None if the issue doesn't exist.
+    pub fn trash_issue(&self, id: i64) -> Result<Option<i64>> {
+        let Some(issue) = self.get_issue(id)? else {
+            return Ok(None);
+        };
+        let snapshot = self.snapshot_issue(issue)?;
+        let subissue_count = snapshot.descendant_count();
+        self.conn.execute(
+            "INSERT INTO trash (issue_id, title, subissue_count, snapshot, deleted_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                id,
+                snapshot.issue.title,
+                subissue_count as i64,
+                serde_json::to_string(&snapshot)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        let trash_id = self.conn.last_insert_rowid();
+        self.delete_issue(id)?;
+        Ok(Some(trash_id))
+    }
+
+    fn snapshot_issue(&self, issue: Issue) -> Result<IssueSnapshot> {
+        let labels = self.get_labels(issue.id)?;
+        let comments = self.get_comments(issue.id, None)?;
+        let subissues = self
+            .get_subissues(issue.id)?
+            .into_iter()
+            .map(|sub| self.snapshot_issue(sub))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IssueSnapshot {
+            issue,
+            labels,
+            comments,
+            subissues,
+        })
+    }
+
+    /// Trashed issues, most recently deleted first.
+    pub fn list_trashed(&self) -> Result<Vec<TrashedIssue>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, issue_id, title, subissue_count, deleted_at FROM trash ORDER BY id DESC",
+        )?;
+        let trashed = stmt
+            .query_map([], |row| {
+                Ok(TrashedIssue {
+                    id: row.get(0)?,
+                    issue_id: row.get(1)?,
+                    title: row.get(2)?,
+                    subissue_count: row.get::<_, i64>(3)? as usize,
+                    deleted_at: parse_datetime(row.get::<_, String>(4)?),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(trashed)
+    }
+
+    /// Recreate a trashed issue with its labels, comments and subissues, and
+    /// remove it from the trash. The issue gets a new id, which is returned;
+    /// None if there is no such trash entry. Dependencies and relations are
+    /// not restored.
+    pub fn restore_trashed(&self, trash_id: i64) -> Result<Option<i64>> {
+        let snapshot: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT snapshot FROM trash WHERE id = ?1",
+                [trash_id],
+                |row| row.get(0),
+            )
+            .ok();
+        let Some(snapshot) = snapshot else {
+            return Ok(None);
+        };
+        let snapshot: IssueSnapshot = serde_json::from_str(&snapshot)
+            .with_context(|| format!("Trash entry {} is corrupt", trash_id))?;
+
+        // Keep the original parent only if it still exists
+        let parent_id = match snapshot.issue.parent_id {
+            Some(pid) if self.get_issue(pid)?.is_some() => Some(pid),
+            _ => None,
+        };
+        let new_id = self.restore_snapshot(&snapshot, parent_id)?;
+        self.conn
+            .execute("DELETE FROM trash WHERE id = ?1", [trash_id])?;
+        Ok(Some(new_id))
+    }
+
+    fn restore_snapshot(&self, snapshot: &IssueSnapshot, parent_id: Option<i64>) -> Result<i64> {
+        let issue = &snapshot.issue;
+        self.conn.execute(
+            "INSERT INTO issues (title, description, status, priority, parent_id, created_at, updated_at, closed_at, source) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                issue.title,
+                issue.description,
+                issue.status,
+                issue.priority,
+                parent_id,
+                issue.created_at.to_rfc3339(),
+                issue.updated_at.to_rfc3339(),
+                issue.closed_at.map(|dt| dt.to_rfc3339()),
+                issue.source
+            ],
+        )?;
+        let id = self.conn.last_insert_rowid();
+        for label in &snapshot.labels {
+            self.add_label(id, label)?;
+        }
+        for comment in &snapshot.comments {
+            self.conn.execute(
+                "INSERT INTO comments (issue_id, content, created_at) VALUES (?1, ?2, ?3)",
+                params![id, comment.content, comment.created_at.to_rfc3339()],
+            )?;
+        }
+        for sub in &snapshot.subissues {
+            self.restore_snapshot(sub, Some(id))?;
+        }
+        Ok(id)
+    }
+
+    // Activity
+    /// Issue creation, closing, comments and session starts/ends at or after `since`,
+    /// newest first.
+    pub fn activity_feed(&self, since: DateTime<Utc>) -> Result<Vec<ActivityItem>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT created_at, 'created', id, title, NULL FROM issues WHERE created_at >= ?1
+            UNION ALL
+            SELECT closed_at, CASE status WHEN 'wontdo' THEN 'dropped' ELSE 'closed' END,
+                id, title, closed_by FROM issues
+                WHERE closed_at IS NOT NULL AND closed_at >= ?1
+            UNION ALL
+            SELECT created_at, 'comment', issue_id, content, author FROM comments
+                WHERE created_at >= ?1
+            UNION ALL
+            SELECT started_at, 'session started', active_issue_id, 'Session #' || id, NULL
+                FROM sessions WHERE started_at >= ?1
+            UNION ALL
+            SELECT ended_at, 'session ended', active_issue_id, 'Session #' || id, NULL
+                FROM sessions WHERE ended_at IS NOT NULL AND ended_at >= ?1
+            "#,
+        )?;
+
+        let mut items = stmt
+            .query_map([since.to_rfc3339()], |row| {
+                Ok(ActivityItem {
+                    timestamp: parse_datetime(row.get::<_, String>(0)?),
+                    kind: row.get(1)?,
+                    issue_id: row.get(2)?,
+                    summary: row.get(3)?,
+                    author: row.get(4)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        items.sort_by_key(|item| std::cmp::Reverse(item.timestamp));
+        Ok(items)
+    }
+
+    // Integrity
+    /// Check for rows that reference issues or milestones which no longer exist.
+    /// These can only appear if foreign keys were disabled at some point (e.g. by an
+    /// external tool or a partial import). Returns one human-readable line per problem.
+    pub fn validate_integrity(&self) -> Result<Vec<String>> {
+        let mut problems = Vec::new();
+
+        let rows = self.dangling_pairs(
+            r#"
+            SELECT d.blocker_id, d.blocked_id,
+                   EXISTS (SELECT 1 FROM issues WHERE id = d.blocker_id),
+                   EXISTS (SELECT 1 FROM issues WHERE id = d.blocked_id)
+            FROM dependencies d
+            WHERE NOT EXISTS (SELECT 1 FROM issues WHERE id = d.blocker_id)
+               OR NOT EXISTS (SELECT 1 FROM issues WHERE id = d.blocked_id)
+            ORDER BY d.blocked_id, d.blocker_id
+            "#,
+        )?;
+        for (blocker, blocked, blocker_exists, blocked_exists) in rows {
+            let missing = missing_ids(&[(blocker, blocker_exists), (blocked, blocked_exists)]);
+            problems.push(format!(
+                "Dependency #{} blocked by #{} references missing issue {}",
+                blocked, blocker, missing
+            ));
+        }
+
+        let rows = self.dangling_pairs(
+            r#"
+            SELECT r.issue_id_1, r.issue_id_2,
+                   EXISTS (SELECT 1 FROM issues WHERE id = r.issue_id_1),
+                   EXISTS (SELECT 1 FROM issues WHERE id = r.issue_id_2)
+            FROM relations r
+            WHERE NOT EXISTS (SELECT 1 FROM issues WHERE id = r.issue_id_1)
+               OR NOT EXISTS (SELECT 1 FROM issues WHERE id = r.issue_id_2)
+            ORDER BY r.issue_id_1, r.issue_id_2
+            "#,
+        )?;
+        for (a, b, a_exists, b_exists) in rows {
+            let missing = missing_ids(&[(a, a_exists), (b, b_exists)]);
+            problems.push(format!(
+                "Relation #{} <-> #{} references missing issue {}",
+                a, b, missing
+            ));
+        }
+
+        let rows = self.dangling_pairs(
+            r#"
+            SELECT mi.milestone_id, mi.issue_id,
+                   EXISTS (SELECT 1 FROM milestones WHERE id = mi.milestone_id),
+                   EXISTS (SELECT 1 FROM issues WHERE id = mi.issue_id)
+            FROM milestone_issues mi
+            WHERE NOT EXISTS (SELECT 1 FROM milestones WHERE id = mi.milestone_id)
+               OR NOT EXISTS (SELECT 1 FROM issues WHERE id = mi.issue_id)
+            ORDER BY mi.milestone_id, mi.issue_id
+            "#,
+        )?;
+        for (milestone, issue, milestone_exists, issue_exists) in rows {
+            if !milestone_exists {
+                problems.push(format!(
+                    "Milestone entry for issue #{} references missing milestone #{}",
+                    issue, milestone
+                ));
+            }
+            if !issue_exists {
+                problems.push(format!(
+                    "Milestone #{} references missing issue #{}",
+                    milestone, issue
+                ));
+            }
+        }
+
+        for cycle in self.find_dependency_cycles()? {
+            let ids: Vec<String> = cycle.iter().map(|id| format!("#{}", id)).collect();
+            problems.push(format!(
+                "Dependency cycle between {} (use 'chainlink unblock' to break it)",
+                ids.join(", ")
+            ));
+        }
+
+        Ok(problems)
+    }
+
+    /// Runs a query yielding (id_a, id_b, a_exists, b_exists) rows.
+    fn dangling_pairs(&self, sql: &str) -> Result<Vec<(i64, i64, bool, bool)>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
+
+/// Formats the ids whose existence flag is false, e.g. "#3" or "#3, #7".
+fn missing_ids(ids: &[(i64, bool)]) -> String {
+    ids.iter()
+        .filter(|(_, exists)| !exists)
+        .map(|(id, _)| format!("#{}", id))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// What the trash keeps of a deleted issue.
+#[derive(Serialize, Deserialize)]
+struct IssueSnapshot {
+    issue: Issue,
+    labels: Vec<String>,
+    comments: Vec<Comment>,
+    subissues: Vec<IssueSnapshot>,
+}
+
+impl IssueSnapshot {
+    fn descendant_count(&self) -> usize {
+        self.subissues
+            .iter()
+            .map(|sub| 1 + sub.descendant_count())
+            .sum()
+    }
 }
 
 fn parse_datetime(s: String) -> DateTime<Utc> {
@@ -968,7 +2466,7 @@ fn parse_datetime(s: String) -> DateTime<Utc> {
 }
 
 /// Maps a database row to an Issue struct.
-/// Expects columns in order: id, title, description, status, priority, parent_id, created_at, updated_at, closed_at
+/// Expects columns in order: id, title, description, status, priority, parent_id, created_at, updated_at, closed_at, source, due_at
 fn issue_from_row(row: &rusqlite::Row) -> rusqlite::Result<Issue> {
     Ok(Issue {
         id: row.get(0)?,
@@ -980,6 +2478,31 @@ fn issue_from_row(row: &rusqlite::Row) -> rusqlite::Result<Issue> {
         created_at: parse_datetime(row.get::<_, String>(6)?),
         updated_at: parse_datetime(row.get::<_, String>(7)?),
         closed_at: row.get::<_, Option<String>>(8)?.map(parse_datetime),
+        source: row.get(9)?,
+        due_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+    })
+}
+
+/// Maps a database row to a Session struct.
+/// Expects columns in order: id, started_at, ended_at, active_issue_id, handoff_notes, last_action
+fn session_from_row(row: &rusqlite::Row) -> rusqlite::Result<Session> {
+    Ok(Session {
+        id: row.get(0)?,
+        started_at: parse_datetime(row.get::<_, String>(1)?),
+        ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+        active_issue_id: row.get(3)?,
+        handoff_notes: row.get(4)?,
+        last_action: row.get(5)?,
+    })
+}
+
+fn time_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<TimeEntry> {
+    Ok(TimeEntry {
+        id: row.get(0)?,
+        issue_id: row.get(1)?,
+        started_at: parse_datetime(row.get::<_, String>(2)?),
+        ended_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
+        duration_seconds: row.get(4)?,
     })
 }
 
@@ -1014,6 +2537,24 @@ mod tests {
         assert!(issue.closed_at.is_none());
     }
 
+    #[test]
+    fn test_bulk_create_reuses_cached_statements() {
+        let (db, _dir) = setup_test_db();
+
+        let ids = db
+            .transaction(|| {
+                (0..5000)
+                    .map(|n| db.create_issue(&format!("Issue {}", n), None, "low"))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .unwrap();
+
+        assert_eq!(ids.len(), 5000);
+        assert_eq!(db.count_issues().unwrap(), 5000);
+        let last = db.get_issue(ids[4999]).unwrap().unwrap();
+        assert_eq!(last.title, "Issue 4999");
+    }
+
     #[test]
     fn test_create_issue_with_description() {
         let (db, _dir) = setup_test_db();
@@ -1028,6 +2569,44 @@ mod tests {
         assert_eq!(issue.priority, "high");
     }
 
+    #[test]
+    fn test_create_issue_with_id() {
+        let (db, _dir) = setup_test_db();
+
+        assert_eq!(db.create_issue_with_id(7, "Seven", None, "low").unwrap(), 7);
+        assert_eq!(db.get_issue(7).unwrap().unwrap().title, "Seven");
+        assert_eq!(db.count_issues().unwrap(), 1);
+
+        // Reusing an id is an error, not an overwrite
+        assert!(db.create_issue_with_id(7, "Again", None, "low").is_err());
+        assert_eq!(db.get_issue(7).unwrap().unwrap().title, "Seven");
+    }
+
+    #[test]
+    fn test_find_similar_titles() {
+        let (db, _dir) = setup_test_db();
+
+        let dup = db.create_issue("Fix login bug", None, "high").unwrap();
+        db.create_issue("Add dark mode", None, "low").unwrap();
+        let closed = db.create_issue("Fix the login bug", None, "low").unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        let similar = db.find_similar_titles("fix login bug").unwrap();
+        assert_eq!(similar, vec![(dup, "Fix login bug".to_string())]);
+        assert!(db.find_similar_titles("Write docs").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_issue_source_defaults_to_manual() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().source, "manual");
+
+        assert!(db.set_issue_source(id, "import").unwrap());
+        assert_eq!(db.get_issue(id).unwrap().unwrap().source, "import");
+    }
+
     #[test]
     fn test_create_subissue() {
         let (db, _dir) = setup_test_db();
@@ -1070,7 +2649,7 @@ mod tests {
 
         let id1 = db.create_issue("Open issue", None, "low").unwrap();
         let id2 = db.create_issue("To be closed", None, "medium").unwrap();
-        db.close_issue(id2).unwrap();
+        db.close_issue(id2, None).unwrap();
 
         let open_issues = db.list_issues(Some("open"), None, None).unwrap();
         assert_eq!(open_issues.len(), 1);
@@ -1106,7 +2685,7 @@ mod tests {
             .update_issue(
                 id,
                 Some("Updated title"),
-                Some("New description"),
+                FieldUpdate::Set("New description"),
                 Some("critical"),
             )
             .unwrap();
@@ -1126,7 +2705,8 @@ mod tests {
             .create_issue("Original title", Some("Original desc"), "low")
             .unwrap();
 
-        db.update_issue(id, Some("New title"), None, None).unwrap();
+        db.update_issue(id, Some("New title"), FieldUpdate::Keep, None)
+            .unwrap();
 
         let issue = db.get_issue(id).unwrap().unwrap();
         assert_eq!(issue.title, "New title");
@@ -1134,13 +2714,48 @@ mod tests {
         assert_eq!(issue.priority, "low");
     }
 
+    #[test]
+    fn test_update_issue_clear_description() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db
+            .create_issue("Title", Some("Original desc"), "low")
+            .unwrap();
+
+        db.update_issue(id, None, FieldUpdate::Clear, None).unwrap();
+
+        let issue = db.get_issue(id).unwrap().unwrap();
+        assert_eq!(issue.description, None);
+        assert_eq!(issue.title, "Title");
+    }
+
+    #[test]
+    fn test_update_issue_with_same_values_keeps_updated_at() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Title", Some("Desc"), "low").unwrap();
+        let before = db.get_issue(id).unwrap().unwrap().updated_at;
+
+        let updated = db
+            .update_issue(id, Some("Title"), FieldUpdate::Set("Desc"), Some("low"))
+            .unwrap();
+        assert!(!updated);
+        assert_eq!(db.get_issue(id).unwrap().unwrap().updated_at, before);
+
+        assert!(db
+            .update_issue(id, Some("Title"), FieldUpdate::Keep, Some("high"))
+            .unwrap());
+        assert!(db.get_issue(id).unwrap().unwrap().updated_at >= before);
+        assert_eq!(db.get_issue(id).unwrap().unwrap().priority, "high");
+    }
+
     #[test]
     fn test_close_and_reopen_issue() {
         let (db, _dir) = setup_test_db();
 
         let id = db.create_issue("Test issue", None, "medium").unwrap();
 
-        let closed = db.close_issue(id).unwrap();
+        let closed = db.close_issue(id, None).unwrap();
         assert!(closed);
 
         let issue = db.get_issue(id).unwrap().unwrap();
@@ -1160,13 +2775,50 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         // Closing an issue that doesn't exist should return false
-        let closed = db.close_issue(99999).unwrap();
+        let closed = db.close_issue(99999, None).unwrap();
         assert!(
             !closed,
             "close_issue should return false for nonexistent issue"
         );
     }
 
+    #[test]
+    fn test_drop_issue() {
+        let (db, _dir) = setup_test_db();
+        let dropped = db.create_issue("Abandoned", None, "low").unwrap();
+        let done = db.create_issue("Done", None, "low").unwrap();
+        db.close_issue(done, None).unwrap();
+
+        assert!(db.drop_issue(dropped).unwrap());
+        assert!(!db.drop_issue(99999).unwrap());
+
+        let issue = db.get_issue(dropped).unwrap().unwrap();
+        assert_eq!(issue.status, "wontdo");
+        assert!(issue.closed_at.is_some());
+        assert!(db.list_issues(Some("open"), None, None).unwrap().is_empty());
+        assert_eq!(db.list_issues(Some("wontdo"), None, None).unwrap().len(), 1);
+
+        // Only the completed issue counts towards time to close
+        db.conn
+            .execute(
+                "UPDATE issues SET created_at = '2020-01-01T00:00:00+00:00' WHERE id = ?1",
+                [dropped],
+            )
+            .unwrap();
+        assert!(db.avg_resolution_seconds().unwrap().unwrap() < 60.0);
+
+        let kinds: Vec<String> = db
+            .activity_feed(Utc::now() - chrono::Duration::hours(1))
+            .unwrap()
+            .into_iter()
+            .map(|item| item.kind)
+            .collect();
+        assert!(kinds.contains(&"dropped".to_string()));
+
+        assert!(db.reopen_issue(dropped).unwrap());
+        assert_eq!(db.get_issue(dropped).unwrap().unwrap().status, "open");
+    }
+
     #[test]
     fn test_reopen_nonexistent_issue_returns_false() {
         let (db, _dir) = setup_test_db();
@@ -1179,6 +2831,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_purge_archived() {
+        let (db, _dir) = setup_test_db();
+        let old = db.create_issue("Old", None, "low").unwrap();
+        let recent = db.create_issue("Recent", None, "low").unwrap();
+        let closed = db.create_issue("Closed", None, "low").unwrap();
+        for id in [old, recent, closed] {
+            db.close_issue(id, None).unwrap();
+        }
+        db.add_comment(old, "history").unwrap();
+        db.archive_issue(old).unwrap();
+        db.archive_issue(recent).unwrap();
+        db.backdate_archived_at(old, "2020-01-01T00:00:00+00:00");
+
+        assert!(db.purgeable_archived(Some(u32::MAX)).unwrap().is_empty());
+        assert_eq!(db.purge_archived(Some(30)).unwrap(), 1);
+        assert!(db.get_issue(old).unwrap().is_none());
+        assert!(db.get_comments(old, None).unwrap().is_empty());
+        assert_eq!(db.purgeable_archived(None).unwrap()[0].id, recent);
+        assert_eq!(db.purge_archived(None).unwrap(), 1);
+        assert_eq!(db.get_issue(closed).unwrap().unwrap().status, "closed");
+    }
+
+    #[test]
+    fn test_purge_older_counts_from_archiving_not_later_edits() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Old", None, "low").unwrap();
+        db.close_issue(id, None).unwrap();
+        db.archive_issue(id).unwrap();
+        db.backdate_archived_at(id, "2020-01-01T00:00:00+00:00");
+        db.add_comment(id, "late note").unwrap();
+        db.update_issue(id, Some("Old, retitled"), FieldUpdate::Keep, None)
+            .unwrap();
+
+        assert_eq!(db.purgeable_archived(Some(30)).unwrap()[0].id, id);
+
+        // Unarchiving and archiving again restarts the clock
+        db.unarchive_issue(id).unwrap();
+        db.archive_issue(id).unwrap();
+        assert!(db.purgeable_archived(Some(30)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_add_label_normalizes_whitespace() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Review me", None, "medium").unwrap();
+
+        assert!(db.add_label(id, "needs review").unwrap());
+        assert!(!db.add_label(id, " needs-review ").unwrap());
+        assert_eq!(db.get_labels(id).unwrap(), vec!["needs-review"]);
+
+        // Filtering goes through the same normalization
+        let found = db.list_issues(None, Some("needs review"), None).unwrap();
+        assert_eq!(found.len(), 1);
+        let kept = db
+            .list_issues_excluding(
+                None,
+                None,
+                None,
+                &["needs review".to_string()],
+                IssueOrder::Newest,
+            )
+            .unwrap();
+        assert!(kept.is_empty());
+
+        assert!(db.add_label(id, "ui,ux").is_err());
+        assert!(db.remove_label(id, "needs review").unwrap());
+    }
+
+    #[test]
+    fn test_add_label_reject_policy() {
+        let (mut db, _dir) = setup_test_db();
+        db.set_label_policy(LabelPolicy::Reject);
+        let id = db.create_issue("Review me", None, "medium").unwrap();
+
+        let err = db.add_label(id, "needs review").unwrap_err();
+        assert!(err.to_string().contains("contains a space"), "{}", err);
+        assert!(db.get_labels(id).unwrap().is_empty());
+        assert!(db.add_label(id, "needs-review").unwrap());
+    }
+
     #[test]
     fn test_delete_issue() {
         let (db, _dir) = setup_test_db();
@@ -1245,154 +2978,545 @@ mod tests {
         let removed = db.remove_label(id, "bug").unwrap();
         assert!(removed);
 
-        let labels = db.get_labels(id).unwrap();
-        assert_eq!(labels.len(), 1);
-        assert_eq!(labels[0], "urgent");
+        let labels = db.get_labels(id).unwrap();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0], "urgent");
+    }
+
+    #[test]
+    fn test_remove_nonexistent_label_returns_false() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        db.add_label(id, "bug").unwrap();
+
+        // Removing a label that doesn't exist should return false
+        let removed = db.remove_label(id, "nonexistent").unwrap();
+        assert!(
+            !removed,
+            "remove_label should return false for nonexistent label"
+        );
+    }
+
+    #[test]
+    fn test_list_issues_filter_by_label() {
+        let (db, _dir) = setup_test_db();
+
+        let id1 = db.create_issue("Bug issue", None, "high").unwrap();
+        let id2 = db.create_issue("Feature issue", None, "medium").unwrap();
+
+        db.add_label(id1, "bug").unwrap();
+        db.add_label(id2, "feature").unwrap();
+
+        let bug_issues = db.list_issues(None, Some("bug"), None).unwrap();
+        assert_eq!(bug_issues.len(), 1);
+        assert_eq!(bug_issues[0].id, id1);
+    }
+
+    // ==================== Comments Tests ====================
+
+    #[test]
+    fn test_add_and_get_comments() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+
+        let comment_id = db.add_comment(id, "First comment").unwrap();
+        assert!(comment_id > 0);
+
+        db.add_comment(id, "Second comment").unwrap();
+
+        let comments = db.get_comments(id, None).unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].content, "First comment");
+        assert_eq!(comments[1].content, "Second comment");
+    }
+
+    #[test]
+    fn test_get_comments_with_limit() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        for n in 1..=5 {
+            db.add_comment(id, &format!("Comment {}", n)).unwrap();
+        }
+
+        let comments = db.get_comments(id, Some(2)).unwrap();
+        let contents: Vec<&str> = comments.iter().map(|c| c.content.as_str()).collect();
+        assert_eq!(contents, vec!["Comment 4", "Comment 5"]);
+        assert_eq!(db.get_comments(id, Some(10)).unwrap().len(), 5);
+        assert_eq!(db.count_comments(id).unwrap(), 5);
+    }
+
+    // ==================== Dependencies Tests ====================
+
+    #[test]
+    fn test_add_and_get_dependencies() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker issue", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked issue", None, "medium").unwrap();
+
+        db.add_dependency(blocked, blocker).unwrap();
+
+        let blockers = db.get_blockers(blocked).unwrap();
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0], blocker);
+
+        let blocking = db.get_blocking(blocker).unwrap();
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0], blocked);
+    }
+
+    #[test]
+    fn test_remove_dependency() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+
+        db.add_dependency(blocked, blocker).unwrap();
+        let removed = db.remove_dependency(blocked, blocker).unwrap();
+        assert!(removed);
+
+        let blockers = db.get_blockers(blocked).unwrap();
+        assert!(blockers.is_empty());
+    }
+
+    #[test]
+    fn test_list_blocked_issues() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let unblocked = db.create_issue("Unblocked", None, "low").unwrap();
+
+        db.add_dependency(blocked, blocker).unwrap();
+
+        let blocked_issues = db.list_blocked_issues().unwrap();
+        assert_eq!(blocked_issues.len(), 1);
+        assert_eq!(blocked_issues[0].id, blocked);
+
+        // Unblocked issue should not appear
+        assert!(!blocked_issues.iter().any(|i| i.id == unblocked));
+    }
+
+    #[test]
+    fn test_list_ready_issues() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let ready = db.create_issue("Ready", None, "low").unwrap();
+
+        db.add_dependency(blocked, blocker).unwrap();
+
+        let ready_issues = db.list_ready_issues().unwrap();
+
+        // Blocker and ready should be in ready list (not blocked by anything)
+        let ready_ids: Vec<i64> = ready_issues.iter().map(|i| i.id).collect();
+        assert!(ready_ids.contains(&blocker));
+        assert!(ready_ids.contains(&ready));
+        assert!(!ready_ids.contains(&blocked));
+    }
+
+    fn query_plan(db: &Database, sql: &str) -> String {
+        let mut stmt = db
+            .conn
+            .prepare(&format!("EXPLAIN QUERY PLAN {}", sql))
+            .unwrap();
+        let details = stmt
+            .query_map([], |row| row.get::<_, String>(3))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        details.join("\n")
+    }
+
+    #[test]
+    fn test_ready_and_blocked_use_dependency_index() {
+        let (db, _dir) = setup_test_db();
+
+        let blocker = db.create_issue("Blocker", None, "high").unwrap();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let closed_blocker = db.create_issue("Closed blocker", None, "low").unwrap();
+        let unblocked = db.create_issue("Unblocked", None, "low").unwrap();
+        db.add_dependency(blocked, blocker).unwrap();
+        db.add_dependency(unblocked, closed_blocker).unwrap();
+        db.close_issue(closed_blocker, None).unwrap();
+
+        let ready: Vec<i64> = db
+            .list_ready_issues()
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ready, vec![blocker, unblocked]);
+        let blocked_ids: Vec<i64> = db
+            .list_blocked_issues()
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(blocked_ids, vec![blocked]);
+
+        for sql in [READY_ISSUES_SQL, BLOCKED_ISSUES_SQL] {
+            let plan = query_plan(&db, sql);
+            assert!(
+                plan.contains("idx_deps_blocked_blocker"),
+                "dependency index not used:\n{}",
+                plan
+            );
+            assert!(
+                plan.contains("idx_issues_status"),
+                "status index not used:\n{}",
+                plan
+            );
+        }
+    }
+
+    #[test]
+    fn test_immediate_transactions_serialize() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        Database::open(&db_path).unwrap();
+
+        // Each writer reads the count, pauses, then writes based on it. With
+        // the write lock taken up front the second waits for the first.
+        let writers: Vec<_> = (0..2)
+            .map(|_| {
+                let path = db_path.clone();
+                std::thread::spawn(move || {
+                    let db = Database::open(&path).unwrap();
+                    db.transaction_immediate(|| {
+                        let n = db.count_issues()?;
+                        std::thread::sleep(std::time::Duration::from_millis(50));
+                        db.create_issue(&format!("Issue {}", n), None, "medium")
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        let mut titles: Vec<String> = db
+            .list_issues(Some("all"), None, None)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Issue 0", "Issue 1"]);
+    }
+
+    #[test]
+    fn test_immediate_transaction_reports_busy() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let first = Database::open(&db_path).unwrap();
+        let second = Database::open(&db_path).unwrap();
+        second
+            .conn
+            .busy_timeout(std::time::Duration::from_millis(10))
+            .unwrap();
+
+        first
+            .transaction_immediate(|| {
+                first.create_issue("Held", None, "medium")?;
+                let err = second
+                    .transaction_immediate(|| second.create_issue("Blocked", None, "medium"))
+                    .unwrap_err();
+                assert!(err.to_string().contains("Database is busy"));
+                Ok(())
+            })
+            .unwrap();
+
+        // The lock is released on commit and the second connection can write
+        second
+            .transaction_immediate(|| second.create_issue("Later", None, "medium"))
+            .unwrap();
+        assert_eq!(second.count_issues().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_attachments_add_list_and_cascade() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Has files", None, "medium").unwrap();
+        let other = db.create_issue("No files", None, "medium").unwrap();
+
+        db.add_attachment(id, "docs/design.md", Some("Design doc"))
+            .unwrap();
+        db.add_attachment(id, "https://example.com/log.txt", None)
+            .unwrap();
+
+        let attachments = db.get_attachments(id).unwrap();
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0].path_or_url, "docs/design.md");
+        assert_eq!(attachments[0].description.as_deref(), Some("Design doc"));
+        assert_eq!(attachments[1].path_or_url, "https://example.com/log.txt");
+        assert!(db.get_attachments(other).unwrap().is_empty());
+
+        db.delete_issue(id).unwrap();
+        let remaining: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM attachments", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_newer_schema_refuses_to_open() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("future.db");
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch("PRAGMA user_version = 999;")
+            .unwrap();
+
+        let err = Database::open(&db_path).err().unwrap().to_string();
+        assert!(err.contains("newer chainlink (schema v999"), "{}", err);
+        assert!(err.contains("upgrade chainlink"), "{}", err);
+    }
+
+    #[test]
+    fn test_v1_database_upgrades_with_data_intact() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        {
+            // The original schema: no subissues, sessions, or issue source
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                r#"
+                CREATE TABLE issues (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    title TEXT NOT NULL,
+                    description TEXT,
+                    status TEXT NOT NULL DEFAULT 'open',
+                    priority TEXT NOT NULL DEFAULT 'medium',
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    closed_at TEXT
+                );
+                CREATE TABLE labels (
+                    issue_id INTEGER NOT NULL,
+                    label TEXT NOT NULL,
+                    PRIMARY KEY (issue_id, label)
+                );
+                CREATE TABLE comments (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    issue_id INTEGER NOT NULL,
+                    content TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+                INSERT INTO issues (title, description, priority, created_at, updated_at)
+                    VALUES ('Old issue', 'from v1', 'high', '2024-01-01T00:00:00+00:00', '2024-01-01T00:00:00+00:00');
+                INSERT INTO labels (issue_id, label) VALUES (1, 'bug');
+                INSERT INTO comments (issue_id, content, created_at)
+                    VALUES (1, 'still here', '2024-01-02T00:00:00+00:00');
+                PRAGMA user_version = 1;
+                "#,
+            )
+            .unwrap();
+        }
+
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+
+        let issue = db.get_issue(1).unwrap().unwrap();
+        assert_eq!(issue.title, "Old issue");
+        assert_eq!(issue.description.as_deref(), Some("from v1"));
+        assert_eq!(issue.priority, "high");
+        assert_eq!(issue.parent_id, None);
+        assert_eq!(issue.source, "manual");
+        assert_eq!(db.get_labels(1).unwrap(), vec!["bug"]);
+        assert_eq!(db.get_comments(1, None).unwrap()[0].content, "still here");
+
+        // Columns and tables added since v1 are usable
+        let child = db.create_subissue(1, "Child", None, "low").unwrap();
+        assert_eq!(db.get_issue(child).unwrap().unwrap().parent_id, Some(1));
+        let session = db.start_session().unwrap();
+        db.set_session_issue(session, 1).unwrap();
+        assert!(db.trash_issue(child).unwrap().is_some());
+
+        // Reopening an up-to-date database is a no-op
+        drop(db);
+        let db = Database::open(&db_path).unwrap();
+        assert_eq!(db.get_subissues(1).unwrap().len(), 0);
+        assert_eq!(db.list_trashed().unwrap().len(), 1);
     }
 
     #[test]
-    fn test_remove_nonexistent_label_returns_false() {
+    fn test_migrations_are_idempotent() {
         let (db, _dir) = setup_test_db();
-
-        let id = db.create_issue("Test issue", None, "medium").unwrap();
-        db.add_label(id, "bug").unwrap();
-
-        // Removing a label that doesn't exist should return false
-        let removed = db.remove_label(id, "nonexistent").unwrap();
-        assert!(
-            !removed,
-            "remove_label should return false for nonexistent label"
-        );
+        for (_, migrate) in MIGRATIONS {
+            migrate(&db.conn).unwrap();
+        }
+        assert!(has_column(&db.conn, "issues", "source").unwrap());
+        assert!(has_column(&db.conn, "sessions", "last_action").unwrap());
     }
 
     #[test]
-    fn test_list_issues_filter_by_label() {
+    fn test_sessions_migration_keeps_last_action() {
         let (db, _dir) = setup_test_db();
+        let session = db.start_session().unwrap();
+        db.set_session_action(session, "Wrote tests").unwrap();
 
-        let id1 = db.create_issue("Bug issue", None, "high").unwrap();
-        let id2 = db.create_issue("Feature issue", None, "medium").unwrap();
-
-        db.add_label(id1, "bug").unwrap();
-        db.add_label(id2, "feature").unwrap();
-
-        let bug_issues = db.list_issues(None, Some("bug"), None).unwrap();
-        assert_eq!(bug_issues.len(), 1);
-        assert_eq!(bug_issues[0].id, id1);
+        migrate_sessions_set_null(&db.conn).unwrap();
+        assert_eq!(
+            db.get_current_session().unwrap().unwrap().last_action,
+            Some("Wrote tests".to_string())
+        );
     }
 
-    // ==================== Comments Tests ====================
-
     #[test]
-    fn test_add_and_get_comments() {
+    fn test_sessions_migration_rebuilds_pre_v7_table() {
         let (db, _dir) = setup_test_db();
+        db.conn
+            .execute_batch(
+                "DROP TABLE sessions;
+                 CREATE TABLE sessions (
+                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                     started_at TEXT NOT NULL,
+                     ended_at TEXT,
+                     active_issue_id INTEGER,
+                     handoff_notes TEXT,
+                     FOREIGN KEY (active_issue_id) REFERENCES issues(id)
+                 );
+                 INSERT INTO sessions (started_at) VALUES ('2024-01-01T00:00:00+00:00');",
+            )
+            .unwrap();
 
-        let id = db.create_issue("Test issue", None, "medium").unwrap();
-
-        let comment_id = db.add_comment(id, "First comment").unwrap();
-        assert!(comment_id > 0);
-
-        db.add_comment(id, "Second comment").unwrap();
-
-        let comments = db.get_comments(id).unwrap();
-        assert_eq!(comments.len(), 2);
-        assert_eq!(comments[0].content, "First comment");
-        assert_eq!(comments[1].content, "Second comment");
+        migrate_sessions_set_null(&db.conn).unwrap();
+        let on_delete: String = db
+            .conn
+            .query_row(
+                "SELECT on_delete FROM pragma_foreign_key_list('sessions')",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(on_delete, "SET NULL");
+        let count: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
     }
 
-    // ==================== Dependencies Tests ====================
-
     #[test]
-    fn test_add_and_get_dependencies() {
-        let (db, _dir) = setup_test_db();
-
-        let blocker = db.create_issue("Blocker issue", None, "high").unwrap();
-        let blocked = db.create_issue("Blocked issue", None, "medium").unwrap();
-
-        db.add_dependency(blocked, blocker).unwrap();
-
-        let blockers = db.get_blockers(blocked).unwrap();
-        assert_eq!(blockers.len(), 1);
-        assert_eq!(blockers[0], blocker);
+    fn test_migration_drops_redundant_dependency_index() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        {
+            let db = Database::open(&db_path).unwrap();
+            db.conn
+                .execute_batch(
+                    "CREATE INDEX idx_deps_blocked ON dependencies(blocked_id);
+                     PRAGMA user_version = 10;",
+                )
+                .unwrap();
+        }
 
-        let blocking = db.get_blocking(blocker).unwrap();
-        assert_eq!(blocking.len(), 1);
-        assert_eq!(blocking[0], blocked);
+        let db = Database::open(&db_path).unwrap();
+        let indexes: Vec<String> = db
+            .conn
+            .prepare(
+                "SELECT name FROM sqlite_master WHERE type = 'index' AND tbl_name = 'dependencies'",
+            )
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(indexes.contains(&"idx_deps_blocked_blocker".to_string()));
+        assert!(!indexes.contains(&"idx_deps_blocked".to_string()));
     }
 
     #[test]
-    fn test_remove_dependency() {
+    fn test_blocked_becomes_ready_when_blocker_closed() {
         let (db, _dir) = setup_test_db();
 
         let blocker = db.create_issue("Blocker", None, "high").unwrap();
         let blocked = db.create_issue("Blocked", None, "medium").unwrap();
 
         db.add_dependency(blocked, blocker).unwrap();
-        let removed = db.remove_dependency(blocked, blocker).unwrap();
-        assert!(removed);
 
-        let blockers = db.get_blockers(blocked).unwrap();
-        assert!(blockers.is_empty());
+        // Initially blocked
+        let blocked_issues = db.list_blocked_issues().unwrap();
+        assert_eq!(blocked_issues.len(), 1);
+
+        // Close blocker
+        db.close_issue(blocker, None).unwrap();
+
+        // Now should be ready
+        let blocked_issues = db.list_blocked_issues().unwrap();
+        assert!(blocked_issues.is_empty());
+
+        let ready_issues = db.list_ready_issues().unwrap();
+        assert!(ready_issues.iter().any(|i| i.id == blocked));
     }
 
     #[test]
-    fn test_list_blocked_issues() {
+    fn test_blocked_ids() {
         let (db, _dir) = setup_test_db();
 
         let blocker = db.create_issue("Blocker", None, "high").unwrap();
         let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let closed_blocker = db.create_issue("Closed blocker", None, "low").unwrap();
         let unblocked = db.create_issue("Unblocked", None, "low").unwrap();
-
         db.add_dependency(blocked, blocker).unwrap();
+        db.add_dependency(unblocked, closed_blocker).unwrap();
+        db.close_issue(closed_blocker, None).unwrap();
 
-        let blocked_issues = db.list_blocked_issues().unwrap();
-        assert_eq!(blocked_issues.len(), 1);
-        assert_eq!(blocked_issues[0].id, blocked);
-
-        // Unblocked issue should not appear
-        assert!(!blocked_issues.iter().any(|i| i.id == unblocked));
+        let ids = db.blocked_ids().unwrap();
+        assert_eq!(ids, HashSet::from([blocked]));
     }
 
     #[test]
-    fn test_list_ready_issues() {
+    fn test_ready_when_only_blocker_deleted() {
         let (db, _dir) = setup_test_db();
 
         let blocker = db.create_issue("Blocker", None, "high").unwrap();
         let blocked = db.create_issue("Blocked", None, "medium").unwrap();
-        let ready = db.create_issue("Ready", None, "low").unwrap();
-
         db.add_dependency(blocked, blocker).unwrap();
 
+        db.delete_issue(blocker).unwrap();
         let ready_issues = db.list_ready_issues().unwrap();
+        assert!(ready_issues.iter().any(|i| i.id == blocked));
 
-        // Blocker and ready should be in ready list (not blocked by anything)
-        let ready_ids: Vec<i64> = ready_issues.iter().map(|i| i.id).collect();
-        assert!(ready_ids.contains(&blocker));
-        assert!(ready_ids.contains(&ready));
-        assert!(!ready_ids.contains(&blocked));
+        // A stale dependency row left behind with foreign keys off must not block either
+        db.conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO dependencies (blocker_id, blocked_id) VALUES (999, ?1)",
+                [blocked],
+            )
+            .unwrap();
+        db.conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+        let ready_issues = db.list_ready_issues().unwrap();
+        assert!(ready_issues.iter().any(|i| i.id == blocked));
     }
 
     #[test]
-    fn test_blocked_becomes_ready_when_blocker_closed() {
+    fn test_ready_when_only_blocker_closed() {
         let (db, _dir) = setup_test_db();
 
         let blocker = db.create_issue("Blocker", None, "high").unwrap();
         let blocked = db.create_issue("Blocked", None, "medium").unwrap();
-
         db.add_dependency(blocked, blocker).unwrap();
-
-        // Initially blocked
-        let blocked_issues = db.list_blocked_issues().unwrap();
-        assert_eq!(blocked_issues.len(), 1);
-
-        // Close blocker
-        db.close_issue(blocker).unwrap();
-
-        // Now should be ready
-        let blocked_issues = db.list_blocked_issues().unwrap();
-        assert!(blocked_issues.is_empty());
-
-        let ready_issues = db.list_ready_issues().unwrap();
-        assert!(ready_issues.iter().any(|i| i.id == blocked));
+        db.close_issue(blocker, None).unwrap();
+
+        let ready_ids: Vec<i64> = db
+            .list_ready_issues()
+            .unwrap()
+            .iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ready_ids, vec![blocked]);
     }
 
     // ==================== Sessions Tests ====================
@@ -1439,6 +3563,66 @@ mod tests {
         assert_eq!(session.active_issue_id, Some(issue_id));
     }
 
+    #[test]
+    fn test_all_sessions_and_insert_session() {
+        let (db, _dir) = setup_test_db();
+
+        let first = db.start_session().unwrap();
+        db.end_session(first, Some("Done")).unwrap();
+        db.start_session().unwrap();
+
+        let sessions = db.all_sessions().unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].handoff_notes, Some("Done".to_string()));
+
+        let copy_id = db.insert_session(&sessions[0]).unwrap();
+        let sessions = db.all_sessions().unwrap();
+        let copy = sessions.iter().find(|s| s.id == copy_id).unwrap();
+        assert_eq!(copy.started_at, sessions[0].started_at);
+        assert_eq!(copy.ended_at, sessions[0].ended_at);
+        assert_eq!(copy.handoff_notes, Some("Done".to_string()));
+    }
+
+    #[test]
+    fn test_watched_issues_track_status_and_comments() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Watched", None, "medium").unwrap();
+        db.create_issue("Not watched", None, "medium").unwrap();
+        assert!(db.watch_issue(id).unwrap());
+        assert!(!db.watch_issue(id).unwrap());
+
+        db.add_comment(id, "First").unwrap();
+        db.close_issue(id, None).unwrap();
+        let watched = db.watched_issues().unwrap();
+        assert_eq!(watched.len(), 1);
+        assert_eq!(watched[0].id, id);
+        assert_eq!(watched[0].status, "closed");
+        assert_eq!(watched[0].comment_count, 1);
+
+        assert!(db.unwatch_issue(id).unwrap());
+        assert!(!db.unwatch_issue(id).unwrap());
+        assert!(db.watched_issues().unwrap().is_empty());
+
+        // Deleting an issue drops its watch
+        db.watch_issue(id).unwrap();
+        db.delete_issue(id).unwrap();
+        assert!(db.watched_issues().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_last_tested_round_trip() {
+        let (db, _dir) = setup_test_db();
+        assert!(db.get_last_tested().unwrap().is_none());
+
+        let first = Utc::now() - chrono::Duration::hours(3);
+        db.set_last_tested(first).unwrap();
+        assert_eq!(db.get_last_tested().unwrap().unwrap(), first);
+
+        let second = Utc::now();
+        db.set_last_tested(second).unwrap();
+        assert_eq!(db.get_last_tested().unwrap().unwrap(), second);
+    }
+
     // ==================== Time Tracking Tests ====================
 
     #[test]
@@ -1473,6 +3657,28 @@ mod tests {
         assert_eq!(total, 0);
     }
 
+    #[test]
+    fn test_all_time_entries_and_insert_time_entry() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test issue", None, "medium").unwrap();
+        let started = Utc::now() - chrono::Duration::hours(1);
+        db.insert_time_entry(&TimeEntry {
+            id: 0,
+            issue_id: id,
+            started_at: started,
+            ended_at: Some(Utc::now()),
+            duration_seconds: Some(3600),
+        })
+        .unwrap();
+
+        let entries = db.all_time_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].issue_id, id);
+        assert_eq!(entries[0].duration_seconds, Some(3600));
+        assert_eq!(db.get_total_time(id).unwrap(), 3600);
+    }
+
     // ==================== Search Tests ====================
 
     #[test]
@@ -1484,7 +3690,7 @@ mod tests {
         db.create_issue("Add dark mode", None, "medium").unwrap();
         db.create_issue("Auth improvements", None, "low").unwrap();
 
-        let results = db.search_issues("auth").unwrap();
+        let results = db.search_issues("auth", None).unwrap();
         assert_eq!(results.len(), 2);
     }
 
@@ -1501,7 +3707,7 @@ mod tests {
         db.create_issue("Feature B", Some("Something else"), "medium")
             .unwrap();
 
-        let results = db.search_issues("authentication").unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
         assert_eq!(results.len(), 1);
     }
 
@@ -1513,13 +3719,79 @@ mod tests {
         db.add_comment(id, "Found the root cause in authentication module")
             .unwrap();
 
-        let results = db.search_issues("authentication").unwrap();
+        let results = db.search_issues("authentication", None).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, id);
+    }
+
+    #[test]
+    fn test_last_comment_id() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Test", None, "medium").unwrap();
+        let other = db.create_issue("Other", None, "medium").unwrap();
+        assert_eq!(db.last_comment_id(id).unwrap(), None);
+
+        db.add_comment(id, "first").unwrap();
+        let second = db.add_comment(id, "second").unwrap();
+        db.add_comment(other, "elsewhere").unwrap();
+        assert_eq!(db.last_comment_id(id).unwrap(), Some(second));
+    }
+
+    #[test]
+    fn test_search_issues_by_label() {
+        let (db, _dir) = setup_test_db();
+
+        let id = db.create_issue("Sidebar flickers", None, "medium").unwrap();
+        db.add_label(id, "regression").unwrap();
+        db.add_label(id, "regression-ui").unwrap();
+        db.add_comment(id, "Another regression from the last release")
+            .unwrap();
+        db.create_issue("Unrelated", None, "medium").unwrap();
+
+        let results = db.search_issues("regression", None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].id, id);
     }
 
     // ==================== Relations Tests ====================
 
+    #[test]
+    fn test_count_relations() {
+        let (db, _dir) = setup_test_db();
+
+        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
+        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
+        let id3 = db.create_issue("Issue 3", None, "medium").unwrap();
+        assert_eq!(db.count_relations(id1).unwrap(), 0);
+
+        db.add_relation(id1, id2, None).unwrap();
+        db.add_relation(id3, id1, None).unwrap();
+        assert_eq!(db.count_relations(id1).unwrap(), 2);
+        assert_eq!(db.count_relations(id2).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_relation_cap_rejects_past_limit() {
+        let (mut db, _dir) = setup_test_db();
+        db.set_max_relations(Some(1));
+
+        let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
+        let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
+        let id3 = db.create_issue("Issue 3", None, "medium").unwrap();
+
+        assert!(db.add_relation(id1, id2, None).unwrap());
+        // Re-adding an existing relation isn't an error
+        assert!(!db.add_relation(id2, id1, None).unwrap());
+
+        let err = db.add_relation(id3, id1, None).unwrap_err();
+        assert!(err.to_string().contains("already has 1 relations"));
+        assert_eq!(db.count_relations(id3).unwrap(), 0);
+
+        db.set_max_relations(None);
+        assert!(db.add_relation(id3, id1, None).unwrap());
+    }
+
     #[test]
     fn test_add_and_get_relations() {
         let (db, _dir) = setup_test_db();
@@ -1527,7 +3799,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        db.add_relation(id1, id2).unwrap();
+        db.add_relation(id1, id2, None).unwrap();
 
         let related = db.get_related_issues(id1).unwrap();
         assert_eq!(related.len(), 1);
@@ -1545,7 +3817,7 @@ mod tests {
 
         let id = db.create_issue("Issue", None, "medium").unwrap();
 
-        let result = db.add_relation(id, id);
+        let result = db.add_relation(id, id, None);
         assert!(result.is_err());
     }
 
@@ -1556,7 +3828,7 @@ mod tests {
         let id1 = db.create_issue("Issue 1", None, "medium").unwrap();
         let id2 = db.create_issue("Issue 2", None, "medium").unwrap();
 
-        db.add_relation(id1, id2).unwrap();
+        db.add_relation(id1, id2, None).unwrap();
         db.remove_relation(id1, id2).unwrap();
 
         let related = db.get_related_issues(id1).unwrap();
@@ -1602,8 +3874,9 @@ mod tests {
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].id, issue_id);
 
-        let milestone = db.get_issue_milestone(issue_id).unwrap().unwrap();
-        assert_eq!(milestone.id, milestone_id);
+        let milestones = db.get_issue_milestones(issue_id).unwrap();
+        assert_eq!(milestones.len(), 1);
+        assert_eq!(milestones[0].id, milestone_id);
     }
 
     #[test]
@@ -1625,7 +3898,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         let id = db.create_issue("Test", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
 
         let archived = db.archive_issue(id).unwrap();
         assert!(archived);
@@ -1634,6 +3907,25 @@ mod tests {
         assert_eq!(issue.status, "archived");
     }
 
+    #[test]
+    fn test_archive_dropped_issue_unarchives_as_dropped() {
+        let (db, _dir) = setup_test_db();
+        let dropped = db.create_issue("Dropped", None, "medium").unwrap();
+        let closed = db.create_issue("Closed", None, "medium").unwrap();
+        db.drop_issue(dropped).unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        assert_eq!(db.closed_older_than(-1).unwrap().len(), 2);
+        assert_eq!(db.archive_older_than(-1).unwrap(), 2);
+        assert!(db.unarchive_issue(dropped).unwrap());
+        assert!(db.unarchive_issue(closed).unwrap());
+        assert_eq!(db.get_issue(dropped).unwrap().unwrap().status, "wontdo");
+        assert_eq!(db.get_issue(closed).unwrap().unwrap().status, "closed");
+
+        assert!(db.archive_issue(dropped).unwrap());
+        assert_eq!(db.get_issue(dropped).unwrap().unwrap().status, "archived");
+    }
+
     #[test]
     fn test_archive_open_issue_fails() {
         let (db, _dir) = setup_test_db();
@@ -1652,7 +3944,7 @@ mod tests {
         let (db, _dir) = setup_test_db();
 
         let id = db.create_issue("Test", None, "medium").unwrap();
-        db.close_issue(id).unwrap();
+        db.close_issue(id, None).unwrap();
         db.archive_issue(id).unwrap();
 
         let unarchived = db.unarchive_issue(id).unwrap();
@@ -1669,7 +3961,7 @@ mod tests {
         let id1 = db.create_issue("Archived", None, "medium").unwrap();
         let _id2 = db.create_issue("Open", None, "medium").unwrap();
 
-        db.close_issue(id1).unwrap();
+        db.close_issue(id1, None).unwrap();
         db.archive_issue(id1).unwrap();
 
         let archived = db.list_archived_issues().unwrap();
@@ -1720,7 +4012,7 @@ mod tests {
 
         let labels = db.get_labels(id).unwrap();
         assert_eq!(labels.len(), 1);
-        assert_eq!(labels[0], malicious);
+        assert_eq!(labels[0], "bug';-DROP-TABLE-labels;---");
     }
 
     #[test]
@@ -1731,7 +4023,7 @@ mod tests {
 
         // Attempt injection in search
         let malicious = "%'; DROP TABLE issues; --";
-        let results = db.search_issues(malicious).unwrap();
+        let results = db.search_issues(malicious, None).unwrap();
 
         // Should return empty results, not crash
         assert!(results.is_empty());
@@ -1750,7 +4042,7 @@ mod tests {
 
         db.add_comment(id, malicious).unwrap();
 
-        let comments = db.get_comments(id).unwrap();
+        let comments = db.get_comments(id, None).unwrap();
         assert_eq!(comments.len(), 1);
         assert_eq!(comments[0].content, malicious);
     }
@@ -1823,7 +4115,7 @@ mod tests {
 
         db.delete_issue(id).unwrap();
 
-        let comments = db.get_comments(id).unwrap();
+        let comments = db.get_comments(id, None).unwrap();
         assert!(comments.is_empty());
     }
 
@@ -1874,6 +4166,185 @@ mod tests {
         assert_eq!(issue.parent_id, None);
     }
 
+    #[test]
+    fn test_get_open_blockers() {
+        let (db, _dir) = setup_test_db();
+        let blocked = db.create_issue("Blocked", None, "medium").unwrap();
+        let open = db.create_issue("Open blocker", None, "medium").unwrap();
+        let closed = db.create_issue("Closed blocker", None, "medium").unwrap();
+        db.add_dependency(blocked, open).unwrap();
+        db.add_dependency(blocked, closed).unwrap();
+        db.close_issue(closed, None).unwrap();
+
+        assert_eq!(db.get_open_blockers(blocked).unwrap(), vec![open]);
+        assert_eq!(db.get_blockers(blocked).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_find_dependency_cycles() {
+        let (db, _dir) = setup_test_db();
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        let c = db.create_issue("C", None, "medium").unwrap();
+        let d = db.create_issue("D", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+        db.add_dependency(d, c).unwrap();
+        assert!(db.find_dependency_cycles().unwrap().is_empty());
+
+        db.add_dependency_unchecked(a, b);
+        assert_eq!(db.find_dependency_cycles().unwrap(), vec![vec![a, b]]);
+
+        let problems = db.validate_integrity().unwrap();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("Dependency cycle"));
+    }
+
+    #[test]
+    fn test_avg_resolution_seconds() {
+        let (db, _dir) = setup_test_db();
+        assert_eq!(db.avg_resolution_seconds().unwrap(), None);
+
+        let created = Utc::now() - chrono::Duration::days(10);
+        let set_times = |id: i64, hours_open: i64| {
+            db.conn
+                .execute(
+                    "UPDATE issues SET created_at = ?1, closed_at = ?2 WHERE id = ?3",
+                    params![
+                        created.to_rfc3339(),
+                        (created + chrono::Duration::hours(hours_open)).to_rfc3339(),
+                        id
+                    ],
+                )
+                .unwrap();
+        };
+
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.create_issue("Still open", None, "medium").unwrap();
+        db.close_issue(a, None).unwrap();
+        db.close_issue(b, None).unwrap();
+        set_times(a, 2);
+        set_times(b, 4);
+        assert_eq!(db.avg_resolution_seconds().unwrap(), Some(3.0 * 3600.0));
+
+        // A reopened issue no longer counts until it is closed again
+        db.reopen_issue(b).unwrap();
+        assert_eq!(db.avg_resolution_seconds().unwrap(), Some(2.0 * 3600.0));
+    }
+
+    #[test]
+    fn test_avg_resolution_seconds_skips_archived_wontdo() {
+        let (db, _dir) = setup_test_db();
+        let id = db.create_issue("Not needed", None, "low").unwrap();
+        db.drop_issue(id).unwrap();
+        assert_eq!(db.avg_resolution_seconds().unwrap(), None);
+
+        db.archive_issue(id).unwrap();
+        assert_eq!(db.get_issue(id).unwrap().unwrap().status, "archived");
+        assert_eq!(db.avg_resolution_seconds().unwrap(), None);
+    }
+
+    // ==================== Activity Tests ====================
+
+    #[test]
+    fn test_activity_feed_respects_since() {
+        let (db, _dir) = setup_test_db();
+        let old = db.create_issue("Old issue", None, "medium").unwrap();
+        db.add_comment(old, "Yesterday's note").unwrap();
+        let created = Utc::now() - chrono::Duration::days(1);
+        let commented = created + chrono::Duration::hours(1);
+        db.conn
+            .execute("UPDATE issues SET created_at = ?1", [created.to_rfc3339()])
+            .unwrap();
+        db.conn
+            .execute(
+                "UPDATE comments SET created_at = ?1",
+                [commented.to_rfc3339()],
+            )
+            .unwrap();
+        db.close_issue(old, None).unwrap();
+
+        let week = db
+            .activity_feed(Utc::now() - chrono::Duration::days(7))
+            .unwrap();
+        let kinds: Vec<&str> = week.iter().map(|i| i.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["closed", "comment", "created"]);
+        assert_eq!(week[1].summary, "Yesterday's note");
+        assert!(week.iter().all(|i| i.issue_id == Some(old)));
+
+        let hour = db
+            .activity_feed(Utc::now() - chrono::Duration::hours(1))
+            .unwrap();
+        assert_eq!(hour.len(), 1);
+        assert_eq!(hour[0].kind, "closed");
+    }
+
+    #[test]
+    fn test_activity_feed_includes_sessions() {
+        let (db, _dir) = setup_test_db();
+        let session = db.start_session().unwrap();
+        db.end_session(session, None).unwrap();
+
+        let feed = db
+            .activity_feed(Utc::now() - chrono::Duration::hours(1))
+            .unwrap();
+        let kinds: Vec<&str> = feed.iter().map(|i| i.kind.as_str()).collect();
+        assert!(kinds.contains(&"session started"));
+        assert!(kinds.contains(&"session ended"));
+    }
+
+    // ==================== Integrity Tests ====================
+
+    #[test]
+    fn test_validate_integrity_clean_db() {
+        let (db, _dir) = setup_test_db();
+
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let b = db.create_issue("B", None, "medium").unwrap();
+        db.add_dependency(b, a).unwrap();
+        db.add_relation(a, b, None).unwrap();
+        let m = db.create_milestone("v1", None).unwrap();
+        db.add_issue_to_milestone(m, a).unwrap();
+
+        assert!(db.validate_integrity().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_integrity_reports_dangling_rows() {
+        let (db, _dir) = setup_test_db();
+
+        let a = db.create_issue("A", None, "medium").unwrap();
+        let m = db.create_milestone("v1", None).unwrap();
+
+        // Simulate rows left behind while foreign keys were disabled
+        db.conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO dependencies (blocker_id, blocked_id) VALUES (999, ?1)",
+                [a],
+            )
+            .unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO relations (issue_id_1, issue_id_2, created_at) VALUES (?1, 998, '')",
+                [a],
+            )
+            .unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO milestone_issues (milestone_id, issue_id) VALUES (?1, 997)",
+                [m],
+            )
+            .unwrap();
+        db.conn.execute("PRAGMA foreign_keys = ON", []).unwrap();
+
+        let problems = db.validate_integrity().unwrap();
+        assert_eq!(problems.len(), 3, "got: {:?}", problems);
+        assert!(problems[0].contains("Dependency") && problems[0].contains("#999"));
+        assert!(problems[1].contains("Relation") && problems[1].contains("#998"));
+        assert!(problems[2].contains("Milestone") && problems[2].contains("#997"));
+    }
+
     // ==================== Database Corruption Recovery ====================
 
     #[test]
@@ -2051,7 +4522,7 @@ mod proptest_tests {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue("Test", None, "medium").unwrap();
             db.add_comment(id, &content).unwrap();
-            let comments = db.get_comments(id).unwrap();
+            let comments = db.get_comments(id, None).unwrap();
             prop_assert_eq!(comments.len(), 1);
             prop_assert_eq!(&comments[0].content, &content);
         }
@@ -2073,7 +4544,7 @@ mod proptest_tests {
             let (db, _dir) = setup_test_db();
             let id = db.create_issue(&title, None, "medium").unwrap();
 
-            db.close_issue(id).unwrap();
+            db.close_issue(id, None).unwrap();
             let issue = db.get_issue(id).unwrap().unwrap();
             prop_assert_eq!(issue.status, "closed");
 
@@ -2111,7 +4582,7 @@ mod proptest_tests {
             db.create_issue(&title, None, "medium").unwrap();
 
             // Search for the unique marker
-            let results = db.search_issues("unique marker").unwrap();
+            let results = db.search_issues("unique marker", None).unwrap();
             prop_assert!(!results.is_empty());
             prop_assert!(results.iter().any(|i| i.title.contains("unique marker")));
         }
@@ -2245,7 +4716,7 @@ mod proptest_tests {
             db.create_issue("other content here", None, "medium").unwrap();
 
             // Search for the special characters literally
-            let results = db.search_issues("%test_").unwrap();
+            let results = db.search_issues("%test_", None).unwrap();
 
             // Should find only the issue with literal % and _
             prop_assert!(results.iter().all(|i| i.title.contains("%test_")));
@@ -2,6 +2,9 @@
 //!
 //! This module exposes the core functionality for use in fuzzing and testing.
 
+pub mod config;
 pub mod db;
 pub mod models;
+pub mod store;
 pub mod utils;
+pub mod workspace;
@@ -1,13 +1,18 @@
 mod commands;
+mod config;
 mod daemon;
 mod db;
 mod models;
+mod store;
 mod utils;
+mod workspace;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
 use clap::{Parser, Subcommand};
 use std::env;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Instant;
 
 use db::Database;
 
@@ -20,10 +25,23 @@ struct Cli {
     #[arg(short, long, global = true)]
     quiet: bool,
 
+    /// Print timing and database diagnostics to stderr
+    #[arg(short, long, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
     /// Output as JSON (supported by list, show, search, session status)
     #[arg(long, global = true)]
     json: bool,
 
+    /// Disable colored output (also honors the NO_COLOR env var)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Use this database file instead of discovering .chainlink/issues.db
+    /// (also settable via the CHAINLINK_DB env var)
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -35,6 +53,12 @@ enum Commands {
         /// Force update hooks even if already initialized
         #[arg(short, long)]
         force: bool,
+        /// Add the database files to .gitignore (the default)
+        #[arg(long, overrides_with = "no_gitignore")]
+        gitignore: bool,
+        /// Leave .gitignore alone
+        #[arg(long, overrides_with = "gitignore")]
+        no_gitignore: bool,
     },
 
     /// Create a new issue
@@ -44,9 +68,9 @@ enum Commands {
         /// Issue description
         #[arg(short, long)]
         description: Option<String>,
-        /// Priority (low, medium, high, critical)
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Priority (low, medium, high, critical) [default: default_priority in config.toml, then hook-config.json, else medium]
+        #[arg(short, long)]
+        priority: Option<String>,
         /// Template (bug, feature, refactor, research)
         #[arg(short, long)]
         template: Option<String>,
@@ -56,6 +80,12 @@ enum Commands {
         /// Set as current session work item
         #[arg(short, long)]
         work: bool,
+        /// Create even if an open issue with a similar title exists
+        #[arg(short, long)]
+        force: bool,
+        /// Accept a title that is empty or only whitespace
+        #[arg(long)]
+        allow_empty_title: bool,
     },
 
     /// Quick-create an issue and start working on it (create + label + session work)
@@ -65,15 +95,21 @@ enum Commands {
         /// Issue description
         #[arg(short, long)]
         description: Option<String>,
-        /// Priority (low, medium, high, critical)
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Priority (low, medium, high, critical) [default: default_priority in config.toml, then hook-config.json, else medium]
+        #[arg(short, long)]
+        priority: Option<String>,
         /// Template (bug, feature, refactor, research)
         #[arg(short, long)]
         template: Option<String>,
         /// Add labels to the issue
         #[arg(short, long)]
         label: Vec<String>,
+        /// Create even if an open issue with a similar title exists
+        #[arg(short, long)]
+        force: bool,
+        /// Accept a title that is empty or only whitespace
+        #[arg(long)]
+        allow_empty_title: bool,
     },
 
     /// Create a subissue under a parent issue
@@ -85,40 +121,131 @@ enum Commands {
         /// Subissue description
         #[arg(short, long)]
         description: Option<String>,
-        /// Priority (low, medium, high, critical)
-        #[arg(short, long, default_value = "medium")]
-        priority: String,
+        /// Priority (low, medium, high, critical) [default: default_priority in config.toml, then hook-config.json, else medium]
+        #[arg(short, long)]
+        priority: Option<String>,
+        /// Template (bug, feature, refactor, research)
+        #[arg(short, long)]
+        template: Option<String>,
         /// Add labels to the subissue
         #[arg(short, long)]
         label: Vec<String>,
         /// Set as current session work item
         #[arg(short, long)]
         work: bool,
+        /// Create even if an open issue with a similar title exists
+        #[arg(short, long)]
+        force: bool,
+        /// Accept a title that is empty or only whitespace
+        #[arg(long)]
+        allow_empty_title: bool,
+    },
+
+    /// Move several issues under a new parent
+    ReparentAll {
+        /// New parent issue ID, or 'none' to make them top-level
+        new_parent: commands::reparent::NewParent,
+        /// Issues to move
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+
+    /// Move an issue before or after another in manual order (`list --sort manual`)
+    Rank {
+        /// Issue ID
+        id: i64,
+        /// Place it just before this issue
+        #[arg(long, conflicts_with = "after", required_unless_present = "after")]
+        before: Option<i64>,
+        /// Place it just after this issue
+        #[arg(long)]
+        after: Option<i64>,
     },
 
     /// List issues
     List {
-        /// Filter by status (open, closed, all)
-        #[arg(short, long, default_value = "open")]
-        status: String,
+        /// Filter by status (open, closed, all) [default: default_list_status in config.toml, then hook-config.json, else open]
+        #[arg(short, long)]
+        status: Option<String>,
         /// Filter by label
         #[arg(short, long)]
         label: Option<String>,
-        /// Filter by priority
+        /// Hide issues with this label (repeatable)
+        #[arg(long, value_name = "LABEL")]
+        not_label: Vec<String>,
+        /// Filter by priority, or by rank with a comparison such as ">=high" or "<medium"
         #[arg(short, long)]
         priority: Option<String>,
+        /// Filter by origin (manual, import, cpitd)
+        #[arg(long)]
+        source: Option<String>,
+        /// Only direct subissues of this issue ID, or "none" for top-level issues
+        #[arg(long, value_name = "ID|none")]
+        parent: Option<commands::list::ParentFilter>,
+        /// Show exact timestamps instead of relative ages
+        #[arg(long)]
+        absolute: bool,
+        /// Append the parent's title to subissue rows
+        #[arg(long)]
+        show_parent: bool,
+        /// Show full titles instead of fitting rows to the terminal width
+        #[arg(long)]
+        full: bool,
+        /// Row order: newest, or manual (as set with `rank`)
+        #[arg(long, default_value = "newest", value_name = "newest|manual")]
+        sort: db::IssueOrder,
+        /// With --json, only include these comma-separated fields (e.g. id,title,status)
+        #[arg(long, value_delimiter = ',')]
+        fields: Option<Vec<String>>,
+    },
+
+    /// Show recent activity (issues created/closed, comments, sessions)
+    Activity {
+        /// How far back to look, e.g. 30m, 12h, 7d, 2w
+        #[arg(long, default_value = "7d")]
+        since: String,
+        /// Only comments and closes by this author
+        #[arg(long)]
+        author: Option<String>,
+        /// Only issues closed by $CHAINLINK_USER
+        #[arg(long, conflicts_with = "author")]
+        closed_by_me: bool,
     },
 
     /// Search issues by text
     Search {
         /// Search query
         query: String,
+        /// Show at most this many results (0 for all)
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Treat the query as a regular expression
+        #[arg(long)]
+        regex: bool,
     },
 
     /// Show issue details
     Show {
         /// Issue ID
         id: i64,
+        /// Show exact timestamps instead of relative ages
+        #[arg(long)]
+        absolute: bool,
+        /// Only show the most recent N comments
+        #[arg(long, value_name = "N")]
+        comments: Option<usize>,
+        /// Print the description as stored instead of wrapping it to the terminal
+        #[arg(long)]
+        no_wrap: bool,
+        /// Only show comments by this author
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// Open an issue at repo_url/issues/<id> in the browser (prints it like `show` without repo_url)
+    Open {
+        /// Issue ID
+        id: i64,
     },
 
     /// Update an issue
@@ -131,18 +258,46 @@ enum Commands {
         /// New description
         #[arg(short, long)]
         description: Option<String>,
+        /// Remove the description entirely
+        #[arg(long, conflicts_with = "description")]
+        clear_description: bool,
         /// New priority
         #[arg(short, long)]
         priority: Option<String>,
+        /// Due date (YYYY-MM-DD, or an offset like 3d)
+        #[arg(long)]
+        due: Option<String>,
+        /// Remove the due date
+        #[arg(long, conflicts_with = "due")]
+        clear_due: bool,
+        /// Reopen a copy after closing: daily, weekly, monthly, or an interval like 10d
+        #[arg(long)]
+        recur: Option<String>,
+        /// Stop the issue recurring
+        #[arg(long, conflicts_with = "recur")]
+        clear_recur: bool,
     },
 
-    /// Close an issue
+    /// Close one or more issues
     Close {
-        /// Issue ID
-        id: i64,
+        /// Issue IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
         /// Skip changelog entry
         #[arg(long)]
         no_changelog: bool,
+        /// Close even if the issue has open subissues
+        #[arg(short, long, conflicts_with = "cascade")]
+        force: bool,
+        /// Close open subissues as well
+        #[arg(long)]
+        cascade: bool,
+        /// How the issue was resolved (fixed, wontfix, duplicate, invalid)
+        #[arg(long, alias = "reason")]
+        resolution: Option<String>,
+        /// Mark as won't do instead: abandoned, not completed (same as `drop`)
+        #[arg(long, conflicts_with_all = ["resolution", "cascade", "force"])]
+        wontdo: bool,
     },
 
     /// Close all issues matching filters
@@ -158,27 +313,69 @@ enum Commands {
         no_changelog: bool,
     },
 
+    /// Mark issues as won't do (abandoned rather than completed)
+    Drop {
+        /// Issue IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+
     /// Reopen a closed issue
     Reopen {
         /// Issue ID
         id: i64,
+        /// Also reopen its closed subissues, at any depth
+        #[arg(long, alias = "all-subissues")]
+        subissues: bool,
     },
 
-    /// Delete an issue
+    /// Delete one or more issues
     Delete {
-        /// Issue ID
-        id: i64,
+        /// Issue IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
         /// Skip confirmation
         #[arg(short, long)]
         force: bool,
+        /// Move the issues to the trash instead of deleting them permanently
+        #[arg(long, conflicts_with = "hard")]
+        trash: bool,
+        /// Delete permanently, even if `delete_behavior` says otherwise
+        #[arg(long)]
+        hard: bool,
+    },
+
+    /// Browse deleted issues kept in the trash
+    Trash {
+        #[command(subcommand)]
+        action: TrashCommands,
+    },
+
+    /// Restore an issue from the trash (it gets a new id)
+    Restore {
+        /// Trash entry ID, as shown by 'chainlink trash list'
+        trash_id: i64,
     },
 
     /// Add a comment to an issue
     Comment {
         /// Issue ID
         id: i64,
-        /// Comment text
-        text: String,
+        /// Comment text (use "-" to read from stdin)
+        #[arg(required_unless_present_any = ["file", "pin", "unpin"])]
+        text: Option<String>,
+        /// Read the comment text from a file
+        #[arg(short, long, conflicts_with = "text")]
+        file: Option<PathBuf>,
+        /// Replace the most recent comment instead of adding a new one
+        #[arg(long)]
+        amend: bool,
+        /// Pin a comment so `show` lists it first (comment ids are in `show --json`)
+        #[arg(long, value_name = "COMMENT_ID", conflicts_with_all = ["text", "file", "amend", "unpin"])]
+        pin: Option<i64>,
+        /// Unpin a comment
+        #[arg(long, value_name = "COMMENT_ID", conflicts_with_all = ["text", "file", "amend"])]
+        unpin: Option<i64>,
     },
 
     /// Add a label to an issue
@@ -223,8 +420,35 @@ enum Commands {
     Relate {
         /// First issue ID
         id: i64,
-        /// Second issue ID
-        related: i64,
+        /// Issue IDs to relate to the first one
+        #[arg(required = true)]
+        related: Vec<i64>,
+        /// Relate every pair of the given issues, not just each to the first
+        #[arg(long)]
+        clique: bool,
+        /// Relate even if one issue is an ancestor of the other
+        #[arg(short, long)]
+        force: bool,
+        /// Why the issues are related, shown by `related` and `show`
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Attach a file path or URL to an issue (stored as given, not copied)
+    Attach {
+        /// Issue ID
+        id: i64,
+        /// File path or URL
+        path_or_url: String,
+        /// What the attachment is
+        #[arg(long)]
+        desc: Option<String>,
+    },
+
+    /// List an issue's attachments
+    Attachments {
+        /// Issue ID
+        id: i64,
     },
 
     /// Remove a relation between issues
@@ -241,14 +465,43 @@ enum Commands {
         id: i64,
     },
 
+    /// Have the daemon report status changes and new comments on issues (lists watched issues without ids)
+    Watch {
+        /// Issue IDs
+        ids: Vec<i64>,
+    },
+
+    /// Stop watching issues
+    Unwatch {
+        /// Issue IDs
+        #[arg(required = true)]
+        ids: Vec<i64>,
+    },
+
     /// Suggest the next issue to work on
-    Next,
+    Next {
+        /// List the top N ready issues instead of a single recommendation
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+        /// Show how each issue was scored
+        #[arg(long)]
+        explain: bool,
+    },
+
+    /// Show issue counts and average time to close
+    Stats,
 
     /// Show issues as a tree hierarchy
     Tree {
         /// Filter by status (open, closed, all)
         #[arg(short, long, default_value = "all")]
         status: String,
+        /// Mark each issue [ready], [blocked] or [closed]
+        #[arg(long)]
+        annotate: bool,
+        /// Show full titles instead of fitting lines to the terminal width
+        #[arg(long)]
+        full: bool,
     },
 
     /// Start a timer for an issue
@@ -261,7 +514,11 @@ enum Commands {
     Stop,
 
     /// Show current timer status
-    Timer,
+    Timer {
+        /// List every time entry for this issue instead, with the total
+        #[arg(long, value_name = "ID")]
+        issue: Option<i64>,
+    },
 
     /// Mark tests as run (resets test reminder)
     Tested,
@@ -271,15 +528,52 @@ enum Commands {
         /// Output file path (defaults to stdout)
         #[arg(short, long)]
         output: Option<String>,
-        /// Format (json, markdown)
+        /// Format (json, jsonl, markdown)
         #[arg(short, long, default_value = "json")]
         format: String,
+        /// Gzip the output (implied when the output file ends in .gz)
+        #[arg(long)]
+        gzip: bool,
+        /// Write one markdown file per issue into --output-dir
+        #[arg(
+            long,
+            requires = "output_dir",
+            conflicts_with_all = ["output", "format", "gzip"]
+        )]
+        split: bool,
+        /// Directory for --split (created if needed)
+        #[arg(long, requires = "split", value_name = "DIR")]
+        output_dir: Option<String>,
+        /// Write into a non-empty --output-dir, removing stale issue files
+        #[arg(long, requires = "split")]
+        force: bool,
+        /// Replace titles, descriptions and comments with placeholders (json and jsonl)
+        #[arg(long, conflicts_with = "split")]
+        anonymize: bool,
     },
 
-    /// Import issues from JSON file
+    /// Interactive issue list: arrow keys to move, c to close, l to label
+    #[cfg(feature = "tui")]
+    Ui {
+        /// Which issues to show (open, closed, all)
+        #[arg(short, long, default_value = "open")]
+        status: String,
+        /// Skip CHANGELOG.md updates when closing
+        #[arg(long)]
+        no_changelog: bool,
+    },
+
+    /// Print JSON schemas for the --json output and export formats
+    #[command(hide = true)]
+    Schema,
+
+    /// Import issues from a JSON export (gzip-compressed or plain)
     Import {
         /// Input file path
         input: String,
+        /// Keep the exported issue ids instead of assigning fresh ones (empty database only)
+        #[arg(long)]
+        preserve_ids: bool,
     },
 
     /// Archive management
@@ -300,6 +594,10 @@ enum Commands {
         action: SessionCommands,
     },
 
+    /// Report environment and database health (for bug reports)
+    #[command(alias = "info")]
+    Doctor,
+
     /// Daemon management
     Daemon {
         #[command(subcommand)]
@@ -311,14 +609,36 @@ enum Commands {
         #[command(subcommand)]
         action: CpitdCommands,
     },
+
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        action: DbCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrashCommands {
+    /// List trashed issues, most recently deleted first
+    List,
 }
 
 #[derive(Subcommand)]
 enum ArchiveCommands {
-    /// Archive a closed issue
+    /// Archive a closed issue, or every closed issue matching --label/--priority
     Add {
         /// Issue ID
-        id: i64,
+        #[arg(required_unless_present_any = ["label", "priority"], conflicts_with_all = ["label", "priority"])]
+        id: Option<i64>,
+        /// Archive closed issues with this label
+        #[arg(short, long)]
+        label: Option<String>,
+        /// Archive closed issues with this priority
+        #[arg(short, long)]
+        priority: Option<String>,
+        /// Reason for archiving, stored as an [archived] comment
+        #[arg(short, long)]
+        note: Option<String>,
     },
     /// Unarchive an issue (restore to closed)
     Remove {
@@ -327,10 +647,22 @@ enum ArchiveCommands {
     },
     /// List archived issues
     List,
+    /// Permanently delete archived issues
+    Purge {
+        /// Only those archived more than this many days ago
+        #[arg(long, value_name = "DAYS")]
+        older: Option<u32>,
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
     /// Archive all issues closed more than N days ago
     Older {
-        /// Days threshold
-        days: i64,
+        /// Days threshold [default: auto_archive_days in config.toml]
+        days: Option<i64>,
+        /// List the issues that would be archived without archiving them
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -360,7 +692,14 @@ enum MilestoneCommands {
         /// Milestone ID
         id: i64,
         /// Issue IDs to add
+        #[arg(required_unless_present_any = ["label", "priority"], conflicts_with_all = ["label", "priority"])]
         issues: Vec<i64>,
+        /// Add open issues with this label
+        #[arg(short, long)]
+        label: Option<String>,
+        /// Add open issues with this priority
+        #[arg(short, long)]
+        priority: Option<String>,
     },
     /// Remove an issue from a milestone
     Remove {
@@ -390,6 +729,12 @@ enum SessionCommands {
         /// Handoff notes for the next session
         #[arg(short, long)]
         notes: Option<String>,
+        /// Stop the running timer too (the default)
+        #[arg(long, overrides_with = "no_stop_timer")]
+        stop_timer: bool,
+        /// Leave the running timer going
+        #[arg(long, overrides_with = "stop_timer")]
+        no_stop_timer: bool,
     },
     /// Show current session status
     Status,
@@ -397,6 +742,9 @@ enum SessionCommands {
     Work {
         /// Issue ID
         id: i64,
+        /// Also start a timer for the issue, stopping any other running timer
+        #[arg(long)]
+        timer: bool,
     },
     /// Show handoff notes from the previous session
     LastHandoff,
@@ -419,7 +767,20 @@ enum CpitdCommands {
         /// Glob patterns to exclude (repeatable)
         #[arg(long)]
         ignore: Vec<String>,
-        /// Show what would be created without creating issues
+        /// File clones with at least this many lines at medium priority
+        #[arg(long, value_name = "LINES")]
+        medium_lines: Option<usize>,
+        /// File clones with at least this many lines at high priority
+        #[arg(long, value_name = "LINES")]
+        high_lines: Option<usize>,
+        /// Keep open clone issues whose pair is no longer detected (by default,
+        /// those under the scanned paths are closed)
+        #[arg(long)]
+        no_auto_close: bool,
+        /// Ignore pairs that already have an open clone issue
+        #[arg(long)]
+        skip_tracked: bool,
+        /// Show what would be created and closed without changing anything
         #[arg(long)]
         dry_run: bool,
     },
@@ -429,6 +790,12 @@ enum CpitdCommands {
     Clear,
 }
 
+#[derive(Subcommand)]
+enum DbCommands {
+    /// Check for dependencies, relations and milestone entries that reference missing rows
+    Check,
+}
+
 #[derive(Subcommand)]
 enum DaemonCommands {
     /// Start the background daemon
@@ -446,33 +813,93 @@ enum DaemonCommands {
 }
 
 fn find_chainlink_dir() -> Result<PathBuf> {
-    let mut current = env::current_dir()?;
+    workspace::chainlink_dir(&env::current_dir()?)
+}
 
-    loop {
-        let candidate = current.join(".chainlink");
-        if candidate.is_dir() {
-            return Ok(candidate);
-        }
+/// Explicit database path from `--db` or `CHAINLINK_DB`, set once at startup.
+static DB_OVERRIDE: OnceLock<Option<PathBuf>> = OnceLock::new();
 
-        if !current.pop() {
-            bail!("Not a chainlink repository (or any parent). Run 'chainlink init' first.");
-        }
-    }
+fn db_override() -> Option<&'static PathBuf> {
+    DB_OVERRIDE.get().and_then(|p| p.as_ref())
 }
 
+/// `label_policy` from config.toml, applied to every database `get_db` opens.
+static LABEL_POLICY: OnceLock<utils::LabelPolicy> = OnceLock::new();
+
 fn get_db() -> Result<Database> {
-    let chainlink_dir = find_chainlink_dir()?;
-    let db_path = chainlink_dir.join("issues.db");
-    Database::open(&db_path).context("Failed to open database")
+    let db_path = match db_override() {
+        Some(path) => path.clone(),
+        None => workspace::discover(&env::current_dir()?)?,
+    };
+    let mut db = workspace::open_db(&db_path)?;
+    if let Some(policy) = LABEL_POLICY.get() {
+        db.set_label_policy(*policy);
+    }
+    utils::debug(format_args!(
+        "opened {} (schema v{})",
+        db_path.display(),
+        db.schema_version()?
+    ));
+    Ok(db)
+}
+
+/// Settings from the project's config.toml and hook-config.json. Outside a project, or if the
+/// file can't be read, the built-in defaults apply.
+fn load_config() -> config::Config {
+    let Ok(dir) = find_chainlink_dir() else {
+        return config::Config::default();
+    };
+    workspace::load_config(&dir).unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}; using default settings", e);
+        config::Config::default()
+    })
+}
+
+/// Where `close` should look for CHANGELOG.md, and whether to update it.
+/// A `--db` tracker outside any project has no changelog to update.
+fn changelog_target(update_changelog: bool) -> Result<(PathBuf, bool)> {
+    match find_chainlink_dir() {
+        Ok(dir) => Ok((dir, update_changelog)),
+        Err(_) if db_override().is_some() => Ok((PathBuf::new(), false)),
+        Err(e) => Err(e),
+    }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let db_path = cli.db.clone().or_else(|| {
+        env::var_os("CHAINLINK_DB")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    });
+    DB_OVERRIDE
+        .set(db_path)
+        .expect("database path is only set once");
+    utils::set_verbosity(utils::Verbosity::from_flags(cli.quiet, cli.verbose));
+    let config = load_config();
+    LABEL_POLICY
+        .set(config.label_policy.unwrap_or_default())
+        .expect("label policy is only set once");
+    utils::set_no_color(cli.no_color || config.color == Some(false));
+
+    let started = Instant::now();
+    let result = run(cli, &config);
+    utils::debug(format_args!("finished in {:.1?}", started.elapsed()));
+    result
+}
 
+fn run(cli: Cli, config: &config::Config) -> Result<()> {
+    // Recorded as the author of comments and closes made by this invocation
+    let user = utils::current_user();
+    let user = user.as_deref();
     match cli.command {
-        Commands::Init { force } => {
+        Commands::Init {
+            force,
+            gitignore: _,
+            no_gitignore,
+        } => {
             let cwd = env::current_dir()?;
-            commands::init::run(&cwd, force)
+            commands::init::run(&cwd, force, !no_gitignore)
         }
 
         Commands::Create {
@@ -482,18 +909,22 @@ fn main() -> Result<()> {
             template,
             label,
             work,
+            force,
+            allow_empty_title,
         } => {
             let db = get_db()?;
             let opts = commands::create::CreateOpts {
                 labels: &label,
                 work,
-                quiet: cli.quiet,
+                force,
+                default_priority: config.priority(),
+                allow_empty_title,
             };
             commands::create::run(
                 &db,
                 &title,
                 description.as_deref(),
-                &priority,
+                priority.as_deref(),
                 template.as_deref(),
                 &opts,
             )
@@ -505,18 +936,22 @@ fn main() -> Result<()> {
             priority,
             template,
             label,
+            force,
+            allow_empty_title,
         } => {
             let db = get_db()?;
             let opts = commands::create::CreateOpts {
                 labels: &label,
                 work: true,
-                quiet: cli.quiet,
+                force,
+                default_priority: config.priority(),
+                allow_empty_title,
             };
             commands::create::run(
                 &db,
                 &title,
                 description.as_deref(),
-                &priority,
+                priority.as_deref(),
                 template.as_deref(),
                 &opts,
             )
@@ -527,80 +962,230 @@ fn main() -> Result<()> {
             title,
             description,
             priority,
+            template,
             label,
             work,
+            force,
+            allow_empty_title,
         } => {
             let db = get_db()?;
             let opts = commands::create::CreateOpts {
                 labels: &label,
                 work,
-                quiet: cli.quiet,
+                force,
+                default_priority: config.priority(),
+                allow_empty_title,
             };
             commands::create::run_subissue(
                 &db,
                 parent,
                 &title,
                 description.as_deref(),
-                &priority,
+                priority.as_deref(),
+                template.as_deref(),
                 &opts,
             )
         }
 
+        Commands::ReparentAll { new_parent, ids } => {
+            let db = get_db()?;
+            commands::reparent::run_all(&db, new_parent, &ids).map(|_| ())
+        }
+
+        Commands::Rank { id, before, after } => {
+            let db = get_db()?;
+            let position = match (before, after) {
+                (Some(anchor), _) => commands::rank::Position::Before(anchor),
+                (None, Some(anchor)) => commands::rank::Position::After(anchor),
+                (None, None) => unreachable!("clap requires --before or --after"),
+            };
+            commands::rank::run(&db, id, position)
+        }
+
         Commands::List {
             status,
             label,
+            not_label,
             priority,
+            source,
+            parent,
+            absolute,
+            show_parent,
+            full,
+            sort,
+            fields,
+        } => {
+            if fields.is_some() && !cli.json {
+                bail!("--fields only applies to --json output");
+            }
+            let db = get_db()?;
+            let filter = commands::list::ListFilter {
+                status: Some(status.as_deref().unwrap_or(config.list_status())),
+                label: label.as_deref(),
+                not_labels: &not_label,
+                priority: priority
+                    .as_deref()
+                    .map(commands::list::parse_priority_filter)
+                    .transpose()?,
+                source: source.as_deref(),
+                parent,
+                order: sort,
+            };
+            if cli.json {
+                commands::list::run_json(&db, &filter, fields.as_deref())
+            } else {
+                commands::list::run(
+                    &db,
+                    &filter,
+                    &commands::list::ListDisplay {
+                        absolute,
+                        date_format: config.date_format(),
+                        show_parent,
+                        width: utils::terminal_width(),
+                        full,
+                    },
+                )
+            }
+        }
+
+        Commands::Activity {
+            since,
+            author,
+            closed_by_me,
         } => {
             let db = get_db()?;
+            let me = if closed_by_me {
+                Some(commands::activity::require_current_user()?)
+            } else {
+                None
+            };
+            let filter = match &me {
+                Some(user) => commands::activity::ActivityFilter::closed_by(user),
+                None => commands::activity::ActivityFilter {
+                    author: author.as_deref(),
+                    closed_only: false,
+                },
+            };
             if cli.json {
-                commands::list::run_json(&db, Some(&status), label.as_deref(), priority.as_deref())
+                commands::activity::run_json(&db, &since, filter)
             } else {
-                commands::list::run(&db, Some(&status), label.as_deref(), priority.as_deref())
+                commands::activity::run(&db, &since, filter)
             }
         }
 
-        Commands::Search { query } => {
+        Commands::Search {
+            query,
+            limit,
+            regex,
+        } => {
             let db = get_db()?;
+            let limit = (limit > 0).then_some(limit);
             if cli.json {
-                commands::search::run_json(&db, &query)
+                commands::search::run_json(&db, &query, limit, regex)
             } else {
-                commands::search::run(&db, &query)
+                commands::search::run(&db, &query, limit, regex)
             }
         }
 
-        Commands::Show { id } => {
+        Commands::Show {
+            id,
+            absolute,
+            comments,
+            no_wrap,
+            author,
+        } => {
             let db = get_db()?;
             if cli.json {
                 commands::show::run_json(&db, id)
             } else {
-                commands::show::run(&db, id)
+                let width = if no_wrap {
+                    None
+                } else {
+                    utils::terminal_width()
+                };
+                commands::show::run(&db, id, absolute, comments, width, author.as_deref())
             }
         }
 
+        Commands::Open { id } => {
+            let db = get_db()?;
+            commands::open::run(
+                &db,
+                id,
+                config.repo_url.as_deref(),
+                utils::terminal_width(),
+                &commands::open::Browser,
+            )
+        }
+
         Commands::Update {
             id,
             title,
             description,
+            clear_description,
             priority,
+            due,
+            clear_due,
+            recur,
+            clear_recur,
         } => {
             let db = get_db()?;
-            commands::update::run(
-                &db,
-                id,
-                title.as_deref(),
-                description.as_deref(),
-                priority.as_deref(),
-            )
+            let description = if clear_description {
+                db::FieldUpdate::Clear
+            } else {
+                description.as_deref().into()
+            };
+            let due = if clear_due {
+                db::FieldUpdate::Clear
+            } else {
+                due.as_deref().into()
+            };
+            let recur = if clear_recur {
+                db::FieldUpdate::Clear
+            } else {
+                recur.as_deref().into()
+            };
+            let scheduling = !due.is_keep() || !recur.is_keep();
+            if !scheduling || title.is_some() || !description.is_keep() || priority.is_some() {
+                commands::update::run(&db, id, title.as_deref(), description, priority.as_deref())?;
+            }
+            if scheduling {
+                commands::update::schedule(&db, id, due, recur)?;
+            }
+            Ok(())
         }
 
-        Commands::Close { id, no_changelog } => {
+        Commands::Close {
+            ids,
+            no_changelog,
+            force,
+            cascade,
+            resolution,
+            wontdo,
+        } => {
             let db = get_db()?;
-            let chainlink_dir = find_chainlink_dir()?;
-            if cli.quiet {
-                commands::status::close_quiet(&db, id, !no_changelog, &chainlink_dir)
-            } else {
-                commands::status::close(&db, id, !no_changelog, &chainlink_dir)
+            if wontdo {
+                return commands::status::drop_issues(&db, &ids, user);
             }
+            let (chainlink_dir, update_changelog) = changelog_target(!no_changelog)?;
+            let subissues = if cascade {
+                commands::status::OpenSubissues::Cascade
+            } else if force {
+                commands::status::OpenSubissues::Ignore
+            } else {
+                commands::status::OpenSubissues::Refuse
+            };
+            commands::status::close_many(
+                &db,
+                &ids,
+                &commands::status::CloseOpts {
+                    update_changelog,
+                    chainlink_dir: &chainlink_dir,
+                    subissues,
+                    resolution: resolution.as_deref(),
+                    closed_by: user,
+                },
+            )
         }
 
         Commands::CloseAll {
@@ -609,29 +1194,82 @@ fn main() -> Result<()> {
             no_changelog,
         } => {
             let db = get_db()?;
-            let chainlink_dir = find_chainlink_dir()?;
+            let (chainlink_dir, update_changelog) = changelog_target(!no_changelog)?;
             commands::status::close_all(
                 &db,
                 label.as_deref(),
                 priority.as_deref(),
-                !no_changelog,
+                update_changelog,
                 &chainlink_dir,
+                user,
             )
         }
 
-        Commands::Reopen { id } => {
+        Commands::Drop { ids } => {
             let db = get_db()?;
-            commands::status::reopen(&db, id)
+            commands::status::drop_issues(&db, &ids, user)
         }
 
-        Commands::Delete { id, force } => {
+        Commands::Reopen { id, subissues } => {
             let db = get_db()?;
-            commands::delete::run(&db, id, force)
+            if subissues {
+                commands::status::reopen_with_subissues(&db, id)
+            } else {
+                commands::status::reopen(&db, id)
+            }
         }
 
-        Commands::Comment { id, text } => {
+        Commands::Delete {
+            ids,
+            force,
+            trash,
+            hard,
+        } => {
+            let db = get_db()?;
+            let mode = if hard {
+                commands::delete::DeleteMode::Hard
+            } else if trash {
+                commands::delete::DeleteMode::Trash
+            } else {
+                config.delete_mode()
+            };
+            commands::delete::run(&db, &ids, force, mode, user)
+        }
+
+        Commands::Trash { action } => {
+            let db = get_db()?;
+            match action {
+                TrashCommands::List if cli.json => commands::trash::list_json(&db),
+                TrashCommands::List => commands::trash::list(&db),
+            }
+        }
+
+        Commands::Restore { trash_id } => {
+            let db = get_db()?;
+            commands::trash::restore(&db, trash_id)
+        }
+
+        Commands::Comment {
+            id,
+            text,
+            file,
+            amend,
+            pin,
+            unpin,
+        } => {
             let db = get_db()?;
-            commands::comment::run(&db, id, &text)
+            if let Some(comment_id) = pin {
+                return commands::comment::pin(&db, id, comment_id, true);
+            }
+            if let Some(comment_id) = unpin {
+                return commands::comment::pin(&db, id, comment_id, false);
+            }
+            match (text.as_deref(), file) {
+                (_, Some(path)) => commands::comment::run_from_file(&db, id, &path, amend, user),
+                (Some("-"), None) => commands::comment::run_from_stdin(&db, id, amend, user),
+                (Some(text), None) => commands::comment::save(&db, id, text, amend, user),
+                (None, None) => bail!("Provide comment text or --file"),
+            }
         }
 
         Commands::Label { id, label } => {
@@ -664,9 +1302,30 @@ fn main() -> Result<()> {
             commands::deps::list_ready(&db)
         }
 
-        Commands::Relate { id, related } => {
+        Commands::Relate {
+            id,
+            related,
+            clique,
+            force,
+            reason,
+        } => {
+            let mut db = get_db()?;
+            db.set_max_relations(config.max_relations_per_issue);
+            commands::relate::add_many(&db, id, &related, clique, force, reason.as_deref())
+        }
+
+        Commands::Attach {
+            id,
+            path_or_url,
+            desc,
+        } => {
+            let db = get_db()?;
+            commands::attach::add(&db, id, &path_or_url, desc.as_deref())
+        }
+
+        Commands::Attachments { id } => {
             let db = get_db()?;
-            commands::relate::add(&db, id, related)
+            commands::attach::list(&db, id)
         }
 
         Commands::Unrelate { id, related } => {
@@ -679,14 +1338,38 @@ fn main() -> Result<()> {
             commands::relate::list(&db, id)
         }
 
-        Commands::Next => {
+        Commands::Watch { ids } => {
             let db = get_db()?;
-            commands::next::run(&db)
+            if ids.is_empty() {
+                commands::watch::list(&db)
+            } else {
+                commands::watch::watch(&db, &ids)
+            }
         }
 
-        Commands::Tree { status } => {
+        Commands::Unwatch { ids } => {
             let db = get_db()?;
-            commands::tree::run(&db, Some(&status))
+            commands::watch::unwatch(&db, &ids)
+        }
+
+        Commands::Next { count, explain } => {
+            let db = get_db()?;
+            commands::next::run(&db, count, explain, config.due_weight())
+        }
+
+        Commands::Stats => {
+            let db = get_db()?;
+            commands::stats::run(&db)
+        }
+
+        Commands::Tree {
+            status,
+            annotate,
+            full,
+        } => {
+            let db = get_db()?;
+            let width = if full { None } else { utils::terminal_width() };
+            commands::tree::run(&db, Some(&status), annotate, width)
         }
 
         Commands::Start { id } => {
@@ -699,40 +1382,113 @@ fn main() -> Result<()> {
             commands::timer::stop(&db)
         }
 
-        Commands::Timer => {
+        Commands::Timer { issue } => {
             let db = get_db()?;
-            commands::timer::status(&db)
+            match (issue, cli.json) {
+                (Some(id), true) => commands::timer::history_json(&db, id),
+                (Some(id), false) => commands::timer::history(&db, id),
+                (None, true) => commands::timer::status_json(&db),
+                (None, false) => commands::timer::status(&db),
+            }
         }
 
         Commands::Tested => {
             let chainlink_dir = find_chainlink_dir()?;
-            commands::tested::run(&chainlink_dir)
+            let db = get_db()?;
+            commands::tested::run(&db, &chainlink_dir)
         }
 
-        Commands::Export { output, format } => {
+        Commands::Export {
+            output,
+            format,
+            gzip,
+            split: _,
+            output_dir,
+            force,
+            anonymize,
+        } => {
             let db = get_db()?;
+            if let Some(dir) = output_dir {
+                return commands::export::run_split(&db, std::path::Path::new(&dir), force);
+            }
             match format.as_str() {
-                "json" => commands::export::run_json(&db, output.as_deref()),
-                "markdown" | "md" => commands::export::run_markdown(&db, output.as_deref()),
+                "json" => commands::export::run_json(&db, output.as_deref(), gzip, anonymize),
+                "jsonl" => commands::export::run_jsonl(&db, output.as_deref(), gzip, anonymize),
+                "markdown" | "md" if anonymize => {
+                    bail!("--anonymize only works with the json and jsonl formats")
+                }
+                "markdown" | "md" => commands::export::run_markdown(
+                    &db,
+                    output.as_deref(),
+                    config.repo_url.as_deref(),
+                    gzip,
+                ),
                 _ => {
-                    bail!("Unknown format '{}'. Use 'json' or 'markdown'", format);
+                    bail!(
+                        "Unknown format '{}'. Use 'json', 'jsonl' or 'markdown'",
+                        format
+                    );
                 }
             }
         }
 
-        Commands::Import { input } => {
+        #[cfg(feature = "tui")]
+        Commands::Ui {
+            status,
+            no_changelog,
+        } => {
+            let db = get_db()?;
+            let (chainlink_dir, update_changelog) = changelog_target(!no_changelog)?;
+            commands::ui::run(&db, &chainlink_dir, update_changelog, user, &status)
+        }
+
+        Commands::Schema => commands::schema::run(),
+
+        Commands::Import {
+            input,
+            preserve_ids,
+        } => {
             let db = get_db()?;
             let path = std::path::Path::new(&input);
-            commands::import::run_json(&db, path)
+            commands::import::run_json(&db, path, preserve_ids)
         }
 
         Commands::Archive { action } => {
             let db = get_db()?;
             match action {
-                ArchiveCommands::Add { id } => commands::archive::archive(&db, id),
+                ArchiveCommands::Add {
+                    id,
+                    label,
+                    priority,
+                    note,
+                } => match id {
+                    Some(id) => commands::archive::archive(&db, id, note.as_deref(), user),
+                    None => {
+                        if let Some(priority) = priority.as_deref() {
+                            commands::create::validate_priority(priority)?;
+                        }
+                        commands::archive::archive_matching(
+                            &db,
+                            label.as_deref(),
+                            priority.as_deref(),
+                            note.as_deref(),
+                            user,
+                        )
+                    }
+                },
                 ArchiveCommands::Remove { id } => commands::archive::unarchive(&db, id),
                 ArchiveCommands::List => commands::archive::list(&db),
-                ArchiveCommands::Older { days } => commands::archive::archive_older(&db, days),
+                ArchiveCommands::Purge { older, force } => {
+                    commands::archive::purge(&db, older, force)
+                }
+                ArchiveCommands::Older { days, dry_run } => {
+                    let Some(days) = days.or(config.auto_archive_days) else {
+                        bail!(
+                            "Give a day count, or set auto_archive_days in .chainlink/config.toml"
+                        );
+                    };
+                    commands::archive::archive_older(&db, days, dry_run)
+                }
             }
         }
 
@@ -744,7 +1500,26 @@ fn main() -> Result<()> {
                 }
                 MilestoneCommands::List { status } => commands::milestone::list(&db, Some(&status)),
                 MilestoneCommands::Show { id } => commands::milestone::show(&db, id),
-                MilestoneCommands::Add { id, issues } => commands::milestone::add(&db, id, &issues),
+                MilestoneCommands::Add {
+                    id,
+                    issues,
+                    label,
+                    priority,
+                } => {
+                    if issues.is_empty() {
+                        if let Some(priority) = priority.as_deref() {
+                            commands::create::validate_priority(priority)?;
+                        }
+                        commands::milestone::add_matching(
+                            &db,
+                            id,
+                            label.as_deref(),
+                            priority.as_deref(),
+                        )
+                    } else {
+                        commands::milestone::add(&db, id, &issues)
+                    }
+                }
                 MilestoneCommands::Remove { id, issue } => {
                     commands::milestone::remove(&db, id, issue)
                 }
@@ -757,14 +1532,24 @@ fn main() -> Result<()> {
             let db = get_db()?;
             match action {
                 SessionCommands::Start => commands::session::start(&db),
-                SessionCommands::End { notes } => commands::session::end(&db, notes.as_deref()),
+                SessionCommands::End {
+                    notes,
+                    stop_timer: _,
+                    no_stop_timer,
+                } => commands::session::end(&db, notes.as_deref(), !no_stop_timer),
                 SessionCommands::Status => commands::session::status(&db),
-                SessionCommands::Work { id } => commands::session::work(&db, id),
+                SessionCommands::Work { id, timer } => commands::session::work(&db, id, timer),
                 SessionCommands::LastHandoff => commands::session::last_handoff(&db),
-                SessionCommands::Action { text } => commands::session::action(&db, &text),
+                SessionCommands::Action { text } => commands::session::action(&db, &text, user),
             }
         }
 
+        Commands::Doctor => {
+            let db = get_db()?;
+            let chainlink_dir = find_chainlink_dir().ok();
+            commands::doctor::run(&db, chainlink_dir.as_deref())
+        }
+
         Commands::Daemon { action } => match action {
             DaemonCommands::Start => {
                 let chainlink_dir = find_chainlink_dir()?;
@@ -788,11 +1573,36 @@ fn main() -> Result<()> {
                     paths,
                     min_tokens,
                     ignore,
+                    medium_lines,
+                    high_lines,
+                    no_auto_close,
+                    skip_tracked,
                     dry_run,
-                } => commands::cpitd::scan(&db, &paths, min_tokens, &ignore, dry_run, cli.quiet),
+                } => commands::cpitd::scan(
+                    &db,
+                    &commands::cpitd::ScanOpts {
+                        paths: &paths,
+                        min_tokens,
+                        ignore_patterns: &ignore,
+                        thresholds: commands::cpitd::SeverityThresholds {
+                            medium_lines,
+                            high_lines,
+                        },
+                        auto_close: !no_auto_close,
+                        skip_tracked,
+                        dry_run,
+                    },
+                ),
                 CpitdCommands::Status => commands::cpitd::status(&db),
                 CpitdCommands::Clear => commands::cpitd::clear(&db),
             }
         }
+
+        Commands::Db { action } => {
+            let db = get_db()?;
+            match action {
+                DbCommands::Check => commands::integrity::check(&db),
+            }
+        }
     }
 }
@@ -1,7 +1,8 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Issue {
     pub id: i64,
     pub title: String,
@@ -12,14 +13,29 @@ pub struct Issue {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub closed_at: Option<DateTime<Utc>>,
+    /// Where the issue came from: "manual", "import", or "cpitd"
+    #[serde(default = "default_source")]
+    pub source: String,
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+fn default_source() -> String {
+    "manual".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Comment {
     pub id: i64,
     pub issue_id: i64,
     pub content: String,
     pub created_at: DateTime<Utc>,
+    /// Pinned comments are listed first by `show`
+    #[serde(default)]
+    pub pinned: bool,
+    /// `$CHAINLINK_USER` when the comment was written, if it was set
+    #[serde(default)]
+    pub author: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -33,6 +49,49 @@ pub struct Session {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimeEntry {
+    pub id: i64,
+    pub issue_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+}
+
+/// One entry in the `activity` feed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActivityItem {
+    pub timestamp: DateTime<Utc>,
+    /// "created", "closed", "comment", "session started" or "session ended"
+    pub kind: String,
+    pub issue_id: Option<i64>,
+    pub summary: String,
+    /// Who wrote the comment or closed the issue, when that was recorded
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+/// A deleted issue kept in the trash so it can be restored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrashedIssue {
+    pub id: i64,
+    /// The id the issue had before it was deleted
+    pub issue_id: i64,
+    pub title: String,
+    /// Number of subissues trashed along with it
+    pub subissue_count: usize,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// What the daemon compares between polls of a watched issue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchedIssue {
+    pub id: i64,
+    pub title: String,
+    pub status: String,
+    pub comment_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Milestone {
     pub id: i64,
     pub name: String,
@@ -42,6 +101,16 @@ pub struct Milestone {
     pub closed_at: Option<DateTime<Utc>>,
 }
 
+/// A file path or URL linked to an issue. Paths are stored as given, not copied.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Attachment {
+    pub id: i64,
+    pub issue_id: i64,
+    pub path_or_url: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,6 +130,8 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             closed_at: None,
+            source: "manual".to_string(),
+            due_at: None,
         };
 
         let json = serde_json::to_string(&issue).unwrap();
@@ -86,6 +157,8 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             closed_at: None,
+            source: "manual".to_string(),
+            due_at: None,
         };
 
         let json = serde_json::to_string(&issue).unwrap();
@@ -107,6 +180,8 @@ mod tests {
             created_at: now,
             updated_at: now,
             closed_at: Some(now),
+            source: "manual".to_string(),
+            due_at: None,
         };
 
         let json = serde_json::to_string(&issue).unwrap();
@@ -127,6 +202,8 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             closed_at: None,
+            source: "manual".to_string(),
+            due_at: None,
         };
 
         let json = serde_json::to_string(&issue).unwrap();
@@ -139,6 +216,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_issue_source_defaults_when_missing() {
+        let json = r#"{"id":1,"title":"Old","description":null,"status":"open","priority":"medium","parent_id":null,"created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z","closed_at":null}"#;
+        let issue: Issue = serde_json::from_str(json).unwrap();
+        assert_eq!(issue.source, "manual");
+    }
+
+    /// `YYYY-MM-DDTHH:MM:SS[.fraction]Z`, the format JSON consumers rely on
+    fn is_rfc3339_utc(s: &str) -> bool {
+        let b = s.as_bytes();
+        b.len() >= 20
+            && b[4] == b'-'
+            && b[7] == b'-'
+            && b[10] == b'T'
+            && b[13] == b':'
+            && b[16] == b':'
+            && s.ends_with('Z')
+            && DateTime::parse_from_rfc3339(s).is_ok()
+    }
+
+    #[test]
+    fn test_issue_datetimes_serialize_as_rfc3339_utc() {
+        let whole = DateTime::parse_from_rfc3339("2024-03-05T07:08:09Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let issue = Issue {
+            id: 1,
+            title: "Dates".to_string(),
+            description: None,
+            status: "closed".to_string(),
+            priority: "medium".to_string(),
+            parent_id: None,
+            created_at: whole,
+            updated_at: Utc::now(),
+            closed_at: Some(whole),
+            source: "manual".to_string(),
+            due_at: None,
+        };
+
+        let value = serde_json::to_value(&issue).unwrap();
+        assert_eq!(value["created_at"], "2024-03-05T07:08:09Z");
+        for field in ["created_at", "updated_at", "closed_at"] {
+            let s = value[field].as_str().unwrap();
+            assert!(is_rfc3339_utc(s), "{} = {}", field, s);
+        }
+    }
+
     // ==================== Comment Tests ====================
 
     #[test]
@@ -148,6 +272,8 @@ mod tests {
             issue_id: 42,
             content: "A comment".to_string(),
             created_at: Utc::now(),
+            pinned: false,
+            author: None,
         };
 
         let json = serde_json::to_string(&comment).unwrap();
@@ -165,6 +291,8 @@ mod tests {
             issue_id: 1,
             content: "".to_string(),
             created_at: Utc::now(),
+            pinned: false,
+            author: None,
         };
 
         let json = serde_json::to_string(&comment).unwrap();
@@ -274,6 +402,8 @@ mod tests {
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 closed_at: None,
+                source: "manual".to_string(),
+                due_at: None,
             };
 
             let json = serde_json::to_string(&issue).unwrap();
@@ -296,6 +426,8 @@ mod tests {
                 issue_id,
                 content: content.clone(),
                 created_at: Utc::now(),
+                pinned: false,
+                author: None,
             };
 
             let json = serde_json::to_string(&comment).unwrap();
@@ -369,6 +501,8 @@ mod tests {
                 created_at: now,
                 updated_at: now,
                 closed_at: if is_closed { Some(now) } else { None },
+                source: "manual".to_string(),
+                due_at: None,
             };
 
             let json = serde_json::to_string(&issue).unwrap();
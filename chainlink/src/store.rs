@@ -0,0 +1,234 @@
+//! Storage abstraction used by commands.
+//!
+//! `Database` is the SQLite implementation. Commands that take `&dyn Store`
+//! can run against any backend that implements this trait.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+use crate::db::{Database, IssueOrder, PriorityFilter};
+use crate::models::{Attachment, Comment, Issue, Milestone, Session};
+
+/// Issue storage operations used by `list`, `show`, `create` and `close`.
+pub trait Store {
+    fn create_issue(&self, title: &str, description: Option<&str>, priority: &str) -> Result<i64>;
+    fn create_subissue(
+        &self,
+        parent_id: i64,
+        title: &str,
+        description: Option<&str>,
+        priority: &str,
+    ) -> Result<i64>;
+    fn get_issue(&self, id: i64) -> Result<Option<Issue>>;
+    fn list_issues(
+        &self,
+        status_filter: Option<&str>,
+        label_filter: Option<&str>,
+        priority_filter: Option<&str>,
+    ) -> Result<Vec<Issue>>;
+    fn list_issues_excluding(
+        &self,
+        status_filter: Option<&str>,
+        label_filter: Option<&str>,
+        priority_filter: Option<PriorityFilter<'_>>,
+        excluded_labels: &[String],
+        order: IssueOrder,
+    ) -> Result<Vec<Issue>>;
+    fn find_similar_titles(&self, title: &str) -> Result<Vec<(i64, String)>>;
+    /// Run `f` in a transaction, rolling back everything it wrote if it fails.
+    fn atomically(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()>;
+    fn close_issue_by(
+        &self,
+        id: i64,
+        resolution: Option<&str>,
+        closed_by: Option<&str>,
+    ) -> Result<bool>;
+    fn drop_issue_by(&self, id: i64, closed_by: Option<&str>) -> Result<bool>;
+    fn reopen_issue(&self, id: i64) -> Result<bool>;
+    fn get_resolution(&self, id: i64) -> Result<Option<String>>;
+    fn set_due_date(&self, id: i64, due_at: Option<DateTime<Utc>>) -> Result<bool>;
+    fn set_recurrence(&self, id: i64, recurrence: Option<&str>) -> Result<bool>;
+    fn get_recurrence(&self, id: i64) -> Result<Option<String>>;
+
+    fn normalize_label(&self, label: &str) -> Result<String>;
+    fn add_label(&self, issue_id: i64, label: &str) -> Result<bool>;
+    fn get_labels(&self, issue_id: i64) -> Result<Vec<String>>;
+    fn get_comments(&self, issue_id: i64, limit: Option<usize>) -> Result<Vec<Comment>>;
+    fn count_comments(&self, issue_id: i64) -> Result<usize>;
+
+    fn get_subissues(&self, parent_id: i64) -> Result<Vec<Issue>>;
+    fn get_blockers(&self, issue_id: i64) -> Result<Vec<i64>>;
+    fn get_blocking(&self, issue_id: i64) -> Result<Vec<i64>>;
+    fn blocked_ids(&self) -> Result<HashSet<i64>>;
+    fn parent_titles(&self) -> Result<HashMap<i64, String>>;
+    fn get_related_issues(&self, issue_id: i64) -> Result<Vec<Issue>>;
+    fn relation_notes(&self, issue_id: i64) -> Result<HashMap<i64, String>>;
+    fn get_attachments(&self, issue_id: i64) -> Result<Vec<Attachment>>;
+    fn count_relations(&self, issue_id: i64) -> Result<i64>;
+    fn get_issue_milestones(&self, issue_id: i64) -> Result<Vec<Milestone>>;
+    fn add_issue_to_milestone(&self, milestone_id: i64, issue_id: i64) -> Result<bool>;
+
+    fn get_current_session(&self) -> Result<Option<Session>>;
+    fn set_session_issue(&self, session_id: i64, issue_id: i64) -> Result<bool>;
+}
+
+impl Store for Database {
+    fn create_issue(&self, title: &str, description: Option<&str>, priority: &str) -> Result<i64> {
+        Database::create_issue(self, title, description, priority)
+    }
+
+    fn create_subissue(
+        &self,
+        parent_id: i64,
+        title: &str,
+        description: Option<&str>,
+        priority: &str,
+    ) -> Result<i64> {
+        Database::create_subissue(self, parent_id, title, description, priority)
+    }
+
+    fn get_issue(&self, id: i64) -> Result<Option<Issue>> {
+        Database::get_issue(self, id)
+    }
+
+    fn list_issues(
+        &self,
+        status_filter: Option<&str>,
+        label_filter: Option<&str>,
+        priority_filter: Option<&str>,
+    ) -> Result<Vec<Issue>> {
+        Database::list_issues(self, status_filter, label_filter, priority_filter)
+    }
+
+    fn list_issues_excluding(
+        &self,
+        status_filter: Option<&str>,
+        label_filter: Option<&str>,
+        priority_filter: Option<PriorityFilter<'_>>,
+        excluded_labels: &[String],
+        order: IssueOrder,
+    ) -> Result<Vec<Issue>> {
+        Database::list_issues_excluding(
+            self,
+            status_filter,
+            label_filter,
+            priority_filter,
+            excluded_labels,
+            order,
+        )
+    }
+
+    fn find_similar_titles(&self, title: &str) -> Result<Vec<(i64, String)>> {
+        Database::find_similar_titles(self, title)
+    }
+
+    fn atomically(&self, f: &mut dyn FnMut() -> Result<()>) -> Result<()> {
+        self.transaction(f)
+    }
+
+    fn close_issue_by(
+        &self,
+        id: i64,
+        resolution: Option<&str>,
+        closed_by: Option<&str>,
+    ) -> Result<bool> {
+        Database::close_issue_by(self, id, resolution, closed_by)
+    }
+
+    fn drop_issue_by(&self, id: i64, closed_by: Option<&str>) -> Result<bool> {
+        Database::drop_issue_by(self, id, closed_by)
+    }
+
+    fn reopen_issue(&self, id: i64) -> Result<bool> {
+        Database::reopen_issue(self, id)
+    }
+
+    fn get_resolution(&self, id: i64) -> Result<Option<String>> {
+        Database::get_resolution(self, id)
+    }
+
+    fn set_due_date(&self, id: i64, due_at: Option<DateTime<Utc>>) -> Result<bool> {
+        Database::set_due_date(self, id, due_at)
+    }
+
+    fn set_recurrence(&self, id: i64, recurrence: Option<&str>) -> Result<bool> {
+        Database::set_recurrence(self, id, recurrence)
+    }
+
+    fn get_recurrence(&self, id: i64) -> Result<Option<String>> {
+        Database::get_recurrence(self, id)
+    }
+
+    fn normalize_label(&self, label: &str) -> Result<String> {
+        Database::normalize_label(self, label)
+    }
+
+    fn add_label(&self, issue_id: i64, label: &str) -> Result<bool> {
+        Database::add_label(self, issue_id, label)
+    }
+
+    fn get_labels(&self, issue_id: i64) -> Result<Vec<String>> {
+        Database::get_labels(self, issue_id)
+    }
+
+    fn get_comments(&self, issue_id: i64, limit: Option<usize>) -> Result<Vec<Comment>> {
+        Database::get_comments(self, issue_id, limit)
+    }
+
+    fn count_comments(&self, issue_id: i64) -> Result<usize> {
+        Database::count_comments(self, issue_id)
+    }
+
+    fn get_subissues(&self, parent_id: i64) -> Result<Vec<Issue>> {
+        Database::get_subissues(self, parent_id)
+    }
+
+    fn get_blockers(&self, issue_id: i64) -> Result<Vec<i64>> {
+        Database::get_blockers(self, issue_id)
+    }
+
+    fn get_blocking(&self, issue_id: i64) -> Result<Vec<i64>> {
+        Database::get_blocking(self, issue_id)
+    }
+
+    fn blocked_ids(&self) -> Result<HashSet<i64>> {
+        Database::blocked_ids(self)
+    }
+
+    fn parent_titles(&self) -> Result<HashMap<i64, String>> {
+        Database::parent_titles(self)
+    }
+
+    fn get_related_issues(&self, issue_id: i64) -> Result<Vec<Issue>> {
+        Database::get_related_issues(self, issue_id)
+    }
+
+    fn relation_notes(&self, issue_id: i64) -> Result<HashMap<i64, String>> {
+        Database::relation_notes(self, issue_id)
+    }
+
+    fn get_attachments(&self, issue_id: i64) -> Result<Vec<Attachment>> {
+        Database::get_attachments(self, issue_id)
+    }
+
+    fn count_relations(&self, issue_id: i64) -> Result<i64> {
+        Database::count_relations(self, issue_id)
+    }
+
+    fn get_issue_milestones(&self, issue_id: i64) -> Result<Vec<Milestone>> {
+        Database::get_issue_milestones(self, issue_id)
+    }
+
+    fn add_issue_to_milestone(&self, milestone_id: i64, issue_id: i64) -> Result<bool> {
+        Database::add_issue_to_milestone(self, milestone_id, issue_id)
+    }
+
+    fn get_current_session(&self) -> Result<Option<Session>> {
+        Database::get_current_session(self)
+    }
+
+    fn set_session_issue(&self, session_id: i64, issue_id: i64) -> Result<bool> {
+        Database::set_session_issue(self, session_id, issue_id)
+    }
+}
@@ -1,3 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use terminal_size::Width;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Set by the global `--no-color` flag.
+static NO_COLOR_FLAG: AtomicBool = AtomicBool::new(false);
+
+/// Record the global `--no-color` flag so `color_enabled` can honor it.
+pub fn set_no_color(no_color: bool) {
+    NO_COLOR_FLAG.store(no_color, Ordering::Relaxed);
+}
+
+/// Whether ANSI colors should be emitted on stdout.
+/// Disabled by `--no-color`, a non-empty `NO_COLOR` env var, or a non-TTY stdout.
+pub fn color_enabled() -> bool {
+    color_enabled_with(
+        NO_COLOR_FLAG.load(Ordering::Relaxed),
+        std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+        std::io::stdout().is_terminal(),
+    )
+}
+
+/// Pure form of `color_enabled` for testing.
+pub fn color_enabled_with(no_color_flag: bool, no_color_env: bool, is_tty: bool) -> bool {
+    !no_color_flag && !no_color_env && is_tty
+}
+
+/// How much the CLI prints, from the global `--quiet` and `--verbose` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only requested data (ids, counts) and errors
+    Quiet,
+    Normal,
+    /// Also timing and database diagnostics on stderr
+    Verbose,
+}
+
+impl Verbosity {
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    pub fn is_quiet(self) -> bool {
+        self == Verbosity::Quiet
+    }
+
+    pub fn is_verbose(self) -> bool {
+        self == Verbosity::Verbose
+    }
+}
+
+/// Set from the global flags; `Normal` until then.
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+/// Print an informational message to stdout unless `--quiet` is set.
+pub fn info(msg: impl Display) {
+    if !verbosity().is_quiet() {
+        println!("{}", msg);
+    }
+}
+
+/// Print a diagnostic to stderr when `--verbose` is set.
+pub fn debug(msg: impl Display) {
+    if verbosity().is_verbose() {
+        eprintln!("debug: {}", msg);
+    }
+}
+
 /// Truncate a string to a maximum number of characters, adding "..." if truncated.
 /// Handles Unicode correctly by counting characters, not bytes.
 pub fn truncate(s: &str, max_chars: usize) -> String {
@@ -10,9 +102,491 @@ pub fn truncate(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Columns `s` takes up on a terminal: wide (e.g. CJK) characters count as two.
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Pad `s` with spaces to `width` columns, like `{:<width$}` but counting
+/// display width rather than chars.
+pub fn pad_display(s: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        s,
+        " ".repeat(width.saturating_sub(display_width(s)))
+    )
+}
+
+/// Like `truncate`, but measures display width and cuts between graphemes, so
+/// the result fits in `max` columns without splitting an accented letter or a
+/// multi-codepoint emoji. Below three columns there is no room for "...", so
+/// the text is just cut.
+pub fn truncate_display(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    let (budget, ellipsis) = if max < 3 { (max, "") } else { (max - 3, "...") };
+    let mut kept = String::new();
+    let mut used = 0;
+    for cluster in s.graphemes(true) {
+        used += display_width(cluster);
+        if used > budget {
+            break;
+        }
+        kept.push_str(cluster);
+    }
+    format!("{}{}", kept, ellipsis)
+}
+
+/// The name recorded as author of comments and closes: `$CHAINLINK_USER`,
+/// or `None` when it is unset or blank.
+pub fn current_user() -> Option<String> {
+    std::env::var("CHAINLINK_USER")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Columns available on the terminal: `$COLUMNS` if set, otherwise the
+/// size the terminal reports (80 if it reports none). `None` when output is
+/// piped, so it isn't cut short.
+pub fn terminal_width() -> Option<usize> {
+    if let Some(cols) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n: &usize| n > 0)
+    {
+        return Some(cols);
+    }
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    let width = terminal_size::terminal_size()
+        .map(|(Width(w), _)| usize::from(w))
+        .filter(|&w| w > 0);
+    Some(width.unwrap_or(80))
+}
+
+/// Wrap `text` to `width` columns at whitespace, keeping its own line
+/// breaks and blank lines. Widths are display columns, so wide characters
+/// count double; a word longer than a whole line is split between graphemes.
+/// Lines that already fit are left untouched, indentation included.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for source in text.lines() {
+        if display_width(source) <= width {
+            lines.push(source.to_string());
+            continue;
+        }
+        let mut line = String::new();
+        let mut len = 0;
+        for word in source.split_whitespace() {
+            let word_width = display_width(word);
+            if len > 0 && len + 1 + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                len = 0;
+            }
+            if len > 0 {
+                line.push(' ');
+                len += 1;
+            }
+            for cluster in word.graphemes(true) {
+                let cluster_width = display_width(cluster);
+                if len > 0 && len + cluster_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    len = 0;
+                }
+                line.push_str(cluster);
+                len += cluster_width;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Describe how long ago `from` was relative to `now`, e.g. "just now", "5 minutes ago",
+/// "3 days ago". Timestamps in the future are treated as "just now".
+pub fn humanize_age(from: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = now.signed_duration_since(from).num_seconds();
+    if secs < 60 {
+        return "just now".to_string();
+    }
+
+    let (count, unit) = match secs {
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86_400 => (s / 3600, "hour"),
+        s if s < 7 * 86_400 => (s / 86_400, "day"),
+        s if s < 30 * 86_400 => (s / (7 * 86_400), "week"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{} ago", count, unit, plural)
+}
+
+/// Compact duration such as "3d 4h", "2h 5m", "12m" or "40s"; negative input counts as zero.
+pub fn format_duration_secs(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (days, hours, minutes) = (secs / 86_400, (secs % 86_400) / 3600, (secs % 3600) / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Format a timestamp either as an exact `absolute_fmt` string or relative to now.
+pub fn format_timestamp(dt: DateTime<Utc>, absolute: bool, absolute_fmt: &str) -> String {
+    if absolute {
+        dt.format(absolute_fmt).to_string()
+    } else {
+        humanize_age(dt, Utc::now())
+    }
+}
+
+/// Parse a relative duration such as "30m", "12h", "7d" or "2w".
+pub fn parse_relative_duration(spec: &str) -> anyhow::Result<chrono::Duration> {
+    let spec = spec.trim();
+    let split = spec.len() - spec.chars().last().map_or(0, char::len_utf8);
+    let (amount, unit) = spec.split_at(split);
+    let amount: i64 = match amount.parse() {
+        Ok(n) if n >= 0 => n,
+        _ => anyhow::bail!(
+            "Invalid duration '{}'. Use a number followed by m, h, d or w (e.g. 7d)",
+            spec
+        ),
+    };
+    let duration = match unit {
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => anyhow::bail!(
+            "Invalid duration '{}'. Use a number followed by m, h, d or w (e.g. 7d)",
+            spec
+        ),
+    };
+    duration.ok_or_else(|| anyhow::anyhow!("Duration '{}' is too large", spec))
+}
+
+/// Parse a due date: either a calendar date ("2026-03-01", midnight UTC) or an
+/// offset from `now` such as "3d" or "2w".
+pub fn parse_due_date(spec: &str, now: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(spec.trim(), "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
+    }
+    let Ok(offset) = parse_relative_duration(spec) else {
+        anyhow::bail!(
+            "Invalid due date '{}'. Use YYYY-MM-DD or an offset like 3d or 2w",
+            spec
+        );
+    };
+    now.checked_add_signed(offset)
+        .ok_or_else(|| anyhow::anyhow!("Due date '{}' is out of range", spec))
+}
+
+/// How often a recurring issue comes back once it is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    /// Same day next month, clamped to the month's last day
+    Monthly,
+    /// A fixed interval such as "10d" or "2w"
+    Every(chrono::Duration),
+}
+
+impl Recurrence {
+    /// The due date one interval after `from`, or an error past the last
+    /// representable date.
+    pub fn next_due(self, from: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+        let next = match self {
+            Recurrence::Daily => from.checked_add_signed(chrono::Duration::days(1)),
+            Recurrence::Weekly => from.checked_add_signed(chrono::Duration::weeks(1)),
+            Recurrence::Monthly => from.checked_add_months(chrono::Months::new(1)),
+            Recurrence::Every(interval) => from.checked_add_signed(interval),
+        };
+        next.ok_or_else(|| anyhow::anyhow!("The next occurrence after {} is out of range", from))
+    }
+}
+
+impl std::str::FromStr for Recurrence {
+    type Err = anyhow::Error;
+
+    /// Accepts daily, weekly, monthly, or an interval like "10d" or "2w".
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "daily" => Ok(Recurrence::Daily),
+            "weekly" => Ok(Recurrence::Weekly),
+            "monthly" => Ok(Recurrence::Monthly),
+            spec => match parse_relative_duration(spec) {
+                Ok(interval) if interval >= chrono::Duration::hours(1) => {
+                    Ok(Recurrence::Every(interval))
+                }
+                _ => anyhow::bail!(
+                    "Invalid recurrence '{}'. Use daily, weekly, monthly, or an interval like 10d or 2w",
+                    s
+                ),
+            },
+        }
+    }
+}
+
+fn title_tokens(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Token-overlap (Jaccard) similarity of two titles, from 0.0 (disjoint) to 1.0 (same words).
+/// Case and punctuation are ignored.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let a = title_tokens(a);
+    let b = title_tokens(b);
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// What to do with whitespace inside a label (`label_policy` in config.toml).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelPolicy {
+    /// Turn each run of whitespace into a single hyphen
+    #[default]
+    Hyphenate,
+    /// Refuse labels containing whitespace
+    Reject,
+}
+
+fn describe_char(c: char) -> String {
+    match c {
+        ' ' => "a space".to_string(),
+        '\t' => "a tab".to_string(),
+        ',' => "a comma".to_string(),
+        c => format!("{:?}", c),
+    }
+}
+
+/// Trim a label and apply `policy` to inner whitespace. Empty labels and commas
+/// are never allowed, since labels are shown and passed around comma-separated.
+pub fn normalize_label(label: &str, policy: LabelPolicy) -> anyhow::Result<String> {
+    let trimmed = label.trim();
+    if trimmed.is_empty() {
+        anyhow::bail!("Label can't be empty");
+    }
+    let offending = trimmed
+        .chars()
+        .find(|&c| c == ',' || (policy == LabelPolicy::Reject && c.is_whitespace()));
+    if let Some(c) = offending {
+        anyhow::bail!(
+            "Label '{}' contains {}, which isn't allowed in labels (use '-' instead)",
+            trimmed,
+            describe_char(c)
+        );
+    }
+    Ok(trimmed.split_whitespace().collect::<Vec<_>>().join("-"))
+}
+
+/// Link to issue `id` on the project's tracker at `repo_url`, tolerating a
+/// trailing slash on the configured URL.
+pub fn issue_url(repo_url: &str, id: i64) -> String {
+    format!("{}/issues/{}", repo_url.trim_end_matches('/'), id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_normalize_label() {
+        use LabelPolicy::*;
+        assert_eq!(normalize_label("bug", Hyphenate).unwrap(), "bug");
+        assert_eq!(
+            normalize_label("  needs   review ", Hyphenate).unwrap(),
+            "needs-review"
+        );
+        assert_eq!(normalize_label(" bug ", Reject).unwrap(), "bug");
+
+        let err = normalize_label("needs review", Reject).unwrap_err();
+        assert!(err.to_string().contains("contains a space"), "{}", err);
+        let err = normalize_label("needs\treview", Reject).unwrap_err();
+        assert!(err.to_string().contains("a tab"));
+        let err = normalize_label("bug,ui", Hyphenate).unwrap_err();
+        assert!(err.to_string().contains("contains a comma"));
+        for blank in ["", "   ", "\t"] {
+            let err = normalize_label(blank, Hyphenate).unwrap_err();
+            assert!(err.to_string().contains("can't be empty"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_wrap_long_line_at_word_boundaries() {
+        let lines = wrap("the quick brown fox jumps over the lazy dog", 15);
+        assert_eq!(
+            lines,
+            vec!["the quick brown", "fox jumps over", "the lazy dog"]
+        );
+        assert!(lines.iter().all(|l| display_width(l) <= 15));
+    }
+
+    #[test]
+    fn test_wrap_preserves_blank_lines_and_short_lines() {
+        let text = "First paragraph here\n\n  indented\nsecond";
+        assert_eq!(
+            wrap(text, 10),
+            vec!["First", "paragraph", "here", "", "  indented", "second"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_counts_columns_and_splits_long_words() {
+        assert_eq!(wrap("café naïve résumé", 11), vec!["café naïve", "résumé"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("🇯🇵🇯🇵🇯🇵", 4), vec!["🇯🇵🇯🇵", "🇯🇵"]);
+        assert_eq!(wrap("日本語 タイトル", 6), vec!["日本語", "タイト", "ル"]);
+    }
+
+    #[test]
+    fn test_truncate_display_short_and_exact() {
+        assert_eq!(truncate_display("hello", 10), "hello");
+        assert_eq!(truncate_display("hello", 5), "hello");
+        assert_eq!(truncate_display("hello world", 8), "hello...");
+        assert_eq!(truncate_display("", 3), "");
+    }
+
+    #[test]
+    fn test_truncate_display_keeps_combining_marks() {
+        // "é" written as e + U+0301 is one grapheme, one column wide
+        let s = "cafe\u{301} au lait";
+        assert_eq!(display_width(s), 12);
+        assert_eq!(truncate_display(s, 7), "cafe\u{301}...");
+    }
+
+    #[test]
+    fn test_truncate_display_multibyte_at_boundary() {
+        // Family emoji (ZWJ sequence) and a flag sit right where the cut falls
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let s = format!("ab{}🇳🇿cdef", family);
+        assert_eq!(display_width(&s), 10);
+        assert_eq!(truncate_display(&s, 9), format!("ab{}🇳🇿...", family));
+        assert_eq!(truncate_display(&s, 8), format!("ab{}...", family));
+        assert_eq!(truncate_display(&s, 6), "ab...");
+    }
+
+    #[test]
+    fn test_truncate_display_counts_wide_characters() {
+        let title = "日本語のタイトル";
+        assert_eq!(display_width(title), 16);
+        assert_eq!(truncate_display(title, 5), "日...");
+        assert_eq!(truncate_display(title, 8), "日本...");
+        assert!(display_width(&truncate_display(title, 8)) <= 8);
+    }
+
+    #[test]
+    fn test_truncate_display_narrower_than_ellipsis() {
+        assert_eq!(truncate_display("hello", 2), "he");
+        assert_eq!(truncate_display("hello", 0), "");
+        assert_eq!(truncate_display("日本", 1), "");
+        assert_eq!(truncate_display("日本", 3), "...");
+    }
+
+    #[test]
+    fn test_pad_display_aligns_wide_characters() {
+        assert_eq!(pad_display("日本", 6), "日本  ");
+        assert_eq!(pad_display("ab", 4), "ab  ");
+        assert_eq!(pad_display("toolong", 3), "toolong");
+    }
+
+    #[test]
+    fn test_verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(true, false), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Verbose);
+        assert!(Verbosity::Quiet < Verbosity::Normal);
+    }
+
+    #[test]
+    fn test_format_duration_secs() {
+        assert_eq!(format_duration_secs(-5), "0s");
+        assert_eq!(format_duration_secs(40), "40s");
+        assert_eq!(format_duration_secs(12 * 60 + 30), "12m");
+        assert_eq!(format_duration_secs(2 * 3600 + 5 * 60), "2h 5m");
+        assert_eq!(format_duration_secs(3 * 86_400 + 4 * 3600 + 59), "3d 4h");
+    }
+
+    #[test]
+    fn test_parse_due_date() {
+        let now = Utc::now();
+        assert_eq!(
+            parse_due_date("2026-03-01", now).unwrap().to_rfc3339(),
+            "2026-03-01T00:00:00+00:00"
+        );
+        assert_eq!(parse_due_date("3d", now).unwrap(), now + Duration::days(3));
+        assert!(parse_due_date("next tuesday", now).is_err());
+        let err = parse_due_date("99999999999d", now).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn test_recurrence_next_due() {
+        let from = DateTime::parse_from_rfc3339("2026-01-31T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let due = |spec: &str| spec.parse::<Recurrence>().unwrap().next_due(from).unwrap();
+        assert_eq!(due("daily"), from + Duration::days(1));
+        assert_eq!(due("Weekly"), from + Duration::days(7));
+        assert_eq!(due("10d"), from + Duration::days(10));
+        // January 31st plus a month is the last day of February
+        assert_eq!(due("monthly").to_rfc3339(), "2026-02-28T09:00:00+00:00");
+        for bad in ["yearly", "", "0d", "30m"] {
+            assert!(bad.parse::<Recurrence>().is_err(), "accepted {:?}", bad);
+        }
+        let huge: Recurrence = "999999999d".parse().unwrap();
+        assert!(huge.next_due(from).is_err());
+        assert!(Recurrence::Daily
+            .next_due(DateTime::<Utc>::MAX_UTC)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_relative_duration() {
+        assert_eq!(
+            parse_relative_duration("30m").unwrap(),
+            Duration::minutes(30)
+        );
+        assert_eq!(parse_relative_duration("1h").unwrap(), Duration::hours(1));
+        assert_eq!(parse_relative_duration("7d").unwrap(), Duration::days(7));
+        assert_eq!(parse_relative_duration("2w").unwrap(), Duration::weeks(2));
+        for bad in ["", "d", "7", "7y", "-1d", "1.5h", "7 d", "99999999999999d"] {
+            assert!(parse_relative_duration(bad).is_err(), "accepted {:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_title_similarity_near_identical() {
+        assert_eq!(title_similarity("Fix login bug", "fix login bug"), 1.0);
+        assert_eq!(title_similarity("Fix login bug", "Fix: login bug!"), 1.0);
+        assert!(title_similarity("Fix login bug", "Fix the login bug") >= 0.6);
+    }
+
+    #[test]
+    fn test_title_similarity_unrelated() {
+        assert_eq!(title_similarity("Fix login bug", "Add dark mode"), 0.0);
+        assert!(title_similarity("Fix login bug", "Fix parser crash on empty input") < 0.6);
+        assert_eq!(title_similarity("", ""), 0.0);
+    }
 
     #[test]
     fn test_truncate_short_string() {
@@ -48,4 +622,92 @@ mod tests {
     fn test_truncate_zero_max() {
         assert_eq!(truncate("hello", 0), "...");
     }
+
+    #[test]
+    fn test_color_enabled_with() {
+        assert!(color_enabled_with(false, false, true));
+        assert!(
+            !color_enabled_with(false, true, true),
+            "NO_COLOR disables color"
+        );
+        assert!(
+            !color_enabled_with(true, false, true),
+            "--no-color disables color"
+        );
+        assert!(
+            !color_enabled_with(false, false, false),
+            "non-TTY disables color"
+        );
+    }
+
+    fn ago(d: Duration) -> String {
+        let now = Utc::now();
+        humanize_age(now - d, now)
+    }
+
+    #[test]
+    fn test_humanize_seconds() {
+        assert_eq!(ago(Duration::seconds(0)), "just now");
+        assert_eq!(ago(Duration::seconds(59)), "just now");
+    }
+
+    #[test]
+    fn test_humanize_future_is_just_now() {
+        assert_eq!(ago(Duration::seconds(-30)), "just now");
+    }
+
+    #[test]
+    fn test_humanize_minutes() {
+        assert_eq!(ago(Duration::seconds(60)), "1 minute ago");
+        assert_eq!(ago(Duration::minutes(59)), "59 minutes ago");
+    }
+
+    #[test]
+    fn test_humanize_hours() {
+        assert_eq!(ago(Duration::minutes(60)), "1 hour ago");
+        assert_eq!(ago(Duration::hours(23)), "23 hours ago");
+    }
+
+    #[test]
+    fn test_humanize_days() {
+        assert_eq!(ago(Duration::hours(24)), "1 day ago");
+        assert_eq!(ago(Duration::days(6)), "6 days ago");
+    }
+
+    #[test]
+    fn test_humanize_weeks() {
+        assert_eq!(ago(Duration::days(7)), "1 week ago");
+        assert_eq!(ago(Duration::days(29)), "4 weeks ago");
+    }
+
+    #[test]
+    fn test_humanize_months_and_years() {
+        assert_eq!(ago(Duration::days(30)), "1 month ago");
+        assert_eq!(ago(Duration::days(364)), "12 months ago");
+        assert_eq!(ago(Duration::days(365)), "1 year ago");
+        assert_eq!(ago(Duration::days(800)), "2 years ago");
+    }
+
+    #[test]
+    fn test_format_timestamp_absolute() {
+        let dt = DateTime::parse_from_rfc3339("2024-01-05T14:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            format_timestamp(dt, true, "%Y-%m-%d %H:%M:%S"),
+            "2024-01-05 14:30:00"
+        );
+    }
+
+    #[test]
+    fn test_issue_url() {
+        assert_eq!(
+            issue_url("https://example.com/project", 7),
+            "https://example.com/project/issues/7"
+        );
+        assert_eq!(
+            issue_url("https://example.com/project/", 7),
+            "https://example.com/project/issues/7"
+        );
+    }
 }
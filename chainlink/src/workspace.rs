@@ -0,0 +1,89 @@
+//! Locating a project's `.chainlink` directory and opening its database.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::db::Database;
+
+/// Name of the per-project directory that holds the database and settings.
+pub const CHAINLINK_DIR: &str = ".chainlink";
+/// File name of the issue database inside [`CHAINLINK_DIR`].
+pub const DB_FILE: &str = "issues.db";
+
+/// Walk up from `start` looking for a `.chainlink` directory.
+/// A regular file named `.chainlink` is not a project and the search continues upward.
+pub fn find_chainlink_dir(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(CHAINLINK_DIR))
+        .find(|candidate| candidate.is_dir())
+}
+
+/// Like [`find_chainlink_dir`], but not finding a project is an error.
+pub fn chainlink_dir(start: &Path) -> Result<PathBuf> {
+    match find_chainlink_dir(start) {
+        Some(dir) => Ok(dir),
+        None => bail!("Not a chainlink repository (or any parent). Run 'chainlink init' first."),
+    }
+}
+
+/// Path to the database of the project containing `start`.
+pub fn discover(start: &Path) -> Result<PathBuf> {
+    Ok(chainlink_dir(start)?.join(DB_FILE))
+}
+
+/// Open the database at `db_path`.
+pub fn open_db(db_path: &Path) -> Result<Database> {
+    Database::open(db_path).context("Failed to open database")
+}
+
+/// Settings for the project whose `.chainlink` directory is `chainlink_dir`.
+pub fn load_config(chainlink_dir: &Path) -> Result<Config> {
+    Config::load(chainlink_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_find_chainlink_dir_skips_chainlink_file() {
+        let root = tempdir().unwrap();
+        let project = root.path().join(".chainlink");
+        fs::create_dir(&project).unwrap();
+        let nested = root.path().join("sub").join("deeper");
+        fs::create_dir_all(&nested).unwrap();
+        // A stray regular file named .chainlink between the start and the real project
+        fs::write(root.path().join("sub").join(".chainlink"), "not a dir").unwrap();
+
+        assert_eq!(find_chainlink_dir(&nested), Some(project.clone()));
+        assert_eq!(find_chainlink_dir(root.path()), Some(project));
+    }
+
+    #[test]
+    fn test_find_chainlink_dir_none() {
+        let root = tempdir().unwrap();
+        fs::write(root.path().join(".chainlink"), "not a dir").unwrap();
+        // Only the temp dir's ancestors remain; none of them should be a project
+        let found = find_chainlink_dir(root.path());
+        assert!(found.is_none_or(|p| !p.starts_with(root.path())));
+    }
+
+    #[test]
+    fn test_discover_walks_up_to_db_path() {
+        let root = tempdir().unwrap();
+        fs::create_dir(root.path().join(".chainlink")).unwrap();
+        let nested = root.path().join("src").join("deep");
+        fs::create_dir_all(&nested).unwrap();
+
+        let db_path = discover(&nested).unwrap();
+        assert_eq!(db_path, root.path().join(".chainlink").join("issues.db"));
+
+        let db = open_db(&db_path).unwrap();
+        let id = db.create_issue("Embedded", None, "medium").unwrap();
+        assert!(db.get_issue(id).unwrap().is_some());
+    }
+}
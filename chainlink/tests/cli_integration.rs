@@ -6,6 +6,8 @@ fn run_chainlink(dir: &std::path::Path, args: &[&str]) -> (bool, String, String)
     let output = Command::new(env!("CARGO_BIN_EXE_chainlink"))
         .current_dir(dir)
         .args(args)
+        // Keep list/tree output independent of the caller's terminal width
+        .env_remove("COLUMNS")
         .output()
         .expect("Failed to execute chainlink");
 
@@ -45,6 +47,44 @@ fn test_init_twice_warns() {
     assert!(stdout.contains("Already") || stdout.contains("already") || stdout.contains("exists"));
 }
 
+#[test]
+fn test_db_flag_uses_explicit_database() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("custom.db");
+    let db_arg = db_path.to_str().unwrap();
+
+    // No `chainlink init` here: --db bypasses .chainlink discovery
+    let (success, _, stderr) = run_chainlink(dir.path(), &["--db", db_arg, "create", "Custom"]);
+    assert!(success, "create failed: {}", stderr);
+    assert!(db_path.exists());
+    assert!(!dir.path().join(".chainlink").exists());
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["list", "--db", db_arg]);
+    assert!(success);
+    assert!(stdout.contains("Custom"));
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["--db", db_arg, "close", "1"]);
+    assert!(success, "close failed: {}", stderr);
+}
+
+#[test]
+fn test_chainlink_db_env_var() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().join("env.db");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_chainlink"))
+        .current_dir(dir.path())
+        .env("CHAINLINK_DB", &db_path)
+        .args(["create", "From env"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(db_path.exists());
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["--db", db_path.to_str().unwrap(), "list"]);
+    assert!(stdout.contains("From env"));
+}
+
 // ==================== Issue Creation Tests ====================
 
 #[test]
@@ -77,6 +117,31 @@ fn test_create_issue_with_priority() {
     assert!(list_out.contains("high"));
 }
 
+#[test]
+fn test_config_default_priority_and_list_status() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    std::fs::write(
+        dir.path().join(".chainlink/hook-config.json"),
+        r#"{"default_priority": "high", "default_list_status": "all"}"#,
+    )
+    .unwrap();
+
+    run_chainlink(dir.path(), &["create", "Uses the default"]);
+    run_chainlink(dir.path(), &["create", "Explicit low", "-p", "low"]);
+    run_chainlink(dir.path(), &["close", "2"]);
+
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1", "--json"]);
+    let issue: serde_json::Value = serde_json::from_str(&show_out).unwrap();
+    assert_eq!(issue["priority"], "high");
+
+    // default_list_status = all includes the closed issue; --status still overrides
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(list_out.contains("Explicit low"), "got: {}", list_out);
+    let (_, open_out, _) = run_chainlink(dir.path(), &["list", "-s", "open"]);
+    assert!(!open_out.contains("Explicit low"), "got: {}", open_out);
+}
+
 #[test]
 fn test_create_issue_with_description() {
     let dir = tempdir().unwrap();
@@ -119,6 +184,84 @@ fn test_create_subissue() {
     assert!(show_out.contains("Child") || show_out.contains("subissue"));
 }
 
+#[test]
+fn test_create_warns_on_similar_title() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Fix login bug"]);
+    let (success, stdout, stderr) = run_chainlink(dir.path(), &["create", "Fix the login bug"]);
+
+    // Non-interactive: warn but still create
+    assert!(success);
+    assert!(stderr.contains("Similar open issue"), "stderr: {}", stderr);
+    assert!(stderr.contains("#1 Fix login bug"), "stderr: {}", stderr);
+    assert!(stdout.contains("Created issue #2"));
+
+    let (_, _, stderr) = run_chainlink(dir.path(), &["create", "Fix login bug", "--force"]);
+    assert!(!stderr.contains("Similar open issue"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_quick_and_subissue_force_skip_similar_title_check() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Fix login bug"]);
+    for args in [
+        &["quick", "Fix the login bug", "-f"][..],
+        &["subissue", "1", "Fix login bug again", "--force"],
+    ] {
+        let (success, _, stderr) = run_chainlink(dir.path(), args);
+        assert!(success, "{:?} failed: {}", args, stderr);
+        assert!(!stderr.contains("Similar open issue"), "stderr: {}", stderr);
+    }
+    let (_, _, stderr) = run_chainlink(dir.path(), &["quick", "Fix login bug"]);
+    assert!(stderr.contains("Similar open issue"), "stderr: {}", stderr);
+}
+
+#[test]
+fn test_quiet_create_prints_only_id() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, stdout, stderr) = run_chainlink(dir.path(), &["-q", "create", "Silent"]);
+    assert!(success, "create failed: {}", stderr);
+    assert_eq!(stdout, "1\n");
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["-q", "label", "1", "bug"]);
+    assert!(success);
+    assert!(stdout.is_empty());
+
+    run_chainlink(dir.path(), &["-q", "create", "Other"]);
+    for args in [
+        &["-q", "block", "1", "2"][..],
+        &["-q", "relate", "1", "2"],
+        &["-q", "session", "start"],
+        &["-q", "milestone", "create", "v1"],
+        &["-q", "close", "2"],
+    ] {
+        let (success, stdout, stderr) = run_chainlink(dir.path(), args);
+        assert!(success, "{:?} failed: {}", args, stderr);
+        assert!(stdout.is_empty(), "{:?} printed: {}", args, stdout);
+    }
+}
+
+#[test]
+fn test_verbose_reports_diagnostics_on_stderr() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, stdout, stderr) = run_chainlink(dir.path(), &["-v", "create", "Loud"]);
+    assert!(success);
+    assert!(stdout.contains("Created issue #1"));
+    assert!(stderr.contains("schema v"));
+    assert!(stderr.contains("finished in"));
+
+    let (success, _, _) = run_chainlink(dir.path(), &["-q", "-v", "list"]);
+    assert!(!success, "--quiet and --verbose conflict");
+}
+
 // ==================== Issue Listing Tests ====================
 
 #[test]
@@ -184,6 +327,137 @@ fn test_list_filter_by_label() {
     assert!(!bug_list.contains("Feature issue"));
 }
 
+#[test]
+fn test_list_not_label() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Spam issue"]);
+    run_chainlink(dir.path(), &["create", "Bug issue"]);
+    run_chainlink(dir.path(), &["create", "Plain issue"]);
+    run_chainlink(dir.path(), &["label", "1", "spam"]);
+    run_chainlink(dir.path(), &["label", "2", "bug"]);
+
+    let (success, list, _) = run_chainlink(dir.path(), &["list", "--not-label", "spam"]);
+    assert!(success);
+    assert!(!list.contains("Spam issue"));
+    assert!(list.contains("Bug issue"));
+    assert!(list.contains("Plain issue"));
+}
+
+#[test]
+fn test_list_filter_by_parent() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Epic"]);
+    run_chainlink(dir.path(), &["subissue", "1", "Child task"]);
+    run_chainlink(dir.path(), &["create", "Standalone"]);
+
+    let (success, children, _) = run_chainlink(dir.path(), &["list", "--parent", "1"]);
+    assert!(success);
+    assert!(children.contains("Child task"));
+    assert!(!children.contains("Standalone"));
+
+    let (success, roots, _) = run_chainlink(dir.path(), &["list", "--parent", "none"]);
+    assert!(success);
+    assert!(roots.contains("Epic") && roots.contains("Standalone"));
+    assert!(!roots.contains("Child task"));
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["list", "--parent", "epic"]);
+    assert!(!success);
+    assert!(stderr.contains("'none'"), "got: {}", stderr);
+}
+
+#[test]
+fn test_rank_and_list_sort_manual() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Older task"]);
+    run_chainlink(dir.path(), &["create", "Newer task"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["rank", "1", "--before", "2"]);
+    assert!(success);
+    assert!(stdout.contains("Ranked #1 before #2"));
+
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list", "--sort", "manual"]);
+    let older = list_out.find("Older task").unwrap();
+    let newer = list_out.find("Newer task").unwrap();
+    assert!(older < newer, "got: {}", list_out);
+
+    let (success, _, _) = run_chainlink(dir.path(), &["rank", "1"]);
+    assert!(!success);
+    let (success, _, stderr) = run_chainlink(dir.path(), &["list", "--sort", "random"]);
+    assert!(!success);
+    assert!(stderr.contains("'manual'"));
+}
+
+#[test]
+fn test_list_json_fields() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Projected"]);
+
+    let (success, stdout, _) =
+        run_chainlink(dir.path(), &["list", "--json", "--fields", "id,title"]);
+    assert!(success);
+    let list: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let object = list[0].as_object().unwrap();
+    assert_eq!(object.len(), 2);
+    assert_eq!(object["id"], 1);
+    assert_eq!(object["title"], "Projected");
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["list", "--json", "--fields", "bogus"]);
+    assert!(!success);
+    assert!(stderr.contains("Unknown field 'bogus'"));
+}
+
+#[test]
+fn test_list_and_show_json_share_datetime_format() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Dated issue"]);
+
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list", "--json"]);
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1", "--json"]);
+    let list: serde_json::Value = serde_json::from_str(&list_out).unwrap();
+    let show: serde_json::Value = serde_json::from_str(&show_out).unwrap();
+
+    let created = list[0]["created_at"].as_str().unwrap();
+    assert_eq!(show["created_at"].as_str().unwrap(), created);
+    assert!(
+        created.ends_with('Z'),
+        "expected UTC RFC3339, got {}",
+        created
+    );
+    assert_eq!(&created[10..11], "T");
+}
+
+#[test]
+fn test_list_fits_columns_unless_full() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    let title = "An issue title long enough to overflow a narrow terminal";
+    run_chainlink(dir.path(), &["create", title]);
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_chainlink"))
+        .current_dir(dir.path())
+        .args(["list"])
+        .env("COLUMNS", "60")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.lines().all(|l| l.chars().count() <= 60),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains(title));
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["list", "--full"]);
+    assert!(stdout.contains(title));
+}
+
 // ==================== Issue Show Tests ====================
 
 #[test]
@@ -238,6 +512,30 @@ fn test_update_issue_priority() {
     assert!(show_out.contains("critical"));
 }
 
+#[test]
+fn test_recurring_issue_reopens_on_close() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Water the plants"]);
+    let (success, _, _) = run_chainlink(
+        dir.path(),
+        &["update", "1", "--due", "2026-06-01", "--recur", "weekly"],
+    );
+    assert!(success);
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(show_out.contains("Due: 2026-06-01"));
+    assert!(show_out.contains("Repeats: weekly"));
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["close", "1", "--no-changelog"]);
+    assert!(success);
+    assert!(stdout.contains("Created #2 for the next occurrence (due 2026-06-08)"));
+
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "2"]);
+    assert!(show_out.contains("Water the plants"));
+    assert!(show_out.contains("Due: 2026-06-08"));
+}
+
 // ==================== Issue Close/Reopen Tests ====================
 
 #[test]
@@ -255,6 +553,112 @@ fn test_close_issue() {
     assert!(show_out.contains("closed"));
 }
 
+#[test]
+fn test_close_with_resolution() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Test issue"]);
+    let (success, _, stderr) = run_chainlink(dir.path(), &["close", "1", "--resolution", "bogus"]);
+    assert!(!success);
+    assert!(stderr.contains("Invalid resolution"));
+
+    let (success, _, _) = run_chainlink(
+        dir.path(),
+        &["close", "1", "--resolution", "duplicate", "--no-changelog"],
+    );
+    assert!(success);
+
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(show_out.contains("Resolution: duplicate"));
+}
+
+#[test]
+fn test_close_multiple_issues() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "First fix"]);
+    run_chainlink(dir.path(), &["create", "Second fix"]);
+    run_chainlink(dir.path(), &["create", "Third fix"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["close", "1", "2", "3"]);
+
+    assert!(success);
+    assert!(stdout.contains("Closed 3 issue(s)"), "got: {}", stdout);
+
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list", "-s", "closed"]);
+    assert!(list_out.contains("First fix"));
+    assert!(list_out.contains("Second fix"));
+    assert!(list_out.contains("Third fix"));
+
+    let changelog = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("- First fix (#1)"));
+    assert!(changelog.contains("- Second fix (#2)"));
+    assert!(changelog.contains("- Third fix (#3)"));
+}
+
+#[test]
+fn test_wontdo_leaves_open_list_and_changelog() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Shipped fix", "-l", "bug"]);
+    run_chainlink(dir.path(), &["create", "Abandoned fix", "-l", "bug"]);
+    run_chainlink(
+        dir.path(),
+        &["create", "Abandoned feature", "-l", "feature"],
+    );
+    run_chainlink(dir.path(), &["close", "1"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["close", "2", "--wontdo"]);
+    assert!(success);
+    assert!(stdout.contains("Dropped issue #2"));
+    let (success, _, _) = run_chainlink(dir.path(), &["drop", "3"]);
+    assert!(success);
+
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(!list_out.contains("Abandoned"), "got: {}", list_out);
+    let (_, wontdo_out, _) = run_chainlink(dir.path(), &["list", "-s", "wontdo"]);
+    assert!(wontdo_out.contains("Abandoned fix"));
+    assert!(wontdo_out.contains("Abandoned feature"));
+
+    let changelog = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+    assert!(changelog.contains("- Shipped fix (#1)"));
+    assert!(!changelog.contains("Abandoned"), "got: {}", changelog);
+}
+
+#[test]
+fn test_close_multiple_skips_missing() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Real issue"]);
+    let (success, stdout, stderr) =
+        run_chainlink(dir.path(), &["close", "1", "99", "--no-changelog"]);
+
+    assert!(success);
+    assert!(stderr.contains("#99"), "got: {}", stderr);
+    assert!(stdout.contains("Closed 1 issue(s)"), "got: {}", stdout);
+}
+
+#[test]
+fn test_close_with_open_subissues() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Parent"]);
+    run_chainlink(dir.path(), &["subissue", "1", "Child"]);
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["close", "1", "--no-changelog"]);
+    assert!(!success);
+    assert!(stderr.contains("#2 Child"), "got: {}", stderr);
+
+    let (success, stdout, _) =
+        run_chainlink(dir.path(), &["close", "1", "--cascade", "--no-changelog"]);
+    assert!(success);
+    assert!(stdout.contains("Closed issue #2"), "got: {}", stdout);
+    assert!(stdout.contains("Closed issue #1"), "got: {}", stdout);
+}
+
 #[test]
 fn test_reopen_issue() {
     let dir = tempdir().unwrap();
@@ -287,6 +691,69 @@ fn test_delete_issue() {
     assert!(!list_out.contains("To delete"));
 }
 
+#[test]
+fn test_delete_blocker_warns_before_prompting() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Blocker"]);
+    run_chainlink(dir.path(), &["create", "Blocked"]);
+    run_chainlink(dir.path(), &["block", "2", "1"]);
+
+    // No stdin, so the prompt is declined and nothing is deleted
+    let (_, stdout, stderr) = run_chainlink(dir.path(), &["delete", "1"]);
+    assert!(stderr.contains("#1 blocks #2"));
+    assert!(stdout.contains("Cancelled"));
+}
+
+#[test]
+fn test_delete_to_trash_and_restore() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Keep me"]);
+    run_chainlink(dir.path(), &["create", "Also gone"]);
+    run_chainlink(dir.path(), &["delete", "1", "2", "-f", "--trash"]);
+
+    let (_, trash_out, _) = run_chainlink(dir.path(), &["trash", "list"]);
+    assert!(trash_out.contains("Keep me"));
+    assert!(trash_out.contains("Also gone"));
+
+    // Entry 1 is the older of the two
+    let (success, stdout, _) = run_chainlink(dir.path(), &["restore", "1"]);
+    assert!(success);
+    assert!(stdout.contains("as issue #3"), "got: {}", stdout);
+
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(list_out.contains("Keep me"));
+    assert!(!list_out.contains("Also gone"));
+}
+
+#[test]
+fn test_delete_behavior_archive_keeps_the_issue() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    std::fs::write(
+        dir.path().join(".chainlink/config.toml"),
+        "delete_behavior = \"archive\"\n",
+    )
+    .unwrap();
+
+    run_chainlink(dir.path(), &["create", "Keep my history"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["delete", "1", "-f"]);
+    assert!(success);
+    assert!(stdout.contains("Archived issue #1"));
+
+    let (success, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(success);
+    assert!(show_out.contains("archived"), "got: {}", show_out);
+
+    // --hard still removes it for good
+    run_chainlink(dir.path(), &["delete", "1", "-f", "--hard"]);
+    let (success, _, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(!success);
+}
+
 // ==================== Labels Tests ====================
 
 #[test]
@@ -303,6 +770,34 @@ fn test_add_label() {
     assert!(show_out.contains("bug"));
 }
 
+#[test]
+fn test_label_whitespace_policy() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Test issue"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["label", "1", "needs review"]);
+    assert!(success);
+    assert!(stdout.contains("'needs-review'"), "got: {}", stdout);
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list", "-l", "needs review"]);
+    assert!(list_out.contains("Test issue"));
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["label", "1", "ui,ux"]);
+    assert!(!success);
+    assert!(stderr.contains("contains a comma"));
+
+    std::fs::write(
+        dir.path().join(".chainlink/config.toml"),
+        "label_policy = \"reject\"\n",
+    )
+    .unwrap();
+    let (success, _, stderr) = run_chainlink(dir.path(), &["create", "Other", "-l", "two words"]);
+    assert!(!success);
+    assert!(stderr.contains("contains a space"), "got: {}", stderr);
+    let (_, list_out, _) = run_chainlink(dir.path(), &["list"]);
+    assert!(!list_out.contains("Other"));
+}
+
 #[test]
 fn test_remove_label() {
     let dir = tempdir().unwrap();
@@ -334,6 +829,30 @@ fn test_add_comment() {
     assert!(show_out.contains("This is a comment"));
 }
 
+#[test]
+fn test_pin_and_unpin_comment() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Test issue"]);
+    run_chainlink(dir.path(), &["comment", "1", "Routine note"]);
+    run_chainlink(dir.path(), &["comment", "1", "Root cause found"]);
+
+    let (success, _, _) = run_chainlink(dir.path(), &["comment", "1", "--pin", "2"]);
+    assert!(success);
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    let pinned = show_out.find("Pinned:").unwrap();
+    let comments = show_out.find("Comments:").unwrap();
+    let key = show_out.find("Root cause found").unwrap();
+    assert!(pinned < key && key < comments);
+
+    let (success, _, _) = run_chainlink(dir.path(), &["comment", "1", "--unpin", "2"]);
+    assert!(success);
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(!show_out.contains("Pinned:"));
+    assert!(show_out.find("Routine note").unwrap() < show_out.find("Root cause found").unwrap());
+}
+
 // ==================== Dependencies Tests ====================
 
 #[test]
@@ -423,6 +942,25 @@ fn test_session_work() {
     assert!(stdout.contains("Working") || stdout.contains("#1"));
 }
 
+#[test]
+fn test_session_work_with_timer() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Timed issue"]);
+    run_chainlink(dir.path(), &["session", "start"]);
+    let (success, _, _) = run_chainlink(dir.path(), &["session", "work", "1", "--timer"]);
+    assert!(success);
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["timer"]);
+    assert!(stdout.contains("Timer running: #1"));
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["session", "end"]);
+    assert!(stdout.contains("Stopped timer for #1"));
+    let (_, stdout, _) = run_chainlink(dir.path(), &["timer"]);
+    assert!(stdout.contains("No timer running"));
+}
+
 #[test]
 fn test_session_end() {
     let dir = tempdir().unwrap();
@@ -454,6 +992,25 @@ fn test_search_issues() {
     assert!(!stdout.contains("Dark mode"));
 }
 
+#[test]
+fn test_search_limit_notes_truncation() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    for i in 0..4 {
+        run_chainlink(dir.path(), &["create", &format!("Timeout in job {}", i)]);
+    }
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["search", "timeout", "--limit", "3"]);
+    assert!(success);
+    assert!(stdout.contains("Found 4 issue(s)"));
+    assert_eq!(stdout.matches("Timeout in job").count(), 3);
+    assert!(stdout.contains("Showing 3 of 4"));
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["search", "timeout"]);
+    assert!(!stdout.contains("Showing"));
+}
+
 // ==================== Error Handling Tests ====================
 
 #[test]
@@ -592,6 +1149,59 @@ fn test_archive_list() {
     assert!(stdout.contains("Issue to archive") || stdout.contains("#1"));
 }
 
+#[test]
+fn test_archive_add_by_label() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Shipped", "-l", "done"]);
+    run_chainlink(dir.path(), &["create", "Still going", "-l", "done"]);
+    run_chainlink(dir.path(), &["close", "1"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["archive", "add", "--label", "done"]);
+    assert!(success);
+    assert!(stdout.contains("Skipping #2"));
+    assert!(stdout.contains("Archived 1 issue(s)"), "got: {}", stdout);
+
+    // An id and a filter can't be combined, and one of them is required
+    let (success, _, _) = run_chainlink(dir.path(), &["archive", "add", "2", "--label", "done"]);
+    assert!(!success);
+    let (success, _, _) = run_chainlink(dir.path(), &["archive", "add"]);
+    assert!(!success);
+}
+
+#[test]
+fn test_archive_purge() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Ancient history"]);
+    run_chainlink(dir.path(), &["create", "Still open"]);
+    run_chainlink(dir.path(), &["close", "1"]);
+    run_chainlink(dir.path(), &["archive", "add", "1"]);
+
+    // No stdin, so the prompt is declined
+    let (_, stdout, _) = run_chainlink(dir.path(), &["archive", "purge"]);
+    assert!(stdout.contains("Cancelled"));
+    let (success, _, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(success);
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["archive", "purge", "--older", "30", "-f"]);
+    assert!(stdout.contains("No issues archived more than 30 days ago"));
+
+    // A negative age would put the cutoff in the future and match everything
+    let (success, _, _) = run_chainlink(dir.path(), &["archive", "purge", "--older=-1", "-f"]);
+    assert!(!success);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["archive", "purge", "-f"]);
+    assert!(success);
+    assert!(stdout.contains("Purged 1 archived issue(s)"));
+    let (success, _, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(!success);
+    let (success, _, _) = run_chainlink(dir.path(), &["show", "2"]);
+    assert!(success);
+}
+
 #[test]
 fn test_unarchive_issue() {
     let dir = tempdir().unwrap();
@@ -681,6 +1291,56 @@ fn test_milestone_add_issues() {
     assert!(show_out.contains("Feature 1") || show_out.contains("#1"));
 }
 
+#[test]
+fn test_milestone_add_by_label() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["milestone", "create", "v1.0"]);
+    run_chainlink(dir.path(), &["create", "Changelog", "-l", "release"]);
+    run_chainlink(dir.path(), &["create", "Version bump", "-l", "release"]);
+    run_chainlink(dir.path(), &["create", "Someday"]);
+
+    let (success, stdout, _) =
+        run_chainlink(dir.path(), &["milestone", "add", "1", "--label", "release"]);
+    assert!(success);
+    assert!(
+        stdout.contains("Added 2 issue(s) to milestone #1"),
+        "got: {}",
+        stdout
+    );
+    let (_, show_out, _) = run_chainlink(dir.path(), &["milestone", "show", "1"]);
+    assert!(show_out.contains("Changelog") && show_out.contains("Version bump"));
+    assert!(!show_out.contains("Someday"));
+
+    // Ids and filters are mutually exclusive
+    let (success, _, _) = run_chainlink(
+        dir.path(),
+        &["milestone", "add", "1", "3", "--label", "release"],
+    );
+    assert!(!success);
+}
+
+#[test]
+fn test_watch_and_unwatch() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Flaky build"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["watch", "1"]);
+    assert!(success);
+    assert!(stdout.contains("Watching #1"));
+    let (_, stdout, _) = run_chainlink(dir.path(), &["watch"]);
+    assert!(stdout.contains("Flaky build"), "got: {}", stdout);
+
+    let (success, _, _) = run_chainlink(dir.path(), &["watch", "99"]);
+    assert!(!success);
+
+    run_chainlink(dir.path(), &["unwatch", "1"]);
+    let (_, stdout, _) = run_chainlink(dir.path(), &["watch"]);
+    assert!(stdout.contains("No watched issues."));
+}
+
 #[test]
 fn test_milestone_close() {
     let dir = tempdir().unwrap();
@@ -766,6 +1426,28 @@ fn test_timer_status_no_timer() {
     );
 }
 
+#[test]
+fn test_timer_status_json() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["timer", "--json"]);
+    assert!(success);
+    let idle: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(idle["running"], false);
+    assert!(idle["issue_id"].is_null());
+
+    run_chainlink(dir.path(), &["create", "First"]);
+    run_chainlink(dir.path(), &["create", "Second"]);
+    run_chainlink(dir.path(), &["start", "2"]);
+    let (success, stdout, _) = run_chainlink(dir.path(), &["timer", "--json"]);
+    assert!(success);
+    let running: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(running["running"], true);
+    assert_eq!(running["issue_id"], 2);
+    assert!(running["elapsed_seconds"].as_i64().unwrap() >= 0);
+}
+
 // ==================== Relate Tests ====================
 
 #[test]
@@ -796,6 +1478,44 @@ fn test_related_list() {
     assert!(stdout.contains("Issue 2") || stdout.contains("#2"));
 }
 
+#[test]
+fn test_relate_with_reason_shows_note() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Issue 1"]);
+    run_chainlink(dir.path(), &["create", "Issue 2"]);
+    let (success, _, _) = run_chainlink(
+        dir.path(),
+        &["relate", "1", "2", "--reason", "same root cause"],
+    );
+    assert!(success);
+
+    let (_, related_out, _) = run_chainlink(dir.path(), &["related", "2"]);
+    assert!(related_out.contains("same root cause"));
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(show_out.contains("same root cause"));
+}
+
+#[test]
+fn test_attach_and_list_attachments() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Needs a spec"]);
+    let (success, _, _) = run_chainlink(
+        dir.path(),
+        &["attach", "1", "docs/spec.md", "--desc", "Design doc"],
+    );
+    assert!(success);
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["attachments", "1"]);
+    assert!(stdout.contains("docs/spec.md — Design doc"));
+    let (_, stdout, _) = run_chainlink(dir.path(), &["show", "1"]);
+    assert!(stdout.contains("Attachments:"));
+    assert!(stdout.contains("docs/spec.md"));
+}
+
 #[test]
 fn test_unrelate_issues() {
     let dir = tempdir().unwrap();
@@ -812,6 +1532,25 @@ fn test_unrelate_issues() {
     assert!(!related_out.contains("Issue 2") || related_out.contains("No related"));
 }
 
+#[test]
+fn test_relate_several_ids() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    for title in ["One", "Two", "Three"] {
+        run_chainlink(dir.path(), &["create", title]);
+    }
+    let (success, stdout, stderr) = run_chainlink(dir.path(), &["relate", "1", "2", "3"]);
+    assert!(success, "relate failed: {}", stderr);
+    assert!(stdout.contains("#1 ↔ #2"));
+    assert!(stdout.contains("#1 ↔ #3"));
+
+    let (_, stdout, _) = run_chainlink(dir.path(), &["related", "1"]);
+    assert!(stdout.contains("Two") && stdout.contains("Three"));
+    let (_, stdout, _) = run_chainlink(dir.path(), &["related", "2"]);
+    assert!(!stdout.contains("Three"));
+}
+
 // ==================== Tree Tests ====================
 
 #[test]
@@ -887,6 +1626,30 @@ fn test_next_no_issues() {
     );
 }
 
+#[test]
+fn test_next_count_lists_top_issues() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    for (title, priority) in [
+        ("Alpha", "low"),
+        ("Bravo", "critical"),
+        ("Charlie", "medium"),
+        ("Delta", "high"),
+        ("Echo", "low"),
+    ] {
+        run_chainlink(dir.path(), &["create", title, "-p", priority]);
+    }
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["next", "--count", "3"]);
+    assert!(success);
+    let lines: Vec<&str> = stdout.lines().filter(|l| l.contains(". #")).collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("Bravo"));
+    assert!(lines[1].contains("Delta"));
+    assert!(lines[2].contains("Charlie"));
+}
+
 // ==================== Export/Import Tests ====================
 
 #[test]
@@ -912,6 +1675,76 @@ fn test_export_json() {
     assert!(content.contains("Issue 2"));
 }
 
+#[test]
+fn test_export_json_stdout_is_pure_json() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Piped issue"]);
+    run_chainlink(dir.path(), &["comment", "1", "A comment"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["export", "-f", "json"]);
+    assert!(success);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout is not pure JSON ({}): {}", e, stdout));
+    assert_eq!(parsed["issues"][0]["title"], "Piped issue");
+    assert!(!stdout.contains("Exported"));
+}
+
+#[test]
+fn test_export_anonymize() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Secret customer bug", "-l", "bug"]);
+    run_chainlink(dir.path(), &["create", "Secret follow-up"]);
+    run_chainlink(dir.path(), &["block", "2", "1"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["export", "--anonymize"]);
+    assert!(success);
+    assert!(!stdout.contains("Secret"), "got: {}", stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["issues"].as_array().unwrap().len(), 2);
+    let issue = |id: i64| {
+        parsed["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|i| i["id"] == id)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(issue(2)["blocked_by"][0], 1);
+    assert_eq!(issue(1)["labels"][0], "bug");
+    assert_eq!(issue(1)["title"], "Issue #1");
+
+    let (success, _, stderr) =
+        run_chainlink(dir.path(), &["export", "-f", "markdown", "--anonymize"]);
+    assert!(!success);
+    assert!(stderr.contains("json and jsonl"));
+}
+
+#[test]
+fn test_export_split_rejects_single_file_flags() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+    run_chainlink(dir.path(), &["create", "Issue 1"]);
+    let wiki = dir.path().join("wiki");
+    let wiki = wiki.to_str().unwrap();
+
+    for extra in [&["-o", "export.json"][..], &["-f", "json"], &["--gzip"]] {
+        let mut args = vec!["export", "--split", "--output-dir", wiki];
+        args.extend_from_slice(extra);
+        let (success, _, stderr) = run_chainlink(dir.path(), &args);
+        assert!(!success, "{:?} was accepted", extra);
+        assert!(stderr.contains("cannot be used with"));
+    }
+    assert!(!dir.path().join("wiki").exists());
+
+    let (success, _, _) = run_chainlink(dir.path(), &["export", "--force"]);
+    assert!(!success);
+}
+
 #[test]
 fn test_export_markdown() {
     let dir = tempdir().unwrap();
@@ -968,6 +1801,61 @@ fn test_import_json() {
     assert!(list_out.contains("Exported Issue") || list_out.contains("#1"));
 }
 
+fn total_tracked_seconds(export: &serde_json::Value) -> i64 {
+    export["time_entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|e| e["duration_seconds"].as_i64())
+        .sum()
+}
+
+#[test]
+fn test_export_import_preserves_tracked_time() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Tracked issue"]);
+    run_chainlink(dir.path(), &["session", "start"]);
+    run_chainlink(dir.path(), &["start", "1"]);
+    run_chainlink(dir.path(), &["stop"]);
+    let export_path = dir.path().join("export.json");
+    run_chainlink(
+        dir.path(),
+        &["export", "-o", export_path.to_str().unwrap(), "-f", "json"],
+    );
+
+    // Give the entry a meaningful duration so the round-trip is observable
+    let mut export: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&export_path).unwrap()).unwrap();
+    assert_eq!(export["time_entries"].as_array().unwrap().len(), 1);
+    assert_eq!(export["sessions"].as_array().unwrap().len(), 1);
+    export["time_entries"][0]["duration_seconds"] = serde_json::json!(5400);
+    std::fs::write(&export_path, export.to_string()).unwrap();
+
+    let dir2 = tempdir().unwrap();
+    init_chainlink(dir2.path());
+    let (success, _, stderr) =
+        run_chainlink(dir2.path(), &["import", export_path.to_str().unwrap()]);
+    assert!(success, "Import failed: {}", stderr);
+
+    let reexport_path = dir2.path().join("reexport.json");
+    run_chainlink(
+        dir2.path(),
+        &[
+            "export",
+            "-o",
+            reexport_path.to_str().unwrap(),
+            "-f",
+            "json",
+        ],
+    );
+    let reexport: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&reexport_path).unwrap()).unwrap();
+    assert_eq!(total_tracked_seconds(&reexport), 5400);
+    assert_eq!(reexport["sessions"].as_array().unwrap().len(), 1);
+}
+
 // ==================== Tested Command Tests ====================
 
 #[test]
@@ -1744,6 +2632,35 @@ fn test_template_with_user_description() {
     assert!(show_out.contains("User provided details") || show_out.contains("Steps to reproduce"));
 }
 
+// --- create.rs: Subissue from a template ---
+#[test]
+fn test_subissue_with_template() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Epic", "-p", "low"]);
+    let (success, stdout, stderr) = run_chainlink(
+        dir.path(),
+        &[
+            "subissue",
+            "1",
+            "Crash on save",
+            "-t",
+            "bug",
+            "-d",
+            "Seen twice",
+        ],
+    );
+    assert!(success, "subissue failed: {}", stderr);
+    assert!(stdout.contains("Applied template: bug"));
+
+    let (_, show_out, _) = run_chainlink(dir.path(), &["show", "2"]);
+    assert!(show_out.contains("bug"));
+    assert!(show_out.contains("high"));
+    assert!(show_out.contains("Steps to reproduce"));
+    assert!(show_out.contains("Seen twice"));
+}
+
 // --- create.rs: Subissue with invalid parent ---
 #[test]
 fn test_subissue_invalid_parent() {
@@ -1833,6 +2750,24 @@ fn test_export_markdown_format() {
     );
 }
 
+#[test]
+fn test_export_markdown_table_of_contents() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Fix: login, again!"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["export", "-f", "markdown"]);
+    assert!(success);
+    assert!(stdout.contains("## Contents"));
+    assert!(
+        stdout.contains("](#issue-1-fix-login-again)"),
+        "TOC should link to the issue's slug, got: {}",
+        stdout
+    );
+    assert!(stdout.contains("<a id=\"issue-1-fix-login-again\"></a>"));
+}
+
 // --- Archive older days test ---
 #[test]
 fn test_archive_older_no_matches() {
@@ -2001,6 +2936,27 @@ fn test_unlabel_nonexistent_label() {
     );
 }
 
+#[test]
+fn test_unlabel_echoes_normalized_label() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    run_chainlink(dir.path(), &["create", "Issue"]);
+    run_chainlink(dir.path(), &["label", "1", "needs review"]);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["unlabel", "1", "needs review"]);
+    assert!(success);
+    assert!(
+        stdout.contains("Removed label 'needs-review'"),
+        "{}",
+        stdout
+    );
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["label", "1", "   "]);
+    assert!(!success);
+    assert!(stderr.contains("can't be empty"), "{}", stderr);
+}
+
 // --- create.rs: Invalid priority ---
 #[test]
 fn test_create_invalid_priority() {
@@ -2331,22 +3287,30 @@ fn test_security_control_characters() {
     assert!(success);
 }
 
+#[test]
+fn test_create_whitespace_title_needs_escape_flag() {
+    let dir = tempdir().unwrap();
+    init_chainlink(dir.path());
+
+    let (success, _, stderr) = run_chainlink(dir.path(), &["create", "   "]);
+    assert!(!success, "whitespace-only title should be rejected");
+    assert!(stderr.contains("--allow-empty-title"), "got: {}", stderr);
+
+    let (success, stdout, _) = run_chainlink(dir.path(), &["create", "   ", "--allow-empty-title"]);
+    assert!(success);
+    assert!(stdout.contains("Created issue #1"), "got: {}", stdout);
+}
+
 /// Test empty strings
 #[test]
 fn test_edge_empty_strings() {
     let dir = tempdir().unwrap();
     init_chainlink(dir.path());
 
-    // Empty title - should either fail or succeed (both acceptable, just don't crash)
-    let (success, stdout, _) = run_chainlink(dir.path(), &["create", ""]);
-    if success {
-        // If it accepted empty title, verify the issue was created
-        assert!(
-            stdout.contains("Created issue"),
-            "If success, should show created message, got: {}",
-            stdout
-        );
-    }
+    // Empty title is rejected unless explicitly allowed
+    let (success, _, stderr) = run_chainlink(dir.path(), &["create", ""]);
+    assert!(!success);
+    assert!(stderr.contains("title is empty"), "got: {}", stderr);
 
     // Empty comment
     run_chainlink(dir.path(), &["create", "Issue"]);